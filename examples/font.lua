@@ -1,5 +1,9 @@
 return {
     Size = 50,
+    LineHeight = 50,
+    Base = 41,
+    TextureSize = Vector2.new(1024, 1024),
+    Pages = { "ROBUST_0.png" },
     Characters = {
         [""] = { Vector2.new(1, 1), Vector2.new(1022, 0), Vector2.new(0, 0), 0 },
         [" "] = { Vector2.new(3, 1), Vector2.new(646, 31), Vector2.new(-1, 49), 11 },
@@ -97,5 +101,57 @@ return {
         ["|"] = { Vector2.new(7, 45), Vector2.new(81, 0), Vector2.new(1, 5), 9 },
         ["}"] = { Vector2.new(15, 45), Vector2.new(0, 0), Vector2.new(1, 5), 18 },
         ["~"] = { Vector2.new(24, 7), Vector2.new(563, 31), Vector2.new(0, 24), 24 },
+    },
+    Kerning = {
+        ["0"] = { ["1"] = -1, ["7"] = -2 },
+        ["1"] = { ["1"] = -1, ["7"] = -2 },
+        ["2"] = { ["1"] = -1, ["7"] = -2 },
+        ["3"] = { ["7"] = -1 },
+        ["4"] = { ["1"] = -2, ["3"] = -2, ["5"] = -1, ["7"] = -2, ["9"] = -2 },
+        ["5"] = { ["7"] = -1 },
+        ["6"] = { ["1"] = -2, ["4"] = -1, ["7"] = -2, ["9"] = -2 },
+        ["7"] = { ["6"] = -2 },
+        ["8"] = { ["1"] = -1, ["7"] = -2 },
+        ["9"] = { ["3"] = -1, ["7"] = -1 },
+        ["A"] = { ["\""] = -1, ["'"] = -1, [")"] = -1, ["*"] = -1, ["?"] = -2, ["T"] = -2, ["V"] = -2, ["\\"] = -2, ["t"] = -2, ["v"] = -2, ["y"] = -1, ["{"] = -2 },
+        ["B"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["C"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["D"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["E"] = { ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
+        ["F"] = { ["("] = -1, [","] = -2, ["/"] = -2, [";"] = -1, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["G"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -1, ["}"] = -1 },
+        ["K"] = { ["\""] = -1, ["'"] = -1, ["*"] = -2, ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
+        ["L"] = { ["\""] = -2, ["'"] = -2, ["*"] = -2, ["?"] = -2, ["T"] = -3, ["V"] = -2, ["\\"] = -2, ["t"] = -3, ["v"] = -2, ["y"] = -2, ["{"] = -2 },
+        ["O"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["P"] = { [")"] = -2, [","] = -2, ["/"] = -2, ["X"] = -1, ["\\"] = -2, ["x"] = -1, ["{"] = -1, ["}"] = -1 },
+        ["Q"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["R"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -1, ["}"] = -1 },
+        ["S"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["T"] = { [","] = -2, ["/"] = -2, [";"] = -2, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["V"] = { ["("] = -1, [","] = -1, ["/"] = -2, [";"] = -1, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["W"] = { ["/"] = -2, ["\\"] = -1, ["{"] = -2, ["}"] = -1 },
+        ["X"] = { ["("] = -1, ["*"] = -1, [";"] = -1, ["{"] = -2 },
+        ["Z"] = { ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
+        ["a"] = { ["\""] = -1, ["'"] = -1, [")"] = -1, ["*"] = -1, ["?"] = -2, ["T"] = -2, ["V"] = -2, ["\\"] = -2, ["t"] = -2, ["v"] = -2, ["y"] = -1, ["{"] = -2 },
+        ["b"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["c"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["d"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["e"] = { ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
+        ["f"] = { ["("] = -1, [","] = -2, ["/"] = -2, [";"] = -1, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["g"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -1, ["}"] = -1 },
+        ["k"] = { ["\""] = -1, ["'"] = -1, ["*"] = -2, ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
+        ["l"] = { ["\""] = -2, ["'"] = -2, ["*"] = -2, ["?"] = -2, ["T"] = -3, ["V"] = -2, ["\\"] = -2, ["t"] = -3, ["v"] = -2, ["y"] = -2, ["{"] = -2 },
+        ["n"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["o"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["p"] = { [")"] = -2, [","] = -2, ["/"] = -2, ["X"] = -1, ["\\"] = -2, ["x"] = -1, ["{"] = -1, ["}"] = -1 },
+        ["q"] = { [")"] = -2, ["/"] = -2, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["r"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -1, ["}"] = -1 },
+        ["s"] = { [")"] = -2, ["/"] = -1, ["\\"] = -2, ["{"] = -2, ["}"] = -1 },
+        ["t"] = { [","] = -2, ["/"] = -2, [";"] = -2, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["v"] = { ["("] = -1, [","] = -1, ["/"] = -2, [";"] = -1, ["A"] = -2, ["J"] = -2, ["a"] = -2, ["j"] = -2, ["{"] = -2 },
+        ["w"] = { ["/"] = -2, ["\\"] = -1, ["{"] = -2, ["}"] = -1 },
+        ["x"] = { ["("] = -1, ["*"] = -1, [";"] = -1, ["{"] = -2 },
+        ["y"] = { [","] = -2, ["/"] = -2, ["\\"] = -1, ["{"] = -1 },
+        ["z"] = { ["/"] = -1, ["\\"] = -1, ["{"] = -2 },
     }
 }
@@ -0,0 +1,17 @@
+//! Lining up the baselines of a family's fonts, e.g. regular and bold, so
+//! glyphs from several of them mixed in one run of text sit on one line.
+
+use crate::font::Font;
+
+/// How far each of `fonts` must move its glyphs down for its baseline to
+/// sit as far below the top of the line as the lowest baseline among them,
+/// in the fonts' own pixels: the [`OutputOptions::baseline_shift`] that
+/// aligns it with the others. Fonts without `<common>` metrics have no
+/// baseline and are not moved.
+///
+/// [`OutputOptions::baseline_shift`]: crate::formatter::OutputOptions::baseline_shift
+pub fn baseline_shifts(fonts: &[&Font]) -> Vec<f32> {
+    let bases: Vec<Option<f32>> = fonts.iter().map(|font| font.metrics.common.as_ref().map(|common| common.base)).collect();
+    let lowest = bases.iter().flatten().copied().fold(f32::NEG_INFINITY, f32::max);
+    bases.iter().map(|base| base.map_or(0.0, |base| lowest - base)).collect()
+}
@@ -0,0 +1,449 @@
+//! Converting files on disk, one at a time or in parallel.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{LuaChunks, OutputOptions, output_bytes, render_chunks};
+use crate::parse_cache::ParseCache;
+use crate::parser::{parse_fnt_with, read_source};
+use crate::report::{ConvertSummary, FileReport};
+use crate::schema::check_schema_file;
+use crate::verify::verify_output;
+use globset::Glob;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{debug, info, warn};
+
+/// Converts the font at `input` and writes the result to `output`, reading
+/// it back afterwards if [`OutputOptions::verify`] is set.
+pub fn convert_file(
+    input: &Path,
+    output: &Path,
+    options: &OutputOptions,
+) -> Result<ConvertSummary, ConvertError> {
+    let font = parse_input(input, options)?;
+    write_font(&font, input, output, options)
+}
+
+/// Like [`convert_file`], but reuses the font `cache` holds for `input` while
+/// the file is unchanged, so only the formatting runs again.
+pub fn convert_file_cached(
+    cache: &ParseCache,
+    input: &Path,
+    output: &Path,
+    options: &OutputOptions,
+) -> Result<ConvertSummary, ConvertError> {
+    // Checked before the cache, which may hold the font from a parse that
+    // was not.
+    if options.check_schema {
+        check_schema_file(&input.display().to_string())?;
+    }
+    let font = cache.parse(input, options.lenient, || parse_fnt_with(&input.display().to_string(), options.lenient, |_| {}))?;
+    write_font(&font, input, output, options)
+}
+
+/// Converts the font at `input` into `directory`, named after
+/// [`output_file_name`], and returns where it was written.
+pub fn convert_into(
+    input: &Path,
+    directory: &Path,
+    options: &OutputOptions,
+) -> Result<(PathBuf, ConvertSummary), ConvertError> {
+    let font = parse_input(input, options)?;
+    let output = directory.join(output_file_name(input, Some(&font), options));
+    std::fs::create_dir_all(output.parent().unwrap_or(directory))?;
+    let summary = write_font(&font, input, &output, options)?;
+    Ok((output, summary))
+}
+
+/// Parses the font at `input`, checking it against the BMFont structure
+/// first if [`OutputOptions::check_schema`] is set.
+fn parse_input(input: &Path, options: &OutputOptions) -> Result<Font, ConvertError> {
+    let filename = input.display().to_string();
+    if options.check_schema {
+        check_schema_file(&filename)?;
+    }
+    parse_fnt_with(&filename, options.lenient, |_| {})
+}
+
+fn write_font(font: &Font, input: &Path, output: &Path, options: &OutputOptions) -> Result<ConvertSummary, ConvertError> {
+    let rendered = with_checksums(options, font, &[input])?;
+    let chunks = render_chunks(font, &source_name(&[input]), &rendered, &module_name(output))?;
+    write_chunks(&chunks, output, options, options.overwrite)?;
+    info!(input = %input.display(), output = %output.display(), "wrote output");
+    if options.verify {
+        verify_output(font, &read_source(&output.display().to_string())?, &rendered)?;
+    }
+    Ok(ConvertSummary::of(font, options))
+}
+
+/// File names of the inputs a conversion was made from, for header comments.
+pub fn source_name<P: AsRef<Path>>(inputs: &[P]) -> String {
+    let names: Vec<String> = inputs
+        .iter()
+        .map(|input| input.as_ref().file_name().unwrap_or_default().to_string_lossy().into_owned())
+        .collect();
+    names.join(", ")
+}
+
+/// `options` with the checksums it asks for computed: the
+/// [`HeaderOptions::source_checksum`] of `inputs` when the header records
+/// provenance, and the [`OutputOptions::page_checksums`] of the textures of
+/// `font`, which sit next to the first input. The glyphs those textures leave
+/// blank are found for [`GlyphFilter::blank_pixels`] too. The source checksum
+/// is left out if an input cannot be read, e.g. when it came from stdin; a
+/// missing texture is an error.
+///
+/// [`HeaderOptions::source_checksum`]: crate::formatter::HeaderOptions::source_checksum
+/// [`GlyphFilter::blank_pixels`]: crate::glyph_filter::GlyphFilter::blank_pixels
+pub fn with_checksums<'a, P: AsRef<Path>>(
+    options: &'a OutputOptions,
+    font: &Font,
+    inputs: &[P],
+) -> Result<Cow<'a, OutputOptions>, ConvertError> {
+    let mut options = Cow::Borrowed(options);
+    if options.header.as_ref().is_some_and(|header| header.provenance) {
+        let mut hasher = crc32fast::Hasher::new();
+        let read = inputs.iter().try_for_each(|input| {
+            hasher.update(&std::fs::read(input)?);
+            Ok::<_, std::io::Error>(())
+        });
+        if read.is_ok()
+            && let Some(ref mut header) = options.to_mut().header
+        {
+            header.source_checksum = Some(hasher.finalize());
+        }
+    }
+    if options.texture_checksums {
+        let directory = inputs.first().and_then(|input| input.as_ref().parent()).unwrap_or(Path::new(""));
+        let checksums = font
+            .metrics
+            .pages
+            .iter()
+            .map(|(id, file)| {
+                let path = directory.join(file);
+                // Not `ConvertError::Texture`, which needs the atlas feature.
+                let bytes = std::fs::read(&path).map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+                Ok((*id, crc32fast::hash(&bytes)))
+            })
+            .collect::<Result<BTreeMap<u32, u32>, ConvertError>>()?;
+        debug!(pages = checksums.len(), "computed texture checksums");
+        options.to_mut().page_checksums = checksums;
+    }
+    #[cfg(feature = "atlas")]
+    if options.glyph_filter.blank_pixels {
+        let directory = inputs.first().and_then(|input| input.as_ref().parent()).unwrap_or(Path::new(""));
+        // The filter applies after the remap, so blank glyphs are found by
+        // their new codepoints.
+        let font = match options.remap {
+            Some(ref map) if !map.is_empty() => Cow::Owned(map.apply(font)),
+            _ => Cow::Borrowed(font),
+        };
+        let pages = crate::trim::load_pages(&font, directory)?;
+        let blank = crate::glyph_filter::blank_glyphs(&font, &pages);
+        debug!(blank = blank.len(), "found blank glyphs");
+        options.to_mut().glyph_filter.blank = blank;
+    }
+    Ok(options)
+}
+
+/// What happens to a file already at an output path.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Overwrite {
+    /// Fail with [`ConvertError::OutputExists`] instead of replacing it.
+    #[default]
+    Refuse,
+    Replace,
+    /// Copy it to a timestamped `.bak` next to it, then replace it.
+    Backup,
+}
+
+impl Overwrite {
+    pub const ALL: [Overwrite; 3] = [Overwrite::Refuse, Overwrite::Replace, Overwrite::Backup];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Overwrite::Refuse => "Keep",
+            Overwrite::Replace => "Overwrite",
+            Overwrite::Backup => "Back up, then overwrite",
+        }
+    }
+}
+
+/// Applies `policy` to a file already at `output` before it is written,
+/// returning where it was backed up to, if anywhere.
+pub fn prepare_output(output: &Path, policy: Overwrite) -> Result<Option<PathBuf>, ConvertError> {
+    if !output.exists() {
+        return Ok(None);
+    }
+    match policy {
+        Overwrite::Refuse => Err(ConvertError::OutputExists(output.to_path_buf())),
+        Overwrite::Replace => Ok(None),
+        Overwrite::Backup => {
+            let mut backup = output.as_os_str().to_owned();
+            backup.push(chrono::Local::now().format(".%Y%m%d-%H%M%S.bak").to_string());
+            std::fs::copy(output, &backup)?;
+            let backup = PathBuf::from(backup);
+            info!(output = %output.display(), backup = %backup.display(), "backed up existing output");
+            Ok(Some(backup))
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path`, then renames it over
+/// `path`, so a crash or full disk mid-write leaves the previous file, or
+/// none, instead of a truncated one a game then fails to load. The temporary
+/// file takes the permissions of the file it replaces and is removed again
+/// when writing fails.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.tmp", std::process::id()));
+    let temporary = PathBuf::from(temporary);
+    let result = (|| {
+        let mut file = std::fs::File::create(&temporary)?;
+        file.write_all(contents.as_ref())?;
+        if let Ok(existing) = std::fs::metadata(path) {
+            file.set_permissions(existing.permissions())?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&temporary, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// The name the module written to `output` is required by, which its part
+/// modules are named after: the file name without its extension, or the
+/// folder's name for an `init.lua`, as Rojo names the ModuleScript.
+pub fn module_name(output: &Path) -> String {
+    let stem = output.file_stem().unwrap_or_default();
+    let stem = match output.parent().and_then(Path::file_name) {
+        Some(folder) if stem == "init" => folder,
+        _ => stem,
+    };
+    stem.to_string_lossy().into_owned()
+}
+
+/// Writes the module of `chunks` to `output` and its parts next to it, with
+/// the same extension. `policy` applies to every file, and all of them are
+/// checked before any is written. Returns where the module was backed up to,
+/// if anywhere.
+pub fn write_chunks(chunks: &LuaChunks, output: &Path, options: &OutputOptions, policy: Overwrite) -> Result<Option<PathBuf>, ConvertError> {
+    let parts = part_files(chunks, output);
+    if policy == Overwrite::Refuse
+        && let Some((existing, _)) = parts.iter().find(|(path, _)| path.exists())
+    {
+        return Err(ConvertError::OutputExists(existing.clone()));
+    }
+    let backup = prepare_output(output, policy)?;
+    write_atomic(output, output_bytes(&chunks.index, options))?;
+    for (path, source) in parts {
+        prepare_output(&path, policy)?;
+        write_atomic(&path, output_bytes(source, options))?;
+    }
+    if !chunks.parts.is_empty() {
+        debug!(output = %output.display(), parts = chunks.parts.len(), "wrote part modules");
+    }
+    Ok(backup)
+}
+
+/// Where [`write_chunks`] writes each part module of `chunks`, next to
+/// `output`.
+fn part_files<'a>(chunks: &'a LuaChunks, output: &Path) -> Vec<(PathBuf, &'a String)> {
+    let extension = output.extension().unwrap_or_default().to_string_lossy();
+    chunks
+        .parts
+        .iter()
+        .map(|(name, source)| (output.with_file_name(format!("{name}.{extension}")), source))
+        .collect()
+}
+
+/// The files that writing `chunks` to `output` would change or create,
+/// module first; empty when the files on disk are up to date.
+pub fn stale_files(chunks: &LuaChunks, output: &Path, options: &OutputOptions) -> Vec<PathBuf> {
+    let files = std::iter::once((output.to_path_buf(), &chunks.index)).chain(part_files(chunks, output));
+    files
+        .filter(|(path, source)| std::fs::read(path).ok().as_deref() != Some(&output_bytes(source, options)[..]))
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Placeholders of [`OutputOptions::file_name`] that only the parsed font
+/// can fill in.
+const FONT_PLACEHOLDERS: [&str; 3] = ["{size}", "{line_height}", "{glyphs}"];
+
+/// The output file name for `input` following [`OutputOptions::file_name`],
+/// dropping a `.gz` suffix first. The placeholders taken from the font are
+/// left as they are without one; see [`names_from_font`].
+pub fn output_file_name(input: &Path, font: Option<&Font>, options: &OutputOptions) -> PathBuf {
+    let input = match input.extension() {
+        Some(ext) if ext == "gz" => input.with_extension(""),
+        _ => input.to_path_buf(),
+    };
+    let name = input.file_stem().unwrap_or_default().to_string_lossy();
+    let pattern = options.file_name.as_deref().unwrap_or("{name}.{ext}");
+    let mut file_name = pattern.replace("{name}", &name).replace("{ext}", options.format.extension());
+    if let Some(font) = font {
+        let line_height = font.metrics.common.as_ref().map_or(0.0, |common| common.line_height);
+        let glyphs = font.characters.keys().filter(|id| options.includes(**id)).count();
+        let values = [options.font_size(font).to_string(), line_height.to_string(), glyphs.to_string()];
+        for (placeholder, value) in FONT_PLACEHOLDERS.iter().zip(values) {
+            file_name = file_name.replace(placeholder, &value);
+        }
+    }
+    PathBuf::from(file_name)
+}
+
+/// Whether [`OutputOptions::file_name`] has placeholders [`output_file_name`]
+/// needs the parsed font for.
+pub fn names_from_font(options: &OutputOptions) -> bool {
+    options
+        .file_name
+        .as_deref()
+        .is_some_and(|pattern| FONT_PLACEHOLDERS.iter().any(|placeholder| pattern.contains(placeholder)))
+}
+
+/// The outcome of converting one input in [`convert_batch`].
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    pub input: PathBuf,
+    pub result: Result<PathBuf, String>,
+    /// The same outcome with glyph count, warnings and error details.
+    pub report: FileReport,
+}
+
+/// Reported from the worker threads of [`convert_batch`] and
+/// [`convert_directory`] as each file starts and finishes.
+#[derive(Debug, Clone, Copy)]
+pub enum BatchProgress<'a> {
+    Started {
+        input: &'a Path,
+        total: usize,
+    },
+    Finished {
+        result: &'a BatchResult,
+        /// Files finished so far, this one included.
+        completed: usize,
+        total: usize,
+    },
+}
+
+/// Converts every input into `output_dir` in parallel. A `threads` value of 0
+/// uses rayon's default of one thread per core.
+pub fn convert_batch(
+    inputs: &[PathBuf],
+    output_dir: &Path,
+    options: &OutputOptions,
+    threads: usize,
+    progress: impl Fn(BatchProgress) + Sync,
+) -> Vec<BatchResult> {
+    let jobs: Vec<(PathBuf, PathBuf)> = inputs.iter().map(|input| (input.clone(), output_dir.to_path_buf())).collect();
+    convert_jobs(&jobs, options, threads, progress)
+}
+
+/// Lists every `.fnt` (or `.fnt.gz`) file under `root`, recursively and in a
+/// stable order. Files whose path relative to `root` matches the `exclude`
+/// glob are skipped.
+pub fn find_fonts(root: &Path, exclude: Option<&str>) -> Result<Vec<PathBuf>, ConvertError> {
+    let exclude = exclude.map(|pattern| Glob::new(pattern).map(|glob| glob.compile_matcher())).transpose()?;
+
+    let mut fonts = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(directory) = pending.pop() {
+        let mut entries = std::fs::read_dir(&directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort();
+
+        for path in entries {
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if is_font_file(&path) && !exclude.as_ref().is_some_and(|glob| glob.is_match(relative)) {
+                fonts.push(path);
+            }
+        }
+    }
+    fonts.sort();
+    Ok(fonts)
+}
+
+fn is_font_file(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    name.ends_with(".fnt") || name.ends_with(".fnt.gz")
+}
+
+/// Converts every font under `root` into `output_dir`, mirroring the folder
+/// structure; see [`find_fonts`] for how inputs are selected.
+pub fn convert_directory(
+    root: &Path,
+    output_dir: &Path,
+    exclude: Option<&str>,
+    options: &OutputOptions,
+    threads: usize,
+    progress: impl Fn(BatchProgress) + Sync,
+) -> Result<Vec<BatchResult>, ConvertError> {
+    let jobs: Vec<(PathBuf, PathBuf)> = find_fonts(root, exclude)?
+        .into_iter()
+        .map(|input| {
+            let relative = input.strip_prefix(root).unwrap_or(&input);
+            let directory = output_dir.join(relative.parent().unwrap_or(Path::new("")));
+            (input, directory)
+        })
+        .collect();
+    Ok(convert_jobs(&jobs, options, threads, progress))
+}
+
+/// Converts each `(input, output directory)` pair.
+fn convert_jobs(
+    jobs: &[(PathBuf, PathBuf)],
+    options: &OutputOptions,
+    threads: usize,
+    progress: impl Fn(BatchProgress) + Sync,
+) -> Vec<BatchResult> {
+    let total = jobs.len();
+    info!(files = total, threads, "converting batch");
+    let completed = AtomicUsize::new(0);
+    let convert = || {
+        jobs.par_iter()
+            .map(|(input, directory)| {
+                progress(BatchProgress::Started { input, total });
+                let name = input.display().to_string();
+                let result = match convert_into(input, directory, options) {
+                    Ok((output, summary)) => BatchResult {
+                        input: input.clone(),
+                        report: FileReport::converted(name, Some(output.display().to_string()), summary),
+                        result: Ok(output),
+                    },
+                    Err(e) => {
+                        warn!(input = %input.display(), error = %e, "conversion failed");
+                        BatchResult {
+                            input: input.clone(),
+                            result: Err(e.to_string()),
+                            report: FileReport::failed(name, &e),
+                        }
+                    }
+                };
+                progress(BatchProgress::Finished {
+                    result: &result,
+                    completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                    total,
+                });
+                result
+            })
+            .collect()
+    };
+
+    match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => pool.install(convert),
+        Err(_) => convert(),
+    }
+}
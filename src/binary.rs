@@ -0,0 +1,239 @@
+//! Reading the binary BMFont format (version 3), which starts with the magic
+//! bytes `BMF\x03` followed by typed, length-prefixed blocks.
+
+use crate::Progress;
+use crate::error::ConvertError;
+use crate::font::{Character, CharacterOffset, CharacterPosition, CharacterSize, CommonMetrics, FontMetrics, GlyphSpacing};
+use std::collections::BTreeMap;
+
+pub(crate) const MAGIC: &[u8] = b"BMF";
+
+const BLOCK_INFO: u8 = 1;
+const BLOCK_COMMON: u8 = 2;
+const BLOCK_PAGES: u8 = 3;
+const BLOCK_CHARS: u8 = 4;
+const BLOCK_KERNING: u8 = 5;
+
+const CHAR_LEN: usize = 20;
+const KERNING_LEN: usize = 10;
+
+fn malformed(reason: &'static str) -> ConvertError {
+    ConvertError::MalformedBinary(reason)
+}
+
+fn u16_at(data: &[u8], at: usize) -> Result<u16, ConvertError> {
+    data.get(at..at + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(malformed("block is too short"))
+}
+
+fn i16_at(data: &[u8], at: usize) -> Result<i16, ConvertError> {
+    u16_at(data, at).map(|value| value as i16)
+}
+
+fn u32_at(data: &[u8], at: usize) -> Result<u32, ConvertError> {
+    data.get(at..at + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or(malformed("block is too short"))
+}
+
+/// Binary counterpart of
+/// [`parse_fnt_streaming_with_progress`](crate::parser::parse_fnt_streaming_with_progress).
+pub fn parse_fnt_binary_with_progress<F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: &[u8],
+    mut callback: F,
+    mut progress: P,
+) -> Result<FontMetrics, ConvertError> {
+    let rest = source.strip_prefix(MAGIC).ok_or(malformed("missing BMF header"))?;
+    let (&version, mut rest) = rest.split_first().ok_or(malformed("missing version"))?;
+    if version != 3 {
+        return Err(malformed("only version 3 is supported"));
+    }
+
+    let mut font_size = None;
+    let mut glyph_spacing = GlyphSpacing::default();
+    let mut common = None;
+    let mut pages = BTreeMap::new();
+    let mut kerning = BTreeMap::new();
+
+    while let Some((&kind, after)) = rest.split_first() {
+        let offset = (source.len() - rest.len()) as u64;
+        let located = |e| ConvertError::at(offset, e);
+        let len = u32_at(after, 0).map_err(located)? as usize;
+        let data = after
+            .get(4..4 + len)
+            .ok_or_else(|| located(malformed("block extends past the end of the file")))?;
+        rest = &after[4 + len..];
+
+        let handled = (|| -> Result<(), ConvertError> {
+            match kind {
+                BLOCK_INFO => {
+                    font_size = Some(i32::from(i16_at(data, 0)?));
+                    // Single bytes after the size, bit field, charset,
+                    // stretchH and aa.
+                    let byte = |at: usize| data.get(at).map(|&byte| i32::from(byte)).ok_or(malformed("block is too short"));
+                    glyph_spacing = GlyphSpacing {
+                        padding: [byte(7)?, byte(8)?, byte(9)?, byte(10)?],
+                        spacing: [byte(11)?, byte(12)?],
+                        outline: byte(13)?,
+                    };
+                }
+                BLOCK_PAGES => {
+                    // Page names are NUL-terminated and stored in id order.
+                    let names = data.strip_suffix(&[0]).unwrap_or(data);
+                    for (id, name) in names.split(|&byte| byte == 0).enumerate() {
+                        pages.insert(id as u32, std::str::from_utf8(name)?.to_string());
+                    }
+                }
+                BLOCK_CHARS => {
+                    let records = data.chunks_exact(CHAR_LEN);
+                    if !records.remainder().is_empty() {
+                        return Err(malformed("truncated char block"));
+                    }
+                    let count = records.len();
+                    for (index, record) in records.enumerate() {
+                        callback(
+                            u32_at(record, 0)?,
+                            Character {
+                                size: CharacterSize {
+                                    width: f32::from(u16_at(record, 8)?),
+                                    height: f32::from(u16_at(record, 10)?),
+                                },
+                                position: CharacterPosition {
+                                    x: f32::from(u16_at(record, 4)?),
+                                    y: f32::from(u16_at(record, 6)?),
+                                },
+                                offset: CharacterOffset {
+                                    x: f32::from(i16_at(record, 12)?),
+                                    y: f32::from(i16_at(record, 14)?),
+                                },
+                                advance: f32::from(i16_at(record, 16)?),
+                                page: u32::from(record[18]),
+                                channel: record[19],
+                                letter: None,
+                            },
+                        );
+                        progress(Progress::Fraction((index + 1) as f32 / count as f32));
+                    }
+                }
+                BLOCK_KERNING => {
+                    let records = data.chunks_exact(KERNING_LEN);
+                    if !records.remainder().is_empty() {
+                        return Err(malformed("truncated kerning block"));
+                    }
+                    for record in records {
+                        let pair = (u32_at(record, 0)?, u32_at(record, 4)?);
+                        kerning.insert(pair, f32::from(i16_at(record, 8)?));
+                    }
+                }
+                BLOCK_COMMON => {
+                    common = Some(CommonMetrics {
+                        line_height: f32::from(u16_at(data, 0)?),
+                        base: f32::from(u16_at(data, 2)?),
+                        scale_w: u32::from(u16_at(data, 4)?),
+                        scale_h: u32::from(u16_at(data, 6)?),
+                    });
+                }
+                _ => return Err(malformed("unknown block type")),
+            }
+            Ok(())
+        })();
+        handled.map_err(located)?;
+    }
+
+    let size = font_size.ok_or(ConvertError::MissingInfo)?;
+    progress(Progress::Fraction(1.0));
+    // The binary format has no block for distance field metadata.
+    Ok(FontMetrics {
+        size,
+        glyph_spacing,
+        common,
+        distance_field: None,
+        declared_chars: None,
+        filled_in: Vec::new(),
+        pages,
+        kerning,
+        extents: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ConvertError;
+    use crate::example_font;
+    use crate::font::Font;
+    use crate::parser::parse_fnt_bytes_with_progress;
+
+    fn block(output: &mut Vec<u8>, kind: u8, data: &[u8]) {
+        output.push(kind);
+        output.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        output.extend_from_slice(data);
+    }
+
+    /// `font` in the binary format, as BMFont would write it.
+    fn to_binary(font: &Font) -> Vec<u8> {
+        let metrics = &font.metrics;
+        let spacing = &metrics.glyph_spacing;
+        let mut output = b"BMF\x03".to_vec();
+
+        let mut info = (metrics.size as i16).to_le_bytes().to_vec();
+        info.extend_from_slice(&[0b0000_0010, 0, 100, 0, 1]);
+        info.extend(spacing.padding.iter().chain(&spacing.spacing).chain([&spacing.outline]).map(|&value| value as u8));
+        info.extend_from_slice(b"ROBUST\0");
+        block(&mut output, super::BLOCK_INFO, &info);
+
+        let common = metrics.common.as_ref().unwrap();
+        let mut data = Vec::new();
+        for value in [common.line_height as u16, common.base as u16, common.scale_w as u16, common.scale_h as u16, metrics.pages.len() as u16] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(&[0, 0, 4, 4, 4]);
+        block(&mut output, super::BLOCK_COMMON, &data);
+
+        let names: Vec<u8> = metrics.pages.values().flat_map(|name| name.bytes().chain([0])).collect();
+        block(&mut output, super::BLOCK_PAGES, &names);
+
+        let mut chars = Vec::new();
+        for (id, data) in &font.characters {
+            chars.extend_from_slice(&id.to_le_bytes());
+            for value in [data.position.x, data.position.y, data.size.width, data.size.height] {
+                chars.extend_from_slice(&(value as u16).to_le_bytes());
+            }
+            for value in [data.offset.x, data.offset.y, data.advance] {
+                chars.extend_from_slice(&(value as i16).to_le_bytes());
+            }
+            chars.extend_from_slice(&[data.page as u8, data.channel]);
+        }
+        block(&mut output, super::BLOCK_CHARS, &chars);
+
+        let mut kerning = Vec::new();
+        for (&(first, second), &amount) in &metrics.kerning {
+            kerning.extend_from_slice(&first.to_le_bytes());
+            kerning.extend_from_slice(&second.to_le_bytes());
+            kerning.extend_from_slice(&(amount as i16).to_le_bytes());
+        }
+        block(&mut output, super::BLOCK_KERNING, &kerning);
+        output
+    }
+
+    #[test]
+    fn binary_font_parses_like_the_xml_one() {
+        let font = example_font();
+        let parsed = parse_fnt_bytes_with_progress(&to_binary(&font), |_| {}).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&font).unwrap());
+    }
+
+    #[test]
+    fn truncated_char_blocks_are_refused() {
+        let mut font = example_font();
+        font.metrics.kerning.clear();
+        let mut binary = to_binary(&font);
+        // Drop the empty kerning block and the last byte of the final glyph.
+        binary.truncate(binary.len() - 6);
+        let chars_len = font.characters.len() * super::CHAR_LEN;
+        let at = binary.len() + 1 - chars_len - 4;
+        binary[at..at + 4].copy_from_slice(&(chars_len as u32 - 1).to_le_bytes());
+        let error = parse_fnt_bytes_with_progress(&binary, |_| {}).unwrap_err();
+        assert!(matches!(error.cause(), ConvertError::MalformedBinary("truncated char block")), "{error}");
+    }
+}
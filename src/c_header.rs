@@ -0,0 +1,178 @@
+//! Writing fonts as C/C++ headers, for native tools that share a font with
+//! the Lua client.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputFormatter, OutputOptions, Rounding, adjusted_font, header_lines, script_name};
+
+/// The header of [`write_c_header`], named after the exported module.
+pub struct CHeaderFormatter;
+
+impl OutputFormatter for CHeaderFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::CHeader
+    }
+
+    fn name(&self) -> &'static str {
+        "c-header"
+    }
+
+    fn label(&self) -> &'static str {
+        "C header"
+    }
+
+    fn description(&self) -> &'static str {
+        "C/C++ header with static glyph and kerning arrays"
+    }
+
+    fn extension(&self) -> &'static str {
+        "h"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(write_c_header(&adjusted_font(font, options), &script_name(source, options), source, options))
+    }
+}
+
+/// Turns `name` into a lowercase C identifier, e.g. `Title Font` into
+/// `title_font`.
+fn c_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    if !identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+/// Renders an already adjusted `font` as a header declaring
+/// `static const Glyph NAME_glyphs[]` and `static const Kerning
+/// NAME_kerning[]`, prefixed with `name` so several fonts can be included in
+/// one translation unit. Metrics are `int` unless rounding keeps floats; the
+/// types are defined by whichever header is included first, so fonts sharing
+/// a translation unit should use the same rounding.
+pub fn write_c_header(font: &Font, name: &str, source: &str, options: &OutputOptions) -> String {
+    let prefix = c_identifier(name);
+    let guard = format!("{}_H", prefix.to_ascii_uppercase());
+    let number = if options.rounding == Rounding::KeepFloat { "float" } else { "int" };
+    let value = |value: f32| {
+        if options.rounding == Rounding::KeepFloat {
+            format!("{value:?}f")
+        } else {
+            value.to_string()
+        }
+    };
+
+    let mut output = String::new();
+    if let Some(ref header) = options.header {
+        for line in header_lines(header, source, options) {
+            output.push_str(&format!("// {line}\n"));
+        }
+    }
+    output.push_str(&format!("#ifndef {guard}\n#define {guard}\n\n"));
+
+    // Shared by every font header, so only the first one included defines it.
+    output.push_str("#ifndef XMLTOLUA_GLYPH_TYPES\n#define XMLTOLUA_GLYPH_TYPES\n");
+    output.push_str(&format!(
+        "typedef struct {{\n    unsigned int id;\n    {number} x, y, width, height;\n    {number} offset_x, offset_y;\n    {number} advance;\n    unsigned int page;\n}} Glyph;\n\n"
+    ));
+    output.push_str(&format!("typedef struct {{\n    unsigned int first, second;\n    {number} amount;\n}} Kerning;\n#endif\n\n"));
+
+    output.push_str(&format!("static const int {prefix}_size = {};\n", font.metrics.size));
+    if let Some(ref common) = font.metrics.common {
+        output.push_str(&format!("static const {number} {prefix}_line_height = {};\n", value(common.line_height)));
+        output.push_str(&format!("static const {number} {prefix}_base = {};\n", value(common.base)));
+        if let Some(extents) = font.metrics.extents {
+            output.push_str(&format!("static const {number} {prefix}_ascent = {};\n", value(extents.ascent)));
+            output.push_str(&format!("static const {number} {prefix}_descent = {};\n", value(extents.descent)));
+        }
+        output.push_str(&format!("static const int {prefix}_texture_width = {};\n", common.scale_w));
+        output.push_str(&format!("static const int {prefix}_texture_height = {};\n", common.scale_h));
+    }
+    let spacing = &font.metrics.glyph_spacing;
+    if !spacing.is_zero() {
+        let list = |values: &[i32]| values.iter().map(i32::to_string).collect::<Vec<_>>().join(", ");
+        output.push_str(&format!("static const int {prefix}_padding[4] = {{ {} }};\n", list(&spacing.padding)));
+        output.push_str(&format!("static const int {prefix}_spacing[2] = {{ {} }};\n", list(&spacing.spacing)));
+        output.push_str(&format!("static const int {prefix}_outline = {};\n", spacing.outline));
+    }
+
+    output.push_str(&format!("\nstatic const unsigned int {prefix}_glyph_count = {};\n", font.characters.len()));
+    // C forbids empty initializer lists, so an empty font gets a single zeroed entry.
+    output.push_str(&format!("static const Glyph {prefix}_glyphs[] = {{\n"));
+    for (id, data) in &font.characters {
+        output.push_str(&format!(
+            "    {{ {id}, {}, {}, {}, {}, {}, {}, {}, {} }},\n",
+            value(data.position.x),
+            value(data.position.y),
+            value(data.size.width),
+            value(data.size.height),
+            value(data.offset.x),
+            value(data.offset.y),
+            value(data.advance),
+            data.page
+        ));
+    }
+    if font.characters.is_empty() {
+        output.push_str("    { 0 },\n");
+    }
+    output.push_str("};\n");
+
+    output.push_str(&format!("\nstatic const unsigned int {prefix}_kerning_count = {};\n", font.metrics.kerning.len()));
+    output.push_str(&format!("static const Kerning {prefix}_kerning[] = {{\n"));
+    for (&(first, second), amount) in &font.metrics.kerning {
+        output.push_str(&format!("    {{ {first}, {second}, {} }},\n", value(*amount)));
+    }
+    if font.metrics.kerning.is_empty() {
+        output.push_str("    { 0 },\n");
+    }
+    output.push_str(&format!("}};\n\n#endif // {guard}\n"));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn names_become_c_identifiers() {
+        assert_eq!(c_identifier("Title Font"), "title_font");
+        assert_eq!(c_identifier("robust-50.v2"), "robust_50_v2");
+        assert_eq!(c_identifier("2x"), "_2x");
+    }
+
+    #[test]
+    fn glyphs_and_kerning_are_listed_in_id_order() {
+        let font = example_font();
+        let header = write_c_header(&font, "Robust", "font.fnt", &OutputOptions { header: None, ..OutputOptions::default() });
+        assert!(header.starts_with("#ifndef ROBUST_H\n#define ROBUST_H\n"));
+        assert!(header.ends_with("#endif // ROBUST_H\n"));
+        assert!(header.contains("static const int robust_size = 50;\n"));
+        assert!(header.contains(&format!("static const unsigned int robust_glyph_count = {};\n", font.characters.len())));
+        assert!(header.contains("    { 65, 514, 0, 19, 30, 0, 11, 19, 0 },\n"));
+        assert!(header.contains("    { 65, 34, -1 },\n"));
+        assert_eq!(header.matches("\n    { ").count(), font.characters.len() + font.metrics.kerning.len());
+    }
+
+    #[test]
+    fn kept_floats_are_float_literals() {
+        let font = example_font();
+        let options = OutputOptions { rounding: Rounding::KeepFloat, header: None, ..OutputOptions::default() };
+        let header = write_c_header(&font, "robust", "font.fnt", &options);
+        assert!(header.contains("    float x, y, width, height;\n"));
+        assert!(header.contains("    { 65, 514.0f, 0.0f, 19.0f, 30.0f, 0.0f, 11.0f, 19.0f, 0 },\n"));
+    }
+
+    #[test]
+    fn an_empty_font_has_no_empty_initializers() {
+        let mut font = example_font();
+        font.characters.clear();
+        font.metrics.kerning.clear();
+        let header = write_c_header(&font, "empty", "font.fnt", &OutputOptions { header: None, ..OutputOptions::default() });
+        assert!(header.contains("static const Glyph empty_glyphs[] = {\n    { 0 },\n};\n"));
+        assert!(header.contains("static const Kerning empty_kerning[] = {\n    { 0 },\n};\n"));
+    }
+}
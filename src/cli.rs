@@ -0,0 +1,1458 @@
+//! The command line: the flags, the presets and project configs they can
+//! come from, and converting with them without opening the GUI.
+
+use notify::{RecursiveMode, Watcher};
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tracing::info;
+use crate::{DIFF_CONTEXT, diff_excerpt, glyph_label, touches_input, watch_directory};
+use crate::logging::log_to_stderr;
+use xml_to_lua::align::baseline_shifts;
+use xml_to_lua::batch::{
+    Overwrite, convert_directory, module_name, names_from_font, output_file_name, prepare_output, source_name, stale_files, with_checksums,
+    write_atomic, write_chunks,
+};
+use xml_to_lua::contact_sheet::{page_prefix, render_contact_sheet, write_contact_sheet};
+use xml_to_lua::coverage::{CoverageReport, ReportFormat};
+use xml_to_lua::diff::{diff_lines, lua_diff};
+use xml_to_lua::glyph_filter::GlyphFilter;
+use xml_to_lua::formatter::{FORMATTERS, check_module_name, output_bytes, render_chunks};
+use xml_to_lua::icons::IconNames;
+use xml_to_lua::lua_import::parse_lua_file;
+use xml_to_lua::pack::{repack, split_channels};
+use xml_to_lua::page_paths::{PagePaths, PageRule};
+use xml_to_lua::parse_cache::ParseCache;
+use xml_to_lua::parser::{parse_fnt_bytes_with, parse_fnt_with, read_source};
+use xml_to_lua::rasterize::{RasterOptions, rasterize};
+use xml_to_lua::remap::CodepointMap;
+use xml_to_lua::report::{ConvertSummary, FileReport};
+use xml_to_lua::rojo::{self, module_path};
+use xml_to_lua::runtime::{runtime_loader as runtime_loader_source, write_runtime_binary};
+use xml_to_lua::schema::check_schema_file;
+use xml_to_lua::subset::Subset;
+use xml_to_lua::synthesize::{SpaceWidth, WhitespaceRules};
+use xml_to_lua::trim::{load_pages, trim_glyphs};
+use xml_to_lua::merge::{ConflictPolicy, merge_files};
+use xml_to_lua::validate::{collect_warnings, missing_characters, pruned_kerning};
+use xml_to_lua::verify::{can_verify, verify_output};
+use xml_to_lua::error::ErrorKind;
+use xml_to_lua::{
+    CharacterOrder, ConvertError, Font, HeaderOptions, KeyEscape, ModuleStyle, OutputFormat, OutputOptions, Progress, RectStyle, Rounding, Style, UvCoordinates, VectorStyle,
+    render,
+};
+
+/// Accepts the name of any registered [`OutputFormatter`].
+fn format_parser() -> impl clap::builder::TypedValueParser<Value = OutputFormat> {
+    use clap::builder::TypedValueParser;
+    let names = FORMATTERS
+        .iter()
+        .map(|formatter| clap::builder::PossibleValue::new(formatter.name()).help(formatter.description()));
+    clap::builder::PossibleValuesParser::new(names)
+        .map(|name| OutputFormat::from_name(&name).expect("only registered names are accepted"))
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum OrderArg {
+    Codepoint,
+    /// The order the input file defines them in.
+    Source,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum KeyEscapeArg {
+    /// `["\n"]`, or decimal byte escapes where Lua has no named escape.
+    Escaped,
+    /// `[string.char(10)]`, readable by every Lua version.
+    StringChar,
+    /// `[utf8.char(0xA)]`.
+    Utf8Char,
+}
+
+impl From<KeyEscapeArg> for KeyEscape {
+    fn from(arg: KeyEscapeArg) -> Self {
+        match arg {
+            KeyEscapeArg::Escaped => KeyEscape::Escaped,
+            KeyEscapeArg::StringChar => KeyEscape::StringChar,
+            KeyEscapeArg::Utf8Char => KeyEscape::Utf8Char,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum UvArg {
+    /// UV coordinates in place of the pixel position and size.
+    Replace,
+    /// UV coordinates after the other values of each glyph.
+    Append,
+}
+
+impl From<UvArg> for UvCoordinates {
+    fn from(arg: UvArg) -> Self {
+        match arg {
+            UvArg::Replace => UvCoordinates::Replace,
+            UvArg::Append => UvCoordinates::Append,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum RoundingArg {
+    Round,
+    Floor,
+    Ceil,
+    Float,
+}
+
+impl From<RoundingArg> for Rounding {
+    fn from(arg: RoundingArg) -> Self {
+        match arg {
+            RoundingArg::Round => Rounding::Round,
+            RoundingArg::Floor => Rounding::Floor,
+            RoundingArg::Ceil => Rounding::Ceil,
+            RoundingArg::Float => Rounding::KeepFloat,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum VectorArg {
+    /// `Vector2.new(x, y)`
+    Vector2,
+    /// `{ x = x, y = y }`
+    Table,
+    /// `{ x, y }`
+    Array,
+    /// Named glyph fields such as `Width = 3, Height = 1`
+    Flat,
+}
+
+impl From<VectorArg> for VectorStyle {
+    fn from(arg: VectorArg) -> Self {
+        match arg {
+            VectorArg::Vector2 => VectorStyle::Vector2New,
+            VectorArg::Table => VectorStyle::TableLiteral,
+            VectorArg::Array => VectorStyle::Array,
+            VectorArg::Flat => VectorStyle::Flattened,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum RectArg {
+    /// `Rect.new(x, y, width, height)`
+    Rect,
+    /// `{ x, y, width, height }`
+    Array,
+}
+
+impl From<RectArg> for RectStyle {
+    fn from(arg: RectArg) -> Self {
+        match arg {
+            RectArg::Rect => RectStyle::RectNew,
+            RectArg::Array => RectStyle::Array,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ReportArg {
+    Json,
+    Markdown,
+}
+
+impl From<ReportArg> for ReportFormat {
+    fn from(arg: ReportArg) -> Self {
+        match arg {
+            ReportArg::Json => ReportFormat::Json,
+            ReportArg::Markdown => ReportFormat::Markdown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ResultReportArg {
+    Json,
+}
+
+const EXIT_CODES: &str = "Exit codes: 0 on success, 2 for invalid arguments, 3 when an input fails to parse, \
+4 when --strict, --require-chars, --verify or --check validation fails, 5 on I/O errors and 1 otherwise.";
+
+/// Converts BMFont descriptors into Lua modules. Run without arguments, or
+/// with just the path of a font, to open the GUI instead.
+#[derive(clap::Parser)]
+#[command(name = "xmltolua", version, after_help = EXIT_CODES, args_override_self = true)]
+pub struct Cli {
+    /// Input .fnt files, msdf-bmfont .json descriptors, or `-` to read a
+    /// BMFont from stdin; more than one requires --merge. A single directory
+    /// converts every .fnt under it into the --output directory. A generated
+    /// .lua module can be converted back with `--format fnt`. A .ttf or .otf
+    /// font is rasterized first, writing a .fnt and atlas next to the output.
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+    /// Where to write the output file, or the output directory; `-` writes to
+    /// stdout. Required unless the input is `-`, --stdout is given or a
+    /// project config sets `output-dir`. A single font converted into a
+    /// directory is named after --naming.
+    #[arg(short, long, value_name = "OUTPUT.lua")]
+    output: Option<PathBuf>,
+    /// Write the output to stdout instead of a file.
+    #[arg(long, conflicts_with_all = ["output", "watch"])]
+    stdout: bool,
+    /// Write the Lua module into the Rojo project with this project file
+    /// instead of to --output: as `init.lua` in a folder named after the
+    /// font under --rojo-folder, which Rojo syncs as a ModuleScript.
+    #[arg(long, value_name = "default.project.json", conflicts_with_all = ["output", "stdout"])]
+    rojo_project: Option<PathBuf>,
+    /// The folder, relative to the Rojo project file, fonts are written
+    /// into; `src/shared/Fonts` by default. A `$path` in the project's tree
+    /// must include it.
+    #[arg(long, value_name = "PATH")]
+    rojo_folder: Option<PathBuf>,
+    /// Replace output files that already exist; without it or --backup the
+    /// conversion fails instead.
+    #[arg(long)]
+    force: bool,
+    /// Copy output files that already exist to a timestamped `.bak` next to
+    /// them, then replace them.
+    #[arg(long, conflicts_with = "force")]
+    backup: bool,
+    /// Before replacing an existing output, print what changes: the glyphs
+    /// added, removed and changed and the lines that differ. Without --force
+    /// or --backup nothing is written and the exit code is 0, so this
+    /// previews a regeneration.
+    #[arg(long, conflicts_with_all = ["stdout", "watch"])]
+    diff: bool,
+    /// Parse and validate the input and render the output without writing
+    /// anything, failing with exit code 4 when the existing output (and its
+    /// part modules) would change. Meant as a CI or pre-commit gate; add
+    /// --strict to fail on warnings too, and --diff to print what changed.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "watch", "trim", "repack", "split_channels", "coverage_report", "contact_sheet", "runtime_binary",
+            "runtime_loader", "verify",
+        ]
+    )]
+    check: bool,
+    /// Read output options from this TOML file, whose keys are the long names
+    /// of the output flags (`format = "json"`, `scale = 0.5`, `numeric-keys =
+    /// true`). Flags given on the command line take precedence.
+    #[arg(long, value_name = "FILE.toml")]
+    preset: Option<PathBuf>,
+    /// Don't look for a `.xmltolua.toml` project config in the directories
+    /// above the input.
+    #[arg(long)]
+    no_config: bool,
+    /// Name output files written into a directory after this pattern, where
+    /// `{name}` is the input file name without extension, `{ext}` the output
+    /// format's extension, and `{size}`, `{line_height}` and `{glyphs}` the
+    /// exported font's size, line height and glyph count.
+    #[arg(long, value_name = "PATTERN", default_value = "{name}.{ext}")]
+    naming: String,
+    /// Output file format; ignored when --template is given.
+    #[arg(long, default_value = "lua", value_parser = format_parser())]
+    format: OutputFormat,
+    /// When converting a directory, skip fonts whose relative path matches this glob.
+    #[arg(long, value_name = "GLOB")]
+    exclude: Option<String>,
+    /// When converting a directory, the number of fonts converted in parallel;
+    /// 0 uses one thread per CPU core.
+    #[arg(long, short = 'j', value_name = "N", default_value_t = 0)]
+    jobs: usize,
+    /// Merge all inputs into a single module.
+    #[arg(long)]
+    merge: bool,
+    /// With --merge, let later inputs override earlier ones on conflicting codepoints.
+    #[arg(long, requires = "merge")]
+    keep_last: bool,
+    /// With --merge, scale factors for the glyph metrics of each input in
+    /// order, e.g. `--merge-scale 1,0.78` to shrink a fallback font.
+    #[arg(long, requires = "merge", value_name = "FACTORS", value_delimiter = ',')]
+    merge_scale: Vec<f32>,
+    /// Reconvert whenever an input changes.
+    #[arg(long)]
+    watch: bool,
+    /// Fail when the font has validation warnings.
+    #[arg(long)]
+    strict: bool,
+    /// Fail when the font lacks any of the characters in this text.
+    #[arg(long, value_name = "TEXT")]
+    require_chars: Option<String>,
+    /// Fail when the font lacks any of the characters in this text file
+    /// (combined with --require-chars).
+    #[arg(long, value_name = "FILE")]
+    require_chars_file: Option<PathBuf>,
+    /// Also write a per-block Unicode coverage report of the exported glyphs
+    /// next to the output, e.g. `font.coverage.json` for `font.lua`.
+    #[arg(long, value_enum, value_name = "FORMAT", requires = "output")]
+    coverage_report: Option<ReportArg>,
+    /// Also write a contact sheet showing every exported glyph cut out of the
+    /// atlas, for reviewing the font: an HTML page for the browser, or with
+    /// a `.png` extension an image of the glyphs labeled with their
+    /// codepoints.
+    #[arg(long, value_name = "FILE.html|FILE.png", conflicts_with = "merge")]
+    contact_sheet: Option<PathBuf>,
+    /// Also write the exported glyphs, metrics and kerning as a compact
+    /// binary file, for engines where loading a large Lua table is slow.
+    #[arg(long, value_name = "FILE.bin")]
+    runtime_binary: Option<PathBuf>,
+    /// Also write a Lua module that decodes --runtime-binary files: it
+    /// returns a function taking a file's contents and returning the font.
+    #[arg(long, value_name = "FILE.lua")]
+    runtime_loader: Option<PathBuf>,
+    /// Print the result of each conversion to stdout: status, glyph count,
+    /// warnings and where in the input an error occurred.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["watch", "stdout"])]
+    report: Option<ResultReportArg>,
+    /// Suppress warnings and progress messages; errors are still reported.
+    #[arg(short, long)]
+    quiet: bool,
+    /// Print timestamped log events of parsing, rendering and file access to
+    /// stderr, e.g. to attach to a bug report.
+    #[arg(short, long)]
+    verbose: bool,
+    /// Emit this value as Size instead of the parsed font size.
+    #[arg(long, alias = "size", value_name = "N", allow_negative_numbers = true)]
+    size_override: Option<i32>,
+    /// Indentation per level: a number of spaces (or tabs with --tabs), or `tab`.
+    #[arg(long, value_name = "N|tab", default_value = "4", value_parser = parse_indent)]
+    indent: IndentArg,
+    /// Indent with tabs instead of spaces.
+    #[arg(long)]
+    tabs: bool,
+    /// Leave out the comma after the last entry of multi-line tables.
+    #[arg(long)]
+    no_trailing_commas: bool,
+    /// Leave out the newline at the end of the written output.
+    #[arg(long)]
+    no_trailing_newline: bool,
+    /// Start the written output with a UTF-8 byte order mark.
+    #[arg(long)]
+    bom: bool,
+    /// Write each value of a glyph table on its own line.
+    #[arg(long)]
+    field_per_line: bool,
+    /// Key characters by codepoint (`[65]`) instead of the literal character.
+    #[arg(long)]
+    numeric_keys: bool,
+    /// How keys for control characters, which a string literal can only
+    /// spell with an escape, are written.
+    #[arg(long, value_enum, default_value = "escaped")]
+    key_escapes: KeyEscapeArg,
+    /// Write glyph rects as UV coordinates, divided by the texture size, in
+    /// place of the pixels or after them (Lua and JSON).
+    #[arg(long, value_enum, value_name = "MODE")]
+    uv: Option<UvArg>,
+    /// Key glyphs by their `letter` attribute when the source has one.
+    #[arg(long)]
+    letters: bool,
+    /// Start with `--!strict` and export Luau types for the font table.
+    #[arg(long)]
+    luau_types: bool,
+    /// Add a `MeasureText(text)` function returning the width of `text` in
+    /// pixels, from the advances and kerning.
+    #[arg(long, conflicts_with = "metrics_only")]
+    measure_text: bool,
+    /// Split the `Characters` table across part modules of at most N glyphs,
+    /// written next to the output as `NAME_part1.lua` and so on, which the
+    /// output module loads when it is required. Lua output only.
+    #[arg(long, value_name = "N", conflicts_with = "metrics_only")]
+    chunk_size: Option<usize>,
+    /// Write the glyphs as one packed string that a small decoder in the
+    /// module turns back into the `Characters` table when it is required;
+    /// several times smaller for fonts with many glyphs. Metrics are
+    /// rounded to whole pixels.
+    #[arg(long, conflicts_with_all = ["metrics_only", "chunk_size"])]
+    packed: bool,
+    /// Write each distinct size, offset and advance once in a `Shared` list
+    /// that glyphs refer to by index, which shrinks fonts whose glyphs
+    /// mostly share metrics, such as monospace and icon fonts.
+    #[arg(long, conflicts_with_all = ["metrics_only", "packed"])]
+    dedupe_metrics: bool,
+    /// Write the whole output on a single line, without indentation.
+    #[arg(long)]
+    minify: bool,
+    /// Emit only the font-wide metrics, without per-glyph data.
+    #[arg(long)]
+    metrics_only: bool,
+    /// Write each glyph as just its advance, leaving out the atlas data, for
+    /// measuring text width. Lua and JSON output only.
+    #[arg(long, conflicts_with = "metrics_only")]
+    advances_only: bool,
+    /// Leave out the kerning pairs.
+    #[arg(long)]
+    no_kerning: bool,
+    /// Write kerning pairs with a glyph that is not exported, because the
+    /// subset leaves it out or the font does not define it, instead of
+    /// dropping them.
+    #[arg(long, conflicts_with = "no_kerning")]
+    keep_unused_kerning: bool,
+    /// Write the CRC-32 of each texture page as `PageChecksums`, to detect
+    /// when the metrics and the uploaded texture drift out of sync.
+    #[arg(long)]
+    texture_checksums: bool,
+    /// Shrink glyph rects by the padding recorded in `<info>`, adjusting
+    /// offsets so glyphs render in place.
+    #[arg(long)]
+    bake_padding: bool,
+    /// Write how far the glyphs reach above and below the baseline, as
+    /// `Ascent` and `Descent`.
+    #[arg(long)]
+    vertical_metrics: bool,
+    /// Only export these codepoints, e.g. `0x20-0x7E,0x400-0x4FF`.
+    #[arg(long, value_name = "RANGES", value_parser = parse_subset)]
+    subset: Option<Subset>,
+    /// Only export the characters in this text (combined with --subset).
+    #[arg(long, value_name = "TEXT")]
+    subset_text: Option<String>,
+    /// Drop glyphs whose rect has no width or height, such as the space.
+    #[arg(long)]
+    drop_empty_glyphs: bool,
+    /// Drop glyphs without a visible pixel in the atlas, which is read from
+    /// next to the input.
+    #[arg(long)]
+    drop_blank_glyphs: bool,
+    /// Only export printable characters, dropping control characters,
+    /// noncharacters and invisible format characters.
+    #[arg(long)]
+    printable_only: bool,
+    /// With a .ttf/.otf input, the em size to rasterize at, in pixels.
+    #[arg(long, value_name = "PX", default_value_t = 32.0)]
+    px_size: f32,
+    /// With a .ttf/.otf input, the codepoints to rasterize.
+    #[arg(long, value_name = "RANGES", default_value = "0x20-0x7E", value_parser = parse_subset)]
+    charset: Subset,
+    /// With a .ttf/.otf input, the width and height of each atlas page.
+    #[arg(long, value_name = "N", default_value_t = 512)]
+    texture_size: u32,
+    /// Shrink glyph rects to the pixels of the atlas with alpha above this
+    /// value (0 when given without one), adjusting offsets to match.
+    #[arg(long, value_name = "ALPHA", num_args = 0..=1, default_missing_value = "0", conflicts_with = "merge")]
+    trim: Option<u8>,
+    /// Copy the glyphs into the smallest power-of-two atlas that holds them,
+    /// at most MAX pixels wide and high (4096 when given without a value).
+    /// The new pages are written next to the output as `NAME_packed_0.png`.
+    #[arg(long, value_name = "MAX", num_args = 0..=1, default_missing_value = "4096", conflicts_with = "merge")]
+    repack: Option<u32>,
+    /// Split a channel-packed atlas into a page per channel, white with the
+    /// glyphs in alpha, for runtimes that cannot select a channel in a
+    /// shader. The new pages are written next to the output as
+    /// `NAME_channels_0.png`.
+    #[arg(long, conflicts_with = "merge")]
+    split_channels: bool,
+    /// Multiply every metric by this factor before rounding.
+    #[arg(long, value_name = "FACTOR", default_value_t = 1.0, value_parser = parse_scale)]
+    scale: f32,
+    /// Write a Lua sub-table per scale, e.g. `1,0.75,0.5`, keyed by the
+    /// scale, so UI code can pick the closest pre-scaled metrics. Replaces
+    /// --scale.
+    #[arg(long, value_name = "FACTORS", value_delimiter = ',', conflicts_with = "scale", value_parser = parse_scale)]
+    scales: Vec<f32>,
+    /// Grow every glyph rect by N atlas pixels on each side (shrink when
+    /// negative), adjusting offsets so glyphs render in place.
+    #[arg(long, value_name = "N", default_value_t = 0, allow_negative_numbers = true)]
+    padding: i32,
+    /// How `(x, y)` pairs such as sizes and offsets are written.
+    #[arg(long, value_enum, default_value = "vector2")]
+    vectors: VectorArg,
+    /// Write pairs as calls to this function, e.g. `vec2(x, y)`.
+    #[arg(long, value_name = "NAME", conflicts_with = "vectors")]
+    vector_function: Option<String>,
+    /// Combine each glyph's atlas position and size into one rect.
+    #[arg(long, value_enum, value_name = "STYLE")]
+    rects: Option<RectArg>,
+    /// How fractional coordinates are rounded.
+    #[arg(long, value_enum, default_value = "round")]
+    rounding: RoundingArg,
+    /// Codepoints never exported; an empty list keeps them all.
+    #[arg(long, value_name = "RANGES", default_value = "0,13", value_parser = parse_subset)]
+    exclude_chars: Subset,
+    /// The order glyphs are listed in.
+    #[arg(long, value_enum, default_value = "codepoint")]
+    order: OrderArg,
+    /// List glyphs in these ranges first, range by range, e.g. `0x20-0x7E`
+    /// for ASCII first; the rest follow by codepoint.
+    #[arg(long, value_name = "RANGES", value_parser = parse_subset, conflicts_with = "order")]
+    priority: Option<Subset>,
+    /// Render through this Handlebars template instead of the built-in Lua layout.
+    #[arg(long, value_name = "FILE")]
+    template: Option<PathBuf>,
+    /// Only export the glyphs on these texture pages, given by id, e.g.
+    /// `0,2`, leaving the other pages out of the output too.
+    #[arg(long, value_name = "IDS", value_delimiter = ',')]
+    pages: Option<Vec<u32>>,
+    /// Move every glyph down by this many pixels, with the base and line
+    /// height, recorded in the Lua output as `BaselineShift`.
+    #[arg(long, value_name = "PX", default_value_t = 0.0, allow_negative_numbers = true)]
+    baseline_shift: f32,
+    /// Shift the baseline to line up with these fonts of the same family,
+    /// e.g. the bold next to the regular, so they can be mixed in one run of
+    /// text. Can be given more than once.
+    #[arg(long, value_name = "FONT", conflicts_with = "baseline_shift")]
+    align_baseline_with: Vec<PathBuf>,
+    /// Write the texture pages whose file name matches PATTERN, a glob, as
+    /// REPLACEMENT, e.g. `font_0.png=rbxassetid://123456` or
+    /// `*.png=rbxasset://textures/fonts/{file}`. `{file}`, `{stem}` and
+    /// `{page}` stand for the file name, the name without its extension and
+    /// the page id. Can be given more than once; the last matching rule
+    /// wins.
+    #[arg(long, value_name = "PATTERN=REPLACEMENT", value_parser = parse_page_rule)]
+    page_path: Vec<PageRule>,
+    /// Move glyphs to other codepoints before export, as listed in a CSV
+    /// file of `from,to` lines or a TOML file of `"from" = "to"` keys, e.g.
+    /// `U+E000,U+2605`. --subset and --exclude-chars use the new codepoints.
+    #[arg(long, value_name = "FILE")]
+    remap: Option<PathBuf>,
+    /// Add an `Icons` table naming the icons of an icon font, e.g.
+    /// `Icons = { ["gear"] = 0xF013 }`, from a CSV file of `name,codepoint`
+    /// lines or a JSON object such as the `glyphnames.json` of Nerd Fonts.
+    #[arg(long, value_name = "FILE")]
+    icons: Option<PathBuf>,
+    /// Add empty glyphs for a space, tab and no-break space when the font
+    /// has none, marked `Synthesized = true`. A space is as wide as WIDTH:
+    /// `digits` for the average digit advance (the default), a fraction of
+    /// the font size such as `0.25em`, or pixels such as `8px`.
+    #[arg(long, value_name = "WIDTH", num_args = 0..=1, default_missing_value = "digits", value_parser = parse_space_width)]
+    synthesize_whitespace: Option<SpaceWidth>,
+    /// How many spaces a synthesized tab advances.
+    #[arg(long, value_name = "N", default_value_t = 4.0, requires = "synthesize_whitespace")]
+    tab_spaces: f32,
+    /// Prepend a "generated by" comment.
+    #[arg(long)]
+    header: bool,
+    /// Add the generation time to the header comment (implies --header).
+    #[arg(long)]
+    header_timestamp: bool,
+    /// Add the source checksum, converter version, generation time and the
+    /// options that differ from the defaults to the header comment (implies
+    /// --header).
+    #[arg(long)]
+    provenance: bool,
+    /// Clamp negative widths, heights and advances to zero.
+    #[arg(long)]
+    clamp_negative: bool,
+    /// Keep the sign of a negative <info size>.
+    #[arg(long)]
+    keep_negative_size: bool,
+    /// Build a named table (`local NAME = {}`) instead of a bare return.
+    #[arg(long, value_name = "NAME", value_parser = parse_module_name)]
+    module: Option<String>,
+    /// With --module, assign fields as NAME["Key"] instead of NAME.Key.
+    #[arg(long, requires = "module")]
+    indexed: bool,
+    /// Read the output back after writing it and fail if it does not hold
+    /// the exported glyphs, metrics and kerning. Works for Lua, JSON and FNT
+    /// output without --template.
+    #[arg(long)]
+    verify: bool,
+    /// Skip elements of the input that fail to parse, listing them as
+    /// warnings, instead of failing the conversion.
+    #[arg(long)]
+    lenient: bool,
+    /// Check XML input against the BMFont structure before parsing it, and
+    /// fail listing every missing element or attribute and malformed value
+    /// with its line and column.
+    #[arg(long)]
+    check_schema: bool,
+}
+
+#[derive(Clone, Copy)]
+struct IndentArg {
+    tabs: bool,
+    width: usize,
+}
+
+fn parse_indent(value: &str) -> Result<IndentArg, String> {
+    if value == "tab" {
+        return Ok(IndentArg { tabs: true, width: 1 });
+    }
+    value
+        .parse()
+        .map(|width| IndentArg { tabs: false, width })
+        .map_err(|_| format!("expected a number of spaces or `tab`, got {value:?}"))
+}
+
+/// A scale factor: a finite number greater than 0, since `nan` would be
+/// written into the output as `NaN` and 0 or less flattens or mirrors every
+/// glyph.
+fn parse_scale(value: &str) -> Result<f32, String> {
+    match value.trim().parse::<f32>() {
+        Ok(scale) if scale.is_finite() && scale > 0.0 => Ok(scale),
+        _ => Err(format!("expected a finite number greater than 0, got {value:?}")),
+    }
+}
+
+fn parse_space_width(value: &str) -> Result<SpaceWidth, String> {
+    SpaceWidth::parse(value).ok_or_else(|| format!("expected `digits`, a fraction such as `0.25em` or pixels such as `8px`, got {value:?}"))
+}
+
+fn parse_page_rule(value: &str) -> Result<PageRule, String> {
+    PageRule::parse(value).map_err(|e| e.to_string())
+}
+
+fn parse_module_name(value: &str) -> Result<String, String> {
+    check_module_name(value).map_err(|e| e.to_string())?;
+    Ok(value.to_string())
+}
+
+fn parse_subset(value: &str) -> Result<Subset, String> {
+    Subset::parse_ranges(value).map_err(|e| e.to_string())
+}
+
+impl Cli {
+    fn output_options(&self) -> Result<OutputOptions, Box<dyn std::error::Error>> {
+        let mut options = OutputOptions {
+            format: self.format,
+            trailing_newline: !self.no_trailing_newline,
+            bom: self.bom,
+            size_override: self.size_override,
+            style: Style {
+                use_tabs: self.tabs || self.indent.tabs,
+                indent_width: self.indent.width,
+                trailing_commas: !self.no_trailing_commas,
+                field_per_line: self.field_per_line,
+                minified: self.minify,
+            },
+            numeric_keys: self.numeric_keys,
+            key_escape: self.key_escapes.into(),
+            uv: self.uv.map_or(UvCoordinates::Off, Into::into),
+            luau_types: self.luau_types,
+            measure_text: self.measure_text,
+            chunk_size: self.chunk_size,
+            packed: self.packed,
+            dedupe_metrics: self.dedupe_metrics,
+            prefer_letters: self.letters,
+            metrics_only: self.metrics_only,
+            advances_only: self.advances_only,
+            kerning: !self.no_kerning,
+            prune_kerning: !self.keep_unused_kerning,
+            texture_checksums: self.texture_checksums,
+            bake_padding: self.bake_padding,
+            vertical_metrics: self.vertical_metrics,
+            vector_style: match self.vector_function {
+                Some(ref name) => VectorStyle::FunctionCall(name.clone()),
+                None => self.vectors.into(),
+            },
+            rect_style: self.rects.map(Into::into),
+            rounding: self.rounding.into(),
+            excluded: self.exclude_chars.codepoints(),
+            character_order: match (&self.priority, self.order) {
+                (Some(ranges), _) => CharacterOrder::Priority(ranges.clone()),
+                (None, OrderArg::Codepoint) => CharacterOrder::Codepoint,
+                (None, OrderArg::Source) => CharacterOrder::Source,
+            },
+            scale: self.scale,
+            scales: self.scales.clone(),
+            padding: self.padding,
+            clamp_negative: self.clamp_negative,
+            absolute_size: !self.keep_negative_size,
+            template: self.template.as_deref().map(std::fs::read_to_string).transpose()?,
+            remap: self.remap.as_deref().map(read_codepoint_map).transpose()?,
+            icons: self.icons.as_deref().map(read_icon_names).transpose()?,
+            synthesize: self.synthesize_whitespace.map(|space| WhitespaceRules {
+                space,
+                tab_spaces: self.tab_spaces,
+            }),
+            pages: self.pages.as_ref().map(|pages| pages.iter().copied().collect()),
+            baseline_shift: self.baseline_shift,
+            page_paths: PagePaths {
+                rules: self.page_path.clone(),
+            },
+            glyph_filter: GlyphFilter {
+                empty_rects: self.drop_empty_glyphs,
+                blank_pixels: self.drop_blank_glyphs,
+                printable_only: self.printable_only,
+                ..Default::default()
+            },
+            file_name: Some(self.naming.clone()),
+            verify: self.verify,
+            lenient: self.lenient,
+            check_schema: self.check_schema,
+            overwrite: match (self.force, self.backup) {
+                (_, true) => Overwrite::Backup,
+                (true, false) => Overwrite::Replace,
+                (false, false) => Overwrite::Refuse,
+            },
+            ..Default::default()
+        };
+        if self.header || self.header_timestamp || self.provenance {
+            options.header = Some(HeaderOptions {
+                timestamp: self.header_timestamp,
+                provenance: self.provenance,
+                ..Default::default()
+            });
+        }
+        if self.subset.is_some() || self.subset_text.is_some() {
+            let ranges = self.subset.clone().unwrap_or_default();
+            let text = Subset::from_text(self.subset_text.as_deref().unwrap_or_default());
+            options.subset = Some(ranges.union(text));
+        }
+        if let Some(ref name) = self.module {
+            options.module_style = ModuleStyle::NamedTable {
+                name: name.clone(),
+                indexed: self.indexed,
+            };
+        }
+        Ok(options)
+    }
+}
+
+/// The flags a preset file may set: those that only affect the output.
+const PRESET_KEYS: &[&str] = &[
+    "format",
+    "size-override",
+    "indent",
+    "tabs",
+    "no-trailing-commas",
+    "no-trailing-newline",
+    "bom",
+    "field-per-line",
+    "numeric-keys",
+    "key-escapes",
+    "uv",
+    "letters",
+    "luau-types",
+    "measure-text",
+    "chunk-size",
+    "packed",
+    "dedupe-metrics",
+    "minify",
+    "metrics-only",
+    "advances-only",
+    "no-kerning",
+    "keep-unused-kerning",
+    "texture-checksums",
+    "vertical-metrics",
+    "subset",
+    "subset-text",
+    "drop-empty-glyphs",
+    "drop-blank-glyphs",
+    "printable-only",
+    "scale",
+    "scales",
+    "padding",
+    "bake-padding",
+    "vectors",
+    "vector-function",
+    "rects",
+    "rounding",
+    "order",
+    "priority",
+    "exclude-chars",
+    "lenient",
+    "check-schema",
+    "template",
+    "remap",
+    "icons",
+    "synthesize-whitespace",
+    "tab-spaces",
+    "pages",
+    "baseline-shift",
+    "page-path",
+    "header",
+    "header-timestamp",
+    "provenance",
+    "clamp-negative",
+    "keep-negative-size",
+    "module",
+    "indexed",
+    "naming",
+    "verify",
+];
+
+/// The file name of the project config searched for above each input.
+const PROJECT_CONFIG: &str = ".xmltolua.toml";
+
+/// Finds the project config governing `input` in its directory or the
+/// nearest ancestor that has one, the way rustfmt finds `rustfmt.toml`.
+pub fn find_project_config(input: &Path) -> Option<PathBuf> {
+    let input = std::path::absolute(input).ok()?;
+    input
+        .ancestors()
+        .skip(1)
+        .map(|directory| directory.join(PROJECT_CONFIG))
+        .find(|config| config.is_file())
+}
+
+/// Reads the codepoint mapping at `path`: TOML when it has a `.toml`
+/// extension, with a `"from" = "to"` key per glyph and codepoints as strings
+/// or integers, and CSV otherwise.
+pub fn read_codepoint_map(path: &Path) -> Result<CodepointMap, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)?;
+    if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")) {
+        return Ok(CodepointMap::parse_csv(&text).map_err(|e| format!("{}: {e}", path.display()))?);
+    }
+    let document: toml_edit::DocumentMut = text.parse().map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut map = CodepointMap::default();
+    for (key, item) in document.iter() {
+        let to = match item.as_value() {
+            Some(toml_edit::Value::String(value)) => CodepointMap::parse_codepoint(value.value())?,
+            Some(toml_edit::Value::Integer(value)) => u32::try_from(*value.value())?,
+            _ => return Err(format!("{}: `{key}` must map to a codepoint", path.display()).into()),
+        };
+        map.insert(CodepointMap::parse_codepoint(key)?, to);
+    }
+    Ok(map)
+}
+
+/// Whether `path` has a `.png` extension, which makes contact sheets images
+/// rather than HTML pages.
+pub fn is_png(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+}
+
+/// Reads the icon names at `path`: JSON when it has a `.json` extension, and
+/// CSV otherwise.
+pub fn read_icon_names(path: &Path) -> Result<IconNames, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let names = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        IconNames::parse_json(&text)
+    } else {
+        IconNames::parse_csv(&text)
+    };
+    Ok(names.map_err(|e| format!("{}: {e}", path.display()))?)
+}
+
+/// Turns the TOML preset at `path` into the command-line flags it stands for,
+/// so presets accept exactly the values the flags do. Relative `template`,
+/// `remap` and `icons` paths are resolved against the preset's directory.
+fn preset_args(path: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    config_args(path, &[])
+}
+
+/// Like [`preset_args`], additionally accepting `extra_keys`. An `output-dir`
+/// key sets `--output` to that directory, relative to the config's, as a
+/// `rojo-project` path is.
+fn config_args(path: &Path, extra_keys: &[&str]) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let document: toml_edit::DocumentMut = std::fs::read_to_string(path)?
+        .parse()
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut args = Vec::new();
+    for (key, item) in document.iter() {
+        let flag = key.replace('_', "-");
+        if !PRESET_KEYS.contains(&flag.as_str()) && !extra_keys.contains(&flag.as_str()) {
+            return Err(format!("{}: unknown preset key `{key}`", path.display()).into());
+        }
+        let value = match item.as_value() {
+            Some(toml_edit::Value::Boolean(value)) => {
+                if *value.value() {
+                    args.push(format!("--{flag}"));
+                }
+                continue;
+            }
+            Some(toml_edit::Value::String(value)) if flag == "output-dir" => {
+                let directory = path.parent().unwrap_or(Path::new("")).join(value.value());
+                args.push(format!("--output={}{}", directory.display(), std::path::MAIN_SEPARATOR));
+                continue;
+            }
+            Some(toml_edit::Value::String(value)) if ["template", "remap", "icons", "rojo-project"].contains(&flag.as_str()) => {
+                path.parent().unwrap_or(Path::new("")).join(value.value()).display().to_string()
+            }
+            Some(toml_edit::Value::String(value)) => value.value().clone(),
+            Some(toml_edit::Value::Integer(value)) => value.value().to_string(),
+            Some(toml_edit::Value::Float(value)) => value.value().to_string(),
+            // An array gives a repeatable flag once per string, e.g. a rule
+            // per `page-path`.
+            Some(toml_edit::Value::Array(values)) => {
+                for value in values {
+                    let Some(value) = value.as_str() else {
+                        return Err(format!("{}: `{key}` must list strings", path.display()).into());
+                    };
+                    args.push(format!("--{flag}={value}"));
+                }
+                continue;
+            }
+            _ => return Err(format!("{}: `{key}` must be a string, number, boolean or array", path.display()).into()),
+        };
+        args.push(format!("--{flag}={value}"));
+    }
+    Ok(args)
+}
+
+/// Parses the command line, applying the flags of the project config, then
+/// those of `--preset`, before the ones given explicitly so the latter win.
+pub fn parse_cli() -> Result<Cli, Box<dyn std::error::Error>> {
+    let cli: Cli = clap::Parser::parse();
+    let config = match cli.inputs.first() {
+        Some(input) if !cli.no_config && input != Path::new("-") => find_project_config(input),
+        _ => None,
+    };
+    if config.is_none() && cli.preset.is_none() {
+        return Ok(cli);
+    }
+
+    let mut defaults = Vec::new();
+    if let Some(ref config) = config {
+        if !cli.quiet {
+            eprintln!("Using {}", config.display());
+        }
+        defaults.extend(config_args(config, &["output-dir", "rojo-project", "rojo-folder"])?);
+    }
+    if let Some(ref preset) = cli.preset {
+        defaults.extend(preset_args(preset)?);
+    }
+    let mut args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    args.splice(1..1, defaults.into_iter().map(Into::into));
+    Ok(clap::Parser::parse_from(args))
+}
+
+/// The output options a preset file selects, for applying it in the GUI.
+pub fn preset_options(path: &Path) -> Result<OutputOptions, Box<dyn std::error::Error>> {
+    let args = std::iter::once("xmltolua".to_string())
+        .chain(preset_args(path)?)
+        .chain(std::iter::once("-".to_string()));
+    let cli: Cli = clap::Parser::try_parse_from(args)?;
+    cli.output_options()
+}
+
+/// Parses `input` as a BMFont file, or as a previously generated Lua module
+/// when it has a `.lua` extension. `-` reads a BMFont from stdin. `lenient`
+/// skips malformed BMFont elements; see [`parse_fnt_with`].
+pub fn read_font(input: &Path, lenient: bool, progress: impl FnMut(Progress)) -> Result<Font, ConvertError> {
+    let filename = input.display().to_string();
+    if input == Path::new("-") {
+        let mut bytes = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut bytes)?;
+        parse_fnt_bytes_with(&bytes, lenient, progress)
+    } else if input.extension().is_some_and(|ext| ext == "lua") {
+        parse_lua_file(&filename)
+    } else {
+        parse_fnt_with(&filename, lenient, progress)
+    }
+}
+
+/// Whether `input` is a TrueType or OpenType font to rasterize.
+fn is_outline_font(input: &Path) -> bool {
+    input
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"))
+}
+
+pub fn run_cli(mut cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    if cli.verbose {
+        log_to_stderr();
+    }
+    let from_stdin = cli.inputs.iter().any(|input| input == Path::new("-"));
+    if cli.output.as_deref() == Some(Path::new("-")) || (cli.output.is_none() && from_stdin) {
+        cli.output = None;
+        cli.stdout = true;
+    }
+    let options = cli.output_options()?;
+    // Not `requires`, which clap skips when --output, which conflicts with
+    // --rojo-project, is given.
+    if cli.rojo_folder.is_some() && cli.rojo_project.is_none() {
+        return Err("--rojo-folder needs --rojo-project".into());
+    }
+    if options.check_schema && from_stdin {
+        return Err("--check-schema needs a font file as input".into());
+    }
+    if let Some(ref project) = cli.rojo_project {
+        if from_stdin || cli.inputs[0].is_dir() {
+            return Err("--rojo-project needs a font file as input".into());
+        }
+        if !matches!(options.format, OutputFormat::Lua) {
+            return Err("--rojo-project writes a Lua module and cannot be used with other formats".into());
+        }
+        let folder = cli.rojo_folder.as_deref().unwrap_or(Path::new(rojo::DEFAULT_FOLDER));
+        let module = module_path(project, folder, &cli.inputs[0], &options)?;
+        std::fs::create_dir_all(module.parent().unwrap_or(Path::new("")))?;
+        cli.output = Some(module);
+    }
+    if cli.output.is_none() && !cli.stdout {
+        return Err("--output is required unless writing to stdout".into());
+    }
+    if cli.stdout && cli.report.is_some() {
+        return Err("--report cannot be used when writing the output to stdout".into());
+    }
+    if from_stdin && cli.watch {
+        return Err("--watch cannot be used when reading from stdin".into());
+    }
+    if options.chunk_size.is_some() {
+        if !matches!(options.format, OutputFormat::Lua) || options.template.is_some() {
+            return Err("--chunk-size splits the built-in Lua output and cannot be used with other formats or --template".into());
+        }
+        if cli.stdout {
+            return Err("--chunk-size needs --output to write the part modules next to".into());
+        }
+    }
+    if options.verify && !can_verify(&options) {
+        return Err("--verify can only read back Lua, JSON, FNT and fnt-text output without --template, --advances-only, --uv, --chunk-size or --scales".into());
+    }
+    let raster_options = RasterOptions {
+        size: cli.px_size,
+        charset: cli.charset.clone(),
+        texture_size: cli.texture_size,
+        ..Default::default()
+    };
+    let mut required_chars = cli.require_chars.clone().unwrap_or_default();
+    if let Some(ref path) = cli.require_chars_file {
+        required_chars.push_str(&std::fs::read_to_string(path)?);
+    }
+    let policy = if cli.keep_last {
+        ConflictPolicy::KeepLast
+    } else {
+        ConflictPolicy::KeepFirst
+    };
+    let Cli {
+        inputs,
+        output,
+        merge,
+        merge_scale,
+        watch,
+        quiet,
+        strict,
+        exclude,
+        jobs,
+        coverage_report,
+        contact_sheet,
+        runtime_binary,
+        runtime_loader,
+        trim,
+        repack: repack_size,
+        split_channels: split,
+        report,
+        diff,
+        check,
+        align_baseline_with,
+        ..
+    } = cli;
+    let mut output = output;
+
+    if let [root] = inputs.as_slice()
+        && root.is_dir()
+    {
+        if merge
+            || watch
+            || strict
+            || !required_chars.is_empty()
+            || coverage_report.is_some()
+            || contact_sheet.is_some()
+            || runtime_binary.is_some()
+            || trim.is_some()
+            || repack_size.is_some()
+            || split
+            || diff
+            || check
+            || !align_baseline_with.is_empty()
+        {
+            return Err(
+                "--merge, --watch, --strict, --require-chars, --coverage-report, --contact-sheet, --runtime-binary, --trim, --repack, --split-channels, --diff, --check and --align-baseline-with cannot be used with a directory"
+                    .into(),
+            );
+        }
+        let Some(output) = output else {
+            return Err("--stdout cannot be used with a directory".into());
+        };
+        let started = Instant::now();
+        let results = convert_directory(root, &output, exclude.as_deref(), &options, jobs, |_| {})?;
+        let mut failed = 0;
+        for batch in &results {
+            match batch.result {
+                Ok(ref lua) if !quiet && report.is_none() => println!("{} -> {}", batch.input.display(), lua.display()),
+                Ok(_) => {}
+                Err(ref e) => {
+                    failed += 1;
+                    eprintln!("{}: {e}", batch.input.display());
+                }
+            }
+        }
+        if !quiet {
+            let elapsed = started.elapsed().as_secs_f32();
+            eprintln!("Converted {} of {} files in {elapsed:.2}s", results.len() - failed, results.len());
+        }
+        if report.is_some() {
+            let reports: Vec<&FileReport> = results.iter().map(|batch| &batch.report).collect();
+            println!("{}", serde_json::to_string_pretty(&reports)?);
+        }
+        if failed > 0 {
+            return Err(Box::new(BatchFailed {
+                kind: results
+                    .iter()
+                    .find_map(|batch| batch.report.error.as_ref())
+                    .map_or(ErrorKind::Other, |error| error.kind),
+                message: format!("{failed} of {} files failed to convert", results.len()),
+            }));
+        }
+        return Ok(());
+    }
+
+    if inputs.len() > 1 && !merge {
+        return Err("multiple inputs require --merge".into());
+    }
+    if let Some(directory) = output.take_if(|output| {
+        output.is_dir() || output.as_os_str().to_string_lossy().ends_with(['/', std::path::MAIN_SEPARATOR])
+    }) {
+        std::fs::create_dir_all(&directory)?;
+        // Naming after the font needs it parsed first, which stdin and outline
+        // fonts are not until they are converted.
+        let font = if names_from_font(&options) {
+            if from_stdin || is_outline_font(&inputs[0]) {
+                return Err("--naming with {size}, {line_height} or {glyphs} needs a font file as input".into());
+            }
+            Some(read_font(&inputs[0], options.lenient, |_| {})?)
+        } else {
+            None
+        };
+        output = Some(directory.join(output_file_name(&inputs[0], font.as_ref(), &options)));
+    }
+    if inputs.iter().any(|input| is_outline_font(input)) && (merge || output.is_none()) {
+        return Err("a .ttf or .otf input needs --output and cannot be merged".into());
+    }
+    if check && inputs.iter().any(|input| is_outline_font(input)) {
+        return Err("--check cannot be used with a .ttf or .otf input, which is rasterized into a new .fnt".into());
+    }
+    if repack_size.is_some() && output.is_none() {
+        return Err("--repack needs --output to write the new atlas next to".into());
+    }
+    if split && output.is_none() {
+        return Err("--split-channels needs --output to write the new pages next to".into());
+    }
+    // Checked once: rewriting the output on each change in --watch mode is
+    // expected.
+    let mut refused = false;
+    if let Some(ref output) = output
+        && !check
+    {
+        match prepare_output(output, options.overwrite) {
+            Ok(Some(backup)) if !quiet => eprintln!("Backed up {} to {}", output.display(), backup.display()),
+            Ok(_) => {}
+            // Refused once the changes have been shown.
+            Err(ConvertError::OutputExists(_)) if diff => refused = true,
+            Err(ConvertError::OutputExists(_)) => {
+                return Err(format!("{} already exists; pass --force to replace it or --backup to keep a copy", output.display()).into());
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    // In --watch mode a save that leaves the input as it was is not parsed again.
+    let cache = ParseCache::default();
+    // Read once; in --watch mode only the converted font is read again.
+    let family = align_baseline_with
+        .iter()
+        .map(|path| read_font(path, options.lenient, |_| {}))
+        .collect::<Result<Vec<Font>, _>>()?;
+    let convert = || -> Result<ConvertSummary, ConvertError> {
+        let font = if merge {
+            let (font, warnings) = merge_files(&inputs, &merge_scale, policy)?;
+            if !quiet {
+                for warning in warnings {
+                    eprintln!("{warning}");
+                }
+            }
+            font
+        } else if is_outline_font(&inputs[0]) {
+            let fnt_path = output.as_deref().expect("checked above").with_extension("fnt");
+            let name = fnt_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            let raster = rasterize(&std::fs::read(&inputs[0])?, &name, &raster_options)?;
+            raster.save(&fnt_path)?;
+            if !quiet {
+                eprintln!("Generated {} and {} atlas page(s)", fnt_path.display(), raster.pages.len());
+            }
+            raster.font
+        } else {
+            if options.check_schema {
+                check_schema_file(&inputs[0].display().to_string())?;
+            }
+            let mut font = cache.parse(&inputs[0], options.lenient, || read_font(&inputs[0], options.lenient, |_| {}))?;
+            if trim.is_some() || repack_size.is_some() || split {
+                let mut pages = load_pages(&font, inputs[0].parent().unwrap_or(Path::new("")))?;
+                if let Some(threshold) = trim {
+                    let trimmed = trim_glyphs(&mut font, &pages, threshold);
+                    if !quiet {
+                        eprintln!("Trimmed {trimmed} glyph rects");
+                    }
+                }
+                if let Some(max_size) = repack_size {
+                    let output = output.as_deref().expect("checked above");
+                    let name = format!("{}_packed", output.file_stem().unwrap_or_default().to_string_lossy());
+                    let packed = repack(&font, &pages, &name, 1, max_size)?;
+                    packed.save_pages(output.parent().unwrap_or(Path::new("")))?;
+                    if !quiet && let Some(ref common) = packed.font.metrics.common {
+                        eprintln!(
+                            "Repacked {} page(s) into {} {}×{} page(s)",
+                            pages.len(),
+                            packed.pages.len(),
+                            common.scale_w,
+                            common.scale_h
+                        );
+                    }
+                    font = packed.font;
+                    pages = packed.pages.into_iter().enumerate().map(|(id, page)| (id as u32, page)).collect();
+                }
+                if split {
+                    let output = output.as_deref().expect("checked above");
+                    let name = format!("{}_channels", output.file_stem().unwrap_or_default().to_string_lossy());
+                    let channels = split_channels(&font, &pages, &name);
+                    channels.save_pages(output.parent().unwrap_or(Path::new("")))?;
+                    if !quiet {
+                        eprintln!("Split {} page(s) into {} page(s)", pages.len(), channels.pages.len());
+                    }
+                    font = channels.font;
+                }
+            }
+            font
+        };
+
+        let warnings = collect_warnings(&font);
+        if !quiet {
+            for warning in &warnings {
+                eprintln!("{warning}");
+            }
+        }
+        if strict && !warnings.is_empty() {
+            return Err(ConvertError::Validation(warnings));
+        }
+        let missing = missing_characters(&font, &required_chars);
+        if !missing.is_empty() {
+            for c in &missing {
+                eprintln!("Missing required character {c:?} (U+{:04X})", *c as u32);
+            }
+            return Err(ConvertError::MissingCharacters(missing));
+        }
+        let pruned = pruned_kerning(&font, &options);
+        if !quiet && pruned.total() > 0 {
+            eprintln!(
+                "Dropped {} of {} kerning pairs: {} outside the subset, {} with a missing glyph",
+                pruned.total(),
+                font.metrics.kerning.len(),
+                pruned.unexported,
+                pruned.missing
+            );
+        }
+
+        let mut rendered = with_checksums(&options, &font, &inputs)?;
+        if !family.is_empty() {
+            let fonts: Vec<&Font> = std::iter::once(&font).chain(&family).collect();
+            let shift = baseline_shifts(&fonts)[0];
+            if !quiet && shift == 0.0 {
+                eprintln!("The baseline already lines up with {}", source_name(&align_baseline_with));
+            } else if !quiet {
+                eprintln!("Shifted the baseline by {shift}px to line up with {}", source_name(&align_baseline_with));
+            }
+            rendered.to_mut().baseline_shift = shift;
+        }
+        match output {
+            Some(ref output) => {
+                let chunks = render_chunks(&font, &source_name(&inputs), &rendered, &module_name(output))?;
+                if diff && let Ok(existing) = std::fs::read_to_string(output) {
+                    print_diff(output, &existing, &chunks.index)?;
+                }
+                if check {
+                    let stale = stale_files(&chunks, output, &options);
+                    if !stale.is_empty() {
+                        return Err(ConvertError::OutOfDate(stale));
+                    }
+                    if !quiet {
+                        eprintln!("{} is up to date", output.display());
+                    }
+                    return Ok(ConvertSummary {
+                        glyphs: font.characters.keys().filter(|id| options.includes(**id)).count(),
+                        warnings,
+                    });
+                }
+                if refused {
+                    // Only a preview: the diff was the whole point.
+                    if !quiet {
+                        eprintln!("{} was not replaced; pass --force or --backup to write it", output.display());
+                    }
+                    return Ok(ConvertSummary {
+                        glyphs: font.characters.keys().filter(|id| options.includes(**id)).count(),
+                        warnings,
+                    });
+                }
+                // The module was checked against --force and --backup above;
+                // its parts are rewritten along with it.
+                write_chunks(&chunks, output, &options, Overwrite::Replace)?;
+                info!(output = %output.display(), "wrote output");
+                if options.verify {
+                    verify_output(&font, &read_source(&output.display().to_string())?, &rendered)?;
+                }
+            }
+            None => {
+                let lua = render(&font, &source_name(&inputs), &rendered)?;
+                if check {
+                    return Ok(ConvertSummary {
+                        glyphs: font.characters.keys().filter(|id| options.includes(**id)).count(),
+                        warnings,
+                    });
+                }
+                std::io::stdout().write_all(&output_bytes(&lua, &options))?;
+                if options.verify {
+                    verify_output(&font, &lua, &rendered)?;
+                }
+            }
+        }
+
+        if let Some(ref binary) = runtime_binary {
+            write_atomic(binary, write_runtime_binary(&font, &rendered))?;
+        }
+        if let Some(ref loader) = runtime_loader {
+            write_atomic(loader, runtime_loader_source())?;
+        }
+        let mut exported = font;
+        exported.characters.retain(|id, _| options.includes(*id));
+        if let (Some(format), Some(output)) = (coverage_report, &output) {
+            let format = ReportFormat::from(format);
+            let report = CoverageReport::of(&exported).render(format, &source_name(&inputs));
+            std::fs::write(output.with_extension(format!("coverage.{}", format.extension())), report)?;
+        }
+        if let Some(ref sheet) = contact_sheet {
+            if is_png(sheet) {
+                let pages = load_pages(&exported, inputs[0].parent().unwrap_or(Path::new("")))?;
+                render_contact_sheet(&exported, &pages).save(sheet)?;
+            } else {
+                let html = write_contact_sheet(&exported, &source_name(&inputs), &page_prefix(&inputs[0], sheet));
+                std::fs::write(sheet, html)?;
+            }
+        }
+        Ok(ConvertSummary {
+            glyphs: exported.characters.len(),
+            warnings,
+        })
+    };
+
+    if !watch {
+        let result = convert();
+        if report.is_some() {
+            let input = inputs.iter().map(|input| input.display().to_string()).collect::<Vec<_>>().join(", ");
+            let file_report = match result {
+                Ok(ref summary) => FileReport::converted(input, output.as_ref().map(|output| output.display().to_string()), summary.clone()),
+                Err(ref e) => FileReport::failed(input, e),
+            };
+            println!("{}", serde_json::to_string_pretty(&file_report)?);
+        }
+        result?;
+        return Ok(());
+    }
+
+    let convert_and_log = || {
+        let time = chrono::Local::now().format("%H:%M:%S");
+        match convert() {
+            Ok(_) if quiet => {}
+            Ok(_) => println!("[{time}] Converted to {}", output.as_deref().unwrap_or(Path::new("stdout")).display()),
+            Err(e) => eprintln!("[{time}] Error: {e}"),
+        }
+    };
+    convert_and_log();
+    if !quiet {
+        println!("Watching for changes, press Ctrl+C to stop");
+    }
+    watch_inputs(&inputs, convert_and_log)?;
+    Ok(())
+}
+
+/// Prints what replacing `existing`, the contents of `output`, with `new`
+/// changes, as `--diff` asks. A reader that stops early, such as `head`,
+/// is not an error.
+fn print_diff(output: &Path, existing: &str, new: &str) -> std::io::Result<()> {
+    match write_diff(&mut std::io::stdout().lock(), output, existing, new) {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+fn write_diff(out: &mut impl Write, output: &Path, existing: &str, new: &str) -> std::io::Result<()> {
+    writeln!(out, "--- {}", output.display())?;
+    let lines = diff_excerpt(&diff_lines(existing, new), DIFF_CONTEXT);
+    if lines.iter().all(Option::is_none) {
+        writeln!(out, "No changes")?;
+        return Ok(());
+    }
+    match lua_diff(existing, new) {
+        Some(diff) => {
+            writeln!(
+                out,
+                "{} added, {} removed and {} changed glyphs, {} kerning pairs changed",
+                diff.added.len(),
+                diff.removed.len(),
+                diff.changed.len(),
+                diff.kerning_changes
+            )?;
+            for change in &diff.metrics {
+                writeln!(out, "  {}: {} → {}", change.field, change.old, change.new)?;
+            }
+            for (marker, ids) in [('+', &diff.added), ('-', &diff.removed)] {
+                for id in ids {
+                    writeln!(out, "  {marker} U+{id:04X} {}", glyph_label(*id))?;
+                }
+            }
+            for change in &diff.changed {
+                let fields: Vec<String> =
+                    change.fields.iter().map(|field| format!("{} {} → {}", field.field, field.old, field.new)).collect();
+                writeln!(out, "  ~ U+{:04X} {}: {}", change.id, glyph_label(change.id), fields.join(", "))?;
+            }
+        }
+        None => writeln!(out, "The existing file does not read back as a font; only the lines that change are shown")?,
+    }
+    for line in lines {
+        match line {
+            Some((marker, text)) => writeln!(out, "{marker} {text}")?,
+            None => writeln!(out, "…")?,
+        }
+    }
+    Ok(())
+}
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Runs `convert` whenever one of `inputs` is created or modified.
+fn watch_inputs(inputs: &[PathBuf], mut convert: impl FnMut()) -> notify::Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+
+    for input in inputs {
+        watcher.watch(watch_directory(input), RecursiveMode::NonRecursive)?;
+    }
+
+    let names: Vec<&OsStr> = inputs.iter().filter_map(|input| input.file_name()).collect();
+    while let Ok(event) = receiver.recv() {
+        if !event.is_ok_and(|event| touches_input(&event, &names)) {
+            continue;
+        }
+
+        // A single save usually produces a burst of events; wait for it to settle.
+        while receiver.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+        convert();
+    }
+
+    Ok(())
+}
+
+/// A directory conversion in which some files failed; each was already
+/// reported, and the first failure decides the exit code.
+#[derive(Debug)]
+struct BatchFailed {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl std::fmt::Display for BatchFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for BatchFailed {}
+
+/// The process exit code for a failed CLI run; see [`EXIT_CODES`].
+pub fn exit_code(error: &(dyn std::error::Error + 'static)) -> i32 {
+    let kind = if let Some(e) = error.downcast_ref::<ConvertError>() {
+        e.kind()
+    } else if let Some(e) = error.downcast_ref::<BatchFailed>() {
+        e.kind
+    } else if error.is::<std::io::Error>() {
+        ErrorKind::Io
+    } else {
+        ErrorKind::Other
+    };
+    match kind {
+        ErrorKind::Parse => 3,
+        ErrorKind::Validation => 4,
+        ErrorKind::Io => 5,
+        ErrorKind::Other => 1,
+    }
+}
@@ -0,0 +1,103 @@
+//! The actions of the GUI. Buttons and keyboard shortcuts dispatch them, and
+//! the command palette lists them for searching by name.
+
+use crate::i18n::{tr, trf};
+use egui::{Key, KeyboardShortcut, Modifiers};
+use std::path::Path;
+use xml_to_lua::OutputFormat;
+
+pub const OPEN_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+pub const SAVE_SHORTCUTS: [KeyboardShortcut; 2] = [
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::S),
+    KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter),
+];
+pub const COPY_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::C);
+pub const PALETTE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::P);
+pub const NEW_TAB_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::T);
+pub const CLOSE_TAB_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::W);
+
+/// Something the user can ask the app to do.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Ask for a font to load.
+    Open,
+    /// Load a recently converted input.
+    OpenRecent(String),
+    /// Convert the selected font and preview it, saving it too when quick
+    /// convert is on.
+    Convert,
+    /// Save the preview, converting first when there is none.
+    Save,
+    /// Convert the selected font and copy the output.
+    Copy,
+    /// Switch the output to another format.
+    SetFormat(OutputFormat),
+    /// Start or stop reconverting the selected font whenever it changes.
+    ToggleWatch,
+    /// Open another tab for a font, with the current export settings.
+    NewTab,
+    /// Close the active tab.
+    CloseTab,
+}
+
+impl Action {
+    /// The action's name as listed in the command palette.
+    pub fn label(&self) -> String {
+        match self {
+            Action::Open => tr("📂 Open font…").to_string(),
+            Action::OpenRecent(input) => {
+                trf("🕘 Open recent: {0}", &[&Path::new(input).file_name().unwrap_or_default().to_string_lossy()])
+            }
+            Action::Convert => tr("⚡ Convert").to_string(),
+            Action::Save => tr("💾 Save output…").to_string(),
+            Action::Copy => tr("📋 Copy to clipboard").to_string(),
+            Action::SetFormat(format) => trf("Format: {0}", &[&tr(format.label())]),
+            Action::ToggleWatch => tr("👁 Toggle watching for changes").to_string(),
+            Action::NewTab => tr("➕ New tab").to_string(),
+            Action::CloseTab => tr("✖ Close tab").to_string(),
+        }
+    }
+
+    /// The keyboard shortcut that runs the action, if it has one.
+    pub fn shortcut(&self) -> Option<KeyboardShortcut> {
+        match self {
+            Action::Open => Some(OPEN_SHORTCUT),
+            Action::Save => Some(SAVE_SHORTCUTS[0]),
+            Action::Copy => Some(COPY_SHORTCUT),
+            Action::NewTab => Some(NEW_TAB_SHORTCUT),
+            Action::CloseTab => Some(CLOSE_TAB_SHORTCUT),
+            _ => None,
+        }
+    }
+}
+
+/// The open command palette: what has been typed, and which of the matching
+/// actions Enter runs.
+#[derive(Default)]
+pub struct CommandPalette {
+    pub query: String,
+    pub selected: usize,
+}
+
+/// How well `query` matches `text`, or `None` when `text` does not contain
+/// the characters of `query` in order. Case is ignored; characters that
+/// follow each other in `text` or start a word score higher, and gaps
+/// between them lower.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = next + text[next..].iter().position(|&c| c == wanted)?;
+        score += match previous {
+            Some(previous) if previous + 1 == index => 8,
+            _ if index == 0 || !text[index - 1].is_alphanumeric() => 6,
+            Some(previous) => 1 - (index - previous - 1).min(5) as i32,
+            None => 1,
+        };
+        previous = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
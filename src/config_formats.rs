@@ -0,0 +1,321 @@
+//! Writing fonts as TOML or YAML, for toolchains that read config files.
+//! Both hold the same data as the JSON output, converted from its serde
+//! representation.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputFormatter, OutputOptions, header_lines, json_value};
+use serde_json::{Map, Number, Value};
+
+/// The TOML of [`write_toml`].
+pub struct TomlFormatter;
+
+impl OutputFormatter for TomlFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Toml
+    }
+
+    fn name(&self) -> &'static str {
+        "toml"
+    }
+
+    fn label(&self) -> &'static str {
+        "TOML"
+    }
+
+    fn description(&self) -> &'static str {
+        "The adjusted font as TOML tables"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(header_comment(source, options) + &write_toml(&json_value(font, options)))
+    }
+}
+
+/// The YAML of [`write_yaml`].
+pub struct YamlFormatter;
+
+impl OutputFormatter for YamlFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Yaml
+    }
+
+    fn name(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn label(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn description(&self) -> &'static str {
+        "The adjusted font as a YAML document"
+    }
+
+    fn extension(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        let value = json_value(font, options);
+        // JSON is valid YAML, and the most compact way to write it.
+        let yaml = if options.style.minified {
+            serde_json::to_string(&value).expect("JSON values serialize") + "\n"
+        } else {
+            write_yaml(&value)
+        };
+        Ok(header_comment(source, options) + &yaml)
+    }
+}
+
+/// The header text as `#` comments, which both formats share.
+fn header_comment(source: &str, options: &OutputOptions) -> String {
+    match options.header {
+        Some(ref header) => header_lines(header, source, options).iter().map(|line| format!("# {line}\n")).collect(),
+        None => String::new(),
+    }
+}
+
+/// `number` as written in TOML and YAML. Numbers that came from `f32`
+/// metrics are written as short as they were, e.g. `0.3` rather than the
+/// `0.30000001192092896` of their `f64` representation, and floats always
+/// keep a decimal point.
+fn number(number: &Number) -> String {
+    if number.is_f64() {
+        let value = number.as_f64().unwrap_or_default();
+        let text = match value as f32 {
+            short if f64::from(short) == value => short.to_string(),
+            _ => value.to_string(),
+        };
+        return if text.contains(['.', 'e']) { text } else { text + ".0" };
+    }
+    number.to_string()
+}
+
+/// A double-quoted string; TOML basic strings and YAML double-quoted scalars
+/// both read JSON's escapes.
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).expect("strings serialize")
+}
+
+/// Writes `value`, an object, as a TOML document: the scalars and arrays of
+/// each table first, then its tables as `[a.b]` sections and its arrays of
+/// objects as `[[a.b]]` sections. `null`s, which TOML cannot express, are
+/// left out.
+pub fn write_toml(value: &Value) -> String {
+    let mut output = String::new();
+    if let Value::Object(table) = value {
+        toml_table(table, &[], &mut output);
+    }
+    output
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare { key.to_string() } else { quoted(key) }
+}
+
+fn toml_inline(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => number(value),
+        Value::String(value) => quoted(value),
+        Value::Array(items) => format!("[{}]", items.iter().filter(|item| !item.is_null()).map(toml_inline).collect::<Vec<_>>().join(", ")),
+        Value::Object(table) => {
+            let entries: Vec<String> = table
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| format!("{} = {}", toml_key(key), toml_inline(value)))
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+    }
+}
+
+/// Whether `value` becomes a section of its own rather than a `key = value`
+/// line of its table.
+fn is_section(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => true,
+        Value::Array(items) => !items.is_empty() && items.iter().all(Value::is_object),
+        _ => false,
+    }
+}
+
+fn toml_table(table: &Map<String, Value>, path: &[String], output: &mut String) {
+    for (key, value) in table.iter().filter(|(_, value)| !value.is_null() && !is_section(value)) {
+        output.push_str(&format!("{} = {}\n", toml_key(key), toml_inline(value)));
+    }
+    for (key, value) in table.iter().filter(|(_, value)| is_section(value)) {
+        let path: Vec<String> = path.iter().cloned().chain([toml_key(key)]).collect();
+        match value {
+            Value::Object(child) => {
+                // Tables holding only tables are implied by their sections.
+                if !child.values().all(is_section) || child.is_empty() {
+                    output.push_str(&format!("\n[{}]\n", path.join(".")));
+                }
+                toml_table(child, &path, output);
+            }
+            Value::Array(items) => {
+                for item in items.iter().filter_map(Value::as_object) {
+                    output.push_str(&format!("\n[[{}]]\n", path.join(".")));
+                    toml_table(item, &path, output);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes `value` as a block-style YAML document. Arrays of scalars are
+/// written inline, as `[1, 2]`, and keys are quoted unless they are plain
+/// identifiers, so codepoint keys stay strings.
+pub fn write_yaml(value: &Value) -> String {
+    let mut output = String::new();
+    match value {
+        Value::Object(table) if !table.is_empty() => yaml_table(table, 0, &mut output),
+        value => {
+            output.push_str(&yaml_scalar(value));
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn yaml_key(key: &str) -> String {
+    let plain = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !["true", "false", "null", "yes", "no", "on", "off"].contains(&key.to_ascii_lowercase().as_str());
+    if plain { key.to_string() } else { quoted(key) }
+}
+
+/// `value` on one line: scalars, and empty or scalar-only collections.
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => number(value),
+        Value::String(value) => quoted(value),
+        Value::Array(items) => format!("[{}]", items.iter().map(yaml_scalar).collect::<Vec<_>>().join(", ")),
+        Value::Object(table) => {
+            let entries: Vec<String> = table.iter().map(|(key, value)| format!("{}: {}", yaml_key(key), yaml_scalar(value))).collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Whether `value` is written on the line of its key.
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::Object(table) => table.is_empty(),
+        Value::Array(items) => items.iter().all(|item| !matches!(item, Value::Object(_) | Value::Array(_))),
+        _ => true,
+    }
+}
+
+fn yaml_table(table: &Map<String, Value>, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    for (key, value) in table {
+        if is_inline(value) {
+            output.push_str(&format!("{indent}{}: {}\n", yaml_key(key), yaml_scalar(value)));
+            continue;
+        }
+        output.push_str(&format!("{indent}{}:\n", yaml_key(key)));
+        match value {
+            Value::Object(child) => yaml_table(child, depth + 1, output),
+            Value::Array(items) => yaml_list(items, depth + 1, output),
+            _ => {}
+        }
+    }
+}
+
+/// Writes `items` as `- ` entries, with an object's first key on the line of
+/// its dash.
+fn yaml_list(items: &[Value], depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    for item in items {
+        match item {
+            Value::Object(table) if !table.is_empty() => {
+                let mut entry = String::new();
+                yaml_table(table, depth + 1, &mut entry);
+                output.push_str(&format!("{indent}- {}", entry.trim_start()));
+            }
+            Value::Array(items) if !is_inline(item) => {
+                output.push_str(&format!("{indent}-\n"));
+                yaml_list(items, depth + 1, output);
+            }
+            item => output.push_str(&format!("{indent}- {}\n", yaml_scalar(item))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use serde_json::json;
+
+    #[test]
+    fn toml_writes_scalars_before_sections_and_drops_nulls() {
+        let value = json!({
+            "metrics": { "size": 50, "line_height": 0.3, "base": 41.0 },
+            "name": "font \"A\"",
+            "missing": null,
+            "pages": [{ "id": 0 }],
+            "1": [1, 2],
+        });
+        let expected = "\
+1 = [1, 2]
+name = \"font \\\"A\\\"\"
+
+[metrics]
+base = 41.0
+line_height = 0.3
+size = 50
+
+[[pages]]
+id = 0
+";
+        assert_eq!(write_toml(&value), expected);
+    }
+
+    #[test]
+    fn yaml_quotes_keys_that_would_not_read_back_as_strings() {
+        let value = json!({ "65": { "advance": 19.0 }, "yes": [1, 2], "glyphs": [{ "id": 65, "size": [19, 30] }], "empty": {} });
+        let expected = "\
+\"65\":
+  advance: 19.0
+empty: {}
+glyphs:
+  - id: 65
+    size: [19, 30]
+\"yes\": [1, 2]
+";
+        assert_eq!(write_yaml(&value), expected);
+    }
+
+    #[test]
+    fn minified_yaml_is_the_json_output() {
+        let font = example_font();
+        let mut options = OutputOptions { format: OutputFormat::Yaml, ..OutputOptions::default() };
+        options.style.minified = true;
+        let yaml = YamlFormatter.render(&font, "font.fnt", &options).unwrap();
+        let value: Value = serde_json::from_str(&yaml).unwrap();
+        assert_eq!(value, json_value(&font, &options));
+    }
+
+    #[test]
+    fn each_glyph_gets_a_table() {
+        let font = example_font();
+        let options = OutputOptions { format: OutputFormat::Toml, header: None, ..OutputOptions::default() };
+        let toml = TomlFormatter.render(&font, "font.fnt", &options).unwrap();
+        let yaml = YamlFormatter.render(&font, "font.fnt", &OutputOptions { format: OutputFormat::Yaml, ..options.clone() }).unwrap();
+        assert!(toml.contains("\n[characters.65]\n"), "{toml}");
+        assert!(yaml.contains("\n  \"65\":\n"), "{yaml}");
+    }
+}
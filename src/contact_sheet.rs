@@ -0,0 +1,195 @@
+//! Contact sheets that show every glyph cut out of the atlas, for reviewing
+//! a font without opening any tool: HTML pages that cut the glyphs out with
+//! CSS, and PNG images with the glyphs copied into a labeled grid.
+
+use crate::font::Font;
+#[cfg(feature = "atlas")]
+use image::{Rgba, RgbaImage, imageops};
+use quick_xml::escape::escape;
+#[cfg(feature = "atlas")]
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The prefix that makes page file names of the font at `fnt_path` resolve
+/// from an HTML file at `html_path`: nothing when both are in the same
+/// directory, or a `file://` URL of the font's directory otherwise.
+pub fn page_prefix(fnt_path: &Path, html_path: &Path) -> String {
+    let directory = |path: &Path| path.parent().and_then(|parent| std::path::absolute(parent).ok());
+    match directory(fnt_path) {
+        Some(fnt_dir) if Some(&fnt_dir) != directory(html_path).as_ref() => {
+            let url = fnt_dir.to_string_lossy().replace('\\', "/");
+            let separator = if url.starts_with('/') { "" } else { "/" };
+            format!("file://{separator}{url}/")
+        }
+        _ => String::new(),
+    }
+}
+
+/// Renders `font` as a standalone HTML page with one cell per glyph, each
+/// showing the glyph via `background-position` into its texture page. Page
+/// file names are prefixed with `page_prefix` (see [`page_prefix`]).
+pub fn write_contact_sheet(font: &Font, title: &str, page_prefix: &str) -> String {
+    let title = escape(title);
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{title}</title>\n<style>\n"));
+    html.push_str(
+        "body { background: #11111b; color: #cdd6f4; font-family: sans-serif; }\n\
+         .sheet { display: flex; flex-wrap: wrap; gap: 8px; }\n\
+         .glyph { background: #1e1e2e; border-radius: 4px; padding: 8px; min-width: 64px; text-align: center; }\n\
+         .sprite { margin: 0 auto 6px; background-repeat: no-repeat; image-rendering: pixelated; outline: 1px dashed #45475a; }\n\
+         .label { font-size: 12px; }\n\
+         .id { font-size: 10px; color: #a6adc8; }\n",
+    );
+    for (id, file) in &font.metrics.pages {
+        // Style elements are not entity-decoded, so percent-encode anything
+        // that could end the string or the element instead.
+        let url = format!("{page_prefix}{file}").replace('"', "%22").replace('<', "%3C");
+        html.push_str(&format!(".page-{id} {{ background-image: url(\"{url}\"); }}\n"));
+    }
+    html.push_str("</style>\n</head>\n<body>\n");
+    html.push_str(&format!("<h1>{title}</h1>\n<p>{} glyphs</p>\n<div class=\"sheet\">\n", font.characters.len()));
+
+    for (id, data) in &font.characters {
+        let label = match std::char::from_u32(*id) {
+            Some(c) if !c.is_control() && !c.is_whitespace() => escape(c.to_string()).into_owned(),
+            _ => "&nbsp;".to_string(),
+        };
+        html.push_str(&format!(
+            "<div class=\"glyph\" title=\"offset {}, {} · advance {} · page {}\">\
+             <div class=\"sprite page-{}\" style=\"width: {}px; height: {}px; background-position: -{}px -{}px;\"></div>\
+             <div class=\"label\">{label}</div><div class=\"id\">U+{id:04X} · {id}</div></div>\n",
+            data.offset.x,
+            data.offset.y,
+            data.advance,
+            data.page,
+            data.page,
+            data.size.width,
+            data.size.height,
+            data.position.x,
+            data.position.y,
+        ));
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+/// 3×5 pixel glyphs for the codepoint labels of PNG contact sheets: a row of
+/// three bits per line from the top, the leftmost pixel in the highest bit.
+#[cfg(feature = "atlas")]
+const LABEL_GLYPHS: [(char, [u8; 5]); 18] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('+', [0b000, 0b010, 0b111, 0b010, 0b000]),
+];
+
+/// How many sheet pixels each pixel of a label glyph covers.
+#[cfg(feature = "atlas")]
+const LABEL_SCALE: u32 = 2;
+/// Space around a cell's contents, and between the glyph and its label.
+#[cfg(feature = "atlas")]
+const MARGIN: u32 = 6;
+#[cfg(feature = "atlas")]
+const SHEET_BACKGROUND: Rgba<u8> = Rgba([0x11, 0x11, 0x1b, 0xff]);
+#[cfg(feature = "atlas")]
+const CELL_BACKGROUND: Rgba<u8> = Rgba([0x1e, 0x1e, 0x2e, 0xff]);
+#[cfg(feature = "atlas")]
+const LABEL_COLOR: Rgba<u8> = Rgba([0xa6, 0xad, 0xc8, 0xff]);
+
+/// Width in sheet pixels of `label` drawn by [`draw_label`].
+#[cfg(feature = "atlas")]
+fn label_width(label: &str) -> u32 {
+    (label.chars().count() as u32 * 4).saturating_sub(1) * LABEL_SCALE
+}
+
+/// Draws `label` with [`LABEL_GLYPHS`] with its top-left corner at `x`, `y`.
+#[cfg(feature = "atlas")]
+fn draw_label(sheet: &mut RgbaImage, label: &str, x: u32, y: u32) {
+    for (index, c) in label.chars().enumerate() {
+        let Some((_, rows)) = LABEL_GLYPHS.iter().find(|(glyph, _)| *glyph == c) else {
+            continue;
+        };
+        let left = x + index as u32 * 4 * LABEL_SCALE;
+        for (row, bits) in rows.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..LABEL_SCALE {
+                    for dx in 0..LABEL_SCALE {
+                        sheet.put_pixel(left + column * LABEL_SCALE + dx, y + row as u32 * LABEL_SCALE + dy, LABEL_COLOR);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Renders `font` as an image with one cell per glyph in codepoint order,
+/// each holding the glyph copied out of its page in `pages` above its
+/// codepoint, e.g. `U+0041`. Glyphs are centered horizontally and placed
+/// vertically by their offsets, so they share a baseline. Cells are as
+/// large as the largest glyph and
+/// label, laid out in a roughly square grid at least 16 cells wide. Glyphs
+/// whose page is not in `pages` get a cell with just the label.
+#[cfg(feature = "atlas")]
+pub fn render_contact_sheet(font: &Font, pages: &BTreeMap<u32, RgbaImage>) -> RgbaImage {
+    let labels: Vec<String> = font.characters.keys().map(|id| format!("U+{id:04X}")).collect();
+    let glyph_width = font.characters.values().map(|data| data.size.width.round().max(0.0) as u32).max().unwrap_or(0);
+    // Glyphs keep their vertical offsets, so they share a baseline.
+    let top_offset = font.characters.values().map(|data| data.offset.y.round() as i64).min().unwrap_or(0);
+    let bottom_offset = font
+        .characters
+        .values()
+        .map(|data| (data.offset.y + data.size.height.max(0.0)).round() as i64)
+        .max()
+        .unwrap_or(0);
+    let glyph_height = (bottom_offset - top_offset).max(0) as u32;
+    let label_height = 5 * LABEL_SCALE;
+    let cell_width = glyph_width.max(labels.iter().map(|label| label_width(label)).max().unwrap_or(0)) + 2 * MARGIN;
+    let cell_height = glyph_height + label_height + 3 * MARGIN;
+
+    let count = font.characters.len().max(1) as u32;
+    let columns = count.isqrt().max(16).min(count);
+    let rows = count.div_ceil(columns);
+    let mut sheet = RgbaImage::from_pixel(columns * cell_width + 1, rows * cell_height + 1, SHEET_BACKGROUND);
+
+    for (index, (data, label)) in font.characters.values().zip(&labels).enumerate() {
+        let left = index as u32 % columns * cell_width;
+        let top = index as u32 / columns * cell_height;
+        for y in top + 1..top + cell_height {
+            for x in left + 1..left + cell_width {
+                sheet.put_pixel(x, y, CELL_BACKGROUND);
+            }
+        }
+
+        if let Some(page) = pages.get(&data.page) {
+            let x = (data.position.x.round().max(0.0) as u32).min(page.width());
+            let y = (data.position.y.round().max(0.0) as u32).min(page.height());
+            let width = (data.size.width.round().max(0.0) as u32).min(page.width() - x);
+            let height = (data.size.height.round().max(0.0) as u32).min(page.height() - y);
+            let glyph = imageops::crop_imm(page, x, y, width, height).to_image();
+            let glyph_left = left + (cell_width - width) / 2;
+            let glyph_top = i64::from(top + MARGIN) + data.offset.y.round() as i64 - top_offset;
+            imageops::overlay(&mut sheet, &glyph, i64::from(glyph_left), glyph_top);
+        }
+        draw_label(&mut sheet, label, left + (cell_width - label_width(label)) / 2, top + glyph_height + 2 * MARGIN);
+    }
+    sheet
+}
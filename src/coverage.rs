@@ -0,0 +1,140 @@
+//! Per-block Unicode coverage reports, for reviewing which characters a font
+//! is missing before it ships.
+
+use crate::font::Font;
+use crate::unicode_block::BLOCKS;
+use serde::Serialize;
+
+/// A run of consecutive codepoints, written as `U+0041` or `U+0041–U+005A`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CodepointRange {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl std::fmt::Display for CodepointRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.first == self.last {
+            write!(f, "U+{:04X}", self.first)
+        } else {
+            write!(f, "U+{:04X}–U+{:04X}", self.first, self.last)
+        }
+    }
+}
+
+/// How much of one Unicode block a font covers. `total` counts every
+/// codepoint in the block, including unassigned ones.
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockCoverage {
+    pub block: &'static str,
+    pub range: CodepointRange,
+    pub covered: usize,
+    pub total: usize,
+    pub missing: Vec<CodepointRange>,
+}
+
+/// Coverage of every block the font has at least one glyph in.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageReport {
+    pub blocks: Vec<BlockCoverage>,
+    /// Glyphs outside every block in [`BLOCKS`].
+    pub other: Vec<u32>,
+}
+
+/// The file formats a [`CoverageReport`] can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "JSON",
+            ReportFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+impl CoverageReport {
+    pub fn of(font: &Font) -> Self {
+        let mut blocks = Vec::new();
+        for (block, range) in BLOCKS {
+            let covered = font.characters.range(range.clone()).count();
+            if covered == 0 {
+                continue;
+            }
+
+            let mut missing: Vec<CodepointRange> = Vec::new();
+            for id in range.clone().filter(|id| !font.characters.contains_key(id)) {
+                match missing.last_mut() {
+                    Some(last) if last.last + 1 == id => last.last = id,
+                    _ => missing.push(CodepointRange { first: id, last: id }),
+                }
+            }
+
+            blocks.push(BlockCoverage {
+                block,
+                range: CodepointRange {
+                    first: *range.start(),
+                    last: *range.end(),
+                },
+                covered,
+                total: range.clone().count(),
+                missing,
+            });
+        }
+
+        let other = font
+            .characters
+            .keys()
+            .copied()
+            .filter(|id| !BLOCKS.iter().any(|(_, range)| range.contains(id)))
+            .collect();
+        CoverageReport { blocks, other }
+    }
+
+    /// Writes the report in `format`; `source` names the font in the
+    /// Markdown heading.
+    pub fn render(&self, format: ReportFormat, source: &str) -> String {
+        match format {
+            ReportFormat::Json => {
+                let mut json = serde_json::to_string_pretty(self).expect("report serializes to JSON");
+                json.push('\n');
+                json
+            }
+            ReportFormat::Markdown => self.markdown(source),
+        }
+    }
+
+    fn markdown(&self, source: &str) -> String {
+        let mut markdown = format!("# Unicode coverage: {source}\n\n");
+        markdown.push_str("| Block | Range | Covered | Missing |\n");
+        markdown.push_str("|---|---|---|---|\n");
+        for block in &self.blocks {
+            let missing: Vec<String> = block.missing.iter().map(ToString::to_string).collect();
+            markdown.push_str(&format!(
+                "| {} | {} | {}/{} | {} |\n",
+                block.block,
+                block.range,
+                block.covered,
+                block.total,
+                missing.join(", ")
+            ));
+        }
+        if !self.other.is_empty() {
+            let other: Vec<String> = self.other.iter().map(|id| format!("U+{id:04X}")).collect();
+            markdown.push_str(&format!("\nOutside known blocks: {}\n", other.join(", ")));
+        }
+        markdown
+    }
+}
@@ -0,0 +1,193 @@
+//! Differences between two versions of a font, to review what upgrading it
+//! changes before the new version is exported.
+
+use crate::font::{Character, Font};
+use crate::lua_import::parse_lua;
+
+/// A value that differs between the old and the new font, formatted for
+/// display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// A glyph present in both fonts whose metrics differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphChange {
+    pub id: u32,
+    pub fields: Vec<FieldChange>,
+}
+
+/// What changed from an old version of a font to a new one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FontDiff {
+    /// Codepoints only the new font has.
+    pub added: Vec<u32>,
+    /// Codepoints only the old font has.
+    pub removed: Vec<u32>,
+    pub changed: Vec<GlyphChange>,
+    /// Font-wide values that differ, such as the line height or the pages.
+    pub metrics: Vec<FieldChange>,
+    /// Kerning pairs added, removed or with a different amount.
+    pub kerning_changes: usize,
+}
+
+impl FontDiff {
+    pub fn of(old: &Font, new: &Font) -> Self {
+        let added = new.characters.keys().filter(|id| !old.characters.contains_key(id)).copied().collect();
+        let removed = old.characters.keys().filter(|id| !new.characters.contains_key(id)).copied().collect();
+        let changed = old
+            .characters
+            .iter()
+            .filter_map(|(id, old_glyph)| {
+                let fields = changes(glyph_fields(old_glyph), glyph_fields(new.characters.get(id)?));
+                (!fields.is_empty()).then_some(GlyphChange { id: *id, fields })
+            })
+            .collect();
+
+        let old_kerning = &old.metrics.kerning;
+        let new_kerning = &new.metrics.kerning;
+        let kerning_changes = old_kerning
+            .iter()
+            .filter(|(pair, amount)| new_kerning.get(pair) != Some(amount))
+            .count()
+            + new_kerning.keys().filter(|pair| !old_kerning.contains_key(pair)).count();
+
+        FontDiff {
+            added,
+            removed,
+            changed,
+            metrics: changes(metric_fields(old), metric_fields(new)),
+            kerning_changes,
+        }
+    }
+
+    /// Whether the fonts have the same glyphs, metrics and kerning.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.metrics.is_empty()
+            && self.kerning_changes == 0
+    }
+}
+
+/// What replacing the Lua module `old`, e.g. an existing output file, with
+/// `new` changes; `None` when either does not read back as a font, as
+/// hand-written modules and other formats do not.
+pub fn lua_diff(old: &str, new: &str) -> Option<FontDiff> {
+    let old = parse_lua(old).ok()?;
+    let new = parse_lua(new).ok()?;
+    Some(FontDiff::of(&old, &new))
+}
+
+fn glyph_fields(glyph: &Character) -> Vec<(&'static str, String)> {
+    vec![
+        ("size", format!("{}×{}", glyph.size.width, glyph.size.height)),
+        ("position", format!("{}, {}", glyph.position.x, glyph.position.y)),
+        ("offset", format!("{}, {}", glyph.offset.x, glyph.offset.y)),
+        ("advance", glyph.advance.to_string()),
+        ("page", glyph.page.to_string()),
+    ]
+}
+
+fn metric_fields(font: &Font) -> Vec<(&'static str, String)> {
+    let metrics = &font.metrics;
+    let common = metrics.common.as_ref();
+    let pages: Vec<&str> = metrics.pages.values().map(String::as_str).collect();
+    vec![
+        ("size", metrics.size.to_string()),
+        ("line height", common.map_or("–".to_string(), |common| common.line_height.to_string())),
+        ("base", common.map_or("–".to_string(), |common| common.base.to_string())),
+        (
+            "texture size",
+            common.map_or("–".to_string(), |common| format!("{}×{}", common.scale_w, common.scale_h)),
+        ),
+        ("pages", pages.join(", ")),
+        (
+            "distance field",
+            metrics
+                .distance_field
+                .as_ref()
+                .map_or("–".to_string(), |field| format!("{} {}", field.field_type, field.range)),
+        ),
+    ]
+}
+
+/// The fields whose values differ, given the same fields in the same order.
+fn changes(old: Vec<(&'static str, String)>, new: Vec<(&'static str, String)>) -> Vec<FieldChange> {
+    old.into_iter()
+        .zip(new)
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| FieldChange { field, old, new })
+        .collect()
+}
+
+/// A line of a [`diff_lines`] result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineChange<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Above this many line pairs between the first and last differing lines,
+/// the whole range is reported as replaced instead of aligned.
+const MAX_ALIGNED_PAIRS: usize = 4_000_000;
+
+/// The lines of `old` and `new` aligned on their longest common subsequence,
+/// e.g. two rendered Lua modules.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<LineChange<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<LineChange> = old[..prefix].iter().map(|line| LineChange::Same(line)).collect();
+    if old_middle.len() * new_middle.len() > MAX_ALIGNED_PAIRS {
+        lines.extend(old_middle.iter().map(|line| LineChange::Removed(line)));
+        lines.extend(new_middle.iter().map(|line| LineChange::Added(line)));
+    } else {
+        // common[i][j] is the length of the longest common subsequence of
+        // old_middle[i..] and new_middle[j..].
+        let width = new_middle.len() + 1;
+        let mut common = vec![0u32; (old_middle.len() + 1) * width];
+        for i in (0..old_middle.len()).rev() {
+            for j in (0..new_middle.len()).rev() {
+                common[i * width + j] = if old_middle[i] == new_middle[j] {
+                    common[(i + 1) * width + j + 1] + 1
+                } else {
+                    common[(i + 1) * width + j].max(common[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_middle.len() && j < new_middle.len() {
+            if old_middle[i] == new_middle[j] {
+                lines.push(LineChange::Same(old_middle[i]));
+                i += 1;
+                j += 1;
+            } else if common[(i + 1) * width + j] >= common[i * width + j + 1] {
+                lines.push(LineChange::Removed(old_middle[i]));
+                i += 1;
+            } else {
+                lines.push(LineChange::Added(new_middle[j]));
+                j += 1;
+            }
+        }
+        lines.extend(old_middle[i..].iter().map(|line| LineChange::Removed(line)));
+        lines.extend(new_middle[j..].iter().map(|line| LineChange::Added(line)));
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|line| LineChange::Same(line)));
+    lines
+}
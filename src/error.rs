@@ -0,0 +1,243 @@
+//! Errors produced while reading or converting a font.
+
+use serde::Serialize;
+use std::fmt;
+use thiserror::Error;
+
+/// Why a font could not be read or converted.
+#[derive(Debug, Error)]
+pub enum ConvertError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("XML error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+    /// The input is not valid in the encoding its BOM or XML declaration names.
+    #[error("invalid {0} text")]
+    Encoding(&'static str),
+    #[error("invalid value {value:?} for attribute `{key}`")]
+    BadAttribute { key: String, value: String },
+    #[error("missing attribute `{0}`")]
+    MissingAttribute(&'static str),
+    #[error("malformed binary font: {0}")]
+    MalformedBinary(&'static str),
+    #[error("template error: {0}")]
+    Template(#[from] handlebars::RenderError),
+    #[error("invalid glob pattern: {0}")]
+    Glob(#[from] globset::Error),
+    #[error("invalid codepoint range {0:?}")]
+    BadRange(String),
+    #[cfg(feature = "ttf")]
+    #[error("invalid font file: {0}")]
+    InvalidFont(#[from] ab_glyph::InvalidFont),
+    #[cfg(feature = "atlas")]
+    #[error("image error: {0}")]
+    Image(#[from] image::ImageError),
+    /// A texture page of the font could not be loaded.
+    #[cfg(feature = "atlas")]
+    #[error("{file}: {source}")]
+    Texture { file: String, source: image::ImageError },
+    /// A glyph is larger than the texture pages it must be packed into.
+    #[cfg(feature = "atlas")]
+    #[error("glyph U+{id:04X} ({width}×{height}) does not fit a {texture_width}×{texture_height} texture")]
+    GlyphTooLarge {
+        id: u32,
+        width: u32,
+        height: u32,
+        texture_width: u32,
+        texture_height: u32,
+    },
+    /// A Rojo project file could not be read, or does not sync the folder
+    /// a font was to be written into.
+    #[error("Rojo project: {0}")]
+    Rojo(String),
+    #[error("invalid codepoint mapping: {0}")]
+    Remap(String),
+    #[error("invalid icon names: {0}")]
+    IconNames(String),
+    #[error("invalid texture path rule: {0}")]
+    PagePath(String),
+    #[error("invalid module name: {0}")]
+    ModuleName(String),
+    #[error("invalid Lua: {0}")]
+    Lua(String),
+    /// The output format names a single texture, but the font has several
+    /// pages.
+    #[error("{0} output holds one texture page, but the font has {1}")]
+    SinglePage(&'static str, usize),
+    #[error("missing <info> element")]
+    MissingInfo,
+    #[error("validation failed with {} warning(s)", .0.len())]
+    Validation(Vec<String>),
+    /// The XML input breaks the BMFont structure; see
+    /// [`check_schema`](crate::schema::check_schema). Each problem starts
+    /// with its line and column.
+    #[error("input does not match the BMFont XML structure:\n  {}", .0.join("\n  "))]
+    Schema(Vec<String>),
+    #[error("font is missing {} required character(s)", .0.len())]
+    MissingCharacters(Vec<char>),
+    /// The output file exists and [`Overwrite::Refuse`](crate::batch::Overwrite::Refuse)
+    /// is in effect.
+    #[error("{} already exists", .0.display())]
+    OutputExists(std::path::PathBuf),
+    /// Converting again would change these existing output files, or create
+    /// them.
+    #[error("out of date: {}", .0.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", "))]
+    OutOfDate(Vec<std::path::PathBuf>),
+    /// The written output did not read back as the font it was rendered from.
+    #[error("output does not read back as exported: {}", .0.join("; "))]
+    RoundTrip(Vec<String>),
+    /// An error in the element, line or block starting at `location`.
+    #[error("{location}: {source}")]
+    At {
+        location: Location,
+        source: Box<ConvertError>,
+    },
+}
+
+impl ConvertError {
+    pub(crate) fn at(offset: u64, error: impl Into<ConvertError>) -> Self {
+        ConvertError::At {
+            location: Location {
+                offset,
+                line_column: None,
+            },
+            source: Box::new(error.into()),
+        }
+    }
+
+    /// Fills in the line and column of a located error from the text it was
+    /// parsed from.
+    pub(crate) fn with_line_column(mut self, text: &str) -> Self {
+        if let ConvertError::At { ref mut location, .. } = self {
+            *location = Location::in_text(location.offset, text);
+        }
+        self
+    }
+
+    /// Where in the input the error occurred, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            ConvertError::At { location, .. } => Some(*location),
+            _ => None,
+        }
+    }
+
+    /// Which class of failure this is, for choosing an exit code.
+    pub fn kind(&self) -> ErrorKind {
+        match self.cause() {
+            ConvertError::Io(_) | ConvertError::OutputExists(_) => ErrorKind::Io,
+            #[cfg(feature = "atlas")]
+            ConvertError::Texture { .. } => ErrorKind::Io,
+            ConvertError::Xml(_)
+            | ConvertError::Json(_)
+            | ConvertError::Utf8(_)
+            | ConvertError::Encoding(_)
+            | ConvertError::BadAttribute { .. }
+            | ConvertError::MissingAttribute(_)
+            | ConvertError::MalformedBinary(_)
+            | ConvertError::Lua(_)
+            | ConvertError::Schema(_)
+            | ConvertError::MissingInfo => ErrorKind::Parse,
+            #[cfg(feature = "ttf")]
+            ConvertError::InvalidFont(_) => ErrorKind::Parse,
+            ConvertError::Validation(_)
+            | ConvertError::MissingCharacters(_)
+            | ConvertError::RoundTrip(_)
+            | ConvertError::OutOfDate(_) => ErrorKind::Validation,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// What the user can do about the error, in a sentence, when there is
+    /// something more specific to do than retrying.
+    pub fn hint(&self) -> Option<&'static str> {
+        Some(match self.cause() {
+            ConvertError::Io(e) if e.kind() == std::io::ErrorKind::NotFound => "Check that the file exists and has not been moved.",
+            ConvertError::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                "Check that the file is readable and not locked by another program."
+            }
+            ConvertError::Xml(_) => "The file is not well-formed XML; fix the line shown or export the font again.",
+            ConvertError::Json(_) => "The file is not a well-formed JSON font; fix the line shown or export the font again.",
+            ConvertError::Utf8(_) | ConvertError::Encoding(_) => "Save the file as UTF-8, or declare its encoding in the XML declaration.",
+            ConvertError::BadAttribute { .. } => "Fix the value, or skip malformed elements to convert the rest of the font.",
+            ConvertError::MissingAttribute(_) => "Add the attribute, or skip malformed elements to convert the rest of the font.",
+            ConvertError::MalformedBinary(_) => "The file is truncated or not a BMFont; export it again.",
+            ConvertError::Schema(_) => "Fix the elements listed, or export the font again with BMFont.",
+            ConvertError::MissingInfo => "Export the font again with BMFont, which always writes an <info> element.",
+            ConvertError::Lua(_) => "Only Lua modules generated by this converter can be read back.",
+            ConvertError::Template(_) => "Check the template's syntax and the fields it uses.",
+            ConvertError::Glob(_) | ConvertError::BadRange(_) => "Check the pattern for typos.",
+            ConvertError::Remap(_) => "Write one `from,to` pair of codepoints per line, e.g. U+E000,U+2605.",
+            ConvertError::IconNames(_) => "Write one `name,codepoint` pair per line, e.g. gear,U+F013, or a JSON object of names.",
+            ConvertError::ModuleName(_) => "Use letters, digits and underscores, not starting with a digit, e.g. TitleFont.",
+            ConvertError::PagePath(_) => "Write each rule as PATTERN=REPLACEMENT, e.g. font_*.png=rbxassetid://123456.",
+            #[cfg(feature = "atlas")]
+            ConvertError::Texture { .. } => "Put the texture pages next to the font file, under the names it lists.",
+            ConvertError::OutputExists(_) => "Choose to overwrite or back up existing files.",
+            ConvertError::MissingCharacters(_) => "Generate the font again with the missing characters.",
+            ConvertError::OutOfDate(_) => "Convert the font again and commit the regenerated output.",
+            ConvertError::SinglePage(..) => "Export the pages one at a time, or repack the font onto a single page.",
+            ConvertError::Rojo(_) => "Pick the project's default.project.json and a folder one of its $path entries includes.",
+            _ => return None,
+        })
+    }
+
+    /// The error itself, without its location.
+    pub fn cause(&self) -> &ConvertError {
+        match self {
+            ConvertError::At { source, .. } => source.cause(),
+            error => error,
+        }
+    }
+}
+
+/// The broad class of a [`ConvertError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorKind {
+    /// The input is not a well-formed font.
+    Parse,
+    /// The font parsed but failed `--strict`, required-character, `--verify`
+    /// or `--check` checks.
+    Validation,
+    /// A file could not be read or written.
+    Io,
+    /// Anything else, such as an invalid option or template.
+    Other,
+}
+
+/// A position in the (decompressed) input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    /// Byte offset from the start of the input.
+    pub offset: u64,
+    /// 1-based line and column, known for XML and text input.
+    pub line_column: Option<(usize, usize)>,
+}
+
+impl Location {
+    /// The location of byte `offset` in `text`, with its line and column.
+    pub(crate) fn in_text(offset: u64, text: &str) -> Self {
+        let before = &text.as_bytes()[..(offset as usize).min(text.len())];
+        let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+        let line_start = before.iter().rposition(|&byte| byte == b'\n').map_or(0, |index| index + 1);
+        let column = String::from_utf8_lossy(&before[line_start..]).chars().count() + 1;
+        Self {
+            offset,
+            line_column: Some((line, column)),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line_column {
+            Some((line, column)) => write!(f, "line {line}, column {column}"),
+            None => write!(f, "byte {}", self.offset),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! The in-memory representation of a parsed BMFont.
+
+use serde::ser::{SerializeSeq, Serializer};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterOffset {
+    #[serde(serialize_with = "number")]
+    pub x: f32,
+    #[serde(serialize_with = "number")]
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterSize {
+    #[serde(serialize_with = "number")]
+    pub width: f32,
+    #[serde(serialize_with = "number")]
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CharacterPosition {
+    #[serde(serialize_with = "number")]
+    pub x: f32,
+    #[serde(serialize_with = "number")]
+    pub y: f32,
+}
+
+/// Layout of a single glyph, in texture pixels.
+#[derive(Debug, Clone, Serialize)]
+pub struct Character {
+    pub size: CharacterSize,
+    pub position: CharacterPosition,
+    pub offset: CharacterOffset,
+    #[serde(serialize_with = "number")]
+    pub advance: f32,
+    pub page: u32,
+    /// The `chnl` bitmask of the atlas channels holding the glyph: 1 for
+    /// blue, 2 green, 4 red, 8 alpha, or [`ALL_CHANNELS`] when it is drawn
+    /// into all of them, as in fonts that are not channel-packed.
+    #[serde(skip_serializing_if = "is_all_channels")]
+    pub channel: u8,
+    /// The `letter` attribute some exporters write next to `id`, already
+    /// entity-decoded and with names such as `space` resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter: Option<String>,
+}
+
+/// The `chnl` of a glyph drawn into every channel of the atlas.
+pub const ALL_CHANNELS: u8 = 15;
+
+fn is_all_channels(channel: &u8) -> bool {
+    *channel == ALL_CHANNELS
+}
+
+/// The `<common>` block: line metrics and the texture dimensions glyph
+/// positions are relative to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommonMetrics {
+    #[serde(serialize_with = "number")]
+    pub line_height: f32,
+    /// Distance from the top of a line to the baseline.
+    #[serde(serialize_with = "number")]
+    pub base: f32,
+    pub scale_w: u32,
+    pub scale_h: u32,
+}
+
+/// The `<distanceField>` element of fonts generated as signed distance
+/// fields, e.g. by msdf-bmfont.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DistanceField {
+    /// `sdf`, `psdf` or `msdf`.
+    pub field_type: String,
+    /// The distance range encoded in the atlas, in texture pixels.
+    #[serde(serialize_with = "number")]
+    pub range: f32,
+}
+
+/// How far the glyphs of a font reach above and below the baseline, in
+/// texture pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Extents {
+    /// From the baseline up to the top of the tallest glyph.
+    #[serde(serialize_with = "number")]
+    pub ascent: f32,
+    /// From the baseline down to the bottom of the lowest descender.
+    #[serde(serialize_with = "number")]
+    pub descent: f32,
+}
+
+impl Extents {
+    /// The extents of the glyphs in `characters` relative to `base`, the
+    /// distance from the top of a line to the baseline. Glyphs without
+    /// pixels, such as spaces, are left out.
+    pub fn of<'a>(characters: impl IntoIterator<Item = &'a Character>, base: f32) -> Self {
+        characters
+            .into_iter()
+            .filter(|data| data.size.width > 0.0 && data.size.height > 0.0)
+            .fold(Extents { ascent: 0.0, descent: 0.0 }, |extents, data| Extents {
+                ascent: extents.ascent.max(base - data.offset.y),
+                descent: extents.descent.max(data.offset.y + data.size.height - base),
+            })
+    }
+}
+
+/// The `padding`, `spacing` and `outline` attributes of `<info>`: how the
+/// exporter laid out the glyphs in the atlas, in texture pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct GlyphSpacing {
+    /// Blank space inside each glyph's rect and offset: up, right, down and
+    /// left.
+    pub padding: [i32; 4],
+    /// Gap between neighbouring glyphs in the atlas, horizontal and vertical.
+    pub spacing: [i32; 2],
+    /// Thickness of the outline drawn around each glyph.
+    pub outline: i32,
+}
+
+impl GlyphSpacing {
+    pub fn is_zero(&self) -> bool {
+        *self == GlyphSpacing::default()
+    }
+}
+
+/// Font-wide values shared by every glyph.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FontMetrics {
+    pub size: i32,
+    #[serde(skip_serializing_if = "GlyphSpacing::is_zero")]
+    pub glyph_spacing: GlyphSpacing,
+    pub common: Option<CommonMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_field: Option<DistanceField>,
+    /// Texture file names keyed by `<page id>`.
+    pub pages: BTreeMap<u32, String>,
+    /// Advance adjustments keyed by `(first, second)` codepoint pairs.
+    #[serde(serialize_with = "kerning_pairs")]
+    pub kerning: BTreeMap<(u32, u32), f32>,
+    /// The `<chars count>` the source declares, if any.
+    #[serde(skip)]
+    pub declared_chars: Option<usize>,
+    /// Warnings for the glyphs whose left-out or invalid attributes were
+    /// filled in while parsing, one per glyph.
+    #[serde(skip)]
+    pub filled_in: Vec<String>,
+    /// The ascent and descent of the glyphs, which the formatter works out
+    /// for [`OutputOptions::vertical_metrics`](crate::formatter::OutputOptions::vertical_metrics).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extents: Option<Extents>,
+}
+
+/// A parsed font: its metrics and glyphs keyed by codepoint.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Font {
+    pub metrics: FontMetrics,
+    pub characters: BTreeMap<u32, Character>,
+    /// Codepoints defined more than once in the source; the last definition
+    /// is the one kept.
+    #[serde(skip)]
+    pub duplicate_ids: Vec<u32>,
+    /// Codepoints in the order the source first defines them, for
+    /// [`CharacterOrder::Source`](crate::formatter::CharacterOrder::Source).
+    /// Glyphs added after parsing are missing from it.
+    #[serde(skip)]
+    pub source_order: Vec<u32>,
+    /// The declared `<chars count>` and the number of glyphs the source
+    /// actually holds, when they differ, e.g. because the file is truncated.
+    #[serde(skip)]
+    pub count_mismatch: Option<(usize, usize)>,
+    /// Warnings for the elements a lenient parse left out; see
+    /// [`parse_fnt_with`](crate::parser::parse_fnt_with).
+    #[serde(skip)]
+    pub skipped: Vec<String>,
+    /// Codepoints of glyphs made up rather than read, see
+    /// [`synthesize_whitespace`](crate::synthesize::synthesize_whitespace).
+    #[serde(skip)]
+    pub synthesized: BTreeSet<u32>,
+}
+
+impl Font {
+    /// Whether the glyphs are packed into separate channels of the atlas,
+    /// so a renderer has to pick each glyph's channel out of the texture.
+    pub fn uses_channels(&self) -> bool {
+        self.characters.values().any(|data| data.channel != ALL_CHANNELS)
+    }
+}
+
+#[derive(Serialize)]
+struct KerningPair {
+    first: u32,
+    second: u32,
+    #[serde(serialize_with = "number")]
+    amount: f32,
+}
+
+/// Serializes kerning as a list of pairs, since tuple keys cannot be map keys
+/// in formats like JSON.
+pub(crate) fn kerning_pairs<S: Serializer>(kerning: &BTreeMap<(u32, u32), f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(kerning.len()))?;
+    for (&(first, second), &amount) in kerning {
+        seq.serialize_element(&KerningPair { first, second, amount })?;
+    }
+    seq.end()
+}
+
+/// Serializes whole numbers as integers, so JSON and templates print `12`, as
+/// the Lua output does, rather than `12.0`.
+pub(crate) fn number<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f32 {
+        serializer.serialize_i64(*value as i64)
+    } else {
+        serializer.serialize_f32(*value)
+    }
+}
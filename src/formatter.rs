@@ -0,0 +1,2328 @@
+//! Rendering parsed fonts as Lua modules.
+
+use crate::Progress;
+use crate::batch::Overwrite;
+use crate::c_header::CHeaderFormatter;
+use crate::config_formats::{TomlFormatter, YamlFormatter};
+use crate::error::ConvertError;
+use crate::font::{Character, CharacterPosition, CharacterSize, Extents, Font, FontMetrics, kerning_pairs, number};
+use crate::glyph_filter::GlyphFilter;
+use crate::icons::IconNames;
+use crate::love::LoveFormatter;
+use crate::page_paths::PagePaths;
+use crate::plist::PlistFormatter;
+use crate::remap::CodepointMap;
+use crate::synthesize::{WhitespaceRules, synthesize_whitespace};
+use crate::subset::Subset;
+use crate::template::render_template;
+use crate::writer::{FntFormatter, FntTextFormatter, RbxmxFormatter};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use tracing::debug;
+
+
+/// Applies the subset, padding, scale, clamping and rounding settings to a
+/// copy of the glyph metrics of `font`.
+pub fn adjust_metrics(font: &Font, options: &OutputOptions) -> BTreeMap<u32, Character> {
+    let mut adjusted = font.characters.clone();
+    adjusted.retain(|id, _| options.includes(*id));
+    for data in adjusted.values_mut() {
+        if options.bake_padding && data.size.width > 0.0 && data.size.height > 0.0 {
+            let [up, right, down, left] = font.metrics.glyph_spacing.padding.map(|side| side as f32);
+            data.position.x += left;
+            data.position.y += up;
+            data.offset.x += left;
+            data.offset.y += up;
+            data.size.width = (data.size.width - left - right).max(0.0);
+            data.size.height = (data.size.height - up - down).max(0.0);
+        }
+        // Padding is in atlas pixels, so it goes on before scaling. Empty
+        // glyphs such as spaces have no pixels to pad.
+        if options.padding != 0 && data.size.width > 0.0 && data.size.height > 0.0 {
+            let padding = options.padding as f32;
+            let width = (data.size.width + 2.0 * padding).max(0.0);
+            let height = (data.size.height + 2.0 * padding).max(0.0);
+            // A rect shrunk to nothing keeps its center.
+            let (grow_x, grow_y) = ((width - data.size.width) / 2.0, (height - data.size.height) / 2.0);
+            data.position.x -= grow_x;
+            data.position.y -= grow_y;
+            data.offset.x -= grow_x;
+            data.offset.y -= grow_y;
+            data.size.width = width;
+            data.size.height = height;
+        }
+
+        let values = [
+            &mut data.size.width,
+            &mut data.size.height,
+            &mut data.position.x,
+            &mut data.position.y,
+            &mut data.offset.x,
+            &mut data.offset.y,
+            &mut data.advance,
+        ];
+        for value in values {
+            *value *= options.scale;
+        }
+
+        if options.clamp_negative {
+            // Offsets may legitimately be negative and are left alone.
+            data.size.width = data.size.width.max(0.0);
+            data.size.height = data.size.height.max(0.0);
+            data.advance = data.advance.max(0.0);
+        }
+
+        let values = [
+            &mut data.size.width,
+            &mut data.size.height,
+            &mut data.position.x,
+            &mut data.position.y,
+            &mut data.offset.x,
+            &mut data.offset.y,
+            &mut data.advance,
+        ];
+        for value in values {
+            *value = options.rounding.apply(*value);
+        }
+    }
+    adjusted
+}
+
+/// Applies the scale factor to the line metrics, texture size and kerning of
+/// `font`, rounding the texture size to whole pixels and the rest like glyph
+/// metrics, and drops the kerning pairs [`OutputOptions::prune_kerning`]
+/// asks to.
+pub fn adjust_font_metrics(font: &Font, options: &OutputOptions) -> FontMetrics {
+    let mut adjusted = font.metrics.clone();
+    adjusted.kerning.retain(|pair, _| options.kerning && (!options.prune_kerning || options.exports_pair(font, *pair)));
+    if options.bake_padding {
+        adjusted.glyph_spacing.padding = [0; 4];
+    }
+    let spacing = &mut adjusted.glyph_spacing;
+    for value in spacing.padding.iter_mut().chain(&mut spacing.spacing).chain([&mut spacing.outline]) {
+        *value = (*value as f32 * options.scale).round() as i32;
+    }
+
+    let scale = |value: f32| options.rounding.apply(value * options.scale);
+    if let Some(ref mut common) = adjusted.common {
+        common.line_height = scale(common.line_height);
+        common.base = scale(common.base);
+        common.scale_w = (f64::from(common.scale_w) * f64::from(options.scale)).round() as u32;
+        common.scale_h = (f64::from(common.scale_h) * f64::from(options.scale)).round() as u32;
+    }
+    for amount in adjusted.kerning.values_mut() {
+        *amount = scale(*amount);
+    }
+    adjusted
+}
+
+/// The [`Extents`] of the glyphs `options` exports, scaled and rounded like
+/// the line metrics, or `None` without `<common>` metrics to measure from.
+pub(crate) fn glyph_extents(font: &Font, options: &OutputOptions) -> Option<Extents> {
+    let base = font.metrics.common.as_ref()?.base;
+    let glyphs = font.characters.iter().filter(|(id, _)| options.includes(**id)).map(|(_, data)| data);
+    let extents = Extents::of(glyphs, base);
+    let scale = |value: f32| options.rounding.apply(value * options.scale);
+    Some(Extents {
+        ascent: scale(extents.ascent),
+        descent: scale(extents.descent),
+    })
+}
+
+/// How glyph coordinates, line metrics and kerning are rounded before they
+/// are written.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Rounding {
+    Round,
+    Floor,
+    Ceil,
+    /// Emit coordinates exactly as parsed, fractions included.
+    KeepFloat,
+}
+
+impl Rounding {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Rounding::Round => "Round",
+            Rounding::Floor => "Floor",
+            Rounding::Ceil => "Ceil",
+            Rounding::KeepFloat => "Keep float",
+        }
+    }
+
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = match self {
+            Rounding::Round => value.round(),
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::KeepFloat => value,
+        };
+        // Normalize -0.0 so it prints as 0.
+        value + 0.0
+    }
+}
+
+/// How `(x, y)` pairs are written in the generated Lua.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VectorStyle {
+    Vector2New,
+    FunctionCall(String),
+    /// `{ x = 1, y = 2 }`
+    TableLiteral,
+    /// `{ 1, 2 }`
+    Array,
+    /// No pairs at all: glyphs are tables of named fields such as
+    /// `Width = 3, Height = 1`, and `TextureSize` becomes `TextureWidth` and
+    /// `TextureHeight`.
+    Flattened,
+}
+
+impl VectorStyle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VectorStyle::Vector2New => "Vector2.new",
+            VectorStyle::FunctionCall(_) => "Function call",
+            VectorStyle::TableLiteral => "Table literal",
+            VectorStyle::Array => "Array",
+            VectorStyle::Flattened => "Flattened fields",
+        }
+    }
+
+    fn format(&self, x: impl Display, y: impl Display, layout: &Layout) -> String {
+        let Layout { comma, equals, .. } = layout;
+        match self {
+            VectorStyle::Vector2New => format!("Vector2.new({x}{comma}{y})"),
+            VectorStyle::FunctionCall(name) => format!("{name}({x}{comma}{y})"),
+            // Flattened output never writes a pair; fall back to a table.
+            VectorStyle::TableLiteral | VectorStyle::Flattened => {
+                layout.braces(&format!("x{equals}{x}{comma}y{equals}{y}"))
+            }
+            VectorStyle::Array => layout.braces(&format!("{x}{comma}{y}")),
+        }
+    }
+
+    /// Renders the values of one glyph: named fields when flattened, and
+    /// otherwise the pairs as vectors followed by the single values.
+    fn values<T: Display>(&self, pairs: &[[(&str, T); 2]], singles: &[(&str, T)], layout: &Layout) -> Vec<String> {
+        if *self == VectorStyle::Flattened {
+            let equals = layout.equals;
+            return pairs
+                .iter()
+                .flatten()
+                .chain(singles)
+                .map(|(key, value)| format!("{key}{equals}{value}"))
+                .collect();
+        }
+        pairs
+            .iter()
+            .map(|[(_, x), (_, y)]| self.format(x, y, layout))
+            .chain(singles.iter().map(|(_, value)| value.to_string()))
+            .collect()
+    }
+}
+
+/// How a glyph's atlas position and size are combined into one entry.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RectStyle {
+    /// `Rect.new(x, y, width, height)`
+    RectNew,
+    /// `{ x, y, width, height }`
+    Array,
+}
+
+impl RectStyle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RectStyle::RectNew => "Rect.new",
+            RectStyle::Array => "Array",
+        }
+    }
+
+    fn format<T: Display>(&self, position: (T, T), size: (T, T), layout: &Layout) -> String {
+        let values = [position.0, position.1, size.0, size.1].map(|value| value.to_string());
+        match self {
+            RectStyle::RectNew => format!("Rect.new({})", layout.list(&values)),
+            RectStyle::Array => layout.braces(&layout.list(&values)),
+        }
+    }
+}
+
+/// Whitespace and punctuation choices for the generated Lua.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Style {
+    /// Indents with tabs instead of spaces.
+    pub use_tabs: bool,
+    /// Spaces, or tabs, per indentation level.
+    pub indent_width: usize,
+    /// Writes a comma after the last entry of multi-line tables.
+    pub trailing_commas: bool,
+    /// Writes each value of a glyph table on its own line instead of one
+    /// glyph per line.
+    pub field_per_line: bool,
+    /// Writes the whole output on a single line, XML formats included;
+    /// overrides the other settings.
+    pub minified: bool,
+}
+
+impl Style {
+    /// One level of indentation.
+    pub fn indent(&self) -> String {
+        if self.use_tabs {
+            "\t".repeat(self.indent_width)
+        } else {
+            " ".repeat(self.indent_width)
+        }
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            use_tabs: false,
+            indent_width: 4,
+            trailing_commas: true,
+            field_per_line: false,
+            minified: false,
+        }
+    }
+}
+
+/// The whitespace used when rendering Lua source.
+struct Layout {
+    indent: String,
+    trailing_commas: bool,
+    field_per_line: bool,
+    newline: &'static str,
+    comma: &'static str,
+    equals: &'static str,
+    pad: &'static str,
+    statement: &'static str,
+}
+
+impl Layout {
+    fn new(style: &Style) -> Self {
+        if style.minified {
+            Self {
+                indent: String::new(),
+                trailing_commas: false,
+                field_per_line: false,
+                newline: "",
+                comma: ",",
+                equals: "=",
+                pad: "",
+                statement: ";",
+            }
+        } else {
+            Self {
+                indent: style.indent(),
+                trailing_commas: style.trailing_commas,
+                field_per_line: style.field_per_line,
+                newline: "\n",
+                comma: ", ",
+                equals: " = ",
+                pad: " ",
+                statement: "\n",
+            }
+        }
+    }
+
+    fn braces(&self, inner: &str) -> String {
+        format!("{{{pad}{inner}{pad}}}", pad = self.pad)
+    }
+
+    fn list(&self, items: &[String]) -> String {
+        items.join(self.comma)
+    }
+
+    /// Renders `entries` as a Lua table whose closing brace sits at `base`,
+    /// one entry per line unless minified, or as `{}` when there are none.
+    fn table(&self, entries: &[String], base: &str) -> String {
+        if entries.is_empty() {
+            return "{}".to_string();
+        }
+        if self.newline.is_empty() {
+            return format!("{{{}}}", entries.join(","));
+        }
+
+        let mut table = format!("{{{}", self.newline);
+        for (index, entry) in entries.iter().enumerate() {
+            let comma = if self.trailing_commas || index + 1 < entries.len() { "," } else { "" };
+            table.push_str(&format!("{base}{}{entry}{comma}{}", self.indent, self.newline));
+        }
+        table.push_str(base);
+        table.push('}');
+        table
+    }
+
+    /// Renders the values of one glyph, as an entry of a table whose closing
+    /// brace sits at `base`.
+    fn glyph(&self, values: &[String], base: &str) -> String {
+        if self.field_per_line {
+            self.table(values, &format!("{base}{}", self.indent))
+        } else {
+            self.braces(&self.list(values))
+        }
+    }
+}
+
+/// The shape of the generated Lua module.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ModuleStyle {
+    /// `return { Size = ..., Characters = { ... } }`
+    BareReturn,
+    /// `local Font = {}` followed by `Font.Size = ...` (or `Font["Size"] = ...`
+    /// when `indexed`) and `return Font`.
+    NamedTable { name: String, indexed: bool },
+}
+
+/// Lua's reserved words, which cannot name a local; `goto` is reserved since
+/// Lua 5.2.
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in", "local", "nil", "not", "or",
+    "repeat", "return", "then", "true", "until", "while",
+];
+
+impl ModuleStyle {
+    /// Fails if the table of a [`ModuleStyle::NamedTable`] cannot be named by
+    /// `local NAME = {}`; see [`check_module_name`].
+    pub fn check(&self) -> Result<(), ConvertError> {
+        match self {
+            ModuleStyle::NamedTable { name, .. } => check_module_name(name),
+            ModuleStyle::BareReturn => Ok(()),
+        }
+    }
+}
+
+/// Fails unless `name` is a Lua identifier, made of ASCII letters, digits
+/// and underscores and not starting with a digit, that is not a reserved
+/// word such as `end`.
+pub fn check_module_name(name: &str) -> Result<(), ConvertError> {
+    let identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !identifier {
+        return Err(ConvertError::ModuleName(format!("{name:?} is not a Lua identifier")));
+    }
+    if LUA_KEYWORDS.contains(&name) {
+        return Err(ConvertError::ModuleName(format!("`{name}` is a Lua keyword")));
+    }
+    Ok(())
+}
+
+/// A comment block written above the generated module.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeaderOptions {
+    /// Comment text; `{source}` is replaced with the input file name.
+    pub text: String,
+    pub timestamp: bool,
+    /// Adds the source checksum, the converter version, the generation time
+    /// and the options that differ from the defaults, so whoever reads the
+    /// output can tell how to regenerate it.
+    pub provenance: bool,
+    /// CRC-32 of the input files, filled in by whoever read them; see
+    /// [`with_checksums`](crate::batch::with_checksums).
+    #[serde(skip)]
+    pub source_checksum: Option<u32>,
+}
+
+impl Default for HeaderOptions {
+    fn default() -> Self {
+        Self {
+            text: "Generated by XmlToLuaConverter from {source}; do not edit".to_string(),
+            timestamp: false,
+            provenance: false,
+            source_checksum: None,
+        }
+    }
+}
+
+/// The order glyphs are written in.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum CharacterOrder {
+    #[default]
+    Codepoint,
+    /// The order the source file defines them in; see [`Font::source_order`].
+    Source,
+    /// Glyphs in the first range, then those in the second, and so on, e.g.
+    /// ASCII before everything else; the rest follow by codepoint.
+    Priority(Subset),
+}
+
+impl CharacterOrder {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CharacterOrder::Codepoint => "Codepoint",
+            CharacterOrder::Source => "Source order",
+            CharacterOrder::Priority(_) => "Priority list",
+        }
+    }
+
+    /// The codepoints of `characters` in this order. `source_order` is the
+    /// font's [`Font::source_order`]; glyphs missing from it come last.
+    pub fn arrange(&self, characters: &BTreeMap<u32, Character>, source_order: &[u32]) -> Vec<u32> {
+        let mut ids: Vec<u32> = characters.keys().copied().collect();
+        match self {
+            CharacterOrder::Codepoint => {}
+            CharacterOrder::Source => {
+                let positions: HashMap<u32, usize> = source_order.iter().enumerate().map(|(position, id)| (*id, position)).collect();
+                ids.sort_by_key(|id| positions.get(id).copied().unwrap_or(usize::MAX));
+            }
+            CharacterOrder::Priority(ranges) => ids.sort_by_key(|id| ranges.rank(*id).unwrap_or(usize::MAX)),
+        }
+        ids
+    }
+}
+
+/// How the Lua output writes table keys for characters a string literal
+/// can only spell with an escape, i.e. control characters and codepoints
+/// that are not Unicode scalar values.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum KeyEscape {
+    /// `["\n"]`, or decimal byte escapes such as `["\194\133"]` where Lua
+    /// has no named escape.
+    #[default]
+    Escaped,
+    /// `[string.char(10)]`, the UTF-8 bytes of the character, which every
+    /// Lua version reads.
+    StringChar,
+    /// `[utf8.char(0xA)]`, which needs Lua 5.3 or Luau.
+    Utf8Char,
+}
+
+impl KeyEscape {
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyEscape::Escaped => "Escaped string",
+            KeyEscape::StringChar => "string.char",
+            KeyEscape::Utf8Char => "utf8.char",
+        }
+    }
+}
+
+/// Whether glyphs carry UV coordinates: their atlas position and size
+/// divided by the texture size, from 0 to 1, which shaders sample with
+/// without knowing the texture's dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum UvCoordinates {
+    /// Pixels only.
+    #[default]
+    Off,
+    /// UV coordinates in place of the pixel position and size.
+    Replace,
+    /// UV coordinates after the other values of each glyph, as `uv` in
+    /// JSON, or `U`, `V`, `UWidth` and `VHeight` in flattened Lua.
+    Append,
+}
+
+impl UvCoordinates {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UvCoordinates::Off => "Pixels",
+            UvCoordinates::Replace => "UV instead of pixels",
+            UvCoordinates::Append => "UV and pixels",
+        }
+    }
+}
+
+/// The atlas rect of `data` divided by the texture size in `metrics`, as
+/// `[u, v, width, height]`, or `None` without a texture size to divide by.
+/// Both should be adjusted alike, so the scale cancels out.
+fn uv_rect(data: &Character, metrics: &FontMetrics) -> Option<[f64; 4]> {
+    let common = metrics.common.as_ref().filter(|common| common.scale_w > 0 && common.scale_h > 0)?;
+    let (width, height) = (f64::from(common.scale_w), f64::from(common.scale_h));
+    Some([
+        f64::from(data.position.x) / width,
+        f64::from(data.position.y) / height,
+        f64::from(data.size.width) / width,
+        f64::from(data.size.height) / height,
+    ])
+}
+
+/// The kind of file a font is exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutputFormat {
+    Lua,
+    Json,
+    /// BMFont XML, e.g. to write back a font imported from Lua.
+    Fnt,
+    /// The plain-text BMFont format, which Godot, Defold and cocos2d import.
+    FntText,
+    /// A cocos2d sprite frame list with a frame per glyph.
+    Plist,
+    /// The Lua output wrapped in a Roblox model file holding a ModuleScript,
+    /// which can be dragged straight into Roblox Studio.
+    Rbxmx,
+    /// A C/C++ header declaring the glyphs and kerning as static arrays.
+    CHeader,
+    /// A Lua module for LÖVE with a quad per glyph.
+    Love,
+    /// The JSON data as TOML tables.
+    Toml,
+    /// The JSON data as a YAML document.
+    Yaml,
+}
+
+impl OutputFormat {
+    /// The formatter registered in [`FORMATTERS`] for this format.
+    pub fn formatter(&self) -> &'static dyn OutputFormatter {
+        FORMATTERS
+            .iter()
+            .copied()
+            .find(|formatter| formatter.format() == *self)
+            .expect("every format has a registered formatter")
+    }
+
+    /// The format whose [`OutputFormatter::name`] is `name`.
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        FORMATTERS.iter().find(|formatter| formatter.name() == name).map(|formatter| formatter.format())
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.formatter().label()
+    }
+
+    /// File extension for the format, without the dot.
+    pub fn extension(&self) -> &'static str {
+        self.formatter().extension()
+    }
+}
+
+/// Renders fonts in one [`OutputFormat`]. Every format has one formatter,
+/// listed in [`FORMATTERS`], which the GUI and `--format` offer.
+pub trait OutputFormatter: Sync {
+    /// The format this renders, as stored in [`OutputOptions::format`].
+    fn format(&self) -> OutputFormat;
+
+    /// The name `--format` and presets select the format by.
+    fn name(&self) -> &'static str;
+
+    /// The name shown in the GUI.
+    fn label(&self) -> &'static str;
+
+    /// A one-line description for `--help`.
+    fn description(&self) -> &'static str;
+
+    /// File extension, without the dot.
+    fn extension(&self) -> &'static str;
+
+    /// Whether a custom template is rendered instead of this format. Formats
+    /// that wrap another one keep their own output and wrap the template's.
+    fn replaced_by_template(&self) -> bool {
+        true
+    }
+
+    /// Renders `font`; `source` names the inputs it was read from.
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError>;
+}
+
+/// Every export format, in the order they are offered.
+pub static FORMATTERS: [&dyn OutputFormatter; 10] = [
+    &LuaFormatter,
+    &JsonFormatter,
+    &TomlFormatter,
+    &YamlFormatter,
+    &FntFormatter,
+    &FntTextFormatter,
+    &PlistFormatter,
+    &RbxmxFormatter,
+    &CHeaderFormatter,
+    &LoveFormatter,
+];
+
+/// The Lua module of [`format_output`].
+pub struct LuaFormatter;
+
+impl OutputFormatter for LuaFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Lua
+    }
+
+    fn name(&self) -> &'static str {
+        "lua"
+    }
+
+    fn label(&self) -> &'static str {
+        "Lua"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lua module returning the font table"
+    }
+
+    fn extension(&self) -> &'static str {
+        "lua"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        if !options.scales.is_empty() {
+            return Ok(format_scales(font, source, options));
+        }
+        Ok(format_output(font, source, options))
+    }
+}
+
+/// The JSON of [`format_json`].
+pub struct JsonFormatter;
+
+impl OutputFormatter for JsonFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Json
+    }
+
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn label(&self) -> &'static str {
+        "JSON"
+    }
+
+    fn description(&self) -> &'static str {
+        "The adjusted font as JSON"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn render(&self, font: &Font, _source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(format_json(font, options))
+    }
+}
+
+/// Settings controlling how [`render`] renders a font.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    pub vector_style: VectorStyle,
+    pub trailing_newline: bool,
+    pub bom: bool,
+    pub dedupe_metrics: bool,
+    pub clamp_negative: bool,
+    /// Emits this value as `Size` instead of the parsed `<info size>`.
+    pub size_override: Option<i32>,
+    /// BMFont writes a negative size when the font was generated to match the
+    /// character height rather than the cell height; when set, the sign is
+    /// dropped so `Size` is always the pixel size.
+    pub absolute_size: bool,
+    pub module_style: ModuleStyle,
+    pub header: Option<HeaderOptions>,
+    pub style: Style,
+    pub rounding: Rounding,
+    /// Emits only the font-wide metadata, leaving out per-glyph tables.
+    pub metrics_only: bool,
+    /// Writes each glyph as just its advance and leaves out the texture
+    /// size, pages and distance field, for measuring text width where the
+    /// atlas is never drawn. Applies to Lua, JSON, TOML and YAML output.
+    pub advances_only: bool,
+    /// Writes the kerning pairs.
+    pub kerning: bool,
+    /// Drops kerning pairs with a glyph that is not exported, because the
+    /// subset or exclusions leave it out or the font does not define it.
+    /// When off, such pairs are written as the source has them.
+    pub prune_kerning: bool,
+    /// Writes a `PageChecksums` list next to `Pages` with the CRC-32 of each
+    /// texture, so a game can tell when the metrics and the uploaded atlas
+    /// have drifted apart. Textures that could not be read get `""`.
+    pub texture_checksums: bool,
+    /// The CRC-32 of each texture page by page id, filled in by
+    /// [`with_checksums`](crate::batch::with_checksums).
+    #[serde(skip)]
+    pub page_checksums: BTreeMap<u32, u32>,
+    /// Removes the `<info padding>` the exporter left around each glyph from
+    /// the glyph rects, moving the offsets so glyphs still render in place.
+    pub bake_padding: bool,
+    /// Writes the ascent and descent of the exported glyphs next to the line
+    /// height and base, for aligning text vertically; see [`Extents`].
+    pub vertical_metrics: bool,
+    /// Adds a `MeasureText(text)` function to the Lua module that returns the
+    /// width of `text` in pixels from the exported advances and kerning.
+    pub measure_text: bool,
+    /// Writes the glyphs of the Lua module as one packed string, which a
+    /// decoder in the module turns back into the `Characters` table when it
+    /// is required. Metrics are rounded to whole pixels, glyphs are keyed by
+    /// their codepoint's character rather than `letter`, and
+    /// [`dedupe_metrics`](Self::dedupe_metrics) and
+    /// [`chunk_size`](Self::chunk_size) do not apply.
+    pub packed: bool,
+    /// Handlebars template source used instead of the built-in Lua layout;
+    /// see [`render_template`](crate::template::render_template).
+    pub template: Option<String>,
+    /// Exports only these characters; kerning pairs involving any other
+    /// character are dropped too.
+    pub subset: Option<Subset>,
+    /// Drops glyphs that fail any of its predicates, with their kerning
+    /// pairs. Only [`render`] applies it.
+    pub glyph_filter: GlyphFilter,
+    /// Moves glyphs to other codepoints before anything else is applied, so
+    /// the subset and exclusions refer to the new codepoints. Only
+    /// [`render`] applies it.
+    pub remap: Option<CodepointMap>,
+    /// Writes an `Icons` table after `Kerning` that maps each of these names
+    /// to its codepoint, leaving out names whose glyph is not exported.
+    /// Codepoints are those after [`remap`](Self::remap). Applies to Lua
+    /// output.
+    pub icons: Option<IconNames>,
+    /// Adds empty glyphs for a space, tab and no-break space when the font
+    /// has none, sized by these rules, so runtimes never look up whitespace
+    /// in vain. The Lua output marks them with `Synthesized = true`, except
+    /// in advances-only, packed and Luau-typed modules. Only [`render`]
+    /// applies it.
+    pub synthesize: Option<WhitespaceRules>,
+    /// Exports only the glyphs on these texture pages, and only these pages;
+    /// all of them when unset. Only [`render`] applies it.
+    pub pages: Option<BTreeSet<u32>>,
+    /// Moves every glyph down by this many pixels, before scaling, along
+    /// with the base and line height, so the font lines up with the others
+    /// of its family; see [`baseline_shifts`](crate::align::baseline_shifts).
+    /// The Lua output records it as `BaselineShift`. Only [`render`] applies
+    /// it.
+    pub baseline_shift: f32,
+    /// Rewrites the texture file names of the pages, e.g. to the asset ids
+    /// they were uploaded as, after any other change. Only [`render`]
+    /// applies it.
+    pub page_paths: PagePaths,
+    /// Multiplies every metric before rounding, e.g. 0.5 when the atlas was
+    /// exported at twice the resolution used at runtime.
+    pub scale: f32,
+    /// Writes the Lua module as a sub-table per scale, keyed by the scale and
+    /// rendered as with that [`scale`](Self::scale), after a `Scales` list of
+    /// them, so UI code can pick the closest pre-scaled metrics. Luau types,
+    /// `MeasureText`, [`packed`](Self::packed) and
+    /// [`chunk_size`](Self::chunk_size) do not apply to such modules.
+    pub scales: Vec<f32>,
+    /// Grows (or, when negative, shrinks) every glyph rect by this many atlas
+    /// pixels on each side, moving the offset so the glyph still renders in
+    /// place. Linear filtering needs more bleed margin than nearest.
+    pub padding: i32,
+    /// Keys `Characters` and `Kerning` by codepoint (`[65]`) instead of by the
+    /// literal character, which is unambiguous for combining marks and
+    /// invisible characters.
+    pub numeric_keys: bool,
+    /// How keys for control characters are written.
+    pub key_escape: KeyEscape,
+    /// Writes glyph rects as UV coordinates, in place of or next to the
+    /// pixels. Applies to Lua, except packed modules, and to JSON and the
+    /// formats converted from it.
+    pub uv: UvCoordinates,
+    /// Starts the module with `--!strict` and exports Luau `GlyphData` and
+    /// `FontData` types that the returned table is annotated with.
+    pub luau_types: bool,
+    /// Writes each glyph's atlas position and size as one rect in place of
+    /// the two pairs. Has no effect with [`VectorStyle::Flattened`].
+    pub rect_style: Option<RectStyle>,
+    /// Keys glyphs by their `letter` attribute when the source has one,
+    /// instead of the character their id maps to.
+    pub prefer_letters: bool,
+    /// Pattern for the names of output files the converter picks itself, as
+    /// in batch conversions: `{name}` is the input file name without its
+    /// extension, `{ext}` the extension of `format`, and `{size}`,
+    /// `{line_height}` and `{glyphs}` the exported font's size, line height
+    /// and glyph count. `{name}.{ext}` when unset.
+    pub file_name: Option<String>,
+    /// Reads written files back and fails the conversion if they do not hold
+    /// what was exported; see [`verify_output`](crate::verify::verify_output).
+    pub verify: bool,
+    /// What happens to an existing file at the output path in
+    /// [`convert_file`](crate::batch::convert_file) and batch conversions.
+    pub overwrite: Overwrite,
+    /// The order glyphs are listed in by the Lua output and templates.
+    pub character_order: CharacterOrder,
+    /// Splits the `Characters` table of the Lua output across part modules
+    /// of at most this many glyphs, which the written module loads when it
+    /// is required. Only [`render_chunks`] applies it.
+    pub chunk_size: Option<usize>,
+    /// Leaves out elements of the input that fail to parse, reporting them as
+    /// warnings, instead of failing the conversion; see
+    /// [`parse_fnt_with`](crate::parser::parse_fnt_with).
+    pub lenient: bool,
+    /// Checks XML input against the BMFont structure before parsing it,
+    /// failing with every problem found; see
+    /// [`check_schema`](crate::schema::check_schema).
+    pub check_schema: bool,
+    /// Codepoints never exported, whatever the subset. NUL and carriage
+    /// return by default: BMFont exporters add them, but they are never drawn.
+    pub excluded: BTreeSet<u32>,
+}
+
+impl OutputOptions {
+    /// The `Size` written for `font`, after the override, sign and scale
+    /// settings. An overridden size is written as given.
+    pub fn font_size(&self, font: &Font) -> i32 {
+        let size = match self.size_override {
+            Some(size) => return size,
+            None if self.absolute_size => font.metrics.size.abs(),
+            None => font.metrics.size,
+        };
+        (size as f32 * self.scale).round() as i32
+    }
+
+    /// Whether the glyph for codepoint `id` is exported.
+    pub fn includes(&self, id: u32) -> bool {
+        !self.excluded.contains(&id) && self.subset.as_ref().is_none_or(|subset| subset.contains(id))
+    }
+
+    /// Whether a kerning pair is exported, i.e. both of its glyphs are.
+    pub fn includes_pair(&self, (first, second): (u32, u32)) -> bool {
+        self.includes(first) && self.includes(second)
+    }
+
+    /// Whether both glyphs of a kerning pair are exported and defined by
+    /// `font`.
+    pub fn exports_pair(&self, font: &Font, (first, second): (u32, u32)) -> bool {
+        self.includes_pair((first, second))
+            && font.characters.contains_key(&first)
+            && font.characters.contains_key(&second)
+    }
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Lua,
+            vector_style: VectorStyle::Vector2New,
+            trailing_newline: true,
+            bom: false,
+            dedupe_metrics: false,
+            clamp_negative: false,
+            size_override: None,
+            absolute_size: true,
+            module_style: ModuleStyle::BareReturn,
+            header: None,
+            style: Style::default(),
+            rounding: Rounding::Round,
+            metrics_only: false,
+            advances_only: false,
+            kerning: true,
+            prune_kerning: true,
+            texture_checksums: false,
+            page_checksums: BTreeMap::new(),
+            bake_padding: false,
+            vertical_metrics: false,
+            measure_text: false,
+            packed: false,
+            template: None,
+            subset: None,
+            glyph_filter: GlyphFilter::default(),
+            remap: None,
+            icons: None,
+            synthesize: None,
+            pages: None,
+            baseline_shift: 0.0,
+            page_paths: PagePaths::default(),
+            scale: 1.0,
+            scales: Vec::new(),
+            padding: 0,
+            numeric_keys: false,
+            key_escape: KeyEscape::default(),
+            uv: UvCoordinates::default(),
+            luau_types: false,
+            rect_style: None,
+            prefer_letters: false,
+            file_name: None,
+            verify: false,
+            overwrite: Overwrite::default(),
+            character_order: CharacterOrder::default(),
+            chunk_size: None,
+            lenient: false,
+            check_schema: false,
+            excluded: BTreeSet::from([0, 13]),
+        }
+    }
+}
+
+/// Turns formatted output into the bytes written to disk, applying the
+/// trailing newline and BOM settings.
+pub fn output_bytes(output: &str, options: &OutputOptions) -> Vec<u8> {
+    let body = if options.trailing_newline {
+        output
+    } else {
+        output.trim_end_matches('\n')
+    };
+
+    let mut bytes = Vec::with_capacity(body.len() + 3);
+    if options.bom {
+        bytes.extend_from_slice(b"\xEF\xBB\xBF");
+    }
+    bytes.extend_from_slice(body.as_bytes());
+    bytes
+}
+
+/// `[width, height, xoffset, yoffset, advance]` of a glyph.
+pub type MetricRecord = [f32; 5];
+
+/// Collects the distinct size/offset/advance records in first-seen order,
+/// along with the 1-based Lua index each codepoint refers to.
+pub fn shared_metrics(font_data: &BTreeMap<u32, Character>) -> (Vec<MetricRecord>, BTreeMap<u32, usize>) {
+    let mut records = Vec::new();
+    let mut lookup = HashMap::new();
+    let mut indices = BTreeMap::new();
+
+    for (id, data) in font_data {
+        let record = [data.size.width, data.size.height, data.offset.x, data.offset.y, data.advance];
+        let index = *lookup.entry(record.map(f32::to_bits)).or_insert_with(|| {
+            records.push(record);
+            records.len()
+        });
+        indices.insert(*id, index);
+    }
+
+    (records, indices)
+}
+
+/// Renders `font` with the custom template if one is set, or with the
+/// [`OutputFormatter`] of the selected [`OutputFormat`] otherwise. A Roblox
+/// model wraps whichever of the two would be rendered for Lua. Glyphs are
+/// moved and dropped as in [`exported_font`] first.
+pub fn render(font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+    options.module_style.check()?;
+    let font = &exported_font(font, options);
+    let formatter = options.format.formatter();
+    let templated = options.template.is_some() && formatter.replaced_by_template();
+    debug!(format = formatter.name(), templated, glyphs = font.characters.len(), "rendering");
+    let output = match options.template {
+        Some(ref template) if templated => render_template(font, source, options, template)?,
+        _ => formatter.render(font, source, options)?,
+    };
+    debug!(format = formatter.name(), bytes = output.len(), "rendered");
+    Ok(output)
+}
+
+/// A Lua module whose glyphs are split across part modules.
+pub struct LuaChunks {
+    /// The module to require, which loads the parts.
+    pub index: String,
+    /// The name and source of each part module, in the order they are
+    /// loaded; empty when the glyphs fit one part.
+    pub parts: Vec<(String, String)>,
+}
+
+/// Renders `font` like [`render`], with the `Characters` table of the Lua
+/// output split across part modules of [`OutputOptions::chunk_size`] glyphs
+/// named `{name}_part1`, `{name}_part2` and so on. There are no parts when
+/// the option is unset, the output is not the built-in Lua layout, or the
+/// glyphs fit one part.
+pub fn render_chunks(font: &Font, source: &str, options: &OutputOptions, name: &str) -> Result<LuaChunks, ConvertError> {
+    let (Some(size), OutputFormat::Lua, None, true) = (options.chunk_size, &options.format, &options.template, options.scales.is_empty()) else {
+        return Ok(LuaChunks {
+            index: render(font, source, options)?,
+            parts: Vec::new(),
+        });
+    };
+    options.module_style.check()?;
+    let font = &exported_font(font, options);
+    let chunks = format_lua(font, source, options, |_| {}, Some((name, size.max(1))));
+    debug!(glyphs = font.characters.len(), parts = chunks.parts.len(), "rendered in parts");
+    Ok(chunks)
+}
+
+/// Name of the exported ModuleScript: the table name of a named module, or
+/// the stem of the first input file.
+pub(crate) fn script_name(source: &str, options: &OutputOptions) -> String {
+    if let ModuleStyle::NamedTable { name, .. } = &options.module_style {
+        return name.clone();
+    }
+    let first = source.split(", ").next().unwrap_or_default();
+    let stem = first.strip_suffix(".gz").unwrap_or(first);
+    match std::path::Path::new(stem).file_stem() {
+        Some(stem) => stem.to_string_lossy().into_owned(),
+        None => "Font".to_string(),
+    }
+}
+
+/// `font` with its glyphs moved as [`OutputOptions::remap`] asks, the
+/// glyphs and pages left out by [`OutputOptions::pages`] and the glyphs
+/// rejected by [`OutputOptions::glyph_filter`] dropped, along with their
+/// kerning pairs, the whitespace of [`OutputOptions::synthesize`] added, and
+/// the glyphs moved by [`OutputOptions::baseline_shift`]; `font` itself when
+/// none of these applies.
+pub(crate) fn exported_font<'a>(font: &'a Font, options: &OutputOptions) -> Cow<'a, Font> {
+    let mut font = match options.remap {
+        Some(ref map) if !map.is_empty() => Cow::Owned(map.apply(font)),
+        _ => Cow::Borrowed(font),
+    };
+    if let Some(ref pages) = options.pages {
+        let dropped: BTreeSet<u32> =
+            font.characters.iter().filter(|(_, data)| !pages.contains(&data.page)).map(|(id, _)| *id).collect();
+        let font = font.to_mut();
+        font.characters.retain(|id, _| !dropped.contains(id));
+        font.metrics.kerning.retain(|(first, second), _| !dropped.contains(first) && !dropped.contains(second));
+        font.metrics.pages.retain(|id, _| pages.contains(id));
+        font.source_order.retain(|id| !dropped.contains(id));
+        debug!(pages = pages.len(), dropped = dropped.len(), "left out pages");
+    }
+    if !options.glyph_filter.is_empty() {
+        let dropped = options.glyph_filter.dropped(&font);
+        if !dropped.is_empty() {
+            let font = font.to_mut();
+            font.characters.retain(|id, _| !dropped.contains(id));
+            font.metrics.kerning.retain(|(first, second), _| !dropped.contains(first) && !dropped.contains(second));
+            font.source_order.retain(|id| !dropped.contains(id));
+        }
+        debug!(dropped = dropped.len(), "filtered glyphs");
+    }
+    if let Some(ref rules) = options.synthesize {
+        font = Cow::Owned(synthesize_whitespace(&font, rules));
+    }
+    if options.baseline_shift != 0.0 {
+        let shift = options.baseline_shift;
+        let font = font.to_mut();
+        for data in font.characters.values_mut() {
+            data.offset.y += shift;
+        }
+        if let Some(ref mut common) = font.metrics.common {
+            common.base += shift;
+            common.line_height += shift;
+        }
+        debug!(shift, "shifted baseline");
+    }
+    if !options.page_paths.is_empty() {
+        font = Cow::Owned(options.page_paths.apply(&font));
+    }
+    font
+}
+
+/// A copy of `font` with every metric setting applied, for formats that
+/// serialize the whole font.
+pub(crate) fn adjusted_font(font: &Font, options: &OutputOptions) -> Font {
+    let mut adjusted = Font {
+        metrics: adjust_font_metrics(font, options),
+        characters: adjust_metrics(font, options),
+        duplicate_ids: Vec::new(),
+        source_order: font.source_order.clone(),
+        count_mismatch: None,
+        skipped: Vec::new(),
+        synthesized: font.synthesized.clone(),
+    };
+    adjusted.metrics.size = options.font_size(font);
+    if options.vertical_metrics {
+        adjusted.metrics.extents = glyph_extents(font, options);
+    }
+    if options.metrics_only {
+        adjusted.characters.clear();
+        adjusted.metrics.kerning.clear();
+    }
+    adjusted
+}
+
+/// A number written as by [`number`].
+#[derive(Serialize)]
+struct Number(#[serde(serialize_with = "number")] f32);
+
+/// The JSON written with [`OutputOptions::advances_only`]: the line metrics,
+/// each glyph's advance keyed by codepoint, and the kerning.
+#[derive(Serialize)]
+struct AdvancesJson {
+    size: i32,
+    line_height: Option<Number>,
+    base: Option<Number>,
+    characters: BTreeMap<u32, Number>,
+    #[serde(serialize_with = "kerning_pairs")]
+    kerning: BTreeMap<(u32, u32), f32>,
+}
+
+impl AdvancesJson {
+    fn of(font: &Font) -> Self {
+        let common = font.metrics.common.as_ref();
+        AdvancesJson {
+            size: font.metrics.size,
+            line_height: common.map(|common| Number(common.line_height)),
+            base: common.map(|common| Number(common.base)),
+            characters: font.characters.iter().map(|(id, data)| (*id, Number(data.advance))).collect(),
+            kerning: font.metrics.kerning.clone(),
+        }
+    }
+}
+
+/// Renders `font` as JSON, with the same metric adjustments as the Lua
+/// output. Kerning is written as a list of `{ first, second, amount }`.
+pub fn format_json(font: &Font, options: &OutputOptions) -> String {
+    if options.uv != UvCoordinates::Off && !options.advances_only {
+        let value = json_value(font, options);
+        let json = if options.style.minified { serde_json::to_string(&value) } else { serde_json::to_string_pretty(&value) };
+        let mut json = json.expect("font serializes to JSON");
+        json.push('\n');
+        return json;
+    }
+    let font = adjusted_font(font, options);
+    let json = match (options.advances_only, options.style.minified) {
+        (false, false) => serde_json::to_string_pretty(&font),
+        (false, true) => serde_json::to_string(&font),
+        (true, false) => serde_json::to_string_pretty(&AdvancesJson::of(&font)),
+        (true, true) => serde_json::to_string(&AdvancesJson::of(&font)),
+    };
+    let mut json = json.expect("font serializes to JSON");
+    json.push('\n');
+    json
+}
+
+/// What [`format_json`] writes, as a value, for the formats converted from
+/// it.
+pub(crate) fn json_value(font: &Font, options: &OutputOptions) -> serde_json::Value {
+    let font = adjusted_font(font, options);
+    let value = if options.advances_only {
+        serde_json::to_value(AdvancesJson::of(&font))
+    } else {
+        serde_json::to_value(&font)
+    };
+    let mut value = value.expect("font serializes to JSON");
+    if !options.advances_only {
+        add_json_uvs(&mut value, &font, options.uv);
+    }
+    value
+}
+
+/// Adds the UV rect of each glyph in the JSON of `font` as `uv`, or writes
+/// it over `position` and `size`, as `uv` asks.
+fn add_json_uvs(value: &mut serde_json::Value, font: &Font, uv: UvCoordinates) {
+    let Some(characters) = value.get_mut("characters").and_then(serde_json::Value::as_object_mut) else {
+        return;
+    };
+    for (id, data) in &font.characters {
+        let (Some(entry), Some([x, y, width, height])) = (characters.get_mut(&id.to_string()), uv_rect(data, &font.metrics)) else {
+            continue;
+        };
+        match uv {
+            UvCoordinates::Off => {}
+            UvCoordinates::Replace => {
+                entry["position"] = serde_json::json!({ "x": x, "y": y });
+                entry["size"] = serde_json::json!({ "width": width, "height": height });
+            }
+            UvCoordinates::Append => {
+                entry["uv"] = serde_json::json!({ "x": x, "y": y, "width": width, "height": height });
+            }
+        }
+    }
+}
+
+/// Renders `font` as a Lua module. `source` names the input file and is only
+/// used in the header comment.
+///
+/// This is the raw Lua step of [`render`]: it ignores
+/// [`OutputOptions::format`], the template and [`OutputOptions::scales`],
+/// and does not move or drop glyphs as [`exported_font`] does, so remapping,
+/// page selection, the glyph filter, synthesized whitespace, baseline shift
+/// and page paths are not applied. Use [`render`] unless `font` has been
+/// through [`exported_font`] already.
+pub fn format_output(font: &Font, source: &str, options: &OutputOptions) -> String {
+    format_output_with_progress(font, source, options, |_| {})
+}
+
+/// Like [`format_output`], calling `progress` as glyphs are formatted.
+pub fn format_output_with_progress<P: FnMut(Progress)>(
+    font: &Font,
+    source: &str,
+    options: &OutputOptions,
+    progress: P,
+) -> String {
+    format_lua(font, source, options, progress, None).index
+}
+
+/// The Lua module of `font`, with its glyphs split into parts of `size`
+/// named after `name` when `chunks` is `Some((name, size))` and there are
+/// more glyphs than that.
+fn format_lua<P: FnMut(Progress)>(
+    font: &Font,
+    source: &str,
+    options: &OutputOptions,
+    mut progress: P,
+    chunks: Option<(&str, usize)>,
+) -> LuaChunks {
+    let font_data = &adjust_metrics(font, options);
+    let metrics = &adjust_font_metrics(font, options);
+    let packed = options.packed && !options.metrics_only;
+    // Packed glyphs are whole pixels, so they keep them.
+    let uv_data;
+    let font_data = if options.uv == UvCoordinates::Replace && !packed {
+        let mut data = font_data.clone();
+        for glyph in data.values_mut() {
+            if let Some([x, y, width, height]) = uv_rect(glyph, metrics) {
+                glyph.position = CharacterPosition { x: x as f32, y: y as f32 };
+                glyph.size = CharacterSize { width: width as f32, height: height as f32 };
+            }
+        }
+        uv_data = data;
+        &uv_data
+    } else {
+        font_data
+    };
+
+    let font_size = options.font_size(font);
+
+    let layout = Layout::new(&options.style);
+    let Layout { ref indent, newline, equals, pad, statement, .. } = layout;
+    let vector = &options.vector_style;
+    let base = match options.module_style {
+        ModuleStyle::BareReturn => indent.as_str(),
+        ModuleStyle::NamedTable { .. } => "",
+    };
+    let chunks = chunks.filter(|&(_, size)| !options.metrics_only && !packed && font_data.len() > size);
+    let mut parts = Vec::new();
+
+    let mut fields = vec![("Size", font_size.to_string())];
+    if let Some(ref common) = metrics.common {
+        fields.push(("LineHeight", common.line_height.to_string()));
+        fields.push(("Base", common.base.to_string()));
+        if options.vertical_metrics
+            && let Some(extents) = glyph_extents(font, options)
+        {
+            fields.push(("Ascent", extents.ascent.to_string()));
+            fields.push(("Descent", extents.descent.to_string()));
+        }
+        if options.baseline_shift != 0.0 {
+            fields.push(("BaselineShift", options.rounding.apply(options.baseline_shift * options.scale).to_string()));
+        }
+        if options.advances_only {
+            // Without rects the texture size is of no use.
+        } else if *vector == VectorStyle::Flattened {
+            fields.push(("TextureWidth", common.scale_w.to_string()));
+            fields.push(("TextureHeight", common.scale_h.to_string()));
+        } else {
+            fields.push(("TextureSize", vector.format(common.scale_w as f32, common.scale_h as f32, &layout)));
+        }
+    }
+    if !font.metrics.pages.is_empty() && !options.advances_only {
+        let files: Vec<String> = font.metrics.pages.values().map(|file| lua_string(file)).collect();
+        fields.push(("Pages", layout.braces(&layout.list(&files))));
+        if options.texture_checksums && !options.page_checksums.is_empty() {
+            let checksums: Vec<String> = font
+                .metrics
+                .pages
+                .keys()
+                // An empty string for a texture that could not be read keeps the
+                // list without holes, so it lines up with `Pages` in every Lua.
+                .map(|id| options.page_checksums.get(id).map_or("\"\"".to_string(), |checksum| format!("\"{checksum:08x}\"")))
+                .collect();
+            fields.push(("PageChecksums", layout.braces(&layout.list(&checksums))));
+        }
+    }
+    if let Some(ref field) = metrics.distance_field
+        && !options.advances_only
+    {
+        let values = [
+            format!("Type{equals}{}", lua_string(&field.field_type)),
+            format!("Range{equals}{}", field.range),
+        ];
+        fields.push(("DistanceField", layout.braces(&layout.list(&values))));
+    }
+    let spacing = &metrics.glyph_spacing;
+    if !options.advances_only {
+        if spacing.padding != [0; 4] {
+            fields.push(("Padding", layout.braces(&layout.list(&spacing.padding.map(|side| side.to_string())))));
+        }
+        if spacing.spacing != [0; 2] {
+            fields.push(("Spacing", layout.braces(&layout.list(&spacing.spacing.map(|gap| gap.to_string())))));
+        }
+        if spacing.outline != 0 {
+            fields.push(("Outline", spacing.outline.to_string()));
+        }
+    }
+
+    if !options.metrics_only {
+        let shared = (options.dedupe_metrics && !options.advances_only && !packed).then(|| shared_metrics(font_data));
+        if let Some((ref records, _)) = shared {
+            let entries: Vec<String> = records
+                .iter()
+                .map(|&[width, height, x, y, advance]| {
+                    let values = vector.values(
+                        &[[("Width", width), ("Height", height)], [("XOffset", x), ("YOffset", y)]],
+                        &[("Advance", advance)],
+                        &layout,
+                    );
+                    layout.glyph(&values, base)
+                })
+                .collect();
+            fields.push(("Shared", layout.table(&entries, base)));
+        }
+
+        // Glyphs only carry a page index when there is more than one page to
+        // choose from; it is the 1-based position in `Pages`. In channel-packed
+        // fonts every glyph also carries its `Channel` after the page, which
+        // is then always written so array entries stay in the same order.
+        let channels = font.uses_channels();
+        let page_index: Option<BTreeMap<u32, usize>> = (font.metrics.pages.len() > 1 || channels)
+            .then(|| font.metrics.pages.keys().enumerate().map(|(index, id)| (*id, index + 1)).collect());
+        let rect = options.rect_style.filter(|_| *vector != VectorStyle::Flattened);
+
+        if packed {
+            let data = pack_glyphs(font_data, page_index.as_ref(), channels, options);
+            fields.push(("Characters", format!("decodeGlyphs({})", lua_string(&data))));
+            progress(Progress::Fraction(1.0));
+        } else {
+            // Part modules return the glyph table itself, one level further out.
+            let glyph_base = if chunks.is_some() { "" } else { base };
+            let mut entries = Vec::with_capacity(font_data.len());
+            for (index, id) in options.character_order.arrange(font_data, &font.source_order).iter().enumerate() {
+                progress(Progress::Fraction(index as f32 / font_data.len() as f32));
+                let data = &font_data[id];
+                let key = table_key(*id, font_data, options, &layout);
+                if options.advances_only {
+                    entries.push(format!("{key}{equals}{}", data.advance));
+                    continue;
+                }
+
+                let glyph = GlyphFields {
+                    size: [data.size.width, data.size.height],
+                    position: [data.position.x, data.position.y],
+                    offset: [data.offset.x, data.offset.y],
+                    advance: data.advance,
+                    shared: shared.as_ref().map(|(_, indices)| indices[id] as f32),
+                    page: page_index
+                        .as_ref()
+                        .map(|page_index| page_index.get(&data.page).copied().unwrap_or(data.page as usize + 1) as f32),
+                    channel: channels.then_some(f32::from(data.channel)),
+                };
+                let mut values = glyph.values(rect, vector, &layout);
+                if options.uv == UvCoordinates::Append
+                    && let Some([u, v, width, height]) = uv_rect(data, metrics)
+                {
+                    let [u, v, width, height] = [u, v, width, height].map(|value| value as f32);
+                    match rect {
+                        Some(rect) => values.push(rect.format((u, v), (width, height), &layout)),
+                        // In the order of the pixel size and position.
+                        None => values.extend(vector.values(&[[("UWidth", width), ("VHeight", height)], [("U", u), ("V", v)]], &[], &layout)),
+                    }
+                }
+                if font.synthesized.contains(id) && !options.luau_types {
+                    values.push(format!("Synthesized{equals}true"));
+                }
+                entries.push(format!("{key}{equals}{}", layout.glyph(&values, glyph_base)));
+            }
+            match chunks {
+                Some((name, size)) => {
+                    let header = options.header.as_ref().map(|header| header_comment(header, source, options)).unwrap_or_default();
+                    for (index, chunk) in entries.chunks(size).enumerate() {
+                        let part = format!("{header}return{pad}{}\n", layout.table(chunk, ""));
+                        parts.push((format!("{name}_part{}", index + 1), part));
+                    }
+                    let names: Vec<String> = parts.iter().map(|(name, _)| lua_string(name)).collect();
+                    fields.push(("Characters", format!("loadParts({})", layout.braces(&layout.list(&names)))));
+                }
+                None => fields.push(("Characters", layout.table(&entries, base))),
+            }
+        }
+
+        if !metrics.kerning.is_empty() {
+            let mut by_first: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+            for ((first, second), amount) in &metrics.kerning {
+                by_first
+                    .entry(*first)
+                    .or_default()
+                    .push(format!("{}{equals}{amount}", table_key(*second, font_data, options, &layout)));
+            }
+            let entries: Vec<String> = by_first
+                .iter()
+                .map(|(first, pairs)| format!("{}{equals}{}", table_key(*first, font_data, options, &layout), layout.braces(&layout.list(pairs))))
+                .collect();
+            fields.push(("Kerning", layout.table(&entries, base)));
+        }
+
+        if let Some(ref icons) = options.icons {
+            let entries: Vec<String> = icons
+                .iter()
+                .filter(|(_, codepoint)| font_data.contains_key(codepoint))
+                .map(|(name, codepoint)| format!("[{}]{equals}0x{codepoint:X}", lua_string(name)))
+                .collect();
+            if !entries.is_empty() {
+                fields.push(("Icons", layout.table(&entries, base)));
+            }
+        }
+    }
+
+    progress(Progress::Fraction(1.0));
+
+    let mut output = String::new();
+    if options.luau_types {
+        output.push_str("--!strict\n");
+    }
+    if let Some(ref header) = options.header {
+        output.push_str(&header_comment(header, source, options));
+    }
+    let annotation = if options.luau_types {
+        output.push_str(&luau_types(&fields, options, &layout));
+        format!("{pad}::{pad}FontData")
+    } else {
+        String::new()
+    };
+    let measure_text = options.measure_text && !options.metrics_only;
+    if !parts.is_empty() {
+        output.push_str(&load_parts_function(options, &layout));
+    }
+    if packed {
+        output.push_str(&decode_glyphs_function(font, options, &layout));
+    }
+
+    match options.module_style {
+        // The function needs a name for the table to be added to.
+        ModuleStyle::BareReturn if measure_text => {
+            let body: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{indent}{key}{equals}{value}"))
+                .collect();
+            let separator = format!(",{newline}");
+            output.push_str(&format!("local Font{equals}{{{newline}{}{newline}}}{annotation}{statement}", body.join(&separator)));
+            output.push_str(&measure_text_function("Font", &fields, options, &layout));
+            output.push_str("return Font\n");
+        }
+        ModuleStyle::BareReturn => {
+            let body: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| format!("{indent}{key}{equals}{value}"))
+                .collect();
+            let separator = format!(",{newline}");
+            output.push_str(&format!("return{pad}{{{newline}{}{newline}}}{annotation}\n", body.join(&separator)));
+        }
+        ModuleStyle::NamedTable { ref name, indexed } => {
+            output.push_str(&format!("local {name}{equals}{{}}{annotation}{statement}"));
+            for (key, value) in &fields {
+                if indexed {
+                    output.push_str(&format!("{name}[\"{key}\"]{equals}{value}{statement}"));
+                } else {
+                    output.push_str(&format!("{name}.{key}{equals}{value}{statement}"));
+                }
+            }
+            if measure_text {
+                output.push_str(&measure_text_function(name, &fields, options, &layout));
+            }
+            output.push_str(&format!("return {name}\n"));
+        }
+    }
+
+    LuaChunks { index: output, parts }
+}
+
+/// The Lua module of [`OutputOptions::scales`]: a `Scales` list, then the
+/// module of `font` at each scale as a table keyed by that scale.
+fn format_scales(font: &Font, source: &str, options: &OutputOptions) -> String {
+    let layout = Layout::new(&options.style);
+    let Layout { ref indent, newline, equals, pad, statement, .. } = layout;
+    let nested = matches!(options.module_style, ModuleStyle::BareReturn);
+
+    let scales: Vec<String> = options.scales.iter().map(f32::to_string).collect();
+    let mut fields = vec![("Scales".to_string(), layout.braces(&layout.list(&scales)))];
+    for (key, &scale) in scales.iter().zip(&options.scales) {
+        let scaled = OutputOptions {
+            scale,
+            scales: Vec::new(),
+            module_style: ModuleStyle::BareReturn,
+            header: None,
+            luau_types: false,
+            measure_text: false,
+            packed: false,
+            chunk_size: None,
+            ..options.clone()
+        };
+        let module = format_lua(font, source, &scaled, |_| {}, None).index;
+        // The module is `return {...}`; nested in the returned table, each of
+        // its lines moves in one more level.
+        let table = module.trim_end().strip_prefix("return").unwrap_or(&module).trim_start();
+        let table = if nested { table.replace('\n', &format!("\n{indent}")) } else { table.to_string() };
+        fields.push((format!("[{key}]"), table));
+    }
+
+    let mut output = String::new();
+    if let Some(ref header) = options.header {
+        output.push_str(&header_comment(header, source, options));
+    }
+    match options.module_style {
+        ModuleStyle::BareReturn => {
+            let body: Vec<String> = fields.iter().map(|(key, value)| format!("{indent}{key}{equals}{value}")).collect();
+            let separator = format!(",{newline}");
+            output.push_str(&format!("return{pad}{{{newline}{}{newline}}}\n", body.join(&separator)));
+        }
+        ModuleStyle::NamedTable { ref name, indexed } => {
+            output.push_str(&format!("local {name}{equals}{{}}{statement}"));
+            for (key, value) in &fields {
+                if key.starts_with('[') {
+                    output.push_str(&format!("{name}{key}{equals}{value}{statement}"));
+                } else if indexed {
+                    output.push_str(&format!("{name}[\"{key}\"]{equals}{value}{statement}"));
+                } else {
+                    output.push_str(&format!("{name}.{key}{equals}{value}{statement}"));
+                }
+            }
+            output.push_str(&format!("return {name}\n"));
+        }
+    }
+    output
+}
+
+/// The digits of the packed glyph strings of [`OutputOptions::packed`].
+pub(crate) const PACKED_DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends `value` to `packed` as a base64 VLQ, as in source maps: the sign
+/// in the lowest bit, then five bits per digit, lowest first, with 32 added
+/// to every digit but the last.
+fn push_vlq(packed: &mut String, value: i64) {
+    // `2v` for positive values and `-2v - 1` for negative ones, without
+    // overflowing at the ends of the range.
+    let mut rest = ((value << 1) ^ (value >> 63)) as u64;
+    loop {
+        let digit = (rest & 31) as usize;
+        rest >>= 5;
+        if rest == 0 {
+            packed.push(PACKED_DIGITS[digit] as char);
+            return;
+        }
+        packed.push(PACKED_DIGITS[digit + 32] as char);
+    }
+}
+
+/// The glyphs of `font_data` in codepoint order as one string of
+/// [`push_vlq`] numbers, which [`decode_glyphs_function`] reads back: per
+/// glyph the distance from the previous codepoint, then its advance, or its
+/// size, position, offset and advance, page and channel as the unpacked
+/// glyph tables hold them.
+fn pack_glyphs(
+    font_data: &BTreeMap<u32, Character>,
+    page_index: Option<&BTreeMap<u32, usize>>,
+    channels: bool,
+    options: &OutputOptions,
+) -> String {
+    let mut packed = String::new();
+    let mut previous = 0;
+    for (&id, data) in font_data {
+        push_vlq(&mut packed, i64::from(id) - previous);
+        previous = i64::from(id);
+        let metrics: &[f32] = if options.advances_only {
+            &[data.advance]
+        } else {
+            &[
+                data.size.width,
+                data.size.height,
+                data.position.x,
+                data.position.y,
+                data.offset.x,
+                data.offset.y,
+                data.advance,
+            ]
+        };
+        for value in metrics {
+            // Saturated to the whole numbers BMFont writes, which the decoder's
+            // doubles hold exactly; NaN becomes 0.
+            push_vlq(&mut packed, i64::from(value.round() as i32));
+        }
+        if options.advances_only {
+            continue;
+        }
+        if let Some(page_index) = page_index {
+            let page = page_index.get(&data.page).copied().unwrap_or(data.page as usize + 1);
+            push_vlq(&mut packed, page as i64);
+        }
+        if channels {
+            push_vlq(&mut packed, i64::from(data.channel));
+        }
+    }
+    debug!(glyphs = font_data.len(), bytes = packed.len(), "packed glyphs");
+    packed
+}
+
+/// `local function decodeGlyphs(data)`, which reads the string of
+/// [`pack_glyphs`] back into the `Characters` table, with glyph tables and
+/// keys as the unpacked output writes them. Like `MeasureText` it decodes
+/// without bit operations or `utf8`, so it runs on Lua 5.1 too.
+fn decode_glyphs_function(font: &Font, options: &OutputOptions, layout: &Layout) -> String {
+    let luau = options.luau_types;
+    let typed = |name: &str, luau_type: &str| if luau { format!("{name}: {luau_type}") } else { name.to_string() };
+    let channels = font.uses_channels();
+    let pages = font.metrics.pages.len() > 1 || channels;
+
+    let mut lines: Vec<(usize, String)> = vec![
+        (0, format!("local function decodeGlyphs({}){}", typed("data", "string"), if luau { ": { [any]: any }" } else { "" })),
+        (1, format!("local {} = {{}}", typed("digits", "{ [number]: number }"))),
+        (1, "for index = 1, 64 do".to_string()),
+        (2, format!("digits[string.byte({}, index)] = index - 1", lua_string(std::str::from_utf8(PACKED_DIGITS).expect("ASCII")))),
+        (1, "end".to_string()),
+        (1, "local position = 1".to_string()),
+        (1, format!("local function read(){}", if luau { ": number" } else { "" })),
+        (2, "local value, scale = 0, 1".to_string()),
+        (2, "while true do".to_string()),
+        (3, "local digit = digits[string.byte(data, position)]".to_string()),
+        (3, "position = position + 1".to_string()),
+        (3, "if digit < 32 then".to_string()),
+        (4, "value = value + digit * scale".to_string()),
+        (4, "break".to_string()),
+        (3, "end".to_string()),
+        (3, "value = value + (digit - 32) * scale".to_string()),
+        (3, "scale = scale * 32".to_string()),
+        (2, "end".to_string()),
+        (2, "if value % 2 == 1 then".to_string()),
+        (3, "return -math.floor(value / 2) - 1".to_string()),
+        (2, "end".to_string()),
+        (2, "return math.floor(value / 2)".to_string()),
+        (1, "end".to_string()),
+    ];
+    if !options.numeric_keys {
+        lines.extend([
+            (1, format!("local function character({}){}", typed("code", "number"), if luau { ": string" } else { "" })),
+            (2, "if code < 0x80 then".to_string()),
+            (3, "return string.char(code)".to_string()),
+            (2, "elseif code < 0x800 then".to_string()),
+            (3, "return string.char(0xC0 + math.floor(code / 0x40), 0x80 + code % 0x40)".to_string()),
+            (2, "elseif code < 0x10000 then".to_string()),
+            (3, "return string.char(0xE0 + math.floor(code / 0x1000), 0x80 + math.floor(code / 0x40) % 0x40, 0x80 + code % 0x40)".to_string()),
+            (2, "end".to_string()),
+            (
+                2,
+                "return string.char(0xF0 + math.floor(code / 0x40000), 0x80 + math.floor(code / 0x1000) % 0x40, 0x80 + math.floor(code / 0x40) % 0x40, 0x80 + code % 0x40)"
+                    .to_string(),
+            ),
+            (1, "end".to_string()),
+        ]);
+    }
+    lines.extend([
+        (1, format!("local {} = {{}}", typed("characters", "{ [any]: any }"))),
+        (1, "local id = 0".to_string()),
+        (1, "while position <= #data do".to_string()),
+        (2, "id = id + read()".to_string()),
+    ]);
+    let key = if options.numeric_keys { "id" } else { "character(id)" };
+    let glyph = if options.advances_only {
+        lines.push((2, "local advance = read()".to_string()));
+        "advance".to_string()
+    } else {
+        let mut names = vec!["width", "height", "x", "y", "xOffset", "yOffset", "advance"];
+        if pages {
+            names.push("page");
+        }
+        if channels {
+            names.push("channel");
+        }
+        // One statement each, since Lua leaves the order expressions in a
+        // list are evaluated in unspecified.
+        lines.extend(names.iter().map(|name| (2, format!("local {name} = read()"))));
+        let glyph = GlyphFields {
+            size: ["width", "height"],
+            position: ["x", "y"],
+            offset: ["xOffset", "yOffset"],
+            advance: "advance",
+            shared: None,
+            page: pages.then_some("page"),
+            channel: channels.then_some("channel"),
+        };
+        let rect = options.rect_style.filter(|_| options.vector_style != VectorStyle::Flattened);
+        layout.braces(&layout.list(&glyph.values(rect, &options.vector_style, layout)))
+    };
+    lines.extend([
+        (2, format!("characters[{key}] = {glyph}")),
+        (1, "end".to_string()),
+        (1, "return characters".to_string()),
+        (0, "end".to_string()),
+    ]);
+
+    let code: Vec<String> = lines.into_iter().map(|(depth, line)| format!("{}{line}", layout.indent.repeat(depth))).collect();
+    let separator = if layout.newline.is_empty() { " " } else { layout.newline };
+    format!("{}{}", code.join(separator), layout.statement)
+}
+
+/// The metrics of one glyph as they are written in its table: numbers, or
+/// the names of the variables the packed decoder reads them into.
+struct GlyphFields<T> {
+    size: [T; 2],
+    position: [T; 2],
+    offset: [T; 2],
+    advance: T,
+    /// The index of the shared record holding the size, offset and advance.
+    shared: Option<T>,
+    /// The 1-based position of the glyph's page in `Pages`.
+    page: Option<T>,
+    channel: Option<T>,
+}
+
+impl<T: Display + Copy> GlyphFields<T> {
+    /// The values of the glyph table, as [`VectorStyle::values`] writes them.
+    fn values(&self, rect: Option<RectStyle>, vector: &VectorStyle, layout: &Layout) -> Vec<String> {
+        let [x, y] = self.position;
+        let [width, height] = self.size;
+        let position = [("X", x), ("Y", y)];
+        let size = [("Width", width), ("Height", height)];
+        let offset = [("XOffset", self.offset[0]), ("YOffset", self.offset[1])];
+        let mut values = Vec::with_capacity(5);
+        let mut singles = Vec::with_capacity(3);
+        // The rect stands in for the position, and for the size too unless
+        // that comes from the shared record.
+        let rect = rect.map(|rect| rect.format((x, y), (width, height), layout));
+        let pairs = match (self.shared, rect) {
+            (Some(shared), rect) => {
+                singles.push(("Shared", shared));
+                match rect {
+                    Some(rect) => {
+                        values.push(rect);
+                        vec![]
+                    }
+                    None => vec![position],
+                }
+            }
+            (None, Some(rect)) => {
+                singles.push(("Advance", self.advance));
+                values.push(rect);
+                vec![offset]
+            }
+            (None, None) => {
+                singles.push(("Advance", self.advance));
+                vec![size, position, offset]
+            }
+        };
+        singles.extend(self.page.map(|page| ("Page", page)));
+        singles.extend(self.channel.map(|channel| ("Channel", channel)));
+        values.extend(vector.values(&pairs, &singles, layout));
+        values
+    }
+}
+
+/// `local function loadParts(names)`, which requires the part modules of
+/// [`render_chunks`] and merges their glyphs into one table. On Roblox the
+/// parts are the ModuleScripts next to the module, or inside it when it was
+/// synced from an `init.lua`; elsewhere they are required by name.
+fn load_parts_function(options: &OutputOptions, layout: &Layout) -> String {
+    let luau = options.luau_types;
+    let table = if luau { ": { [any]: any }" } else { "" };
+    // A string argument does not type-check against Roblox's `require`.
+    let require = if luau { "(require :: any)" } else { "require" };
+    let code: Vec<String> = [
+        (0, format!("local function loadParts(names{}){table}", if luau { ": { string }" } else { "" })),
+        (1, format!("local characters{table} = {{}}")),
+        (1, "for _, name in ipairs(names) do".to_string()),
+        (2, format!("local part = script and require(script:FindFirstChild(name) or script.Parent[name]) or {require}(name)")),
+        (2, "for key, glyph in pairs(part) do".to_string()),
+        (3, "characters[key] = glyph".to_string()),
+        (2, "end".to_string()),
+        (1, "end".to_string()),
+        (1, "return characters".to_string()),
+        (0, "end".to_string()),
+    ]
+    .into_iter()
+    .map(|(depth, line)| format!("{}{line}", layout.indent.repeat(depth)))
+    .collect();
+    let separator = if layout.newline.is_empty() { " " } else { layout.newline };
+    format!("{}{}", code.join(separator), layout.statement)
+}
+
+/// `function {table}.MeasureText(text)`, which sums the advances and kerning
+/// of the characters of each line of `text` and returns the widest. It
+/// decodes UTF-8 itself, so it runs on Lua 5.1 too; characters without a
+/// glyph count as zero width.
+fn measure_text_function(table: &str, fields: &[(&str, String)], options: &OutputOptions, layout: &Layout) -> String {
+    let luau = options.luau_types;
+    let cast = |value: String| if luau { format!("{value} :: number") } else { value };
+    let typed = |name: &str, luau_type: &str| if luau { format!("{name}: {luau_type}") } else { name.to_string() };
+    let has_field = |name: &str| fields.iter().any(|(field, _)| *field == name);
+
+    // Where the glyph tables keep the advance: see the glyph values in
+    // `format_output_with_progress`.
+    let flattened = options.vector_style == VectorStyle::Flattened;
+    let rect = options.rect_style.is_some() && !flattened;
+    let advance = if options.advances_only {
+        cast("glyph".to_string())
+    } else if has_field("Shared") {
+        let record = if flattened { "glyph.Shared".to_string() } else { cast("glyph[2]".to_string()) };
+        let advance = if flattened { ".Advance" } else { "[3]" };
+        cast(format!("{table}.Shared[{record}]{advance}"))
+    } else if flattened {
+        "glyph.Advance".to_string()
+    } else if rect {
+        cast("glyph[3]".to_string())
+    } else {
+        cast("glyph[4]".to_string())
+    };
+    let (key_type, newline) = if options.numeric_keys { ("number?", "10") } else { ("string?", "\"\\n\"") };
+
+    let mut lines: Vec<(usize, String)> = vec![
+        (0, format!("function {table}.MeasureText({}){}", typed("text", "string"), if luau { ": number" } else { "" })),
+        (1, "local width, lineWidth = 0, 0".to_string()),
+        (1, format!("local {} = nil", typed("previous", key_type))),
+    ];
+    if options.numeric_keys {
+        lines.push((1, "local masks = { 128, 32, 16, 8 }".to_string()));
+    }
+    lines.extend([
+        (1, "local i = 1".to_string()),
+        (1, "while i <= #text do".to_string()),
+        (2, "local byte = string.byte(text, i)".to_string()),
+        (2, "local length = byte < 0xC0 and 1 or byte < 0xE0 and 2 or byte < 0xF0 and 3 or 4".to_string()),
+    ]);
+    if options.numeric_keys {
+        lines.extend([
+            (2, "local key = byte % masks[length]".to_string()),
+            (2, "for j = i + 1, i + length - 1 do".to_string()),
+            (3, "key = key * 64 + string.byte(text, j) % 64".to_string()),
+            (2, "end".to_string()),
+        ]);
+    } else {
+        lines.push((2, "local key = string.sub(text, i, i + length - 1)".to_string()));
+    }
+    lines.extend([
+        (2, "i = i + length".to_string()),
+        (2, format!("if key == {newline} then")),
+        (3, "width = math.max(width, lineWidth)".to_string()),
+        (3, "lineWidth = 0".to_string()),
+        (3, "previous = nil".to_string()),
+        (2, "else".to_string()),
+        (3, format!("local glyph = {table}.Characters[key]")),
+        (3, "if glyph then".to_string()),
+        (4, format!("lineWidth = lineWidth + {advance}")),
+        (3, "end".to_string()),
+    ]);
+    if has_field("Kerning") {
+        lines.extend([
+            (3, format!("local kerning = previous and {table}.Kerning[previous]")),
+            (3, "if kerning and kerning[key] then".to_string()),
+            (4, "lineWidth = lineWidth + kerning[key]".to_string()),
+            (3, "end".to_string()),
+        ]);
+    }
+    lines.extend([
+        (3, "previous = key".to_string()),
+        (2, "end".to_string()),
+        (1, "end".to_string()),
+        (1, "return math.max(width, lineWidth)".to_string()),
+        (0, "end".to_string()),
+    ]);
+
+    let code: Vec<String> = lines.into_iter().map(|(depth, line)| format!("{}{line}", layout.indent.repeat(depth))).collect();
+    // Lua needs no line breaks between statements, only whitespace.
+    let separator = if layout.newline.is_empty() { " " } else { layout.newline };
+    format!("{}{}", code.join(separator), layout.statement)
+}
+
+/// Luau declarations of the `GlyphData` entries and the `FontData` table
+/// holding `fields`.
+fn luau_types(fields: &[(&str, String)], options: &OutputOptions, layout: &Layout) -> String {
+    let Layout { newline, pad, statement, .. } = *layout;
+    let vector = match options.vector_style {
+        VectorStyle::Vector2New => "Vector2".to_string(),
+        VectorStyle::TableLiteral => layout.braces(&format!("x:{pad}number,{pad}y:{pad}number")),
+        VectorStyle::Array => layout.braces("number"),
+        // Only glyphs are affected, and they become `{ [string]: number }`.
+        VectorStyle::Flattened => "number".to_string(),
+        // The return type of a custom constructor is unknown.
+        VectorStyle::FunctionCall(_) => "any".to_string(),
+    };
+    let key = if options.numeric_keys { "number" } else { "string" };
+
+    let mut entries: Vec<String> = fields
+        .iter()
+        .map(|(field, _)| {
+            let field_type = match *field {
+                "TextureSize" => vector.clone(),
+                "Pages" | "PageChecksums" => layout.braces("string"),
+                "Padding" | "Spacing" => layout.braces("number"),
+                "DistanceField" => layout.braces(&format!("Type:{pad}string,{pad}Range:{pad}number")),
+                "Shared" => layout.braces("GlyphData"),
+                "Characters" => layout.braces(&format!("[{key}]:{pad}GlyphData")),
+                "Kerning" => layout.braces(&format!("[{key}]:{pad}{}", layout.braces(&format!("[{key}]:{pad}number")))),
+                "Icons" => layout.braces(&format!("[string]:{pad}number")),
+                _ => "number".to_string(),
+            };
+            format!("{field}:{pad}{field_type}")
+        })
+        .collect();
+
+    let glyph = if options.advances_only {
+        "number".to_string()
+    } else if options.vector_style == VectorStyle::Flattened {
+        layout.braces(&format!("[string]:{pad}number"))
+    } else {
+        let rect = match options.rect_style {
+            Some(RectStyle::RectNew) => format!("{pad}|{pad}Rect"),
+            Some(RectStyle::Array) if options.vector_style != VectorStyle::Array => format!("{pad}|{pad}{{{pad}number{pad}}}"),
+            _ => String::new(),
+        };
+        layout.braces(&format!("{vector}{rect}{pad}|{pad}number"))
+    };
+    if options.measure_text && !options.metrics_only {
+        entries.push(format!("MeasureText:{pad}(string){pad}->{pad}number"));
+    }
+    let mut types = format!(
+        "export type GlyphData{equals}{glyph}{statement}",
+        equals = layout.equals,
+    );
+    types.push_str(&format!(
+        "export type FontData{equals}{}{statement}{newline}",
+        layout.table(&entries, ""),
+        equals = layout.equals,
+    ));
+    types
+}
+
+/// Quotes `value` as a Lua string literal.
+pub(crate) fn lua_string(value: &str) -> String {
+    format!("\"{}\"", escape_lua(value))
+}
+
+/// Escapes `value` for use inside a double-quoted Lua string, using only
+/// escapes every Lua version reads: quotes, backslashes and the C escapes
+/// such as `\n` are backslash-escaped, and other control characters are
+/// written as their UTF-8 bytes in decimal, e.g. `\000` or `\194\133`.
+fn escape_lua(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{7}' => escaped.push_str("\\a"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{B}' => escaped.push_str("\\v"),
+            '\u{C}' => escaped.push_str("\\f"),
+            c if c.is_control() => escaped.push_str(&byte_escapes(c.encode_utf8(&mut [0; 4]).as_bytes())),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `bytes` as decimal escapes. Three digits each, so a digit following the
+/// escape is not read as part of it.
+fn byte_escapes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("\\{byte:03}")).collect()
+}
+
+/// The bracketed Lua table key for codepoint `id`: `[65]` with numeric keys,
+/// `["A"]` otherwise, or a call as set by [`OutputOptions::key_escape`] for
+/// control characters.
+fn table_key(id: u32, characters: &BTreeMap<u32, Character>, options: &OutputOptions, layout: &Layout) -> String {
+    if options.numeric_keys {
+        return format!("[{id}]");
+    }
+    let key = glyph_key(id, characters, options);
+    let awkward = char::from_u32(id).is_none_or(char::is_control);
+    if awkward && key == char_key(id) {
+        match options.key_escape {
+            KeyEscape::Escaped => {}
+            KeyEscape::StringChar => {
+                let bytes: Vec<String> = lua_utf8(id).iter().map(u8::to_string).collect();
+                return format!("[string.char({})]", bytes.join(layout.comma));
+            }
+            KeyEscape::Utf8Char => return format!("[utf8.char({id:#X})]"),
+        }
+    }
+    format!("[\"{key}\"]")
+}
+
+/// The bytes Lua's `utf8.char` encodes `id` as: UTF-8, extended like Lua's to
+/// surrogates and values above U+10FFFF.
+fn lua_utf8(id: u32) -> Vec<u8> {
+    if id < 0x80 {
+        return vec![id as u8];
+    }
+    let mut bytes = Vec::new();
+    let mut rest = id;
+    // The largest value the lead byte can hold alongside its length prefix.
+    let mut lead_max = 0x3f;
+    loop {
+        bytes.push(0x80 | (rest & 0x3f) as u8);
+        rest >>= 6;
+        lead_max >>= 1;
+        if rest <= lead_max {
+            break;
+        }
+    }
+    bytes.push(((!lead_max << 1) | rest) as u8);
+    bytes.reverse();
+    bytes
+}
+
+/// The escaped string key for codepoint `id`: its `letter` attribute when
+/// [`OutputOptions::prefer_letters`] is set and the glyph has one, or
+/// [`char_key`] otherwise.
+pub(crate) fn glyph_key(id: u32, characters: &BTreeMap<u32, Character>, options: &OutputOptions) -> String {
+    let letter = characters.get(&id).and_then(|data| data.letter.as_deref());
+    match letter {
+        Some(letter) if options.prefer_letters && !letter.is_empty() => escape_lua(letter),
+        _ => char_key(id),
+    }
+}
+
+/// The string-literal contents used as the Lua table key for codepoint `id`.
+/// Ids that are not Unicode scalar values, such as surrogates, are written as
+/// the bytes Lua's `utf8.char` gives them.
+pub(crate) fn char_key(id: u32) -> String {
+    match std::char::from_u32(id) {
+        Some(c) => escape_lua(&c.to_string()),
+        None => byte_escapes(&lua_utf8(id)),
+    }
+}
+
+/// Renders the header as Lua line comments, substituting `{source}` with the
+/// input file name(s).
+pub fn header_comment(header: &HeaderOptions, source: &str, options: &OutputOptions) -> String {
+    header_lines(header, source, options).iter().map(|line| format!("-- {line}\n")).collect()
+}
+
+/// The lines of the header text, uncommented, for formats with their own
+/// comment syntax.
+pub(crate) fn header_lines(header: &HeaderOptions, source: &str, options: &OutputOptions) -> Vec<String> {
+    let mut lines: Vec<String> = header.text.replace("{source}", source).lines().map(str::to_string).collect();
+    if header.provenance {
+        lines.push(match header.source_checksum {
+            Some(checksum) => format!("Source: {source} (CRC-32 {checksum:08x})"),
+            None => format!("Source: {source}"),
+        });
+        lines.push(format!("Converter: XmlToLuaConverter {}", env!("CARGO_PKG_VERSION")));
+    }
+    if header.timestamp || header.provenance {
+        lines.push(format!("Generated at {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
+    }
+    if header.provenance {
+        let changed = changed_options(options);
+        lines.push(if changed.is_empty() { "Options: defaults".to_string() } else { format!("Options: {}", changed.join(", ")) });
+    }
+    lines
+}
+
+/// Options that do not change what is written, left out of the provenance
+/// header.
+const UNRECORDED_OPTIONS: [&str; 4] = ["header", "file_name", "verify", "overwrite"];
+
+/// `name = value` for every option that differs from its default, with
+/// values as JSON. A template is only noted, not copied into the header.
+fn changed_options(options: &OutputOptions) -> Vec<String> {
+    let to_json = |options: &OutputOptions| serde_json::to_value(options).expect("options serialize to JSON");
+    let (serde_json::Value::Object(defaults), serde_json::Value::Object(current)) =
+        (to_json(&OutputOptions::default()), to_json(options))
+    else {
+        return Vec::new();
+    };
+    current
+        .iter()
+        .filter(|(name, value)| !UNRECORDED_OPTIONS.contains(&name.as_str()) && defaults.get(*name) != Some(value))
+        .map(|(name, value)| match name.as_str() {
+            "template" => "template = custom".to_string(),
+            _ => format!("{name} = {value}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn negative_sizes_are_written_as_their_absolute_value() {
+        let mut font = example_font();
+        font.metrics.size = -50;
+
+        let options = OutputOptions::default();
+        assert_eq!(options.font_size(&font), 50);
+        assert!(format_output(&font, "font.fnt", &options).contains("Size = 50,"));
+
+        let signed = OutputOptions { absolute_size: false, ..OutputOptions::default() };
+        assert_eq!(signed.font_size(&font), -50);
+        assert!(format_output(&font, "font.fnt", &signed).contains("Size = -50,"));
+
+        let overridden = OutputOptions { size_override: Some(-24), scale: 2.0, ..OutputOptions::default() };
+        assert_eq!(overridden.font_size(&font), -24);
+    }
+
+    #[test]
+    fn minified_lua_reads_back_like_the_pretty_output() {
+        let font = example_font();
+        let pretty = format_output(&font, "font.fnt", &OutputOptions::default());
+        let mut options = OutputOptions::default();
+        options.style.minified = true;
+        let minified = format_output(&font, "font.fnt", &options);
+
+        assert_eq!(minified.trim_end().lines().count(), 1);
+        assert!(minified.len() < pretty.len());
+        let pretty = crate::lua_import::parse_lua(&pretty).unwrap();
+        let minified = crate::lua_import::parse_lua(&minified).unwrap();
+        assert!(!pretty.characters.is_empty() && !pretty.metrics.kerning.is_empty());
+        assert_eq!(serde_json::to_value(&minified).unwrap(), serde_json::to_value(&pretty).unwrap());
+    }
+
+    #[test]
+    fn each_rounding_mode_applies_to_half_pixels() {
+        assert_eq!(Rounding::Round.apply(12.5), 13.0);
+        assert_eq!(Rounding::Floor.apply(12.5), 12.0);
+        assert_eq!(Rounding::Ceil.apply(12.5), 13.0);
+        assert_eq!(Rounding::KeepFloat.apply(12.5), 12.5);
+
+        let source = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/char_forms.fnt"))
+            .unwrap()
+            .replacen(r#"x="0" y="0""#, r#"x="12.5" y="0""#, 1);
+        let font = crate::parser::parse_fnt_bytes_with_progress(source.as_bytes(), |_| {}).unwrap();
+        assert_eq!(font.characters[&65].position.x, 12.5);
+        for (rounding, written) in [
+            (Rounding::Round, "Vector2.new(13, 0)"),
+            (Rounding::Floor, "Vector2.new(12, 0)"),
+            (Rounding::Ceil, "Vector2.new(13, 0)"),
+            (Rounding::KeepFloat, "Vector2.new(12.5, 0)"),
+        ] {
+            let lua = format_output(&font, "char_forms.fnt", &OutputOptions { rounding, ..OutputOptions::default() });
+            let glyph = lua.lines().find(|line| line.contains("[\"A\"]")).unwrap();
+            assert!(glyph.contains(written), "{rounding:?}: {glyph}");
+        }
+    }
+
+    /// Decodes the body of a string literal written by [`escape_lua`] the way
+    /// Lua 5.1 reads it, accepting only the escapes every Lua version knows.
+    /// `None` if it holds anything else, such as a raw control character or
+    /// an unescaped quote.
+    fn unescape_lua(escaped: &str) -> Option<String> {
+        let mut bytes = Vec::new();
+        let mut chars = escaped.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next()? {
+                    '"' => bytes.push(b'"'),
+                    '\\' => bytes.push(b'\\'),
+                    'n' => bytes.push(b'\n'),
+                    'r' => bytes.push(b'\r'),
+                    't' => bytes.push(b'\t'),
+                    'a' => bytes.push(0x07),
+                    'b' => bytes.push(0x08),
+                    'v' => bytes.push(0x0B),
+                    'f' => bytes.push(0x0C),
+                    digit @ '0'..='9' => {
+                        let mut code = digit.to_digit(10)?;
+                        for _ in 0..2 {
+                            match chars.peek().and_then(|c| c.to_digit(10)) {
+                                Some(digit) => {
+                                    code = code * 10 + digit;
+                                    chars.next();
+                                }
+                                None => break,
+                            }
+                        }
+                        bytes.push(u8::try_from(code).ok()?);
+                    }
+                    _ => return None,
+                },
+                '"' => return None,
+                c if c.is_control() => return None,
+                c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    #[test]
+    fn every_bmp_character_escapes_to_a_valid_lua_string() {
+        let bmp: String = (0..=0xFFFF).filter_map(char::from_u32).collect();
+        for c in bmp.chars() {
+            let text = c.to_string();
+            assert_eq!(unescape_lua(&escape_lua(&text)).as_deref(), Some(text.as_str()), "U+{:04X}", c as u32);
+        }
+        assert_eq!(unescape_lua(&escape_lua(&bmp)).as_deref(), Some(bmp.as_str()));
+        // A digit after a byte escape is not read as part of it.
+        assert_eq!(escape_lua("\u{0}1"), "\\0001");
+        assert_eq!(unescape_lua(&escape_lua("\u{85}9\u{0}")).as_deref(), Some("\u{85}9\u{0}"));
+    }
+
+    #[test]
+    fn escaped_keys_read_back_through_the_lua_reader() {
+        let mut font = example_font();
+        let moved = font.characters[&65].clone();
+        font.characters.clear();
+        font.metrics.kerning.clear();
+        for c in ['"', '\\', '\n', '\u{85}', '\u{2028}', ']'] {
+            font.characters.insert(c as u32, moved.clone());
+        }
+        let lua = format_output(&font, "font.fnt", &OutputOptions { excluded: BTreeSet::new(), ..OutputOptions::default() });
+        let read = crate::lua_import::parse_lua(&lua).unwrap();
+        assert_eq!(read.characters.keys().collect::<Vec<_>>(), font.characters.keys().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn formatters_are_found_by_format_and_name() {
+        let names: BTreeSet<&str> = FORMATTERS.iter().map(|formatter| formatter.name()).collect();
+        assert_eq!(names.len(), FORMATTERS.len());
+        for formatter in FORMATTERS {
+            assert_eq!(OutputFormat::from_name(formatter.name()), Some(formatter.format()));
+            assert_eq!(formatter.format().formatter().name(), formatter.name());
+        }
+        assert_eq!(OutputFormat::from_name("lua"), Some(OutputFormat::Lua));
+        assert_eq!(OutputFormat::from_name("nope"), None);
+    }
+
+    #[test]
+    fn lua_and_json_read_back_as_exported() {
+        let font = example_font();
+        for format in [OutputFormat::Lua, OutputFormat::Json] {
+            let options = OutputOptions { format, ..OutputOptions::default() };
+            let output = render(&font, "font.fnt", &options).unwrap();
+            crate::verify::verify_output(&font, &output, &options).unwrap();
+        }
+    }
+
+    #[test]
+    fn module_names_must_be_lua_identifiers() {
+        for name in ["Font", "_font", "Title_Font2", "End", "goto_"] {
+            check_module_name(name).unwrap();
+        }
+        for name in ["", "My Font", "2x", "font-50", "Fönt", "end", "goto", "nil"] {
+            assert!(matches!(check_module_name(name), Err(ConvertError::ModuleName(_))), "{name:?}");
+        }
+        let options = OutputOptions {
+            module_style: ModuleStyle::NamedTable { name: "end".to_string(), indexed: false },
+            ..OutputOptions::default()
+        };
+        assert!(render(&example_font(), "font.fnt", &options).is_err());
+    }
+
+    #[test]
+    fn packed_metrics_saturate_to_whole_bmfont_numbers() {
+        let mut packed = String::new();
+        for value in [0, 1, -1, 15, -16, 16, i64::MAX, i64::MIN] {
+            push_vlq(&mut packed, value);
+        }
+        assert!(packed.starts_with("ACBefgB"));
+
+        let font = example_font();
+        let glyph = |width: f32| {
+            let mut data = font.characters[&65].clone();
+            data.size.width = width;
+            BTreeMap::from([(65, data)])
+        };
+        let options = OutputOptions { packed: true, ..OutputOptions::default() };
+        let pack = |width| pack_glyphs(&glyph(width), None, false, &options);
+        assert_eq!(pack(f32::MAX), pack(i32::MAX as f32));
+        assert_eq!(pack(f32::NEG_INFINITY), pack(i32::MIN as f32));
+        assert_eq!(pack(f32::NAN), pack(0.0));
+    }
+
+    #[test]
+    fn render_applies_the_export_settings_format_output_leaves_out() {
+        let font = example_font();
+        let mut remap = CodepointMap::default();
+        remap.insert(65, 0x2605);
+        let options = OutputOptions {
+            remap: Some(remap),
+            ..Default::default()
+        };
+        let rendered = render(&font, "font.fnt", &options).unwrap();
+        assert!(rendered.contains("[\"\u{2605}\"] = {") && !rendered.contains("[\"A\"] = {"));
+        assert!(format_output(&font, "font.fnt", &options).contains("[\"A\"] = {"));
+        assert_eq!(rendered, format_output(&exported_font(&font, &options), "font.fnt", &options));
+    }
+
+    #[test]
+    fn page_checksums_have_no_holes() {
+        let mut font = example_font();
+        font.metrics.pages.insert(1, "ROBUST_1.png".to_string());
+        let options = OutputOptions {
+            texture_checksums: true,
+            page_checksums: BTreeMap::from([(1, 0xdeadbeef)]),
+            ..Default::default()
+        };
+        let output = format_output(&font, "font.fnt", &options);
+        assert!(output.contains("PageChecksums = { \"\", \"deadbeef\" },"), "{output}");
+    }
+
+    #[test]
+    fn empty_tables_stay_on_one_line() {
+        let options = OutputOptions {
+            subset: Some(Subset::from_text("\u{4e00}")),
+            ..Default::default()
+        };
+        let output = format_output(&example_font(), "font.fnt", &options);
+        assert!(output.contains("    Characters = {}\n}"), "{output}");
+    }
+}
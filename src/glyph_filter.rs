@@ -0,0 +1,95 @@
+//! Dropping glyphs by what they are rather than by codepoint range: empty
+//! rects, glyphs without a visible pixel, and characters that are never
+//! printed.
+
+use crate::font::{Character, Font};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Predicates glyphs must pass to be exported, on top of the subset and
+/// exclusions. Each one that is set applies.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlyphFilter {
+    /// Drops glyphs whose rect has no width or no height. This includes the
+    /// space, unless it is synthesized again afterwards.
+    pub empty_rects: bool,
+    /// Drops glyphs whose pixels in the atlas are all transparent. Needs the
+    /// `atlas` feature, without which it has no effect.
+    pub blank_pixels: bool,
+    /// Keeps only characters that are printed; see [`is_printable`].
+    pub printable_only: bool,
+    /// The glyphs found blank in the atlas for
+    /// [`blank_pixels`](Self::blank_pixels), filled in by
+    /// [`with_checksums`](crate::batch::with_checksums).
+    #[serde(skip)]
+    pub blank: BTreeSet<u32>,
+}
+
+impl GlyphFilter {
+    /// Whether no predicate is set.
+    pub fn is_empty(&self) -> bool {
+        !self.empty_rects && !self.blank_pixels && !self.printable_only
+    }
+
+    /// Whether glyph `id` with `data` passes every predicate that is set.
+    pub fn keeps(&self, id: u32, data: &Character) -> bool {
+        !(self.empty_rects && (data.size.width <= 0.0 || data.size.height <= 0.0)
+            || self.blank_pixels && self.blank.contains(&id)
+            || self.printable_only && !is_printable(id))
+    }
+
+    /// The glyphs of `font` that fail a predicate.
+    pub fn dropped(&self, font: &Font) -> BTreeSet<u32> {
+        font.characters.iter().filter(|(id, data)| !self.keeps(**id, data)).map(|(id, _)| *id).collect()
+    }
+}
+
+/// Whether `id` is a character that is printed: not a control character,
+/// surrogate or noncharacter, and not one of the invisible format characters
+/// such as zero-width spaces, directional marks and the byte order mark.
+/// Spaces count as printed.
+pub fn is_printable(id: u32) -> bool {
+    let Some(c) = char::from_u32(id) else {
+        return false;
+    };
+    let noncharacter = (0xFDD0..=0xFDEF).contains(&id) || id & 0xFFFE == 0xFFFE;
+    let format = matches!(id, 0xAD | 0x600..=0x605 | 0x61C | 0x180E | 0x200B..=0x200F | 0x202A..=0x202E | 0x2060..=0x206F | 0xFEFF | 0xFFF9..=0xFFFB);
+    !c.is_control() && !noncharacter && !format
+}
+
+/// The glyphs of `font` whose rect holds no pixel with any alpha in
+/// `pages`, or in the glyph's channel for channel-packed fonts. Glyphs
+/// with an empty rect or on a missing page are not included.
+#[cfg(feature = "atlas")]
+pub fn blank_glyphs(font: &Font, pages: &std::collections::BTreeMap<u32, image::RgbaImage>) -> BTreeSet<u32> {
+    use crate::font::ALL_CHANNELS;
+
+    font.characters
+        .iter()
+        .filter(|(_, data)| {
+            let Some(page) = pages.get(&data.page) else {
+                return false;
+            };
+            let left = data.position.x.max(0.0).round() as u32;
+            let top = data.position.y.max(0.0).round() as u32;
+            let right = ((data.position.x + data.size.width).round() as u32).min(page.width());
+            let bottom = ((data.position.y + data.size.height).round() as u32).min(page.height());
+            if left >= right || top >= bottom {
+                return false;
+            }
+            // Channel bits are blue 1, green 2, red 4 and alpha 8.
+            let channels: Vec<usize> = if data.channel == ALL_CHANNELS {
+                vec![3]
+            } else {
+                [(1, 2), (2, 1), (4, 0), (8, 3)]
+                    .into_iter()
+                    .filter(|(bit, _)| data.channel & bit != 0)
+                    .map(|(_, index)| index)
+                    .collect()
+            };
+            (top..bottom).all(|y| (left..right).all(|x| channels.iter().all(|&index| page.get_pixel(x, y)[index] == 0)))
+        })
+        .map(|(id, _)| *id)
+        .collect()
+}
@@ -0,0 +1,96 @@
+//! Translations of the GUI text.
+//!
+//! Strings are written in English at the call site and looked up with [`tr`]
+//! or [`trf`]; a language without an entry for a string shows the English
+//! text. Placeholders are numbered (`{0}`, `{1}`) so translations can reorder
+//! them.
+
+mod de;
+mod es;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A language the GUI can be shown in, picked in the header and remembered
+/// between sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Spanish];
+
+    /// The language's name in itself, as shown in the picker.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+    }
+
+    fn translations(&self) -> Option<&'static HashMap<&'static str, &'static str>> {
+        static GERMAN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static SPANISH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        match self {
+            Language::English => None,
+            Language::German => Some(GERMAN.get_or_init(|| de::STRINGS.iter().copied().collect())),
+            Language::Spanish => Some(SPANISH.get_or_init(|| es::STRINGS.iter().copied().collect())),
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Makes `language` the one [`tr`] translates into.
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+fn current() -> Language {
+    Language::ALL
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// The current language's translation of the English UI text `text`.
+pub fn tr(text: &'static str) -> &'static str {
+    current()
+        .translations()
+        .and_then(|translations| translations.get(text).copied())
+        .unwrap_or(text)
+}
+
+/// Translates `text` and replaces its `{0}`, `{1}`, … placeholders with
+/// `args`.
+pub fn trf(text: &'static str, args: &[&dyn Display]) -> String {
+    let mut output = String::new();
+    let mut rest = tr(text);
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest[1..]
+            .find('}')
+            .and_then(|end| Some((end, args.get(rest[1..=end].parse::<usize>().ok()?)?)));
+        match placeholder {
+            Some((end, arg)) => {
+                output.push_str(&arg.to_string());
+                rest = &rest[end + 2..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
@@ -0,0 +1,480 @@
+//! German translations.
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Header and main actions
+    ("🎨 .fnt to .lua Converter", "🎨 .fnt-zu-.lua-Konverter"),
+    ("Theme:", "Design:"),
+    ("Dark", "Dunkel"),
+    ("Light", "Hell"),
+    ("High contrast", "Hoher Kontrast"),
+    ("📂 Select .fnt file", "📂 .fnt-Datei auswählen"),
+    ("📄 Selected: {0}", "📄 Ausgewählt: {0}"),
+    ("⚡ Convert", "⚡ Konvertieren"),
+    ("📋 Copy to clipboard", "📋 In die Zwischenablage kopieren"),
+    ("Convert and copy the output without saving a file ({0})", "Konvertieren und die Ausgabe kopieren, ohne eine Datei zu speichern ({0})"),
+    ("Convert and preview the output; {0} also saves it", "Konvertieren und die Ausgabe in der Vorschau zeigen; {0} speichert sie auch"),
+    ("📥 Import Lua…", "📥 Lua importieren…"),
+    (
+        "Load a previously generated Lua module, e.g. to export it as .fnt",
+        "Ein zuvor erzeugtes Lua-Modul laden, z. B. um es als .fnt zu exportieren",
+    ),
+    ("💾 Export .fnt", "💾 .fnt exportieren"),
+    ("FNT files", "FNT-Dateien"),
+    ("Lua files", "Lua-Dateien"),
+    ("Fonts", "Schriften"),
+    ("Text", "Text"),
+    ("Preset", "Voreinstellung"),
+    ("⚠️ Please select a .fnt file first", "⚠️ Bitte zuerst eine .fnt-Datei auswählen"),
+    ("❌ Error parsing file: {0}", "❌ Fehler beim Einlesen der Datei: {0}"),
+    ("❌ Error saving file: {0}", "❌ Fehler beim Speichern der Datei: {0}"),
+    ("✅ Saved to {0}", "✅ Gespeichert unter {0}"),
+    ("📚 {0} files in the batch queue", "📚 {0} Dateien in der Warteschlange"),
+    ("📋 Copied {0} glyphs to the clipboard", "📋 {0} Glyphen in die Zwischenablage kopiert"),
+    ("Parsing {0}… {1}s", "Lese {0} ein… {1} s"),
+    ("Converting {0}… {1}s", "Konvertiere {0}… {1} s"),
+    // Error details
+    ("❌ Error details", "❌ Fehlerdetails"),
+    ("Attribute: {0} = {1}", "Attribut: {0} = {1}"),
+    ("At {0} (byte {1})", "Bei {0} (Byte {1})"),
+    // History
+    ("🕘 Recent ⏷", "🕘 Zuletzt ⏷"),
+    ("🕘 Recent", "🕘 Zuletzt verwendet"),
+    ("File no longer exists", "Die Datei existiert nicht mehr"),
+    ("No conversions yet", "Noch keine Konvertierungen"),
+    ("🗑 Clear history", "🗑 Verlauf löschen"),
+    // Output options
+    ("⚙ Output options", "⚙ Ausgabeoptionen"),
+    ("Preset:", "Voreinstellung:"),
+    ("None", "Keine"),
+    ("Add…", "Hinzufügen…"),
+    ("Load a TOML preset, as used by --preset", "Eine TOML-Voreinstellung laden, wie sie --preset verwendet"),
+    ("✅ Applied preset {0}", "✅ Voreinstellung {0} angewendet"),
+    ("❌ Error reading preset: {0}", "❌ Fehler beim Lesen der Voreinstellung: {0}"),
+    ("Format:", "Format:"),
+    ("BMFont XML", "BMFont-XML"),
+    ("BMFont text", "BMFont-Text"),
+    ("cocos2d plist", "cocos2d-plist"),
+    ("Roblox model", "Roblox-Modell"),
+    ("C header", "C-Header"),
+    ("Vectors:", "Vektoren:"),
+    ("Function call", "Funktionsaufruf"),
+    ("Table literal", "Tabellenliteral"),
+    ("Array", "Array"),
+    ("Flattened fields", "Einzelne Felder"),
+    ("Glyph rects:", "Glyphen-Rechtecke:"),
+    ("Separate pairs", "Getrennte Paare"),
+    (
+        "Combine atlas position and size into (x, y, width, height)",
+        "Atlasposition und Größe zu (x, y, Breite, Höhe) zusammenfassen",
+    ),
+    ("Named module table", "Benannte Modultabelle"),
+    ("Indexed keys", "Indizierte Schlüssel"),
+    ("Header comment", "Kopfkommentar"),
+    ("{source} is replaced with the input file name", "{source} wird durch den Namen der Eingabedatei ersetzt"),
+    ("Include timestamp", "Zeitstempel einfügen"),
+    ("Custom template", "Eigene Vorlage"),
+    (
+        "Handlebars template with size, characters, kerning and pages in scope",
+        "Handlebars-Vorlage mit size, characters, kerning und pages im Gültigkeitsbereich",
+    ),
+    ("Load…", "Laden…"),
+    ("❌ Error reading template: {0}", "❌ Fehler beim Lesen der Vorlage: {0}"),
+    ("Only export a subset", "Nur eine Teilmenge exportieren"),
+    (
+        "Characters in the ranges or in the text are exported",
+        "Zeichen in den Bereichen oder im Text werden exportiert",
+    ),
+    ("Ranges:", "Bereiche:"),
+    (
+        "Codepoints and ranges, e.g. 0x20-0x7E,0x400-0x4FF",
+        "Codepoints und Bereiche, z. B. 0x20-0x7E,0x400-0x4FF",
+    ),
+    ("Characters:", "Zeichen:"),
+    ("Font metrics only", "Nur Schriftmetriken"),
+    ("Leave out the Characters table", "Die Characters-Tabelle weglassen"),
+    ("Scale:", "Skalierung:"),
+    ("Multiplies every metric before rounding", "Multipliziert jede Metrik vor dem Runden"),
+    ("Glyph padding:", "Glyphen-Rand:"),
+    (
+        "Grows (or shrinks, when negative) each glyph rect on every side, adjusting offsets to match",
+        "Vergrößert (oder verkleinert, wenn negativ) jedes Glyphen-Rechteck an allen Seiten und passt die Versätze an",
+    ),
+    ("Rounding:", "Rundung:"),
+    ("Round", "Runden"),
+    ("Floor", "Abrunden"),
+    ("Ceil", "Aufrunden"),
+    ("Keep float", "Gleitkomma behalten"),
+    ("Numeric keys", "Numerische Schlüssel"),
+    (
+        "Key characters by codepoint, e.g. [65] instead of [\"A\"]",
+        "Zeichen nach Codepoint indizieren, z. B. [65] statt [\"A\"]",
+    ),
+    ("Use letter attribute", "letter-Attribut verwenden"),
+    (
+        "Key glyphs by the letter=\"…\" attribute some exporters write, when present",
+        "Glyphen nach dem letter=\"…\"-Attribut mancher Exporter indizieren, falls vorhanden",
+    ),
+    ("Luau types", "Luau-Typen"),
+    (
+        "Adds --!strict and exported GlyphData and FontData types",
+        "Fügt --!strict und exportierte GlyphData- und FontData-Typen hinzu",
+    ),
+    ("Trailing newline", "Abschließender Zeilenumbruch"),
+    ("UTF-8 BOM", "UTF-8-BOM"),
+    ("Share identical metrics", "Gleiche Metriken teilen"),
+    (
+        "Emit each distinct size/offset/advance once and reference it by index",
+        "Jede Größe, jeden Versatz und jeden Vorschub nur einmal schreiben und per Index referenzieren",
+    ),
+    ("Clamp negative sizes to 0", "Negative Größen auf 0 begrenzen"),
+    ("Use absolute font size", "Absolute Schriftgröße verwenden"),
+    (
+        "BMFont uses a negative size to mean \"match character height\"",
+        "BMFont verwendet eine negative Größe für „an Zeichenhöhe anpassen“",
+    ),
+    ("Override size", "Größe überschreiben"),
+    ("Minified", "Minimiert"),
+    ("Emit the whole table on a single line", "Die ganze Tabelle in eine Zeile schreiben"),
+    ("Indent:", "Einrückung:"),
+    ("spaces", "Leerzeichen"),
+    ("tabs", "Tabulatoren"),
+    ("Trailing commas", "Abschließende Kommas"),
+    ("One value per line", "Ein Wert pro Zeile"),
+    ("Write each glyph's values on separate lines", "Die Werte jeder Glyphe in eigene Zeilen schreiben"),
+    // Preview and watching
+    ("👁 Preview ({0} glyphs, {1} KB)", "👁 Vorschau ({0} Glyphen, {1} KB)"),
+    ("📋 Copy", "📋 Kopieren"),
+    ("💾 Save…", "💾 Speichern…"),
+    ("✖ Discard", "✖ Verwerfen"),
+    ("✅ Saved {0} glyphs ({1} KB) to {2}", "✅ {0} Glyphen ({1} KB) unter {2} gespeichert"),
+    ("👁 Watch for changes", "👁 Auf Änderungen achten"),
+    ("❌ Error watching file: {0}", "❌ Fehler beim Überwachen der Datei: {0}"),
+    ("Watching {0} → {1}", "Überwache {0} → {1}"),
+    ("✅ Converted to {0} at {1}", "✅ Um {1} nach {0} konvertiert"),
+    ("❌ Conversion failed at {0}: {1}", "❌ Konvertierung um {0} fehlgeschlagen: {1}"),
+    // Batch conversion
+    ("📚 Batch conversion", "📚 Stapelkonvertierung"),
+    ("➕ Add files…", "➕ Dateien hinzufügen…"),
+    ("Clear", "Leeren"),
+    ("Threads:", "Threads:"),
+    ("0 uses one thread per core", "0 verwendet einen Thread pro Kern"),
+    ("Exclude:", "Ausschließen:"),
+    (
+        "Glob matched against paths inside the folder, e.g. **/test*",
+        "Glob-Muster für Pfade innerhalb des Ordners, z. B. **/test*",
+    ),
+    ("{0}/{1} files · {2} · {3}s", "{0}/{1} Dateien · {2} · {3} s"),
+    ("Convert queue…", "Warteschlange konvertieren…"),
+    ("📁 Convert folder…", "📁 Ordner konvertieren…"),
+    ("Folder to convert", "Zu konvertierender Ordner"),
+    ("Output folder", "Ausgabeordner"),
+    ("❌ Error reading folder: {0}", "❌ Fehler beim Lesen des Ordners: {0}"),
+    ("{0} Converted {1}/{2} files to {3} in {4}s", "{0} {1}/{2} Dateien in {4} s nach {3} konvertiert"),
+    // TrueType generation
+    ("🔠 Generate from TrueType", "🔠 Aus TrueType erzeugen"),
+    ("📂 Pick font…", "📂 Schrift auswählen…"),
+    ("No font selected", "Keine Schrift ausgewählt"),
+    ("Size (px):", "Größe (px):"),
+    ("Texture:", "Textur:"),
+    ("Padding:", "Abstand:"),
+    ("Kerning", "Unterschneidung"),
+    (
+        "Look up kerning for every pair of glyphs; slow for very large character sets",
+        "Unterschneidung für jedes Glyphenpaar ermitteln; langsam bei sehr großen Zeichensätzen",
+    ),
+    ("⚙ Generate BMFont…", "⚙ BMFont erzeugen…"),
+    ("✅ Generated {0} glyphs on {1} page(s) to {2}", "✅ {0} Glyphen auf {1} Seite(n) unter {2} erzeugt"),
+    ("❌ Error generating font: {0}", "❌ Fehler beim Erzeugen der Schrift: {0}"),
+    // Merging
+    ("🧩 Merge fonts", "🧩 Schriften zusammenführen"),
+    ("➕ Add fonts…", "➕ Schriften hinzufügen…"),
+    ("Primary: {0}", "Primär: {0}"),
+    ("Fallback: {0}", "Ersatz: {0}"),
+    (
+        "Scales this font's glyph sizes, offsets and advances",
+        "Skaliert Glyphengrößen, Versätze und Vorschübe dieser Schrift",
+    ),
+    ("🧩 Merge…", "🧩 Zusammenführen…"),
+    ("On conflict:", "Bei Konflikten:"),
+    ("Keep first", "Erste behalten"),
+    ("Keep last", "Letzte behalten"),
+    (
+        "✅ Merged {0} files, {1} glyphs ({2} KB) to {3}",
+        "✅ {0} Dateien mit {1} Glyphen ({2} KB) unter {3} zusammengeführt",
+    ),
+    // Statistics and coverage
+    ("📊 Statistics", "📊 Statistik"),
+    ("Glyphs", "Glyphen"),
+    ("Advance", "Vorschub"),
+    ("Texture utilization", "Texturauslastung"),
+    ("unknown (no <common> texture size)", "unbekannt (keine <common>-Texturgröße)"),
+    ("Kerning pairs", "Unterschneidungspaare"),
+    ("Estimated {0} size", "Geschätzte {0}-Größe"),
+    ("Unicode blocks ({0}):", "Unicode-Blöcke ({0}):"),
+    ("  Other: {0}", "  Sonstige: {0}"),
+    ("📄 Export coverage report…", "📄 Abdeckungsbericht exportieren…"),
+    ("✅ Saved {0} coverage report to {1}", "✅ {0}-Abdeckungsbericht unter {1} gespeichert"),
+    ("✅ Required characters", "✅ Benötigte Zeichen"),
+    ("Paste the text the font must render", "Den Text einfügen, den die Schrift darstellen muss"),
+    ("📂 Load from file…", "📂 Aus Datei laden…"),
+    ("❌ Error reading {0}: {1}", "❌ Fehler beim Lesen von {0}: {1}"),
+    ("All required characters are present", "Alle benötigten Zeichen sind vorhanden"),
+    ("❌ Missing {0} character(s):", "❌ {0} Zeichen fehlen:"),
+    // Atlas
+    ("🖼 Atlas", "🖼 Atlas"),
+    ("📦 Repack atlas…", "📦 Atlas neu packen…"),
+    (
+        "Copy the glyphs into the smallest power-of-two texture that holds them and save it as a new font",
+        "Die Glyphen in die kleinste passende Zweierpotenz-Textur kopieren und als neue Schrift speichern",
+    ),
+    ("🌐 Export contact sheet…", "🌐 Kontaktbogen exportieren…"),
+    (
+        "An HTML page or PNG image showing every glyph cut out of the atlas",
+        "Eine HTML-Seite oder ein PNG-Bild, das jede aus dem Atlas ausgeschnittene Glyphe zeigt",
+    ),
+    ("✅ Saved contact sheet to {0}", "✅ Kontaktbogen unter {0} gespeichert"),
+    ("Page:", "Seite:"),
+    ("The font references no texture pages", "Die Schrift verweist auf keine Texturseiten"),
+    ("Offset: {0}, {1}", "Versatz: {0}, {1}"),
+    ("Advance: {0}", "Vorschub: {0}"),
+    (
+        "✅ Repacked {0} page(s) into {1} page(s) at {2}",
+        "✅ {0} Seite(n) in {1} Seite(n) unter {2} neu gepackt",
+    ),
+    ("❌ Error repacking atlas: {0}", "❌ Fehler beim Neupacken des Atlas: {0}"),
+    // Sample text
+    ("🔤 Sample text", "🔤 Beispieltext"),
+    ("⚠️ Not in font: {0}", "⚠️ Nicht in der Schrift: {0}"),
+    // Glyph table
+    ("🔍 Glyphs ({0})", "🔍 Glyphen ({0})"),
+    ("🔍 Glyphs ({0}, edited)", "🔍 Glyphen ({0}, bearbeitet)"),
+    ("✂ Trim rects to alpha", "✂ Rechtecke auf Alpha zuschneiden"),
+    (
+        "Shrink each glyph to the opaque pixels of its atlas page, moving offsets to match",
+        "Jede Glyphe auf die deckenden Pixel ihrer Atlasseite verkleinern und die Versätze anpassen",
+    ),
+    ("✂ Trimmed {0} glyph rects", "✂ {0} Glyphen-Rechtecke zugeschnitten"),
+    ("❌ Error loading atlas: {0}", "❌ Fehler beim Laden des Atlas: {0}"),
+    ("Filter:", "Filter:"),
+    (
+        "Character, decimal id, hex codepoint (0x41 / U+0041) or Unicode block name",
+        "Zeichen, dezimale ID, hexadezimaler Codepoint (0x41 / U+0041) oder Name eines Unicode-Blocks",
+    ),
+    ("Block", "Block"),
+    ("Char", "Zeichen"),
+    ("Id", "ID"),
+    ("Size", "Größe"),
+    ("Position", "Position"),
+    ("Offset", "Versatz"),
+    ("Delete this glyph", "Diese Glyphe löschen"),
+    ("↶ Undo", "↶ Rückgängig"),
+    ("↷ Redo", "↷ Wiederholen"),
+    ("Undo the last glyph edit (Ctrl+Z)", "Letzte Glyphenänderung rückgängig machen (Strg+Z)"),
+    ("Redo the last undone glyph edit (Ctrl+Shift+Z)", "Zuletzt rückgängig gemachte Glyphenänderung wiederholen (Strg+Umschalt+Z)"),
+    ("🔀 Compare fonts", "🔀 Schriften vergleichen"),
+    ("Old:", "Alt:"),
+    ("New:", "Neu:"),
+    ("🔀 Compare", "🔀 Vergleichen"),
+    ("✅ The fonts have the same glyphs and metrics", "✅ Die Schriften haben dieselben Glyphen und Metriken"),
+    ("{0} added, {1} removed and {2} changed glyphs, {3} kerning pairs changed", "{0} hinzugefügte, {1} entfernte und {2} geänderte Glyphen, {3} geänderte Kerning-Paare"),
+    ("➕ Added ({0})", "➕ Hinzugefügt ({0})"),
+    ("➖ Removed ({0})", "➖ Entfernt ({0})"),
+    ("✏ Changed ({0})", "✏ Geändert ({0})"),
+    ("📜 Lua diff", "📜 Lua-Unterschiede"),
+    ("The Lua modules are identical", "Die Lua-Module sind identisch"),
+    ("size", "Größe"),
+    ("position", "Position"),
+    ("offset", "Versatz"),
+    ("advance", "Vorschub"),
+    ("page", "Seite"),
+    ("line height", "Zeilenhöhe"),
+    ("base", "Grundlinie"),
+    ("texture size", "Texturgröße"),
+    ("pages", "Seiten"),
+    ("distance field", "Distanzfeld"),
+    ("Verify after export", "Nach dem Export prüfen"),
+    ("Read saved files back and report any glyph, metric or kerning that did not survive", "Gespeicherte Dateien erneut einlesen und jede Glyphe, Metrik oder jedes Kerning melden, das nicht erhalten blieb"),
+    ("Only Lua, JSON and FNT output without a template can be read back", "Nur Lua-, JSON- und FNT-Ausgaben ohne Vorlage können erneut eingelesen werden"),
+    ("⚡ Quick convert", "⚡ Schnellkonvertierung"),
+    ("Save conversions without a dialog, named after the input", "Konvertierungen ohne Dialog speichern, benannt nach der Eingabe"),
+    ("Into:", "Nach:"),
+    ("the input's folder", "Ordner der Eingabe"),
+    ("Save next to the input", "Neben der Eingabe speichern"),
+    ("Output folder:", "Ausgabeordner:"),
+    ("Ask each time", "Jedes Mal fragen"),
+    ("Ask for the folder on each run", "Bei jedem Durchlauf nach dem Ordner fragen"),
+    ("File names:", "Dateinamen:"),
+    ("{name} is the input file name, {ext} the format's extension, and {size}, {line_height} and {glyphs} come from the font, e.g. {name}_{size}.{ext}", "{name} ist der Name der Eingabedatei, {ext} die Endung des Formats, und {size}, {line_height} und {glyphs} stammen aus der Schrift, z. B. {name}_{size}.{ext}"),
+    ("Keep", "Behalten"),
+    ("Overwrite", "Überschreiben"),
+    ("Back up, then overwrite", "Sichern, dann überschreiben"),
+    ("Existing files:", "Vorhandene Dateien:"),
+    ("Keep asks before quick convert replaces a file and fails batch conversions instead", "Behalten fragt nach, bevor die Schnellkonvertierung eine Datei ersetzt, und lässt Stapelkonvertierungen stattdessen fehlschlagen"),
+    ("{0} already exists.", "{0} existiert bereits."),
+    ("Cancel", "Abbrechen"),
+    (" (previous file backed up to {0})", " (vorherige Datei gesichert als {0})"),
+    ("Record provenance", "Herkunft festhalten"),
+    ("Adds the source checksum, converter version, time and changed options", "Fügt Prüfsumme der Quelle, Konverterversion, Zeitpunkt und geänderte Optionen hinzu"),
+    ("Glyph order:", "Glyph-Reihenfolge:"),
+    ("Codepoint", "Codepoint"),
+    ("Source order", "Reihenfolge der Quelle"),
+    ("Priority list", "Prioritätenliste"),
+    ("The order glyphs are listed in the Characters table", "Die Reihenfolge der Glyphen in der Characters-Tabelle"),
+    ("First:", "Zuerst:"),
+    ("Ranges listed first, in this order, e.g. 0x20-0x7E,0x400-0x4FF", "Bereiche, die zuerst aufgeführt werden, in dieser Reihenfolge, z. B. 0x20-0x7E,0x400-0x4FF"),
+    ("Excluded characters ({0})", "Ausgeschlossene Zeichen ({0})"),
+    ("Keep all", "Alle behalten"),
+    ("Reset", "Zurücksetzen"),
+    ("Exclude NUL and carriage return", "NUL und Wagenrücklauf ausschließen"),
+    ("Skip malformed elements", "Fehlerhafte Elemente überspringen"),
+    ("Convert what can be read and list the elements that could not be parsed as warnings", "Konvertiert, was gelesen werden kann, und listet nicht lesbare Elemente als Warnungen auf"),
+    ("Control character keys:", "Schlüssel für Steuerzeichen:"),
+    ("Escaped string", "Escapte Zeichenkette"),
+    ("Escaped strings and string.char calls work in every Lua version; utf8.char needs Lua 5.3 or Luau", "Escapte Zeichenketten und string.char-Aufrufe funktionieren in jeder Lua-Version; utf8.char braucht Lua 5.3 oder Luau"),
+    ("Advances only", "Nur Vorschübe"),
+    ("Write each glyph as just its advance, for measuring text width without the texture (Lua and JSON)", "Schreibt für jede Glyphe nur ihren Vorschub, um Textbreiten ohne die Textur zu messen (Lua und JSON)"),
+    ("Write the kerning pairs", "Schreibt die Kerning-Paare"),
+    ("MeasureText function", "MeasureText-Funktion"),
+    ("Adds Font.MeasureText(text), which returns the width of text in pixels from the advances and kerning", "Fügt Font.MeasureText(text) hinzu, das die Breite des Texts in Pixeln aus Vorschüben und Kerning berechnet"),
+    ("Ascent and descent", "Ober- und Unterlänge"),
+    ("Write how far the glyphs reach above and below the baseline, for aligning text vertically", "Schreibt, wie weit die Glyphen über und unter die Grundlinie reichen, um Text vertikal auszurichten"),
+    ("Remove exporter padding", "Exporter-Innenabstand entfernen"),
+    ("Shrinks each glyph rect by the padding in the font's info, adjusting offsets to match", "Verkleinert jedes Glyphenrechteck um den Innenabstand aus den Schriftinfos und passt die Versätze an"),
+    ("Texture checksums", "Textur-Prüfsummen"),
+    ("Write the CRC-32 of each texture page, so a game can detect a texture that no longer matches the metrics", "Schreibt die CRC-32 jeder Texturseite, damit ein Spiel eine Textur erkennt, die nicht mehr zu den Metriken passt"),
+    ("Rojo project:", "Rojo-Projekt:"),
+    ("none", "keins"),
+    ("Rojo project", "Rojo-Projekt"),
+    ("Forget the Rojo project", "Rojo-Projekt vergessen"),
+    ("Folder:", "Ordner:"),
+    ("Relative to the project file; a $path of the project must include it", "Relativ zur Projektdatei; ein $path des Projekts muss ihn enthalten"),
+    ("🎮 Save to Rojo", "🎮 In Rojo speichern"),
+    ("Pick a Rojo project and convert to Lua first", "Zuerst ein Rojo-Projekt wählen und nach Lua konvertieren"),
+    ("Check that the file exists and has not been moved.", "Prüfe, ob die Datei existiert und nicht verschoben wurde."),
+    ("Check that the file is readable and not locked by another program.", "Prüfe, ob die Datei lesbar ist und nicht von einem anderen Programm gesperrt wird."),
+    ("The file is not well-formed XML; fix the line shown or export the font again.", "Die Datei ist kein wohlgeformtes XML; korrigiere die angezeigte Zeile oder exportiere die Schrift erneut."),
+    ("Save the file as UTF-8, or declare its encoding in the XML declaration.", "Speichere die Datei als UTF-8 oder gib ihre Kodierung in der XML-Deklaration an."),
+    ("Fix the value, or skip malformed elements to convert the rest of the font.", "Korrigiere den Wert oder überspringe fehlerhafte Elemente, um den Rest der Schrift zu konvertieren."),
+    ("The file is truncated or not a BMFont; export it again.", "Die Datei ist abgeschnitten oder keine BMFont; exportiere sie erneut."),
+    ("Export the font again with BMFont, which always writes an <info> element.", "Exportiere die Schrift erneut mit BMFont, das immer ein <info>-Element schreibt."),
+    ("Only Lua modules generated by this converter can be read back.", "Nur von diesem Konverter erzeugte Lua-Module können zurückgelesen werden."),
+    ("Check the template's syntax and the fields it uses.", "Prüfe die Syntax der Vorlage und die verwendeten Felder."),
+    ("Check the pattern for typos.", "Prüfe das Muster auf Tippfehler."),
+    ("Put the texture pages next to the font file, under the names it lists.", "Lege die Texturseiten unter den angegebenen Namen neben die Schriftdatei."),
+    ("Choose to overwrite or back up existing files.", "Wähle, ob vorhandene Dateien überschrieben oder gesichert werden."),
+    ("Generate the font again with the missing characters.", "Erzeuge die Schrift erneut mit den fehlenden Zeichen."),
+    ("Pick the project's default.project.json and a folder one of its $path entries includes.", "Wähle die default.project.json des Projekts und einen Ordner, den einer seiner $path-Einträge enthält."),
+    ("📜 Log", "📜 Protokoll"),
+    ("📋 Copy log", "📋 Protokoll kopieren"),
+    ("🗑 Clear log", "🗑 Protokoll leeren"),
+    ("Nothing logged yet", "Noch nichts protokolliert"),
+    ("Drag to reorder", "Zum Umsortieren ziehen"),
+    ("Convert again", "Erneut konvertieren"),
+    ("Remove from the queue", "Aus der Warteschlange entfernen"),
+    ("Fit", "Einpassen"),
+    ("Scroll to zoom, drag to pan, click a glyph to find it in the table", "Scrollen zum Zoomen, Ziehen zum Verschieben, Glyphe anklicken, um sie in der Tabelle zu finden"),
+    ("🎨 Split channels…", "🎨 Kanäle aufteilen…"),
+    (
+        "Save the font with a page per atlas channel, for runtimes that cannot pick a channel in a shader",
+        "Die Schrift mit einer Seite pro Atlaskanal speichern, für Laufzeitumgebungen, die keinen Kanal im Shader auswählen können",
+    ),
+    ("✅ Split {0} page(s) into {1} page(s) at {2}", "✅ {0} Seite(n) in {1} Seite(n) aufgeteilt unter {2}"),
+    ("❌ Error splitting channels: {0}", "❌ Fehler beim Aufteilen der Kanäle: {0}"),
+    (
+        "The file is not a well-formed JSON font; fix the line shown or export the font again.",
+        "Die Datei ist keine wohlgeformte JSON-Schrift; korrigiere die angezeigte Zeile oder exportiere die Schrift erneut.",
+    ),
+    ("Remap codepoints", "Codepoints umlegen"),
+    (
+        "Move glyphs to the codepoints listed in a CSV file of from,to lines or a TOML file",
+        "Glyphen auf die Codepoints verschieben, die eine CSV-Datei mit von,nach-Zeilen oder eine TOML-Datei auflistet",
+    ),
+    ("Codepoint maps", "Codepoint-Zuordnungen"),
+    ("❌ Error reading codepoint map: {0}", "❌ Fehler beim Lesen der Codepoint-Zuordnung: {0}"),
+    ("{0} codepoint(s) remapped", "{0} Codepoint(s) umgelegt"),
+    (
+        "Write one `from,to` pair of codepoints per line, e.g. U+E000,U+2605.",
+        "Schreibe ein `von,nach`-Paar von Codepoints pro Zeile, z. B. U+E000,U+2605.",
+    ),
+    ("Pages:", "Seiten:"),
+    ("{0}: {1} glyph(s)", "{0}: {1} Glyphe(n)"),
+
+    ("Split into parts of", "Aufteilen in Teile zu"),
+    (" glyphs", " Glyphen"),
+    (
+        "Writes the glyphs to part modules next to the output, which loads them when required",
+        "Schreibt die Glyphen in Teilmodule neben der Ausgabe, die sie beim require lädt",
+    ),
+
+    ("Packed glyph data", "Gepackte Glyphendaten"),
+    (
+        "Writes the glyphs as one compact string that the module decodes when required, rounded to whole pixels",
+        "Schreibt die Glyphen als eine kompakte Zeichenkette, die das Modul beim require dekodiert, auf ganze Pixel gerundet",
+    ),
+
+    (
+        "The existing file does not read back as a font; only the lines that change are shown",
+        "Die vorhandene Datei lässt sich nicht als Schrift lesen; nur die geänderten Zeilen werden gezeigt",
+    ),
+    ("Replacing it changes:", "Das Ersetzen ändert:"),
+    ("Icon names", "Symbolnamen"),
+    ("Add an Icons table naming each icon's codepoint, from a CSV file of name,codepoint lines or a JSON file such as Nerd Fonts' glyphnames.json (Lua)", "Eine Icons-Tabelle mit dem Codepoint jedes Symbols hinzufügen, aus einer CSV-Datei mit name,codepoint-Zeilen oder einer JSON-Datei wie glyphnames.json von Nerd Fonts (Lua)"),
+    ("❌ Error reading icon names: {0}", "❌ Fehler beim Lesen der Symbolnamen: {0}"),
+    ("{0} icon name(s)", "{0} Symbolname(n)"),
+    ("📂 Open font…", "📂 Schrift öffnen…"),
+    ("🕘 Open recent: {0}", "🕘 Zuletzt geöffnet: {0}"),
+    ("💾 Save output…", "💾 Ausgabe speichern…"),
+    ("Format: {0}", "Format: {0}"),
+    ("👁 Toggle watching for changes", "👁 Auf Änderungen achten ein/aus"),
+    ("Type a command…", "Befehl eingeben…"),
+    ("No matching commands", "Keine passenden Befehle"),
+    ("Command palette ({0})", "Befehlspalette ({0})"),
+    ("Remove this scale", "Diese Skalierung entfernen"),
+    ("Write a sub-table per scale, so UI code can pick the closest pre-scaled metrics (Lua)", "Eine Untertabelle pro Skalierung schreiben, damit UI-Code die nächstliegenden vorskalierten Metriken wählen kann (Lua)"),
+    ("Synthesize whitespace", "Leerraum ergänzen"),
+    ("Add empty glyphs for a space, tab and no-break space when the font has none, marked Synthesized = true", "Leere Glyphen für Leerzeichen, Tabulator und geschütztes Leerzeichen hinzufügen, wenn die Schrift keine hat, markiert mit Synthesized = true"),
+    ("Space:", "Leerzeichen:"),
+    ("Average digit", "Durchschnittliche Ziffer"),
+    ("Fraction of size", "Anteil der Größe"),
+    ("Pixels", "Pixel"),
+    ("Tab:", "Tabulator:"),
+    ("How many spaces a synthesized tab advances", "Wie viele Leerzeichen ein ergänzter Tabulator vorrückt"),
+    ("Prune unused kerning", "Ungenutzte Unterschneidung entfernen"),
+    ("Drop kerning pairs with a glyph that is left out of the export or missing from the font", "Unterschneidungspaare mit einer Glyphe weglassen, die nicht exportiert wird oder der Schrift fehlt"),
+    ("Pruned kerning pairs", "Entfernte Unterschneidungspaare"),
+    ("{0}: {1} outside the subset, {2} with a missing glyph", "{0}: {1} außerhalb der Teilmenge, {2} mit fehlender Glyphe"),
+    ("Re-run with the same settings, saving over the output", "Mit denselben Einstellungen erneut ausführen und die Ausgabe überschreiben"),
+    ("The input no longer exists, or the settings were not recorded", "Die Eingabe existiert nicht mehr, oder die Einstellungen wurden nicht gespeichert"),
+    ("Open the input", "Eingabe öffnen"),
+    ("LÖVE module", "LÖVE-Modul"),
+    ("Drop empty glyphs", "Leere Glyphen weglassen"),
+    ("Leave out glyphs whose rect has no width or height, such as the space", "Glyphen ohne Breite oder Höhe weglassen, etwa das Leerzeichen"),
+    ("Drop blank glyphs", "Unsichtbare Glyphen weglassen"),
+    ("Leave out glyphs without a visible pixel in the atlas", "Glyphen ohne sichtbares Pixel im Atlas weglassen"),
+    ("Printable characters only", "Nur druckbare Zeichen"),
+    ("Leave out control characters, noncharacters and invisible format characters", "Steuerzeichen, Nichtzeichen und unsichtbare Formatzeichen weglassen"),
+    ("➕ New tab", "➕ Neuer Tab"),
+    ("✖ Close tab", "✖ Tab schließen"),
+    ("New tab", "Neuer Tab"),
+    ("No font loaded", "Keine Schrift geladen"),
+    ("Size {0}, line height {1}", "Größe {0}, Zeilenhöhe {1}"),
+    ("Open another tab ({0})", "Weiteren Tab öffnen ({0})"),
+    ("Baseline shift:", "Grundlinienversatz:"),
+    ("Moves every glyph down, with the base and line height, so the font lines up with others of its family", "Verschiebt jede Glyphe samt Grundlinie und Zeilenhöhe nach unten, damit die Schrift zu den anderen ihrer Familie passt"),
+    ("Align across tabs", "Über Tabs ausrichten"),
+    ("Sets the shift of the font in every tab so their baselines line up when mixed in one run of text", "Setzt den Versatz der Schrift in jedem Tab so, dass ihre Grundlinien gemischt in einer Textzeile übereinstimmen"),
+    ("✅ Aligned the baselines of {0} fonts", "✅ Grundlinien von {0} Schriften ausgerichtet"),
+    ("Glyph rects:", "Glyphen-Rechtecke:"),
+    ("UV instead of pixels", "UV statt Pixel"),
+    ("UV and pixels", "UV und Pixel"),
+    ("UV coordinates are the position and size divided by the texture size, for shaders that do not know it (Lua and JSON)", "UV-Koordinaten sind Position und Größe geteilt durch die Texturgröße, für Shader, die sie nicht kennen (Lua und JSON)"),
+    ("Texture paths:", "Texturpfade:"),
+    ("Add rule", "Regel hinzufügen"),
+    ("Write the texture file names matching a pattern as something else, e.g. font_0.png as rbxassetid://123456", "Texturdateinamen, die auf ein Muster passen, anders schreiben, z. B. font_0.png als rbxassetid://123456"),
+    ("{file}, {stem} and {page} stand for the file name, the name without its extension and the page id", "{file}, {stem} und {page} stehen für den Dateinamen, den Namen ohne Endung und die Seiten-ID"),
+    ("Remove this rule", "Diese Regel entfernen"),
+    ("Check XML structure", "XML-Struktur prüfen"),
+    ("Before parsing, list every missing element or attribute and malformed value with its line", "Vor dem Einlesen jedes fehlende Element oder Attribut und jeden fehlerhaften Wert mit seiner Zeile auflisten"),
+    ("✏ Edit", "✏ Bearbeiten"),
+    ("Edit offsets and advances, delete glyphs and trim rects before exporting", "Vor dem Export Versätze und Vorschübe bearbeiten, Glyphen löschen und Rechtecke zuschneiden"),
+];
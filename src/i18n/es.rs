@@ -0,0 +1,480 @@
+//! Spanish translations.
+
+pub(super) const STRINGS: &[(&str, &str)] = &[
+    // Header and main actions
+    ("🎨 .fnt to .lua Converter", "🎨 Conversor de .fnt a .lua"),
+    ("Theme:", "Tema:"),
+    ("Dark", "Oscuro"),
+    ("Light", "Claro"),
+    ("High contrast", "Alto contraste"),
+    ("📂 Select .fnt file", "📂 Seleccionar archivo .fnt"),
+    ("📄 Selected: {0}", "📄 Seleccionado: {0}"),
+    ("⚡ Convert", "⚡ Convertir"),
+    ("📋 Copy to clipboard", "📋 Copiar al portapapeles"),
+    ("Convert and copy the output without saving a file ({0})", "Convertir y copiar el resultado sin guardar un archivo ({0})"),
+    ("Convert and preview the output; {0} also saves it", "Convertir y previsualizar el resultado; {0} también lo guarda"),
+    ("📥 Import Lua…", "📥 Importar Lua…"),
+    (
+        "Load a previously generated Lua module, e.g. to export it as .fnt",
+        "Cargar un módulo Lua generado antes, p. ej. para exportarlo como .fnt",
+    ),
+    ("💾 Export .fnt", "💾 Exportar .fnt"),
+    ("FNT files", "Archivos FNT"),
+    ("Lua files", "Archivos Lua"),
+    ("Fonts", "Fuentes"),
+    ("Text", "Texto"),
+    ("Preset", "Preajuste"),
+    ("⚠️ Please select a .fnt file first", "⚠️ Selecciona primero un archivo .fnt"),
+    ("❌ Error parsing file: {0}", "❌ Error al leer el archivo: {0}"),
+    ("❌ Error saving file: {0}", "❌ Error al guardar el archivo: {0}"),
+    ("✅ Saved to {0}", "✅ Guardado en {0}"),
+    ("📚 {0} files in the batch queue", "📚 {0} archivos en la cola"),
+    ("📋 Copied {0} glyphs to the clipboard", "📋 {0} glifos copiados al portapapeles"),
+    ("Parsing {0}… {1}s", "Leyendo {0}… {1} s"),
+    ("Converting {0}… {1}s", "Convirtiendo {0}… {1} s"),
+    // Error details
+    ("❌ Error details", "❌ Detalles del error"),
+    ("Attribute: {0} = {1}", "Atributo: {0} = {1}"),
+    ("At {0} (byte {1})", "En {0} (byte {1})"),
+    // History
+    ("🕘 Recent ⏷", "🕘 Recientes ⏷"),
+    ("🕘 Recent", "🕘 Recientes"),
+    ("File no longer exists", "El archivo ya no existe"),
+    ("No conversions yet", "Aún no hay conversiones"),
+    ("🗑 Clear history", "🗑 Borrar historial"),
+    // Output options
+    ("⚙ Output options", "⚙ Opciones de salida"),
+    ("Preset:", "Preajuste:"),
+    ("None", "Ninguno"),
+    ("Add…", "Añadir…"),
+    ("Load a TOML preset, as used by --preset", "Cargar un preajuste TOML, como los que usa --preset"),
+    ("✅ Applied preset {0}", "✅ Preajuste {0} aplicado"),
+    ("❌ Error reading preset: {0}", "❌ Error al leer el preajuste: {0}"),
+    ("Format:", "Formato:"),
+    ("BMFont XML", "XML de BMFont"),
+    ("BMFont text", "Texto de BMFont"),
+    ("cocos2d plist", "plist de cocos2d"),
+    ("Roblox model", "Modelo de Roblox"),
+    ("C header", "Cabecera C"),
+    ("Vectors:", "Vectores:"),
+    ("Function call", "Llamada a función"),
+    ("Table literal", "Tabla literal"),
+    ("Array", "Arreglo"),
+    ("Flattened fields", "Campos separados"),
+    ("Glyph rects:", "Rectángulos de glifo:"),
+    ("Separate pairs", "Pares separados"),
+    (
+        "Combine atlas position and size into (x, y, width, height)",
+        "Combinar la posición en el atlas y el tamaño en (x, y, ancho, alto)",
+    ),
+    ("Named module table", "Tabla de módulo con nombre"),
+    ("Indexed keys", "Claves indexadas"),
+    ("Header comment", "Comentario de cabecera"),
+    ("{source} is replaced with the input file name", "{source} se sustituye por el nombre del archivo de entrada"),
+    ("Include timestamp", "Incluir fecha y hora"),
+    ("Custom template", "Plantilla propia"),
+    (
+        "Handlebars template with size, characters, kerning and pages in scope",
+        "Plantilla Handlebars con size, characters, kerning y pages disponibles",
+    ),
+    ("Load…", "Cargar…"),
+    ("❌ Error reading template: {0}", "❌ Error al leer la plantilla: {0}"),
+    ("Only export a subset", "Exportar solo un subconjunto"),
+    (
+        "Characters in the ranges or in the text are exported",
+        "Se exportan los caracteres de los rangos o del texto",
+    ),
+    ("Ranges:", "Rangos:"),
+    (
+        "Codepoints and ranges, e.g. 0x20-0x7E,0x400-0x4FF",
+        "Puntos de código y rangos, p. ej. 0x20-0x7E,0x400-0x4FF",
+    ),
+    ("Characters:", "Caracteres:"),
+    ("Font metrics only", "Solo métricas de la fuente"),
+    ("Leave out the Characters table", "Omitir la tabla Characters"),
+    ("Scale:", "Escala:"),
+    ("Multiplies every metric before rounding", "Multiplica cada métrica antes de redondear"),
+    ("Glyph padding:", "Margen de glifo:"),
+    (
+        "Grows (or shrinks, when negative) each glyph rect on every side, adjusting offsets to match",
+        "Agranda (o reduce, si es negativo) cada rectángulo de glifo por todos los lados y ajusta los desplazamientos",
+    ),
+    ("Rounding:", "Redondeo:"),
+    ("Round", "Redondear"),
+    ("Floor", "Hacia abajo"),
+    ("Ceil", "Hacia arriba"),
+    ("Keep float", "Mantener decimales"),
+    ("Numeric keys", "Claves numéricas"),
+    (
+        "Key characters by codepoint, e.g. [65] instead of [\"A\"]",
+        "Indexar los caracteres por punto de código, p. ej. [65] en lugar de [\"A\"]",
+    ),
+    ("Use letter attribute", "Usar el atributo letter"),
+    (
+        "Key glyphs by the letter=\"…\" attribute some exporters write, when present",
+        "Indexar los glifos por el atributo letter=\"…\" que escriben algunos exportadores, si existe",
+    ),
+    ("Luau types", "Tipos de Luau"),
+    (
+        "Adds --!strict and exported GlyphData and FontData types",
+        "Añade --!strict y los tipos exportados GlyphData y FontData",
+    ),
+    ("Trailing newline", "Salto de línea final"),
+    ("UTF-8 BOM", "BOM UTF-8"),
+    ("Share identical metrics", "Compartir métricas idénticas"),
+    (
+        "Emit each distinct size/offset/advance once and reference it by index",
+        "Escribir cada tamaño, desplazamiento y avance distinto una sola vez y referenciarlo por índice",
+    ),
+    ("Clamp negative sizes to 0", "Limitar los tamaños negativos a 0"),
+    ("Use absolute font size", "Usar el tamaño de fuente absoluto"),
+    (
+        "BMFont uses a negative size to mean \"match character height\"",
+        "BMFont usa un tamaño negativo para indicar «ajustar a la altura del carácter»",
+    ),
+    ("Override size", "Sustituir tamaño"),
+    ("Minified", "Minimizado"),
+    ("Emit the whole table on a single line", "Escribir toda la tabla en una sola línea"),
+    ("Indent:", "Sangría:"),
+    ("spaces", "espacios"),
+    ("tabs", "tabulaciones"),
+    ("Trailing commas", "Comas finales"),
+    ("One value per line", "Un valor por línea"),
+    ("Write each glyph's values on separate lines", "Escribir los valores de cada glifo en líneas separadas"),
+    // Preview and watching
+    ("👁 Preview ({0} glyphs, {1} KB)", "👁 Vista previa ({0} glifos, {1} KB)"),
+    ("📋 Copy", "📋 Copiar"),
+    ("💾 Save…", "💾 Guardar…"),
+    ("✖ Discard", "✖ Descartar"),
+    ("✅ Saved {0} glyphs ({1} KB) to {2}", "✅ {0} glifos ({1} KB) guardados en {2}"),
+    ("👁 Watch for changes", "👁 Vigilar cambios"),
+    ("❌ Error watching file: {0}", "❌ Error al vigilar el archivo: {0}"),
+    ("Watching {0} → {1}", "Vigilando {0} → {1}"),
+    ("✅ Converted to {0} at {1}", "✅ Convertido a {0} a las {1}"),
+    ("❌ Conversion failed at {0}: {1}", "❌ La conversión falló a las {0}: {1}"),
+    // Batch conversion
+    ("📚 Batch conversion", "📚 Conversión por lotes"),
+    ("➕ Add files…", "➕ Añadir archivos…"),
+    ("Clear", "Vaciar"),
+    ("Threads:", "Hilos:"),
+    ("0 uses one thread per core", "0 usa un hilo por núcleo"),
+    ("Exclude:", "Excluir:"),
+    (
+        "Glob matched against paths inside the folder, e.g. **/test*",
+        "Patrón glob comparado con las rutas dentro de la carpeta, p. ej. **/test*",
+    ),
+    ("{0}/{1} files · {2} · {3}s", "{0}/{1} archivos · {2} · {3} s"),
+    ("Convert queue…", "Convertir cola…"),
+    ("📁 Convert folder…", "📁 Convertir carpeta…"),
+    ("Folder to convert", "Carpeta a convertir"),
+    ("Output folder", "Carpeta de salida"),
+    ("❌ Error reading folder: {0}", "❌ Error al leer la carpeta: {0}"),
+    ("{0} Converted {1}/{2} files to {3} in {4}s", "{0} {1}/{2} archivos convertidos en {3} en {4} s"),
+    // TrueType generation
+    ("🔠 Generate from TrueType", "🔠 Generar desde TrueType"),
+    ("📂 Pick font…", "📂 Elegir fuente…"),
+    ("No font selected", "Ninguna fuente seleccionada"),
+    ("Size (px):", "Tamaño (px):"),
+    ("Texture:", "Textura:"),
+    ("Padding:", "Margen:"),
+    ("Kerning", "Interletraje"),
+    (
+        "Look up kerning for every pair of glyphs; slow for very large character sets",
+        "Buscar el interletraje de cada par de glifos; lento con conjuntos de caracteres muy grandes",
+    ),
+    ("⚙ Generate BMFont…", "⚙ Generar BMFont…"),
+    ("✅ Generated {0} glyphs on {1} page(s) to {2}", "✅ {0} glifos generados en {1} página(s) en {2}"),
+    ("❌ Error generating font: {0}", "❌ Error al generar la fuente: {0}"),
+    // Merging
+    ("🧩 Merge fonts", "🧩 Combinar fuentes"),
+    ("➕ Add fonts…", "➕ Añadir fuentes…"),
+    ("Primary: {0}", "Principal: {0}"),
+    ("Fallback: {0}", "Alternativa: {0}"),
+    (
+        "Scales this font's glyph sizes, offsets and advances",
+        "Escala los tamaños, desplazamientos y avances de los glifos de esta fuente",
+    ),
+    ("🧩 Merge…", "🧩 Combinar…"),
+    ("On conflict:", "En caso de conflicto:"),
+    ("Keep first", "Conservar el primero"),
+    ("Keep last", "Conservar el último"),
+    (
+        "✅ Merged {0} files, {1} glyphs ({2} KB) to {3}",
+        "✅ {0} archivos combinados, {1} glifos ({2} KB) en {3}",
+    ),
+    // Statistics and coverage
+    ("📊 Statistics", "📊 Estadísticas"),
+    ("Glyphs", "Glifos"),
+    ("Advance", "Avance"),
+    ("Texture utilization", "Uso de la textura"),
+    ("unknown (no <common> texture size)", "desconocido (sin tamaño de textura en <common>)"),
+    ("Kerning pairs", "Pares de interletraje"),
+    ("Estimated {0} size", "Tamaño estimado en {0}"),
+    ("Unicode blocks ({0}):", "Bloques Unicode ({0}):"),
+    ("  Other: {0}", "  Otros: {0}"),
+    ("📄 Export coverage report…", "📄 Exportar informe de cobertura…"),
+    ("✅ Saved {0} coverage report to {1}", "✅ Informe de cobertura {0} guardado en {1}"),
+    ("✅ Required characters", "✅ Caracteres necesarios"),
+    ("Paste the text the font must render", "Pega el texto que la fuente debe mostrar"),
+    ("📂 Load from file…", "📂 Cargar desde archivo…"),
+    ("❌ Error reading {0}: {1}", "❌ Error al leer {0}: {1}"),
+    ("All required characters are present", "Están todos los caracteres necesarios"),
+    ("❌ Missing {0} character(s):", "❌ Faltan {0} carácter(es):"),
+    // Atlas
+    ("🖼 Atlas", "🖼 Atlas"),
+    ("📦 Repack atlas…", "📦 Reempaquetar atlas…"),
+    (
+        "Copy the glyphs into the smallest power-of-two texture that holds them and save it as a new font",
+        "Copiar los glifos a la textura potencia de dos más pequeña que los contenga y guardarla como una fuente nueva",
+    ),
+    ("🌐 Export contact sheet…", "🌐 Exportar hoja de contactos…"),
+    (
+        "An HTML page or PNG image showing every glyph cut out of the atlas",
+        "Una página HTML o imagen PNG que muestra cada glifo recortado del atlas",
+    ),
+    ("✅ Saved contact sheet to {0}", "✅ Hoja de contactos guardada en {0}"),
+    ("Page:", "Página:"),
+    ("The font references no texture pages", "La fuente no hace referencia a ninguna página de textura"),
+    ("Offset: {0}, {1}", "Desplazamiento: {0}, {1}"),
+    ("Advance: {0}", "Avance: {0}"),
+    (
+        "✅ Repacked {0} page(s) into {1} page(s) at {2}",
+        "✅ {0} página(s) reempaquetada(s) en {1} página(s) en {2}",
+    ),
+    ("❌ Error repacking atlas: {0}", "❌ Error al reempaquetar el atlas: {0}"),
+    // Sample text
+    ("🔤 Sample text", "🔤 Texto de muestra"),
+    ("⚠️ Not in font: {0}", "⚠️ No está en la fuente: {0}"),
+    // Glyph table
+    ("🔍 Glyphs ({0})", "🔍 Glifos ({0})"),
+    ("🔍 Glyphs ({0}, edited)", "🔍 Glifos ({0}, editados)"),
+    ("✂ Trim rects to alpha", "✂ Recortar rectángulos al alfa"),
+    (
+        "Shrink each glyph to the opaque pixels of its atlas page, moving offsets to match",
+        "Reducir cada glifo a los píxeles opacos de su página del atlas y ajustar los desplazamientos",
+    ),
+    ("✂ Trimmed {0} glyph rects", "✂ {0} rectángulos de glifo recortados"),
+    ("❌ Error loading atlas: {0}", "❌ Error al cargar el atlas: {0}"),
+    ("Filter:", "Filtro:"),
+    (
+        "Character, decimal id, hex codepoint (0x41 / U+0041) or Unicode block name",
+        "Carácter, id decimal, punto de código hexadecimal (0x41 / U+0041) o nombre de bloque Unicode",
+    ),
+    ("Block", "Bloque"),
+    ("Char", "Carácter"),
+    ("Id", "Id"),
+    ("Size", "Tamaño"),
+    ("Position", "Posición"),
+    ("Offset", "Desplazamiento"),
+    ("Delete this glyph", "Eliminar este glifo"),
+    ("↶ Undo", "↶ Deshacer"),
+    ("↷ Redo", "↷ Rehacer"),
+    ("Undo the last glyph edit (Ctrl+Z)", "Deshacer la última edición de glifos (Ctrl+Z)"),
+    ("Redo the last undone glyph edit (Ctrl+Shift+Z)", "Rehacer la última edición de glifos deshecha (Ctrl+Mayús+Z)"),
+    ("🔀 Compare fonts", "🔀 Comparar fuentes"),
+    ("Old:", "Anterior:"),
+    ("New:", "Nueva:"),
+    ("🔀 Compare", "🔀 Comparar"),
+    ("✅ The fonts have the same glyphs and metrics", "✅ Las fuentes tienen los mismos glifos y métricas"),
+    ("{0} added, {1} removed and {2} changed glyphs, {3} kerning pairs changed", "{0} glifos añadidos, {1} eliminados y {2} modificados, {3} pares de kerning modificados"),
+    ("➕ Added ({0})", "➕ Añadidos ({0})"),
+    ("➖ Removed ({0})", "➖ Eliminados ({0})"),
+    ("✏ Changed ({0})", "✏ Modificados ({0})"),
+    ("📜 Lua diff", "📜 Diferencias de Lua"),
+    ("The Lua modules are identical", "Los módulos Lua son idénticos"),
+    ("size", "tamaño"),
+    ("position", "posición"),
+    ("offset", "desplazamiento"),
+    ("advance", "avance"),
+    ("page", "página"),
+    ("line height", "altura de línea"),
+    ("base", "línea base"),
+    ("texture size", "tamaño de textura"),
+    ("pages", "páginas"),
+    ("distance field", "campo de distancia"),
+    ("Verify after export", "Verificar tras exportar"),
+    ("Read saved files back and report any glyph, metric or kerning that did not survive", "Volver a leer los archivos guardados e informar de cualquier glifo, métrica o kerning que no se haya conservado"),
+    ("Only Lua, JSON and FNT output without a template can be read back", "Solo la salida Lua, JSON y FNT sin plantilla se puede volver a leer"),
+    ("⚡ Quick convert", "⚡ Conversión rápida"),
+    ("Save conversions without a dialog, named after the input", "Guardar las conversiones sin diálogo, con el nombre de la entrada"),
+    ("Into:", "En:"),
+    ("the input's folder", "la carpeta de la entrada"),
+    ("Save next to the input", "Guardar junto a la entrada"),
+    ("Output folder:", "Carpeta de salida:"),
+    ("Ask each time", "Preguntar cada vez"),
+    ("Ask for the folder on each run", "Preguntar por la carpeta en cada ejecución"),
+    ("File names:", "Nombres de archivo:"),
+    ("{name} is the input file name, {ext} the format's extension, and {size}, {line_height} and {glyphs} come from the font, e.g. {name}_{size}.{ext}", "{name} es el nombre del archivo de entrada, {ext} la extensión del formato, y {size}, {line_height} y {glyphs} provienen de la fuente, p. ej. {name}_{size}.{ext}"),
+    ("Keep", "Conservar"),
+    ("Overwrite", "Sobrescribir"),
+    ("Back up, then overwrite", "Hacer copia y sobrescribir"),
+    ("Existing files:", "Archivos existentes:"),
+    ("Keep asks before quick convert replaces a file and fails batch conversions instead", "Conservar pregunta antes de que la conversión rápida reemplace un archivo y hace fallar las conversiones por lotes"),
+    ("{0} already exists.", "{0} ya existe."),
+    ("Cancel", "Cancelar"),
+    (" (previous file backed up to {0})", " (copia del archivo anterior en {0})"),
+    ("Record provenance", "Registrar procedencia"),
+    ("Adds the source checksum, converter version, time and changed options", "Añade la suma de comprobación de la fuente, la versión del conversor, la hora y las opciones cambiadas"),
+    ("Glyph order:", "Orden de glifos:"),
+    ("Codepoint", "Punto de código"),
+    ("Source order", "Orden del origen"),
+    ("Priority list", "Lista de prioridad"),
+    ("The order glyphs are listed in the Characters table", "El orden de los glifos en la tabla Characters"),
+    ("First:", "Primero:"),
+    ("Ranges listed first, in this order, e.g. 0x20-0x7E,0x400-0x4FF", "Rangos que se listan primero, en este orden, p. ej. 0x20-0x7E,0x400-0x4FF"),
+    ("Excluded characters ({0})", "Caracteres excluidos ({0})"),
+    ("Keep all", "Conservar todos"),
+    ("Reset", "Restablecer"),
+    ("Exclude NUL and carriage return", "Excluir NUL y retorno de carro"),
+    ("Skip malformed elements", "Omitir elementos mal formados"),
+    ("Convert what can be read and list the elements that could not be parsed as warnings", "Convierte lo que se puede leer y lista como advertencias los elementos que no se pudieron analizar"),
+    ("Control character keys:", "Claves de caracteres de control:"),
+    ("Escaped string", "Cadena escapada"),
+    ("Escaped strings and string.char calls work in every Lua version; utf8.char needs Lua 5.3 or Luau", "Las cadenas escapadas y las llamadas a string.char funcionan en cualquier versión de Lua; utf8.char requiere Lua 5.3 o Luau"),
+    ("Advances only", "Solo avances"),
+    ("Write each glyph as just its advance, for measuring text width without the texture (Lua and JSON)", "Escribe solo el avance de cada glifo, para medir el ancho del texto sin la textura (Lua y JSON)"),
+    ("Write the kerning pairs", "Escribe los pares de kerning"),
+    ("MeasureText function", "Función MeasureText"),
+    ("Adds Font.MeasureText(text), which returns the width of text in pixels from the advances and kerning", "Añade Font.MeasureText(text), que devuelve el ancho del texto en píxeles a partir de los avances y el kerning"),
+    ("Ascent and descent", "Ascendente y descendente"),
+    ("Write how far the glyphs reach above and below the baseline, for aligning text vertically", "Escribe cuánto se extienden los glifos por encima y por debajo de la línea base, para alinear el texto verticalmente"),
+    ("Remove exporter padding", "Quitar el relleno del exportador"),
+    ("Shrinks each glyph rect by the padding in the font's info, adjusting offsets to match", "Reduce cada rectángulo de glifo según el relleno indicado en la información de la fuente y ajusta los desplazamientos"),
+    ("Texture checksums", "Sumas de comprobación de texturas"),
+    ("Write the CRC-32 of each texture page, so a game can detect a texture that no longer matches the metrics", "Escribe el CRC-32 de cada página de textura, para que un juego detecte una textura que ya no coincide con las métricas"),
+    ("Rojo project:", "Proyecto Rojo:"),
+    ("none", "ninguno"),
+    ("Rojo project", "Proyecto Rojo"),
+    ("Forget the Rojo project", "Olvidar el proyecto Rojo"),
+    ("Folder:", "Carpeta:"),
+    ("Relative to the project file; a $path of the project must include it", "Relativa al archivo del proyecto; un $path del proyecto debe incluirla"),
+    ("🎮 Save to Rojo", "🎮 Guardar en Rojo"),
+    ("Pick a Rojo project and convert to Lua first", "Elige primero un proyecto Rojo y convierte a Lua"),
+    ("Check that the file exists and has not been moved.", "Comprueba que el archivo existe y no se ha movido."),
+    ("Check that the file is readable and not locked by another program.", "Comprueba que el archivo se puede leer y que otro programa no lo bloquea."),
+    ("The file is not well-formed XML; fix the line shown or export the font again.", "El archivo no es XML bien formado; corrige la línea indicada o vuelve a exportar la fuente."),
+    ("Save the file as UTF-8, or declare its encoding in the XML declaration.", "Guarda el archivo como UTF-8 o declara su codificación en la declaración XML."),
+    ("Fix the value, or skip malformed elements to convert the rest of the font.", "Corrige el valor u omite los elementos mal formados para convertir el resto de la fuente."),
+    ("The file is truncated or not a BMFont; export it again.", "El archivo está truncado o no es una BMFont; vuelve a exportarlo."),
+    ("Export the font again with BMFont, which always writes an <info> element.", "Vuelve a exportar la fuente con BMFont, que siempre escribe un elemento <info>."),
+    ("Only Lua modules generated by this converter can be read back.", "Solo se pueden volver a leer los módulos Lua generados por este conversor."),
+    ("Check the template's syntax and the fields it uses.", "Comprueba la sintaxis de la plantilla y los campos que usa."),
+    ("Check the pattern for typos.", "Comprueba que el patrón no tenga erratas."),
+    ("Put the texture pages next to the font file, under the names it lists.", "Coloca las páginas de textura junto al archivo de fuente, con los nombres que indica."),
+    ("Choose to overwrite or back up existing files.", "Elige sobrescribir o hacer copia de seguridad de los archivos existentes."),
+    ("Generate the font again with the missing characters.", "Vuelve a generar la fuente con los caracteres que faltan."),
+    ("Pick the project's default.project.json and a folder one of its $path entries includes.", "Elige el default.project.json del proyecto y una carpeta que incluya una de sus entradas $path."),
+    ("📜 Log", "📜 Registro"),
+    ("📋 Copy log", "📋 Copiar registro"),
+    ("🗑 Clear log", "🗑 Vaciar registro"),
+    ("Nothing logged yet", "Aún no hay nada registrado"),
+    ("Drag to reorder", "Arrastra para reordenar"),
+    ("Convert again", "Convertir de nuevo"),
+    ("Remove from the queue", "Quitar de la cola"),
+    ("Fit", "Ajustar"),
+    ("Scroll to zoom, drag to pan, click a glyph to find it in the table", "Desplaza para ampliar, arrastra para mover, haz clic en un glifo para encontrarlo en la tabla"),
+    ("🎨 Split channels…", "🎨 Separar canales…"),
+    (
+        "Save the font with a page per atlas channel, for runtimes that cannot pick a channel in a shader",
+        "Guardar la fuente con una página por canal del atlas, para entornos que no pueden elegir un canal en un shader",
+    ),
+    ("✅ Split {0} page(s) into {1} page(s) at {2}", "✅ {0} página(s) separadas en {1} página(s) en {2}"),
+    ("❌ Error splitting channels: {0}", "❌ Error al separar los canales: {0}"),
+    (
+        "The file is not a well-formed JSON font; fix the line shown or export the font again.",
+        "El archivo no es una fuente JSON bien formada; corrige la línea indicada o vuelve a exportar la fuente.",
+    ),
+    ("Remap codepoints", "Reasignar puntos de código"),
+    (
+        "Move glyphs to the codepoints listed in a CSV file of from,to lines or a TOML file",
+        "Mover glifos a los puntos de código de un archivo CSV con líneas origen,destino o de un archivo TOML",
+    ),
+    ("Codepoint maps", "Mapas de puntos de código"),
+    ("❌ Error reading codepoint map: {0}", "❌ Error al leer el mapa de puntos de código: {0}"),
+    ("{0} codepoint(s) remapped", "{0} punto(s) de código reasignados"),
+    (
+        "Write one `from,to` pair of codepoints per line, e.g. U+E000,U+2605.",
+        "Escribe un par `origen,destino` de puntos de código por línea, p. ej. U+E000,U+2605.",
+    ),
+    ("Pages:", "Páginas:"),
+    ("{0}: {1} glyph(s)", "{0}: {1} glifo(s)"),
+
+    ("Split into parts of", "Dividir en partes de"),
+    (" glyphs", " glifos"),
+    (
+        "Writes the glyphs to part modules next to the output, which loads them when required",
+        "Escribe los glifos en módulos parciales junto a la salida, que los carga al hacer require",
+    ),
+
+    ("Packed glyph data", "Datos de glifos empaquetados"),
+    (
+        "Writes the glyphs as one compact string that the module decodes when required, rounded to whole pixels",
+        "Escribe los glifos como una cadena compacta que el módulo decodifica al hacer require, redondeados a píxeles enteros",
+    ),
+
+    (
+        "The existing file does not read back as a font; only the lines that change are shown",
+        "El archivo existente no se puede leer como fuente; solo se muestran las líneas que cambian",
+    ),
+    ("Replacing it changes:", "Reemplazarlo cambia:"),
+    ("Icon names", "Nombres de iconos"),
+    ("Add an Icons table naming each icon's codepoint, from a CSV file of name,codepoint lines or a JSON file such as Nerd Fonts' glyphnames.json (Lua)", "Añadir una tabla Icons con el punto de código de cada icono, desde un archivo CSV de líneas name,codepoint o un archivo JSON como glyphnames.json de Nerd Fonts (Lua)"),
+    ("❌ Error reading icon names: {0}", "❌ Error al leer los nombres de iconos: {0}"),
+    ("{0} icon name(s)", "{0} nombre(s) de icono"),
+    ("📂 Open font…", "📂 Abrir fuente…"),
+    ("🕘 Open recent: {0}", "🕘 Abrir reciente: {0}"),
+    ("💾 Save output…", "💾 Guardar salida…"),
+    ("Format: {0}", "Formato: {0}"),
+    ("👁 Toggle watching for changes", "👁 Activar/desactivar vigilancia de cambios"),
+    ("Type a command…", "Escribe un comando…"),
+    ("No matching commands", "No hay comandos que coincidan"),
+    ("Command palette ({0})", "Paleta de comandos ({0})"),
+    ("Remove this scale", "Quitar esta escala"),
+    ("Write a sub-table per scale, so UI code can pick the closest pre-scaled metrics (Lua)", "Escribir una subtabla por escala, para que el código de la interfaz elija las métricas preescaladas más cercanas (Lua)"),
+    ("Synthesize whitespace", "Sintetizar espacios"),
+    ("Add empty glyphs for a space, tab and no-break space when the font has none, marked Synthesized = true", "Añadir glifos vacíos para el espacio, el tabulador y el espacio de no separación cuando la fuente no los tiene, marcados con Synthesized = true"),
+    ("Space:", "Espacio:"),
+    ("Average digit", "Dígito medio"),
+    ("Fraction of size", "Fracción del tamaño"),
+    ("Pixels", "Píxeles"),
+    ("Tab:", "Tabulador:"),
+    ("How many spaces a synthesized tab advances", "Cuántos espacios avanza un tabulador sintetizado"),
+    ("Prune unused kerning", "Eliminar kerning sin usar"),
+    ("Drop kerning pairs with a glyph that is left out of the export or missing from the font", "Descarta los pares de kerning con un glifo que no se exporta o que falta en la fuente"),
+    ("Pruned kerning pairs", "Pares de kerning eliminados"),
+    ("{0}: {1} outside the subset, {2} with a missing glyph", "{0}: {1} fuera del subconjunto, {2} con un glifo ausente"),
+    ("Re-run with the same settings, saving over the output", "Volver a ejecutar con los mismos ajustes, sobrescribiendo la salida"),
+    ("The input no longer exists, or the settings were not recorded", "La entrada ya no existe o no se guardaron los ajustes"),
+    ("Open the input", "Abrir la entrada"),
+    ("LÖVE module", "Módulo LÖVE"),
+    ("Drop empty glyphs", "Omitir glifos vacíos"),
+    ("Leave out glyphs whose rect has no width or height, such as the space", "Omitir glifos cuyo rectángulo no tiene ancho o alto, como el espacio"),
+    ("Drop blank glyphs", "Omitir glifos invisibles"),
+    ("Leave out glyphs without a visible pixel in the atlas", "Omitir glifos sin ningún píxel visible en el atlas"),
+    ("Printable characters only", "Solo caracteres imprimibles"),
+    ("Leave out control characters, noncharacters and invisible format characters", "Omitir caracteres de control, no caracteres y caracteres de formato invisibles"),
+    ("➕ New tab", "➕ Nueva pestaña"),
+    ("✖ Close tab", "✖ Cerrar pestaña"),
+    ("New tab", "Nueva pestaña"),
+    ("No font loaded", "Ninguna fuente cargada"),
+    ("Size {0}, line height {1}", "Tamaño {0}, altura de línea {1}"),
+    ("Open another tab ({0})", "Abrir otra pestaña ({0})"),
+    ("Baseline shift:", "Desplazamiento de la línea base:"),
+    ("Moves every glyph down, with the base and line height, so the font lines up with others of its family", "Desplaza cada glifo hacia abajo, junto con la base y la altura de línea, para que la fuente se alinee con las demás de su familia"),
+    ("Align across tabs", "Alinear entre pestañas"),
+    ("Sets the shift of the font in every tab so their baselines line up when mixed in one run of text", "Ajusta el desplazamiento de la fuente de cada pestaña para que sus líneas base coincidan al mezclarlas en un mismo texto"),
+    ("✅ Aligned the baselines of {0} fonts", "✅ Líneas base de {0} fuentes alineadas"),
+    ("Glyph rects:", "Rectángulos de glifos:"),
+    ("UV instead of pixels", "UV en lugar de píxeles"),
+    ("UV and pixels", "UV y píxeles"),
+    ("UV coordinates are the position and size divided by the texture size, for shaders that do not know it (Lua and JSON)", "Las coordenadas UV son la posición y el tamaño divididos por el tamaño de la textura, para shaders que no lo conocen (Lua y JSON)"),
+    ("Texture paths:", "Rutas de textura:"),
+    ("Add rule", "Añadir regla"),
+    ("Write the texture file names matching a pattern as something else, e.g. font_0.png as rbxassetid://123456", "Escribir de otra forma los nombres de textura que coinciden con un patrón, p. ej. font_0.png como rbxassetid://123456"),
+    ("{file}, {stem} and {page} stand for the file name, the name without its extension and the page id", "{file}, {stem} y {page} representan el nombre del archivo, el nombre sin extensión y el id de la página"),
+    ("Remove this rule", "Quitar esta regla"),
+    ("Check XML structure", "Comprobar la estructura XML"),
+    ("Before parsing, list every missing element or attribute and malformed value with its line", "Antes de analizar, listar cada elemento o atributo que falta y cada valor mal formado con su línea"),
+    ("✏ Edit", "✏ Editar"),
+    ("Edit offsets and advances, delete glyphs and trim rects before exporting", "Editar desplazamientos y avances, borrar glifos y recortar rectángulos antes de exportar"),
+];
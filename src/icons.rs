@@ -0,0 +1,83 @@
+//! Names for the glyphs of icon fonts such as Nerd Fonts and Font Awesome,
+//! whose icons sit at private use codepoints nobody remembers, so the Lua
+//! output can list them in an `Icons` table game code looks them up in.
+
+use crate::error::ConvertError;
+use crate::subset::parse_codepoint;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// The codepoint each icon name stands for.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IconNames {
+    names: BTreeMap<String, u32>,
+}
+
+impl IconNames {
+    /// Parses `name,codepoint` lines, with codepoints written as for
+    /// [`Subset::parse_ranges`](crate::subset::Subset::parse_ranges), e.g.
+    /// `gear,U+F013`. Blank lines and lines starting with `#` are skipped.
+    pub fn parse_csv(text: &str) -> Result<Self, ConvertError> {
+        let mut names = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad = || ConvertError::IconNames(format!("line {}: expected `name,codepoint`, found {line:?}", index + 1));
+            let (name, codepoint) = line.rsplit_once(',').ok_or_else(bad)?;
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(bad());
+            }
+            names.insert(name.to_string(), parse_codepoint(codepoint).ok_or_else(bad)?);
+        }
+        Ok(Self { names })
+    }
+
+    /// Parses a JSON object keyed by icon name. Each value is a codepoint,
+    /// as a number or a string written as for [`parse_csv`](Self::parse_csv),
+    /// or an object with the codepoint in hex under `code`, as in the
+    /// `glyphnames.json` of Nerd Fonts, or under `unicode`, as in the
+    /// `icons.json` of Font Awesome. Objects with neither, such as the
+    /// `METADATA` entry of Nerd Fonts, are skipped.
+    pub fn parse_json(text: &str) -> Result<Self, ConvertError> {
+        let Value::Object(entries) = serde_json::from_str(text)? else {
+            return Err(ConvertError::IconNames("expected an object of icon names".to_string()));
+        };
+        let mut names = BTreeMap::new();
+        for (name, value) in entries {
+            let codepoint = match value {
+                Value::Number(ref number) => number.as_u64().and_then(|number| u32::try_from(number).ok()),
+                Value::String(ref text) => parse_codepoint(text),
+                Value::Object(ref fields) => match fields.get("code").or_else(|| fields.get("unicode")) {
+                    Some(Value::String(hex)) => u32::from_str_radix(hex.trim(), 16).ok(),
+                    Some(_) => None,
+                    None => continue,
+                },
+                _ => None,
+            };
+            let codepoint = codepoint.ok_or_else(|| ConvertError::IconNames(format!("`{name}` must name a codepoint, found {value}")))?;
+            names.insert(name, codepoint);
+        }
+        Ok(Self { names })
+    }
+
+    pub fn insert(&mut self, name: String, codepoint: u32) {
+        self.names.insert(name, codepoint);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The names and their codepoints, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.names.iter().map(|(name, codepoint)| (name.as_str(), *codepoint))
+    }
+}
@@ -0,0 +1,231 @@
+//! Reading the JSON descriptors msdf-bmfont and the tools following its
+//! schema write: the BMFont blocks as objects, with `chars` and `kernings`
+//! as arrays and `pages` as a list of file names.
+
+use crate::Progress;
+use crate::error::ConvertError;
+use crate::font::{ALL_CHANNELS, Character, CharacterOffset, CharacterPosition, CharacterSize, CommonMetrics, DistanceField, FontMetrics, GlyphSpacing};
+use crate::parser::{Skipped, skip_or_fail};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFont {
+    info: Option<JsonInfo>,
+    common: Option<JsonCommon>,
+    distance_field: Option<JsonDistanceField>,
+    #[serde(default)]
+    pages: Vec<String>,
+    /// Read one by one, so a lenient parse can skip the malformed ones.
+    #[serde(default)]
+    chars: Vec<serde_json::Value>,
+    #[serde(default)]
+    kernings: Vec<JsonKerning>,
+}
+
+#[derive(Deserialize)]
+struct JsonInfo {
+    size: i32,
+    padding: Option<[i32; 4]>,
+    spacing: Option<[i32; 2]>,
+    #[serde(default)]
+    outline: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonCommon {
+    line_height: f32,
+    base: f32,
+    scale_w: u32,
+    scale_h: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonDistanceField {
+    field_type: String,
+    distance_range: f32,
+}
+
+#[derive(Deserialize)]
+struct JsonChar {
+    id: u32,
+    /// The glyph itself, which msdf-bmfont writes next to `id`.
+    #[serde(rename = "char")]
+    letter: Option<String>,
+    #[serde(default)]
+    x: f32,
+    #[serde(default)]
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    xoffset: f32,
+    #[serde(default)]
+    yoffset: f32,
+    #[serde(default)]
+    xadvance: f32,
+    #[serde(default)]
+    page: u32,
+    #[serde(default = "all_channels")]
+    chnl: u8,
+}
+
+fn all_channels() -> u8 {
+    ALL_CHANNELS
+}
+
+#[derive(Deserialize)]
+struct JsonKerning {
+    first: u32,
+    second: u32,
+    amount: f32,
+}
+
+/// Whether `contents` is a JSON descriptor rather than XML or the text
+/// format.
+pub(crate) fn is_json(contents: &str) -> bool {
+    contents.trim_start_matches('\u{feff}').trim_start().starts_with('{')
+}
+
+/// JSON counterpart of
+/// [`parse_fnt_streaming_with_progress`](crate::parser::parse_fnt_streaming_with_progress).
+/// The whole descriptor is deserialized before the first glyph is reported.
+pub fn parse_fnt_json_with_progress<F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: &str,
+    callback: F,
+    progress: P,
+) -> Result<FontMetrics, ConvertError> {
+    parse_json(source, callback, progress, None)
+}
+
+/// [`parse_fnt_json_with_progress`], recording glyphs that fail to parse in
+/// `skipped` and moving on when it is given.
+pub(crate) fn parse_json<F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: &str,
+    mut callback: F,
+    mut progress: P,
+    mut skipped: Option<&mut Skipped>,
+) -> Result<FontMetrics, ConvertError> {
+    let font: JsonFont = serde_json::from_str(source.trim_start_matches('\u{feff}'))?;
+    let info = font.info.ok_or(ConvertError::MissingInfo)?;
+
+    let count = font.chars.len();
+    for (index, value) in font.chars.into_iter().enumerate() {
+        match serde_json::from_value::<JsonChar>(value) {
+            Ok(data) => callback(
+                data.id,
+                Character {
+                    size: CharacterSize {
+                        width: data.width,
+                        height: data.height,
+                    },
+                    position: CharacterPosition { x: data.x, y: data.y },
+                    offset: CharacterOffset {
+                        x: data.xoffset,
+                        y: data.yoffset,
+                    },
+                    advance: data.xadvance,
+                    page: data.page,
+                    channel: data.chnl,
+                    letter: data.letter,
+                },
+            ),
+            Err(e) => skip_or_fail(&mut skipped, Some("char".to_string()), e.into())?,
+        }
+        progress(Progress::Fraction((index + 1) as f32 / count as f32));
+    }
+
+    progress(Progress::Fraction(1.0));
+    Ok(FontMetrics {
+        size: info.size,
+        glyph_spacing: GlyphSpacing {
+            padding: info.padding.unwrap_or_default(),
+            spacing: info.spacing.unwrap_or_default(),
+            outline: info.outline,
+        },
+        common: font.common.map(|common| CommonMetrics {
+            line_height: common.line_height,
+            base: common.base,
+            scale_w: common.scale_w,
+            scale_h: common.scale_h,
+        }),
+        distance_field: font.distance_field.map(|field| DistanceField {
+            field_type: field.field_type,
+            range: field.distance_range,
+        }),
+        pages: font.pages.into_iter().enumerate().map(|(id, file)| (id as u32, file)).collect(),
+        kerning: font.kernings.iter().map(|pair| ((pair.first, pair.second), pair.amount)).collect(),
+        // The array is the whole list; there is no separate count to check.
+        declared_chars: None,
+        filled_in: Vec::new(),
+        extents: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// A descriptor laid out as msdf-bmfont writes it.
+    const MSDF: &str = r#"{
+        "pages": ["font.png"],
+        "chars": [
+            {"id": 65, "char": "A", "x": 1, "y": 2, "width": 20, "height": 30, "xoffset": -1, "yoffset": 5, "xadvance": 19, "page": 0, "chnl": 4},
+            {"id": 66, "x": 22, "y": 2, "width": 18, "height": 30, "xadvance": 17}
+        ],
+        "info": {"face": "Test", "size": 32, "padding": [2, 2, 2, 2], "spacing": [0, 0]},
+        "common": {"lineHeight": 40, "base": 30, "scaleW": 256, "scaleH": 128, "pages": 1, "packed": 0},
+        "distanceField": {"fieldType": "msdf", "distanceRange": 4},
+        "kernings": [{"first": 65, "second": 66, "amount": -2}]
+    }"#;
+
+    fn parse(source: &str, skipped: Option<&mut Skipped>) -> Result<(FontMetrics, BTreeMap<u32, Character>), ConvertError> {
+        let mut characters = BTreeMap::new();
+        let metrics = parse_json(source, |id, data| _ = characters.insert(id, data), |_| {}, skipped)?;
+        Ok((metrics, characters))
+    }
+
+    #[test]
+    fn msdf_descriptors_are_read() {
+        assert!(is_json(&format!("\u{feff}\n{MSDF}")));
+        let (metrics, characters) = parse(MSDF, None).unwrap();
+        assert_eq!(metrics.size, 32);
+        assert_eq!(metrics.glyph_spacing.padding, [2; 4]);
+        let common = metrics.common.unwrap();
+        assert_eq!((common.line_height, common.base, common.scale_w, common.scale_h), (40.0, 30.0, 256, 128));
+        let field = metrics.distance_field.unwrap();
+        assert_eq!((field.field_type.as_str(), field.range), ("msdf", 4.0));
+        assert_eq!(metrics.pages, BTreeMap::from([(0, "font.png".to_string())]));
+        assert_eq!(metrics.kerning[&(65, 66)], -2.0);
+
+        let a = &characters[&65];
+        assert_eq!((a.position.x, a.position.y, a.size.width, a.size.height), (1.0, 2.0, 20.0, 30.0));
+        assert_eq!((a.offset.x, a.offset.y, a.advance, a.channel), (-1.0, 5.0, 19.0, 4));
+        assert_eq!(a.letter.as_deref(), Some("A"));
+        // Left out fields fall back to BMFont's defaults.
+        let b = &characters[&66];
+        assert_eq!((b.offset.x, b.page, b.channel, b.letter.as_deref()), (0.0, 0, ALL_CHANNELS, None));
+    }
+
+    #[test]
+    fn malformed_glyphs_are_skipped_only_when_lenient() {
+        let source = MSDF.replace(r#""id": 66"#, r#""id": "B""#);
+        assert!(parse(&source, None).is_err());
+        let mut skipped = Skipped::new();
+        let (_, characters) = parse(&source, Some(&mut skipped)).unwrap();
+        assert_eq!(characters.keys().collect::<Vec<_>>(), [&65]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0.as_deref(), Some("char"));
+    }
+
+    #[test]
+    fn descriptors_need_an_info_block() {
+        let source = MSDF.replace(r#""info""#, r#""about""#);
+        assert!(matches!(parse(&source, None), Err(ConvertError::MissingInfo)));
+    }
+}
@@ -0,0 +1,54 @@
+//! Placing glyphs for a line of text the way a renderer would.
+
+use crate::font::{Character, Font};
+
+/// A glyph positioned by [`layout_text`].
+#[derive(Debug, Clone)]
+pub struct PlacedGlyph<'a> {
+    pub id: u32,
+    /// Top-left corner of the glyph quad, relative to the top-left of the text.
+    pub x: f32,
+    pub y: f32,
+    pub character: &'a Character,
+}
+
+/// Lays out `text` with the font's offsets, advances and kerning. Lines are
+/// `LineHeight` apart, falling back to the font size without `<common>`.
+/// Characters the font does not define are skipped.
+pub fn layout_text<'a>(font: &'a Font, text: &str) -> Vec<PlacedGlyph<'a>> {
+    let line_height = match font.metrics.common {
+        Some(ref common) => common.line_height,
+        None => font.metrics.size.abs() as f32,
+    };
+
+    let mut placed = Vec::new();
+    let mut pen_x = 0.0;
+    let mut pen_y = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        if c == '\n' {
+            pen_x = 0.0;
+            pen_y += line_height;
+            previous = None;
+            continue;
+        }
+
+        let id = c as u32;
+        let Some(character) = font.characters.get(&id) else {
+            continue;
+        };
+        if let Some(amount) = previous.and_then(|previous| font.metrics.kerning.get(&(previous, id))) {
+            pen_x += *amount;
+        }
+
+        placed.push(PlacedGlyph {
+            id,
+            x: pen_x + character.offset.x,
+            y: pen_y + character.offset.y,
+            character,
+        });
+        pen_x += character.advance;
+        previous = Some(id);
+    }
+    placed
+}
@@ -0,0 +1,78 @@
+//! Conversion of BMFont descriptors into Lua modules for Roblox text
+//! rendering.
+//!
+//! [`render`] converts a parsed font with every [`OutputOptions`] setting
+//! applied, the way the command line and the GUI do:
+//!
+//! ```no_run
+//! use xml_to_lua::{OutputOptions, parse_fnt, render};
+//!
+//! let font = parse_fnt("font.fnt")?;
+//! let lua = render(&font, "font.fnt", &OutputOptions::default())?;
+//! # Ok::<(), xml_to_lua::ConvertError>(())
+//! ```
+
+pub mod align;
+pub mod batch;
+pub mod binary;
+pub mod c_header;
+pub mod config_formats;
+pub mod contact_sheet;
+pub mod coverage;
+pub mod diff;
+pub mod error;
+pub mod font;
+pub mod formatter;
+pub mod glyph_filter;
+pub mod icons;
+pub mod json_format;
+pub mod layout;
+pub mod love;
+pub mod lua_import;
+pub mod merge;
+#[cfg(feature = "atlas")]
+pub mod pack;
+pub mod page_paths;
+pub mod parse_cache;
+pub mod parser;
+pub mod plist;
+pub mod queue;
+mod quirks;
+#[cfg(feature = "ttf")]
+pub mod rasterize;
+pub mod remap;
+pub mod report;
+pub mod rojo;
+pub mod runtime;
+pub mod schema;
+pub mod stats;
+pub mod subset;
+pub mod synthesize;
+pub mod text_format;
+pub mod template;
+#[cfg(feature = "atlas")]
+pub mod trim;
+pub mod unicode_block;
+pub mod validate;
+pub mod verify;
+pub mod writer;
+
+pub use error::ConvertError;
+pub use font::{Character, CommonMetrics, CharacterOffset, CharacterPosition, CharacterSize, DistanceField, Extents, Font, FontMetrics};
+pub use formatter::{CharacterOrder, HeaderOptions, KeyEscape, ModuleStyle, OutputFormat, OutputFormatter, OutputOptions, RectStyle, Rounding, Style, UvCoordinates, VectorStyle, format_output, render};
+pub use parser::{parse_fnt, parse_fnt_streaming};
+
+/// Conversion progress, reported to callers that drive their own UI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    /// Fraction of glyphs processed, from 0.0 to 1.0.
+    Fraction(f32),
+    /// The total glyph count is not known, e.g. `<chars count>` is missing.
+    Indeterminate,
+}
+
+/// The example font shipped in `examples/`, for tests.
+#[cfg(test)]
+pub(crate) fn example_font() -> Font {
+    parse_fnt(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/font.fnt")).expect("the example font parses")
+}
@@ -0,0 +1,138 @@
+//! Collecting the `tracing` events of the converter for the GUI log panel
+//! and, with `--verbose`, for stderr, so a log can be attached to bug
+//! reports.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Entries kept in a [`LogBuffer`] before the oldest are dropped.
+const LOG_LIMIT: usize = 2000;
+
+/// One recorded event.
+#[derive(Clone)]
+pub struct LogEntry {
+    pub time: String,
+    pub level: Level,
+    pub message: String,
+}
+
+impl std::fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:>5} {}", self.time, self.level, self.message)
+    }
+}
+
+/// The most recent events, shared between the subscriber and the log panel.
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl LogBuffer {
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if entries.len() == LOG_LIMIT {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// A copy of the entries, oldest first.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+    }
+}
+
+/// A subscriber for the events of this crate and its library, writing them
+/// to a [`LogBuffer`] or to stderr. Spans are not used and only get ids.
+struct Logger {
+    buffer: Option<LogBuffer>,
+    next_span: AtomicU64,
+}
+
+/// Collects the `message` of an event and appends its other fields as
+/// `name=value`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= Level::DEBUG
+            && ["xml_to_lua", "xmltolua"].iter().any(|target| metadata.target().starts_with(target))
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let entry = LogEntry {
+            time: chrono::Local::now().format("%H:%M:%S%.3f").to_string(),
+            level: *event.metadata().level(),
+            message: visitor.message + &visitor.fields,
+        };
+        match self.buffer {
+            Some(ref buffer) => buffer.push(entry),
+            None => eprintln!("{entry}"),
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Records events up to debug level into the returned buffer, for the GUI.
+pub fn log_to_buffer() -> LogBuffer {
+    let buffer = LogBuffer::default();
+    install(Some(buffer.clone()));
+    buffer
+}
+
+/// Prints events up to debug level to stderr, for `--verbose`.
+pub fn log_to_stderr() {
+    install(None);
+}
+
+fn install(buffer: Option<LogBuffer>) {
+    let logger = Logger {
+        buffer,
+        next_span: AtomicU64::new(1),
+    };
+    // Only fails if a subscriber is already set, which then keeps logging.
+    let _ = tracing::subscriber::set_global_default(logger);
+}
@@ -0,0 +1,153 @@
+//! Writing fonts as Lua modules for LÖVE, which define a quad per glyph
+//! and the glyph string in the shape LÖVE's font APIs take, so the same
+//! bitmap assets serve LÖVE prototypes and Roblox games.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputFormatter, OutputOptions, adjusted_font, char_key, header_comment, lua_string};
+
+/// The module of [`write_love_module`].
+pub struct LoveFormatter;
+
+impl OutputFormatter for LoveFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Love
+    }
+
+    fn name(&self) -> &'static str {
+        "love"
+    }
+
+    fn label(&self) -> &'static str {
+        "LÖVE module"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lua module for LÖVE with a quad per glyph and the glyph string"
+    }
+
+    fn extension(&self) -> &'static str {
+        "lua"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(write_love_module(&adjusted_font(font, options), source, options))
+    }
+}
+
+/// Renders an already adjusted `font` as a module returning a table with the
+/// line metrics, the page file names in `pages`, every glyph's character in
+/// codepoint order in `glyphs`, the quad of each glyph in `characters` keyed
+/// by its character, and `kerning` keyed by first and second character.
+/// Calling the module's `load(directory)` in LÖVE loads the pages from
+/// `directory` into `images` and creates a `love.graphics.newQuad` per glyph
+/// in `quads`, for drawing glyphs with `love.graphics.draw(image, quad, x,
+/// y)` or adding them to a SpriteBatch.
+pub fn write_love_module(font: &Font, source: &str, options: &OutputOptions) -> String {
+    let indent = options.style.indent();
+    let common = font.metrics.common.as_ref();
+    let glyphs: String = font.characters.keys().filter_map(|id| char::from_u32(*id)).collect();
+
+    let mut lines = Vec::new();
+    lines.push("local font = {".to_string());
+    lines.push(format!("{indent}size = {},", font.metrics.size));
+    if let Some(common) = common {
+        lines.push(format!("{indent}lineHeight = {},", common.line_height));
+        lines.push(format!("{indent}base = {},", common.base));
+        lines.push(format!("{indent}textureWidth = {},", common.scale_w));
+        lines.push(format!("{indent}textureHeight = {},", common.scale_h));
+    }
+    let pages: Vec<String> = font.metrics.pages.values().map(|file| lua_string(file)).collect();
+    lines.push(format!("{indent}pages = {{ {} }},", pages.join(", ")));
+    lines.push(format!("{indent}glyphs = {},", lua_string(&glyphs)));
+
+    lines.push(format!("{indent}characters = {{"));
+    for (id, data) in &font.characters {
+        // Pages are listed in id order, and Lua lists count from 1.
+        let page = font.metrics.pages.keys().position(|page| *page == data.page).map_or(1, |index| index + 1);
+        lines.push(format!(
+            "{indent}{indent}[\"{}\"] = {{ x = {}, y = {}, width = {}, height = {}, xoffset = {}, yoffset = {}, advance = {}, page = {page} }},",
+            char_key(*id),
+            data.position.x,
+            data.position.y,
+            data.size.width,
+            data.size.height,
+            data.offset.x,
+            data.offset.y,
+            data.advance
+        ));
+    }
+    lines.push(format!("{indent}}},"));
+
+    lines.push(format!("{indent}kerning = {{{}", if font.metrics.kerning.is_empty() { "}," } else { "" }));
+    let mut pairs = font.metrics.kerning.iter().peekable();
+    while let Some(&(&(first, _), _)) = pairs.peek() {
+        let mut amounts = Vec::new();
+        while let Some((&(_, second), amount)) = pairs.next_if(|((next, _), _)| *next == first) {
+            amounts.push(format!("[\"{}\"] = {amount}", char_key(second)));
+        }
+        lines.push(format!("{indent}{indent}[\"{}\"] = {{ {} }},", char_key(first), amounts.join(", ")));
+    }
+    if !font.metrics.kerning.is_empty() {
+        lines.push(format!("{indent}}},"));
+    }
+    lines.push("}".to_string());
+
+    lines.push(String::new());
+    lines.push("-- Loads the pages from `directory` into `font.images` and creates a".to_string());
+    lines.push("-- quad per glyph in `font.quads`, keyed like `font.characters`.".to_string());
+    lines.push("function font.load(directory)".to_string());
+    lines.push(format!("{indent}local prefix = directory and (directory .. \"/\") or \"\""));
+    lines.push(format!("{indent}font.images = {{}}"));
+    lines.push(format!("{indent}for index, file in ipairs(font.pages) do"));
+    lines.push(format!("{indent}{indent}font.images[index] = love.graphics.newImage(prefix .. file)"));
+    lines.push(format!("{indent}end"));
+    lines.push(format!("{indent}font.quads = {{}}"));
+    lines.push(format!("{indent}for key, glyph in pairs(font.characters) do"));
+    lines.push(format!("{indent}{indent}local image = font.images[glyph.page]"));
+    lines.push(format!(
+        "{indent}{indent}font.quads[key] = love.graphics.newQuad(glyph.x, glyph.y, glyph.width, glyph.height, image:getDimensions())"
+    ));
+    lines.push(format!("{indent}end"));
+    lines.push(format!("{indent}return font"));
+    lines.push("end".to_string());
+    lines.push(String::new());
+    lines.push("return font\n".to_string());
+
+    let mut output = String::new();
+    if let Some(ref header) = options.header {
+        output.push_str(&header_comment(header, source, options));
+    }
+    output.push_str(&lines.join("\n"));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn glyphs_and_kerning_are_keyed_by_character() {
+        let font = example_font();
+        let module = write_love_module(&font, "font.fnt", &OutputOptions { header: None, ..OutputOptions::default() });
+        assert!(module.starts_with("local font = {\n"));
+        assert!(module.ends_with("return font\n"));
+        assert!(module.contains("[\"A\"] = { x = 514, y = 0, width = 19, height = 30, xoffset = 0, yoffset = 11, advance = 19, page = 1 },"));
+        assert!(module.contains("pages = { \"ROBUST_0.png\" },"));
+        let kerning = module.lines().find(|line| line.trim_start().starts_with("[\"A\"] = { [")).unwrap();
+        assert!(kerning.contains("[\"\\\"\"] = -1"), "{kerning}");
+    }
+
+    #[test]
+    fn pages_are_numbered_from_one_in_id_order() {
+        let mut font = example_font();
+        font.metrics.pages = [(3, "b.png".to_string()), (7, "c.png".to_string())].into();
+        for data in font.characters.values_mut() {
+            data.page = 7;
+        }
+        let module = write_love_module(&font, "font.fnt", &OutputOptions { header: None, ..OutputOptions::default() });
+        assert!(module.contains("pages = { \"b.png\", \"c.png\" },"));
+        assert_eq!(module.matches(", page = 2 },").count(), font.characters.len());
+    }
+}
@@ -0,0 +1,865 @@
+//! Reading fonts back from Lua modules this crate generated, so a lost `.fnt`
+//! can be recovered from its converted output.
+
+use crate::error::ConvertError;
+use crate::font::{ALL_CHANNELS, Character, CharacterOffset, CharacterPosition, CharacterSize, CommonMetrics, DistanceField, Font};
+use crate::formatter::PACKED_DIGITS;
+use crate::parser::read_source;
+use tracing::info;
+
+/// A Lua expression, as far as generated modules use them.
+#[derive(Debug, Clone)]
+enum Value {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Table(Entries),
+    /// `name(args)`, e.g. `Vector2.new(1, 2)`.
+    Call(Vec<Value>),
+}
+
+/// Table entries in source order; positional entries have no key.
+type Entries = Vec<(Option<Key>, Value)>;
+
+/// An `(x, y)` or `(width, height)` read from a vector.
+type Pair = (f32, f32);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Key {
+    Name(String),
+    Number(f64),
+}
+
+impl Value {
+    fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Table(entries) => entries
+                .iter()
+                .find(|(key, _)| matches!(key, Some(Key::Name(key)) if key == name))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// The positional entries of a table.
+    fn items(&self) -> Vec<&Value> {
+        match self {
+            Value::Table(entries) => entries.iter().filter(|(key, _)| key.is_none()).map(|(_, value)| value).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Number(f64),
+    String(String),
+    Symbol(char),
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn error(&self, message: impl Into<String>) -> ConvertError {
+        ConvertError::at(self.position as u64, ConvertError::Lua(message.into()))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if !trimmed.starts_with("--") {
+                return;
+            }
+            self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    /// The next token and the offset it starts at, or `None` at the end.
+    fn next(&mut self) -> Result<Option<(usize, Token)>, ConvertError> {
+        self.skip_trivia();
+        let start = self.position;
+        let rest = self.rest();
+        let Some(c) = rest.chars().next() else {
+            return Ok(None);
+        };
+
+        let token = if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+            self.position += len;
+            Token::Name(rest[..len].to_string())
+        } else if c.is_ascii_digit() || (c == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit())) {
+            let len = 1 + rest[1..]
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-' && c != '+')
+                .unwrap_or(rest.len() - 1);
+            let text = &rest[..len];
+            let number = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                Some(hex) => u64::from_str_radix(hex, 16).ok().map(|number| number as f64),
+                None => text.parse().ok(),
+            };
+            let number = number.ok_or_else(|| self.error(format!("bad number {text:?}")))?;
+            self.position += len;
+            Token::Number(number)
+        } else if c == '"' {
+            self.position += 1;
+            Token::String(self.string()?)
+        } else {
+            self.position += c.len_utf8();
+            Token::Symbol(c)
+        };
+        Ok(Some((start, token)))
+    }
+
+    /// Reads a double-quoted string body after the opening quote, decoding
+    /// every escape Lua 5.4 knows. Escapes spell bytes, so the decoded string
+    /// is checked to be UTF-8 once it is complete.
+    fn string(&mut self) -> Result<String, ConvertError> {
+        let mut value = Vec::new();
+        let bytes = self.rest().as_bytes();
+        let mut index = 0;
+        while let Some(&byte) = bytes.get(index) {
+            index += 1;
+            match byte {
+                b'"' => {
+                    self.position += index;
+                    return String::from_utf8(value).map_err(|_| self.error("string is not UTF-8"));
+                }
+                b'\\' => {
+                    let Some(&escape) = bytes.get(index) else { break };
+                    index += 1;
+                    match escape {
+                        b'n' | b'\n' => value.push(b'\n'),
+                        b'r' => value.push(b'\r'),
+                        b't' => value.push(b'\t'),
+                        b'a' => value.push(0x07),
+                        b'b' => value.push(0x08),
+                        b'v' => value.push(0x0b),
+                        b'f' => value.push(0x0c),
+                        b'0'..=b'9' => {
+                            // Up to three decimal digits.
+                            let digits: Vec<u8> = bytes[index - 1..].iter().take(3).take_while(|byte| byte.is_ascii_digit()).copied().collect();
+                            let code = digits.iter().fold(0, |code, digit| code * 10 + u32::from(digit - b'0'));
+                            let code = u8::try_from(code).map_err(|_| self.error(format!("bad escape \\{code}")))?;
+                            value.push(code);
+                            index += digits.len() - 1;
+                        }
+                        b'x' => {
+                            let digits = bytes.get(index..index + 2).and_then(|digits| std::str::from_utf8(digits).ok());
+                            let code = digits.and_then(|digits| u8::from_str_radix(digits, 16).ok());
+                            value.push(code.ok_or_else(|| self.error("bad escape \\x"))?);
+                            index += 2;
+                        }
+                        b'z' => index += bytes[index..].iter().take_while(|byte| byte.is_ascii_whitespace()).count(),
+                        b'u' => {
+                            let end = bytes[index..].iter().position(|byte| *byte == b'}').map(|end| index + end);
+                            let digits = end.and_then(|end| std::str::from_utf8(bytes.get(index + 1..end)?).ok()).unwrap_or("");
+                            let c = u32::from_str_radix(digits, 16).ok().and_then(char::from_u32);
+                            let c = c.ok_or_else(|| self.error(format!("bad escape \\u{{{digits}}}")))?;
+                            value.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                            index = end.map_or(bytes.len(), |end| end + 1);
+                        }
+                        escape => value.push(escape),
+                    }
+                }
+                byte => value.push(byte),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<(usize, Token)>,
+    /// The names `decodeGlyphs` reads each packed glyph into, in order, if
+    /// the module has one.
+    packed_fields: Option<Vec<String>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            lexer: Lexer { source, position: 0 },
+            peeked: None,
+            packed_fields: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, ConvertError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next()?;
+        }
+        Ok(self.peeked.as_ref().map(|(_, token)| token))
+    }
+
+    fn next(&mut self) -> Result<Token, ConvertError> {
+        self.peek()?;
+        match self.peeked.take() {
+            Some((_, token)) => Ok(token),
+            None => Err(self.lexer.error("unexpected end of file")),
+        }
+    }
+
+    fn eat(&mut self, symbol: char) -> Result<bool, ConvertError> {
+        if self.peek()? == Some(&Token::Symbol(symbol)) {
+            self.peeked = None;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ConvertError> {
+        if self.eat(symbol)? {
+            return Ok(());
+        }
+        Err(self.lexer.error(format!("expected `{symbol}`")))
+    }
+
+    fn name(&mut self) -> Result<String, ConvertError> {
+        match self.next()? {
+            Token::Name(name) => Ok(name),
+            _ => Err(self.lexer.error("expected a name")),
+        }
+    }
+
+    /// Skips a Luau `:: Type` cast if one follows.
+    fn annotation(&mut self) -> Result<(), ConvertError> {
+        if self.eat(':')? {
+            self.expect(':')?;
+            self.skip_type()?;
+        }
+        Ok(())
+    }
+
+    /// Skips a Luau type: names and `{ ... }` tables, optionally `?` and
+    /// joined with `|`.
+    fn skip_type(&mut self) -> Result<(), ConvertError> {
+        loop {
+            match self.next()? {
+                Token::Name(_) => {}
+                Token::Symbol('{') => {
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match self.next()? {
+                            Token::Symbol('{') => depth += 1,
+                            Token::Symbol('}') => depth -= 1,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => return Err(self.lexer.error("expected a type")),
+            }
+            self.eat('?')?;
+            if !self.eat('|')? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ConvertError> {
+        match self.next()? {
+            Token::Number(number) => Ok(Value::Number(number)),
+            Token::String(string) => Ok(Value::String(string)),
+            Token::Symbol('{') => self.table(),
+            Token::Name(name) if name == "true" || name == "false" => Ok(Value::Bool(name == "true")),
+            Token::Name(_) => {
+                // Dotted function names such as `Vector2.new`.
+                while self.eat('.')? {
+                    self.name()?;
+                }
+                self.expect('(')?;
+                let mut args = Vec::new();
+                while !self.eat(')')? {
+                    args.push(self.value()?);
+                    self.eat(',')?;
+                }
+                Ok(Value::Call(args))
+            }
+            _ => Err(self.lexer.error("expected a value")),
+        }
+    }
+
+    /// Reads table entries after the opening brace.
+    fn table(&mut self) -> Result<Value, ConvertError> {
+        let mut entries = Vec::new();
+        while !self.eat('}')? {
+            let key = if self.eat('[')? {
+                let key = match self.next()? {
+                    Token::String(key) => Key::Name(key),
+                    Token::Number(key) => Key::Number(key),
+                    Token::Name(name) => self.char_call(name)?,
+                    _ => return Err(self.lexer.error("expected a string or number key")),
+                };
+                self.expect(']')?;
+                self.expect('=')?;
+                Some(key)
+            } else if let Some(Token::Name(name)) = self.peek()?.cloned()
+                && self.lookahead_is_assignment()?
+            {
+                self.expect('=')?;
+                Some(Key::Name(name))
+            } else {
+                None
+            };
+            entries.push((key, self.value()?));
+            if !self.eat(',')? && !self.eat(';')? {
+                self.expect('}')?;
+                break;
+            }
+        }
+        Ok(Value::Table(entries))
+    }
+
+    /// Reads a `string.char(...)` or `utf8.char(...)` key after its first
+    /// name, as written with [`KeyEscape`](crate::formatter::KeyEscape), and
+    /// returns the codepoint it spells.
+    fn char_call(&mut self, mut name: String) -> Result<Key, ConvertError> {
+        while self.eat('.')? {
+            name.push('.');
+            name.push_str(&self.name()?);
+        }
+        self.expect('(')?;
+        let mut args = Vec::new();
+        while !self.eat(')')? {
+            match self.next()? {
+                Token::Number(number) if (0.0..=u32::MAX as f64).contains(&number) && number.fract() == 0.0 => {
+                    args.push(number as u32)
+                }
+                _ => return Err(self.lexer.error(format!("expected a character code in `{name}`"))),
+            }
+            self.eat(',')?;
+        }
+        let id = match (name.as_str(), args.as_slice()) {
+            ("utf8.char", [id]) => Some(*id),
+            ("string.char", bytes) => lua_utf8_decode(bytes),
+            _ => return Err(self.lexer.error(format!("unsupported key `{name}(...)`"))),
+        };
+        let id = id.ok_or_else(|| self.lexer.error(format!("`{name}` key is not a single character")))?;
+        Ok(Key::Number(id as f64))
+    }
+
+    /// Skips to the `end` closing a block that was just opened, e.g. by
+    /// `function`, and returns the tokens skipped.
+    fn skip_block(&mut self) -> Result<Vec<Token>, ConvertError> {
+        let mut depth = 1;
+        let mut tokens = Vec::new();
+        while depth > 0 {
+            let token = self.next()?;
+            match &token {
+                // `for` and `while` open their block with `do`.
+                Token::Name(keyword) if matches!(keyword.as_str(), "function" | "if" | "do") => depth += 1,
+                Token::Name(keyword) if keyword == "end" => depth -= 1,
+                _ => {}
+            }
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    /// With a name peeked, consumes it if it is followed by `=`.
+    fn lookahead_is_assignment(&mut self) -> Result<bool, ConvertError> {
+        let saved = (self.lexer.position, self.peeked.clone());
+        self.peeked = None;
+        if self.eat('=')? {
+            self.peeked = Some((saved.0, Token::Symbol('=')));
+            return Ok(true);
+        }
+        self.lexer.position = saved.0;
+        self.peeked = saved.1;
+        Ok(false)
+    }
+
+    /// Reads the whole module and returns the table it returns. Both
+    /// `return { ... }` and `local Font = {}` followed by field assignments
+    /// are understood.
+    fn module(&mut self) -> Result<Value, ConvertError> {
+        let mut named: Option<(String, Entries)> = None;
+        loop {
+            match self.next()? {
+                Token::Name(keyword) if keyword == "return" => {
+                    let value = match self.peek()? {
+                        Some(Token::Name(name)) if named.as_ref().is_some_and(|(table, _)| table == name) => {
+                            self.next()?;
+                            let (_, entries) = named.take().unwrap_or_default();
+                            Value::Table(entries)
+                        }
+                        _ => self.value()?,
+                    };
+                    self.annotation()?;
+                    return Ok(value);
+                }
+                Token::Name(keyword) if keyword == "local" => {
+                    if self.peek()? == Some(&Token::Name("function".to_string())) {
+                        self.next()?;
+                        let function = self.name()?;
+                        let body = self.skip_block()?;
+                        if function == "decodeGlyphs" {
+                            // Each glyph field is read by a `local name = read()`.
+                            let fields = body.windows(6).filter_map(|window| match window {
+                                [Token::Name(local), Token::Name(name), Token::Symbol('='), Token::Name(read), Token::Symbol('('), Token::Symbol(')')]
+                                    if local == "local" && read == "read" =>
+                                {
+                                    Some(name.clone())
+                                }
+                                _ => None,
+                            });
+                            self.packed_fields = Some(fields.collect());
+                        }
+                        continue;
+                    }
+                    let name = self.name()?;
+                    self.expect('=')?;
+                    let Value::Table(entries) = self.value()? else {
+                        return Err(self.lexer.error("expected a table"));
+                    };
+                    self.annotation()?;
+                    named = Some((name, entries));
+                }
+                Token::Name(keyword) if keyword == "export" => {
+                    if self.name()? != "type" {
+                        return Err(self.lexer.error("expected `type`"));
+                    }
+                    self.name()?;
+                    self.expect('=')?;
+                    self.skip_type()?;
+                }
+                Token::Name(table) if named.as_ref().is_some_and(|(name, _)| *name == table) => {
+                    let key = if self.eat('.')? {
+                        self.name()?
+                    } else {
+                        self.expect('[')?;
+                        let Token::String(key) = self.next()? else {
+                            return Err(self.lexer.error("expected a string key"));
+                        };
+                        self.expect(']')?;
+                        key
+                    };
+                    self.expect('=')?;
+                    let value = self.value()?;
+                    if let Some((_, ref mut entries)) = named {
+                        entries.push((Some(Key::Name(key)), value));
+                    }
+                }
+                // Functions such as the generated `MeasureText` hold no data.
+                Token::Name(keyword) if keyword == "function" => {
+                    self.skip_block()?;
+                }
+                Token::Symbol(';') => {}
+                _ => return Err(self.lexer.error("expected `return`, `local` or a field assignment")),
+            }
+        }
+    }
+}
+
+fn number(value: &Value, what: &str) -> Result<f64, ConvertError> {
+    match value {
+        Value::Number(number) => Ok(*number),
+        _ => Err(ConvertError::Lua(format!("{what} is not a number"))),
+    }
+}
+
+/// Reads an `(x, y)` pair in any [`VectorStyle`](crate::formatter::VectorStyle).
+fn vector(value: &Value, what: &str) -> Result<Pair, ConvertError> {
+    let (x, y) = match value {
+        Value::Call(args) if args.len() == 2 => (&args[0], &args[1]),
+        Value::Table(_) => match (value.field("x"), value.field("y"), value.items().as_slice()) {
+            (Some(x), Some(y), _) => (x, y),
+            (None, None, [x, y]) => (*x, *y),
+            _ => return Err(ConvertError::Lua(format!("{what} is missing x or y"))),
+        },
+        _ => return Err(ConvertError::Lua(format!("{what} is not a vector"))),
+    };
+    Ok((number(x, what)? as f32, number(y, what)? as f32))
+}
+
+/// Reads a glyph's `(x, y)` position and `(width, height)` if `value` is a
+/// [`RectStyle`](crate::formatter::RectStyle) rect rather than a pair.
+fn rect(value: &Value) -> Option<Result<(Pair, Pair), ConvertError>> {
+    let values: Vec<&Value> = match value {
+        Value::Call(args) if args.len() == 4 => args.iter().collect(),
+        Value::Table(entries) if entries.len() == 4 && entries.iter().all(|(key, _)| key.is_none()) => value.items(),
+        _ => return None,
+    };
+    let read = || {
+        let [x, y, width, height] = [0, 1, 2, 3].map(|index| number(values[index], "rect").map(|value| value as f32));
+        Ok(((x?, y?), (width?, height?)))
+    };
+    Some(read())
+}
+
+/// The codepoint a `Characters` or `Kerning` key refers to.
+fn codepoint(key: &Option<Key>) -> Result<u32, ConvertError> {
+    match key {
+        Some(Key::Number(id)) => Ok(*id as u32),
+        // Older versions wrote carriage return and NUL as an empty key; BMFont
+        // exports usually contain the former.
+        Some(Key::Name(name)) if name.is_empty() => Ok(13),
+        Some(Key::Name(name)) => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c as u32),
+                _ => Err(ConvertError::Lua(format!("{name:?} is not a single character"))),
+            }
+        }
+        None => Err(ConvertError::Lua("character entry without a key".to_string())),
+    }
+}
+
+/// The one codepoint `bytes` encode in the UTF-8 of Lua's `utf8.char`, which
+/// also covers surrogates and values above U+10FFFF.
+fn lua_utf8_decode(bytes: &[u32]) -> Option<u32> {
+    let (&lead, rest) = bytes.split_first()?;
+    if lead < 0x80 {
+        return rest.is_empty().then_some(lead);
+    }
+    let length = (lead as u8).leading_ones() as usize;
+    if lead > 0xff || !(2..=6).contains(&length) || rest.len() != length - 1 {
+        return None;
+    }
+    rest.iter().try_fold(lead & (0x7f >> length), |id, byte| {
+        (byte & 0xc0 == 0x80 && *byte <= 0xff).then_some(id << 6 | byte & 0x3f)
+    })
+}
+
+/// A lookup of required numeric fields if `glyph` is written with
+/// [`VectorStyle::Flattened`](crate::formatter::VectorStyle::Flattened).
+fn flattened(glyph: &Value) -> Option<impl Fn(&str) -> Result<f64, ConvertError> + '_> {
+    let is_flattened = matches!(glyph, Value::Table(entries) if entries.iter().all(|(key, _)| key.is_some()));
+    is_flattened.then_some(move |name: &str| match glyph.field(name) {
+        Some(value) => number(value, name),
+        None => Err(ConvertError::Lua(format!("glyph is missing {name}"))),
+    })
+}
+
+fn character(size: Pair, position: Pair, offset: Pair, advance: f64, page: Option<f64>, channel: Option<f64>) -> Character {
+    Character {
+        size: CharacterSize {
+            width: size.0,
+            height: size.1,
+        },
+        position: CharacterPosition {
+            x: position.0,
+            y: position.1,
+        },
+        offset: CharacterOffset { x: offset.0, y: offset.1 },
+        advance: advance as f32,
+        // Page numbers are 1-based positions in `Pages`.
+        page: page.map_or(0, |page| (page as u32).saturating_sub(1)),
+        channel: channel.map_or(ALL_CHANNELS, |channel| channel as u8),
+        letter: None,
+    }
+}
+
+/// Replaces a packed `Characters = decodeGlyphs("...")` in `table` with the
+/// glyph tables `decodeGlyphs` would build from it, reading `fields` for
+/// each glyph after the distance from the previous codepoint.
+fn unpack_characters(table: &mut Value, fields: &[String]) -> Result<(), ConvertError> {
+    let Value::Table(entries) = table else {
+        return Ok(());
+    };
+    let Some((_, characters)) = entries.iter_mut().find(|(key, _)| matches!(key, Some(Key::Name(key)) if key == "Characters")) else {
+        return Ok(());
+    };
+    let Value::Call(args) = characters else {
+        return Ok(());
+    };
+    let [Value::String(data)] = args.as_slice() else {
+        return Err(ConvertError::Lua("decodeGlyphs takes one string".to_string()));
+    };
+    let mut digits = data.bytes().map(|byte| {
+        PACKED_DIGITS
+            .iter()
+            .position(|&digit| digit == byte)
+            .ok_or_else(|| ConvertError::Lua(format!("{:?} is not a packed digit", byte as char)))
+    });
+    let mut read = || -> Result<Option<f64>, ConvertError> {
+        let (mut value, mut shift) = (0u64, 0);
+        loop {
+            let Some(digit) = digits.next().transpose()? else {
+                return if shift == 0 { Ok(None) } else { Err(ConvertError::Lua("packed glyphs are truncated".to_string())) };
+            };
+            value |= ((digit & 31) as u64).checked_shl(shift).unwrap_or_default();
+            shift += 5;
+            if digit < 32 {
+                // The sign is in the lowest bit, as `push_vlq` writes it.
+                let magnitude = (value >> 1) as f64;
+                return Ok(Some(if value & 1 == 1 { -magnitude - 1.0 } else { magnitude }));
+            }
+        }
+    };
+    let mut glyphs = Vec::new();
+    let mut id = 0.0;
+    while let Some(step) = read()? {
+        id += step;
+        let mut values = Vec::with_capacity(fields.len());
+        for _ in fields {
+            values.push(read()?.ok_or_else(|| ConvertError::Lua("packed glyphs are truncated".to_string()))?);
+        }
+        let field = |name: &str| fields.iter().position(|field| field == name).map(|index| Value::Number(values[index]));
+        let pair = |x: &str, y: &str| Value::Call(vec![field(x).unwrap_or(Value::Number(0.0)), field(y).unwrap_or(Value::Number(0.0))]);
+        let advance = field("advance").unwrap_or(Value::Number(0.0));
+        let glyph = if field("width").is_some() {
+            let mut items = vec![pair("width", "height"), pair("x", "y"), pair("xOffset", "yOffset"), advance];
+            items.extend(field("page"));
+            items.extend(field("channel"));
+            Value::Table(items.into_iter().map(|value| (None, value)).collect())
+        } else {
+            advance
+        };
+        glyphs.push((Some(Key::Number(id)), glyph));
+    }
+    *characters = Value::Table(glyphs);
+    Ok(())
+}
+
+/// A `Shared` entry: size, offset and advance.
+type SharedRecord = (Pair, Pair, f64);
+
+fn font_from_table(table: &Value) -> Result<Font, ConvertError> {
+    let mut font = Font::default();
+    let field_number = |name| table.field(name).map(|value| number(value, name)).transpose();
+
+    font.metrics.size = field_number("Size")?.ok_or(ConvertError::MissingInfo)? as i32;
+    let texture = match (table.field("TextureSize"), field_number("TextureWidth")?, field_number("TextureHeight")?) {
+        (Some(texture), _, _) => Some(vector(texture, "TextureSize")?),
+        (None, Some(width), Some(height)) => Some((width as f32, height as f32)),
+        _ => None,
+    };
+    if let (Some(line_height), Some(base), Some((scale_w, scale_h))) =
+        (field_number("LineHeight")?, field_number("Base")?, texture)
+    {
+        font.metrics.common = Some(CommonMetrics {
+            line_height: line_height as f32,
+            base: base as f32,
+            scale_w: scale_w as u32,
+            scale_h: scale_h as u32,
+        });
+    }
+
+    if let Some(pages) = table.field("Pages") {
+        for (id, file) in pages.items().into_iter().enumerate() {
+            let Value::String(file) = file else {
+                return Err(ConvertError::Lua("page file is not a string".to_string()));
+            };
+            font.metrics.pages.insert(id as u32, file.clone());
+        }
+    }
+
+    if let Some(field) = table.field("DistanceField") {
+        let Some(Value::String(field_type)) = field.field("Type") else {
+            return Err(ConvertError::Lua("DistanceField.Type is not a string".to_string()));
+        };
+        let range = field.field("Range").map(|value| number(value, "DistanceField.Range")).transpose()?;
+        font.metrics.distance_field = Some(DistanceField {
+            field_type: field_type.clone(),
+            range: range.unwrap_or_default() as f32,
+        });
+    }
+
+    let list = |name: &str| -> Result<Vec<i32>, ConvertError> {
+        let values = table.field(name).map(Value::items).unwrap_or_default();
+        values.into_iter().map(|value| Ok(number(value, name)? as i32)).collect()
+    };
+    if let Ok(padding) = list("Padding")?.try_into() {
+        font.metrics.glyph_spacing.padding = padding;
+    }
+    if let Ok(spacing) = list("Spacing")?.try_into() {
+        font.metrics.glyph_spacing.spacing = spacing;
+    }
+    font.metrics.glyph_spacing.outline = field_number("Outline")?.unwrap_or_default() as i32;
+
+    let shared = table
+        .field("Shared")
+        .map(|shared| {
+            shared
+                .items()
+                .into_iter()
+                .map(|record| match (flattened(record), record.items().as_slice()) {
+                    (Some(field), _) => Ok((
+                        (field("Width")? as f32, field("Height")? as f32),
+                        (field("XOffset")? as f32, field("YOffset")? as f32),
+                        field("Advance")?,
+                    )),
+                    (None, [size, offset, advance]) => {
+                        Ok((vector(size, "size")?, vector(offset, "offset")?, number(advance, "advance")?))
+                    }
+                    _ => Err(ConvertError::Lua("shared metrics need size, offset and advance".to_string())),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let Some(Value::Table(characters)) = table.field("Characters") else {
+        return Ok(font);
+    };
+    for (key, glyph) in characters {
+        let id = codepoint(key)?;
+        if matches!(glyph.field("Synthesized"), Some(Value::Bool(true))) {
+            font.synthesized.insert(id);
+        }
+        if let Some(field) = flattened(glyph) {
+            let position = (field("X")? as f32, field("Y")? as f32);
+            let (size, offset, advance) = match &shared {
+                Some(shared) => {
+                    let index = field("Shared")? as usize;
+                    *index
+                        .checked_sub(1)
+                        .and_then(|index| shared.get(index))
+                        .ok_or_else(|| ConvertError::Lua(format!("shared index {index} out of range")))?
+                }
+                None => (
+                    (field("Width")? as f32, field("Height")? as f32),
+                    (field("XOffset")? as f32, field("YOffset")? as f32),
+                    field("Advance")?,
+                ),
+            };
+            let page = glyph.field("Page").map(|page| number(page, "Page")).transpose()?;
+            let channel = glyph.field("Channel").map(|channel| number(channel, "Channel")).transpose()?;
+            font.characters.insert(id, character(size, position, offset, advance, page, channel));
+            continue;
+        }
+        let values = glyph.items();
+        let lookup = |shared: &[SharedRecord], index: &Value| {
+            let index = number(index, "shared index")? as usize;
+            index
+                .checked_sub(1)
+                .and_then(|index| shared.get(index).copied())
+                .ok_or_else(|| ConvertError::Lua(format!("shared index {index} out of range")))
+        };
+        let rect = values.first().and_then(|value| rect(value)).transpose()?;
+        let (size, position, offset, advance, rest) = match (&shared, rect, values.as_slice()) {
+            (Some(shared), Some((position, size)), [_, index, rest @ ..]) => {
+                let (_, offset, advance) = lookup(shared, index)?;
+                (size, position, offset, advance, rest)
+            }
+            (Some(shared), None, [position, index, rest @ ..]) => {
+                let (size, offset, advance) = lookup(shared, index)?;
+                (size, vector(position, "position")?, offset, advance, rest)
+            }
+            (None, Some((position, size)), [_, offset, advance, rest @ ..]) => {
+                (size, position, vector(offset, "offset")?, number(advance, "advance")?, rest)
+            }
+            (None, None, [size, position, offset, advance, rest @ ..]) => (
+                vector(size, "size")?,
+                vector(position, "position")?,
+                vector(offset, "offset")?,
+                number(advance, "advance")?,
+                rest,
+            ),
+            _ => return Err(ConvertError::Lua(format!("glyph {id} has too few values"))),
+        };
+        let page = rest.first().map(|page| number(page, "page")).transpose()?;
+        // A channel always follows the page.
+        let channel = rest.get(1).map(|channel| number(channel, "channel")).transpose()?;
+        font.characters.insert(id, character(size, position, offset, advance, page, channel));
+    }
+
+    if let Some(Value::Table(kerning)) = table.field("Kerning") {
+        for (first, pairs) in kerning {
+            let Value::Table(pairs) = pairs else {
+                return Err(ConvertError::Lua("kerning entry is not a table".to_string()));
+            };
+            for (second, amount) in pairs {
+                let pair = (codepoint(first)?, codepoint(second)?);
+                font.metrics.kerning.insert(pair, number(amount, "kerning amount")? as f32);
+            }
+        }
+    }
+
+    Ok(font)
+}
+
+/// Parses a Lua module generated by [`render`](crate::formatter::render)
+/// back into a font. Values the Lua output does not carry, such as the
+/// original page ids, are reconstructed as well as possible.
+pub fn parse_lua(source: &str) -> Result<Font, ConvertError> {
+    let mut parser = Parser::new(source);
+    let mut table = parser.module().map_err(|e| e.with_line_column(source))?;
+    if let Some(fields) = &parser.packed_fields {
+        unpack_characters(&mut table, fields)?;
+    }
+    font_from_table(&table)
+}
+
+/// Reads and parses the Lua module at `filename`; see [`parse_lua`].
+pub fn parse_lua_file(filename: &str) -> Result<Font, ConvertError> {
+    let font = parse_lua(&read_source(filename)?)?;
+    info!(file = filename, glyphs = font.characters.len(), "read Lua module");
+    Ok(font)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use crate::formatter::{ModuleStyle, OutputOptions, VectorStyle, render};
+
+    /// Renders the example font with `options`, checks it verifies and reads
+    /// it back.
+    fn round_trip(options: &OutputOptions) -> serde_json::Value {
+        let font = example_font();
+        let output = render(&font, "font.fnt", options).unwrap();
+        crate::verify::verify_output(&font, &output, options).unwrap();
+        serde_json::to_value(parse_lua(&output).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn default_output_reads_back_as_the_font() {
+        let output = render(&example_font(), "font.fnt", &OutputOptions::default()).unwrap();
+        let parsed = parse_lua(&output).unwrap();
+        // The default glyph filter leaves out the carriage return.
+        assert_eq!(parsed.characters.len(), 95);
+        assert_eq!(parsed.metrics.kerning.len(), 265);
+        assert_eq!(parsed.characters[&65].position.x, 514.0);
+        assert_eq!(parsed.metrics.kerning[&(65, 34)], -1.0);
+    }
+
+    #[test]
+    fn every_layout_reads_back_the_same() {
+        let expected = round_trip(&OutputOptions::default());
+        let mut minified = OutputOptions::default();
+        minified.style.minified = true;
+        let layouts = [
+            minified,
+            OutputOptions {
+                vector_style: VectorStyle::Flattened,
+                ..Default::default()
+            },
+            OutputOptions {
+                packed: true,
+                ..Default::default()
+            },
+            OutputOptions {
+                module_style: ModuleStyle::NamedTable { name: "Font".into(), indexed: false },
+                ..Default::default()
+            },
+            OutputOptions {
+                module_style: ModuleStyle::NamedTable { name: "Font".into(), indexed: true },
+                ..Default::default()
+            },
+        ];
+        for options in layouts {
+            assert_eq!(round_trip(&options), expected, "{options:?}");
+        }
+    }
+
+    #[test]
+    fn truncated_packed_glyphs_are_refused() {
+        let options = OutputOptions {
+            packed: true,
+            ..Default::default()
+        };
+        let output = render(&example_font(), "font.fnt", &options).unwrap();
+        let truncated = output.replacen("\"),", "g\"),", 1);
+        assert!(matches!(parse_lua(&truncated), Err(ConvertError::Lua(_))));
+    }
+}
@@ -1,297 +1,3824 @@
-use egui::{Color32, RichText, Stroke};
-use quick_xml::Reader;
-use quick_xml::events::Event;
-use std::collections::BTreeMap;
-use std::fs::File;
-use std::io::Read;
-
-#[derive(Debug)]
-struct CharacterOffset {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
-struct CharacterSize {
-    width: i32,
-    height: i32,
-}
-
-#[derive(Debug)]
-struct CharacterPosition {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
-struct Character {
-    size: CharacterSize,
-    position: CharacterPosition,
-    offset: CharacterOffset,
-    advance: i32,
-}
-
-fn parse_fnt(
-    filename: &str,
-) -> Result<(i32, BTreeMap<u32, Character>), Box<dyn std::error::Error>> {
-    let mut file = File::open(filename)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-
-    let mut reader = Reader::from_str(&contents);
-    let mut characters = BTreeMap::new();
-    let mut buf = Vec::new();
-    let mut font_size = 0;
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Eof) => break,
-            Ok(Event::Empty(ref e)) if e.name().as_ref() == b"char" => {
-                let mut id = 0;
-                let mut width = 0;
-                let mut height = 0;
-                let mut x = 0;
-                let mut y = 0;
-                let mut xoffset = 0;
-                let mut yoffset = 0;
-                let mut xadvance = 0;
-
-                for attr in e.attributes() {
-                    let attr = attr?;
-                    match attr.key.as_ref() {
-                        b"id" => id = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"x" => x = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"y" => y = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"width" => width = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"height" => height = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"xoffset" => xoffset = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"yoffset" => yoffset = std::str::from_utf8(&attr.value)?.parse()?,
-                        b"xadvance" => xadvance = std::str::from_utf8(&attr.value)?.parse()?,
-                        _ => {}
-                    }
-                }
-
-                characters.insert(
-                    id,
-                    Character {
-                        size: CharacterSize { width, height },
-                        position: CharacterPosition { x, y },
-                        offset: CharacterOffset {
-                            x: xoffset,
-                            y: yoffset,
-                        },
-                        advance: xadvance,
+mod cli;
+mod commands;
+mod i18n;
+mod logging;
+mod theme;
+
+use egui::{Color32, Key, Modifiers, RichText, Stroke};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant};
+use tracing::info;
+use cli::{exit_code, find_project_config, is_png, parse_cli, preset_options, read_codepoint_map, read_font, read_icon_names, run_cli};
+use commands::{
+    Action, CLOSE_TAB_SHORTCUT, COPY_SHORTCUT, CommandPalette, NEW_TAB_SHORTCUT, OPEN_SHORTCUT, PALETTE_SHORTCUT, SAVE_SHORTCUTS,
+    fuzzy_score,
+};
+use i18n::{Language, set_language, tr, trf};
+use logging::{LogBuffer, log_to_buffer};
+use theme::{Palette, Theme, themed_button};
+use xml_to_lua::align::baseline_shifts;
+use xml_to_lua::batch::{
+    BatchProgress, BatchResult, Overwrite, convert_batch, convert_directory, convert_file_cached, module_name, output_file_name, source_name,
+    with_checksums, write_atomic, write_chunks,
+};
+use xml_to_lua::contact_sheet::{page_prefix, render_contact_sheet, write_contact_sheet};
+use xml_to_lua::coverage::{CoverageReport, ReportFormat};
+use xml_to_lua::diff::{FontDiff, LineChange, diff_lines, lua_diff};
+use xml_to_lua::formatter::{FORMATTERS, LuaChunks, check_module_name, output_bytes, render_chunks};
+use xml_to_lua::icons::IconNames;
+use xml_to_lua::layout::layout_text;
+use xml_to_lua::pack::{repack, split_channels};
+use xml_to_lua::page_paths::PageRule;
+use xml_to_lua::parse_cache::ParseCache;
+use xml_to_lua::parser::read_source;
+use xml_to_lua::rasterize::{RasterOptions, rasterize};
+use xml_to_lua::queue::{JobQueue, JobState};
+use xml_to_lua::remap::CodepointMap;
+use xml_to_lua::rojo::{self, module_path};
+use xml_to_lua::schema::check_schema_file;
+use xml_to_lua::stats::FontStats;
+use xml_to_lua::subset::Subset;
+use xml_to_lua::synthesize::{SpaceWidth, WhitespaceRules};
+use xml_to_lua::trim::{load_pages, trim_glyphs};
+use xml_to_lua::unicode_block::block_of;
+use xml_to_lua::merge::{ConflictPolicy, merge_files};
+use xml_to_lua::validate::{collect_warnings, missing_characters, pruned_kerning};
+use xml_to_lua::verify::{can_verify, verify_output};
+use xml_to_lua::writer::write_fnt_xml;
+use xml_to_lua::{
+    Character, CharacterOrder, ConvertError, Font, HeaderOptions, KeyEscape, ModuleStyle, OutputFormat, OutputOptions, Progress, RectStyle, Rounding, UvCoordinates, VectorStyle,
+    parse_fnt, render,
+};
+
+#[derive(Default)]
+struct ParsingStatus {
+    message: String,
+    status: Option<String>
+}
+
+fn glyph_label(id: u32) -> String {
+    match std::char::from_u32(id) {
+        Some(c) if !c.is_control() => c.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Whether glyph `id` passes the glyph table filter: the character itself, a
+/// decimal or hex codepoint, or part of a Unicode block name such as
+/// `cyrillic`.
+fn glyph_matches(id: u32, filter: &str) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() || glyph_label(id) == filter {
+        return true;
+    }
+
+    let hex = filter
+        .strip_prefix("0x")
+        .or_else(|| filter.strip_prefix("U+"))
+        .or_else(|| filter.strip_prefix("u+"));
+    match hex {
+        Some(digits) => u32::from_str_radix(digits, 16) == Ok(id),
+        None => filter.parse::<u32>() == Ok(id) || block_matches(id, filter),
+    }
+}
+
+fn block_matches(id: u32, filter: &str) -> bool {
+    // A single character only ever means that character.
+    filter.chars().nth(1).is_some()
+        && block_of(id).is_some_and(|block| block.to_lowercase().contains(&filter.to_lowercase()))
+}
+
+struct FontParserApp {
+    selected_file: Option<String>,
+    status: ParsingStatus,
+    theme: Theme,
+    language: Language,
+    options: OutputOptions,
+    function_name: String,
+    /// Preset files offered in the options, and the one last applied.
+    presets: Vec<PathBuf>,
+    preset: Option<PathBuf>,
+    /// Whether conversions are saved without a dialog, into
+    /// `quick_output_dir` or next to the input when that is unset.
+    quick_convert: bool,
+    quick_output_dir: Option<PathBuf>,
+    /// A quick convert or Rojo target that exists, waiting for the user to decide
+    /// whether to replace it.
+    confirm_overwrite: Option<PathBuf>,
+    /// What replacing `confirm_overwrite` with the preview changes, so
+    /// regenerating a font does not silently revert edits made to the file.
+    overwrite_diff: Option<Comparison>,
+    /// Where batch conversions are written; asked for on each run when unset.
+    batch_output_dir: Option<PathBuf>,
+    /// The `default.project.json` of the Rojo project previews can be saved
+    /// into, and the folder under it fonts go in.
+    rojo_project: Option<PathBuf>,
+    rojo_folder: String,
+    font: Option<Font>,
+    preview: Option<Preview>,
+    atlas: Option<Atlas>,
+    /// The glyph last clicked in the atlas, highlighted there and in the
+    /// glyph table, which scrolls to it once when `scroll_to_glyph` is set.
+    selected_glyph: Option<u32>,
+    scroll_to_glyph: bool,
+    sample_text: String,
+    subset_ranges: String,
+    subset_text: String,
+    /// Ranges edited for [`CharacterOrder::Priority`], kept while another
+    /// order is picked.
+    priority_ranges: String,
+    /// Characters the loaded font is checked against, e.g. every string the
+    /// game displays.
+    required_chars: String,
+    glyph_filter: String,
+    /// Whether the glyph table edits offsets and advances and deletes
+    /// glyphs, rather than only listing them as parsed.
+    editing_glyphs: bool,
+    /// Opens the glyph table once the newly picked font has loaded.
+    reveal_glyphs: bool,
+    warnings: Vec<String>,
+    batch_queue: JobQueue,
+    batch_exclude: String,
+    batch_threads: usize,
+    conflict_policy: ConflictPolicy,
+    history: Vec<HistoryEntry>,
+    /// Fonts to merge, primary first, with their scale factors.
+    merge_sources: Vec<(PathBuf, f32)>,
+    /// The old and the new version of a font to compare.
+    compare_inputs: [Option<PathBuf>; 2],
+    comparison: Option<Comparison>,
+    /// TrueType/OpenType font to generate a BMFont from.
+    ttf_file: Option<PathBuf>,
+    raster_options: RasterOptions,
+    /// The charset of `raster_options` as typed, e.g. `0x20-0x7E`.
+    ttf_charset: String,
+    input_dir: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    watch: Option<FileWatch>,
+    parse_failure: Option<ParseFailure>,
+    /// Fonts parsed by earlier jobs, reused while their files are unchanged
+    /// so converting again after changing options only formats.
+    parse_cache: Arc<ParseCache>,
+    job: Option<Job>,
+    batch_run: Option<BatchRun>,
+    /// Events logged by the converter, shown in the log panel.
+    log: LogBuffer,
+    /// Whether `font` was changed in the glyph editor, in which case it is
+    /// converted as is instead of being parsed again.
+    font_edited: bool,
+    edit_history: EditHistory,
+    /// Output size for the loaded font, keyed by the options it was rendered
+    /// with so it is only recomputed when they change.
+    size_estimate: Option<(String, Result<usize, String>)>,
+    /// The command palette, while it is open.
+    command_palette: Option<CommandPalette>,
+    /// The open fonts. The active one's state lives in the fields above,
+    /// and its entry here is empty until another tab is picked.
+    tabs: Vec<FontTab>,
+    active_tab: usize,
+}
+
+/// The state of a font open in a tab other than the active one: what was
+/// loaded, how it is exported, and any job still running for it, whose
+/// result is applied once the tab is picked again.
+#[derive(Default)]
+struct FontTab {
+    selected_file: Option<String>,
+    status: ParsingStatus,
+    options: OutputOptions,
+    subset_ranges: String,
+    subset_text: String,
+    font: Option<Font>,
+    preview: Option<Preview>,
+    atlas: Option<Atlas>,
+    selected_glyph: Option<u32>,
+    warnings: Vec<String>,
+    parse_failure: Option<ParseFailure>,
+    job: Option<Job>,
+    font_edited: bool,
+    edit_history: EditHistory,
+    size_estimate: Option<(String, Result<usize, String>)>,
+}
+
+/// Two versions of a font compared in the compare section, or an existing
+/// output and the preview about to replace it.
+struct Comparison {
+    /// `None` when a side does not read back as a font.
+    diff: Option<FontDiff>,
+    /// The Lua diff as lines near a change, each with its `-`, `+` or ` `
+    /// marker; `None` stands in for a run of unchanged lines.
+    lua_diff: Vec<Option<(char, String)>>,
+}
+
+/// Unchanged lines shown around each change in a Lua diff.
+const DIFF_CONTEXT: usize = 2;
+
+/// The lines of `lines` within `context` lines of a change, with each run of
+/// lines further away replaced by a single `None`.
+fn diff_excerpt(lines: &[LineChange], context: usize) -> Vec<Option<(char, String)>> {
+    let mut near = vec![false; lines.len()];
+    for (index, line) in lines.iter().enumerate() {
+        if !matches!(line, LineChange::Same(_)) {
+            let end = (index + context + 1).min(lines.len());
+            near[index.saturating_sub(context)..end].fill(true);
+        }
+    }
+
+    let mut excerpt = Vec::new();
+    for (line, near) in lines.iter().zip(near) {
+        let (marker, text) = match line {
+            LineChange::Same(text) => (' ', text),
+            LineChange::Removed(text) => ('-', text),
+            LineChange::Added(text) => ('+', text),
+        };
+        if near {
+            excerpt.push(Some((marker, text.to_string())));
+        } else if excerpt.last().is_none_or(Option::is_some) {
+            excerpt.push(None);
+        }
+    }
+    excerpt
+}
+
+/// The glyphs, metrics and lines that differ between the two sides of
+/// `comparison`.
+fn comparison_ui(ui: &mut egui::Ui, comparison: &Comparison, palette: &Palette) {
+    let Some(ref diff) = comparison.diff else {
+        ui.weak(tr("The existing file does not read back as a font; only the lines that change are shown"));
+        lua_diff_ui(ui, comparison, palette);
+        return;
+    };
+    if diff.is_empty() {
+        ui.label(RichText::new(tr("✅ The fonts have the same glyphs and metrics")).color(palette.success));
+    } else {
+        ui.label(trf(
+            "{0} added, {1} removed and {2} changed glyphs, {3} kerning pairs changed",
+            &[&diff.added.len(), &diff.removed.len(), &diff.changed.len(), &diff.kerning_changes],
+        ));
+    }
+
+    if !diff.metrics.is_empty() {
+        egui::Grid::new("compare_metrics").num_columns(2).striped(true).show(ui, |ui| {
+            for change in &diff.metrics {
+                ui.label(tr(change.field));
+                ui.label(format!("{} → {}", change.old, change.new));
+                ui.end_row();
+            }
+        });
+    }
+
+    for (title, ids, color) in [("➕ Added ({0})", &diff.added, palette.success), ("➖ Removed ({0})", &diff.removed, palette.error)] {
+        if ids.is_empty() {
+            continue;
+        }
+        egui::CollapsingHeader::new(trf(title, &[&ids.len()])).id_salt(title).default_open(false).show(ui, |ui| {
+            let glyphs: Vec<String> = ids.iter().map(|id| format!("{} U+{id:04X}", glyph_label(*id))).collect();
+            ui.label(RichText::new(glyphs.join("   ")).color(color));
+        });
+    }
+
+    if !diff.changed.is_empty() {
+        egui::CollapsingHeader::new(trf("✏ Changed ({0})", &[&diff.changed.len()])).id_salt("✏ Changed").default_open(false).show(ui, |ui| {
+            egui::ScrollArea::vertical().id_salt("compare_changed").max_height(200.0).show(ui, |ui| {
+                egui::Grid::new("compare_changed_grid").num_columns(3).striped(true).show(ui, |ui| {
+                    for change in &diff.changed {
+                        for (index, field) in change.fields.iter().enumerate() {
+                            if index == 0 {
+                                ui.label(format!("{} U+{:04X}", glyph_label(change.id), change.id));
+                            } else {
+                                ui.label("");
+                            }
+                            ui.label(tr(field.field));
+                            ui.label(format!("{} → {}", field.old, field.new));
+                            ui.end_row();
+                        }
+                    }
+                });
+            });
+        });
+    }
+
+    lua_diff_ui(ui, comparison, palette);
+}
+
+/// The changed lines of `comparison` with a few lines around each change.
+fn lua_diff_ui(ui: &mut egui::Ui, comparison: &Comparison, palette: &Palette) {
+    egui::CollapsingHeader::new(tr("📜 Lua diff")).id_salt("📜 Lua diff").default_open(false).show(ui, |ui| {
+        if comparison.lua_diff.iter().all(Option::is_none) {
+            ui.weak(tr("The Lua modules are identical"));
+            return;
+        }
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::both().id_salt("compare_lua").max_height(300.0).show_rows(
+            ui,
+            row_height,
+            comparison.lua_diff.len(),
+            |ui, rows| {
+                for line in &comparison.lua_diff[rows] {
+                    match line {
+                        Some((marker, text)) => {
+                            let color = match marker {
+                                '-' => palette.error,
+                                '+' => palette.success,
+                                _ => palette.text,
+                            };
+                            ui.label(RichText::new(format!("{marker} {text}")).monospace().color(color));
+                        }
+                        None => {
+                            ui.weak(RichText::new("…").monospace());
+                        }
+                    }
+                }
+            },
+        );
+    });
+}
+
+/// The glyphs an edit in the glyph table replaced, each with its previous
+/// state or `None` if the edit added it.
+type GlyphEdit = Vec<(u32, Option<Character>)>;
+
+/// Undo and redo stacks for the glyph editor.
+#[derive(Default)]
+struct EditHistory {
+    undo: Vec<GlyphEdit>,
+    redo: Vec<GlyphEdit>,
+    /// The value being dragged, whose changes on every frame of the drag are
+    /// a single edit.
+    dragging: Option<egui::Id>,
+}
+
+impl EditHistory {
+    /// Edits kept for undo; the oldest are dropped beyond this.
+    const LIMIT: usize = 100;
+
+    fn record(&mut self, edit: GlyphEdit) {
+        if self.undo.len() == Self::LIMIT {
+            self.undo.remove(0);
+        }
+        self.undo.push(edit);
+        self.redo.clear();
+    }
+
+    /// Reverts the last edit, returning whether there was one.
+    fn undo(&mut self, characters: &mut BTreeMap<u32, Character>) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+        self.redo.push(swap_glyphs(characters, edit));
+        true
+    }
+
+    /// Reapplies the last undone edit, returning whether there was one.
+    fn redo(&mut self, characters: &mut BTreeMap<u32, Character>) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push(swap_glyphs(characters, edit));
+        true
+    }
+}
+
+/// Puts the glyphs of `edit` into `characters` and returns the ones they
+/// replaced, which undo the swap.
+fn swap_glyphs(characters: &mut BTreeMap<u32, Character>, edit: GlyphEdit) -> GlyphEdit {
+    edit.into_iter()
+        .map(|(id, glyph)| {
+            let previous = match glyph {
+                Some(glyph) => characters.insert(id, glyph),
+                None => characters.remove(&id),
+            };
+            (id, previous)
+        })
+        .collect()
+}
+
+/// A change to the batch queue asked for from one of its rows.
+enum QueueAction {
+    /// Move the entry at the first index to the second.
+    Move(usize, usize),
+    Remove(usize),
+    Rerun(usize),
+}
+
+/// A batch conversion running in the background.
+struct BatchRun {
+    output_dir: PathBuf,
+    started: Instant,
+    total: usize,
+    completed: usize,
+    /// The file most recently started.
+    current: Option<PathBuf>,
+    messages: mpsc::Receiver<BatchMessage>,
+}
+
+enum BatchMessage {
+    Started { input: PathBuf, total: usize },
+    Finished { result: Box<BatchResult>, completed: usize },
+    /// The run is over; `Err` if the folder could not be read.
+    Done(Result<(), String>),
+}
+
+impl BatchRun {
+    /// Converts every font under `root`, or the `queue` when there is no
+    /// root, into `output_dir`.
+    fn spawn(
+        ctx: &egui::Context,
+        root: Option<PathBuf>,
+        queue: Vec<PathBuf>,
+        output_dir: PathBuf,
+        exclude: Option<String>,
+        options: OutputOptions,
+        threads: usize,
+    ) -> Self {
+        let (sender, messages) = mpsc::channel();
+        let ctx = ctx.clone();
+        let output = output_dir.clone();
+        std::thread::spawn(move || {
+            let send = |message| {
+                if sender.send(message).is_ok() {
+                    ctx.request_repaint();
+                }
+            };
+            let progress = |event: BatchProgress<'_>| {
+                send(match event {
+                    BatchProgress::Started { input, total } => BatchMessage::Started {
+                        input: input.to_path_buf(),
+                        total,
                     },
+                    BatchProgress::Finished { result, completed, .. } => BatchMessage::Finished {
+                        result: Box::new(result.clone()),
+                        completed,
+                    },
+                })
+            };
+            let done = match root {
+                Some(root) => convert_directory(&root, &output, exclude.as_deref(), &options, threads, progress)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                None => {
+                    convert_batch(&queue, &output, &options, threads, progress);
+                    Ok(())
+                }
+            };
+            send(BatchMessage::Done(done));
+        });
+        Self {
+            output_dir,
+            started: Instant::now(),
+            total: 0,
+            completed: 0,
+            current: None,
+            messages,
+        }
+    }
+}
+
+/// What a [`Job`] does once the font is parsed.
+#[derive(Clone, PartialEq)]
+enum JobKind {
+    /// Only load the font, e.g. after a file is picked.
+    Load,
+    /// Also render it into a [`Preview`].
+    Convert { then: AfterConvert },
+}
+
+/// What happens to a [`Preview`] once it is rendered.
+#[derive(Clone, PartialEq)]
+enum AfterConvert {
+    /// It is only shown.
+    Show,
+    /// It is copied to the clipboard.
+    Copy,
+    /// It is saved, asking where.
+    Save,
+    /// It is saved over this file, as when a conversion is re-run from the
+    /// history.
+    SaveTo(PathBuf),
+}
+
+/// Parsing and rendering running on a worker thread, so that large fonts do
+/// not freeze the window.
+struct Job {
+    file: String,
+    kind: JobKind,
+    started: Instant,
+    /// The latest parse progress reported by the worker.
+    progress: Progress,
+    messages: mpsc::Receiver<JobMessage>,
+}
+
+enum JobMessage {
+    Progress(Progress),
+    Done(Box<JobOutput>),
+}
+
+struct JobOutput {
+    font: Result<Font, ParseFailure>,
+    warnings: Vec<String>,
+    /// The rendered output, for [`JobKind::Convert`] jobs.
+    output: Option<Result<String, ConvertError>>,
+}
+
+impl Job {
+    /// Parses `file` on a worker thread, unless `cache` holds it, or uses
+    /// `edited` instead when the loaded font has been changed in the glyph
+    /// editor.
+    fn spawn(
+        ctx: &egui::Context,
+        cache: &Arc<ParseCache>,
+        file: String,
+        kind: JobKind,
+        options: OutputOptions,
+        edited: Option<Font>,
+    ) -> Self {
+        let (sender, messages) = mpsc::channel();
+        let ctx = ctx.clone();
+        let cache = Arc::clone(cache);
+        let input = file.clone();
+        let renders = kind != JobKind::Load;
+        std::thread::spawn(move || {
+            // Only whole percents are worth a repaint.
+            let mut reported = None;
+            let progress = |progress| {
+                let percent = match progress {
+                    Progress::Fraction(fraction) => Some((fraction * 100.0) as u32),
+                    Progress::Indeterminate => None,
+                };
+                if reported != Some(percent) && sender.send(JobMessage::Progress(progress)).is_ok() {
+                    reported = Some(percent);
+                    ctx.request_repaint();
+                }
+            };
+            let font = match edited {
+                Some(font) => Ok(font),
+                None if options.check_schema => check_schema_file(&input).and_then(|()| {
+                    cache.parse(Path::new(&input), options.lenient, || read_font(Path::new(&input), options.lenient, progress))
+                }),
+                None => cache.parse(Path::new(&input), options.lenient, || read_font(Path::new(&input), options.lenient, progress)),
+            };
+            let output = match font {
+                Ok(font) => JobOutput {
+                    warnings: collect_warnings(&font),
+                    output: renders
+                        .then(|| with_checksums(&options, &font, &[&input]).and_then(|options| render(&font, &source_name(&[&input]), &options))),
+                    font: Ok(font),
+                },
+                Err(error) => JobOutput {
+                    font: Err(ParseFailure::new(&input, error)),
+                    warnings: Vec::new(),
+                    output: None,
+                },
+            };
+            // The app may have started another job and dropped this one.
+            if sender.send(JobMessage::Done(Box::new(output))).is_ok() {
+                ctx.request_repaint();
+            }
+        });
+        Self {
+            file,
+            kind,
+            started: Instant::now(),
+            progress: Progress::Indeterminate,
+            messages,
+        }
+    }
+}
+
+/// A font that failed to parse, with the offending source line if known.
+struct ParseFailure {
+    error: ConvertError,
+    excerpt: Option<String>,
+}
+
+impl ParseFailure {
+    fn new(file: &str, error: ConvertError) -> Self {
+        let excerpt = error
+            .location()
+            .and_then(|location| location.line_column)
+            .and_then(|(line, _)| read_source(file).ok()?.lines().nth(line - 1).map(|line| line.trim().to_string()));
+        Self { error, excerpt }
+    }
+}
+
+/// Reconverts `input` into `output` whenever it changes on disk.
+struct FileWatch {
+    input: PathBuf,
+    output: PathBuf,
+    _watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    /// Time and outcome of the most recent conversion.
+    last_conversion: Option<(String, Result<(), String>)>,
+}
+
+impl FileWatch {
+    fn new(ctx: &egui::Context, input: PathBuf, output: PathBuf) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            if sender.send(event).is_ok() {
+                ctx.request_repaint();
+            }
+        })?;
+        watcher.watch(watch_directory(&input), RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            input,
+            output,
+            _watcher: watcher,
+            events,
+            last_conversion: None,
+        })
+    }
+}
+
+/// How many bytes `chunks` takes on disk, its parts included.
+fn chunks_size(chunks: &LuaChunks, options: &OutputOptions) -> usize {
+    let parts = chunks.parts.iter().map(|(_, source)| output_bytes(source, options).len());
+    output_bytes(&chunks.index, options).len() + parts.sum::<usize>()
+}
+
+/// Converted output waiting to be saved.
+struct Preview {
+    input: String,
+    output: String,
+    glyph_count: usize,
+}
+
+/// Texture pages of the loaded font, read from next to the `.fnt` file.
+struct Atlas {
+    pages: BTreeMap<u32, Result<egui::TextureHandle, String>>,
+    page: u32,
+    /// Screen points per texel, or `None` to fit the page to the view.
+    zoom: Option<f32>,
+    /// Where the page's top-left corner is drawn, relative to the view's.
+    pan: egui::Vec2,
+}
+
+impl Atlas {
+    fn load(ctx: &egui::Context, font: &Font, fnt_path: &Path) -> Self {
+        let directory = fnt_path.parent().unwrap_or(Path::new(""));
+        let pages = font
+            .metrics
+            .pages
+            .iter()
+            .map(|(id, file)| (*id, load_texture(ctx, &directory.join(file))))
+            .collect();
+        Self {
+            pages,
+            page: font.metrics.pages.keys().next().copied().unwrap_or_default(),
+            zoom: None,
+            pan: egui::Vec2::ZERO,
+        }
+    }
+}
+
+/// Height of the atlas view, which fitted pages are scaled to.
+const ATLAS_VIEW_HEIGHT: f32 = 320.0;
+const ATLAS_MIN_ZOOM: f32 = 0.05;
+const ATLAS_MAX_ZOOM: f32 = 32.0;
+
+fn load_texture(ctx: &egui::Context, path: &Path) -> Result<egui::TextureHandle, String> {
+    let image = image::open(path).map_err(|e| format!("{}: {e}", path.display()))?.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    let image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    Ok(ctx.load_texture(path.display().to_string(), image, egui::TextureOptions::NEAREST))
+}
+
+const HISTORY_LIMIT: usize = 50;
+
+/// Glyphs per part module when splitting is first turned on.
+const DEFAULT_CHUNK_SIZE: usize = 500;
+
+const HISTORY_KEY: &str = "history";
+
+const SETTINGS_KEY: &str = "settings";
+
+/// Preferences restored on the next launch. The window size is persisted by
+/// eframe itself.
+#[derive(Serialize, Deserialize)]
+struct Settings {
+    options: OutputOptions,
+    function_name: String,
+    subset_ranges: String,
+    subset_text: String,
+    batch_threads: usize,
+    input_dir: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    #[serde(default)]
+    required_chars: String,
+    #[serde(default)]
+    presets: Vec<PathBuf>,
+    #[serde(default)]
+    theme: Theme,
+    #[serde(default)]
+    language: Language,
+    #[serde(default)]
+    quick_convert: bool,
+    #[serde(default)]
+    quick_output_dir: Option<PathBuf>,
+    #[serde(default)]
+    batch_output_dir: Option<PathBuf>,
+    #[serde(default)]
+    priority_ranges: Option<String>,
+    #[serde(default)]
+    rojo_project: Option<PathBuf>,
+    #[serde(default)]
+    rojo_folder: Option<String>,
+}
+
+/// A conversion saved from the GUI, for reopening its input or running it
+/// again.
+#[derive(Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    input: String,
+    output: String,
+    timestamp: String,
+    /// The settings it was converted with. Entries recorded before these
+    /// were kept have none and cannot be re-run.
+    #[serde(default)]
+    options: Option<OutputOptions>,
+    /// Why saving failed, or `None` when it succeeded.
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl FontParserApp {
+    /// The app with the settings of the last session, loading `file` right
+    /// away when it was launched with one; its output then defaults to the
+    /// folder of `file`.
+    fn new(cc: &eframe::CreationContext<'_>, log: LogBuffer, file: Option<PathBuf>) -> Self {
+        let mut app = Self { log, ..Self::default() };
+        if let Some(storage) = cc.storage {
+            app.history = eframe::get_value(storage, HISTORY_KEY).unwrap_or_default();
+            if let Some(settings) = eframe::get_value::<Settings>(storage, SETTINGS_KEY) {
+                app.options = settings.options;
+                app.function_name = settings.function_name;
+                app.subset_ranges = settings.subset_ranges;
+                app.subset_text = settings.subset_text;
+                app.batch_threads = settings.batch_threads;
+                app.input_dir = settings.input_dir;
+                app.output_dir = settings.output_dir;
+                app.required_chars = settings.required_chars;
+                app.presets = settings.presets;
+                app.theme = settings.theme;
+                app.language = settings.language;
+                app.quick_convert = settings.quick_convert;
+                app.quick_output_dir = settings.quick_output_dir;
+                app.batch_output_dir = settings.batch_output_dir;
+                if let Some(priority_ranges) = settings.priority_ranges {
+                    app.priority_ranges = priority_ranges;
+                }
+                app.rojo_project = settings.rojo_project;
+                if let Some(rojo_folder) = settings.rojo_folder {
+                    app.rojo_folder = rojo_folder;
+                }
+            }
+        }
+        if let Some(file) = file {
+            app.output_dir = file.parent().map(Path::to_path_buf);
+            app.load_file(&cc.egui_ctx, file.display().to_string());
+        }
+        app
+    }
+
+    /// A single dropped font is loaded like a picked file; several are added to
+    /// the batch queue.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|input| {
+            input
+                .raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "fnt" || ext == "json" || ext == "gz"))
+                .collect()
+        });
+
+        match dropped.as_slice() {
+            [] => {}
+            [path] => self.load_file(ctx, path.display().to_string()),
+            _ => {
+                for path in dropped {
+                    self.batch_queue.push(path);
+                }
+                self.status.message = trf("📚 {0} files in the batch queue", &[&self.batch_queue.len()]);
+                self.status.status = None;
+            }
+        }
+    }
+
+    fn load_file(&mut self, ctx: &egui::Context, file: String) {
+        self.select_file(&file);
+        self.job = Some(Job::spawn(ctx, &self.parse_cache, file.clone(), JobKind::Load, self.options.clone(), None));
+        self.selected_file = Some(file);
+    }
+
+    /// Forgets everything about the previously selected font before `file`
+    /// is loaded.
+    fn select_file(&mut self, file: &str) {
+        self.status.message.clear();
+        self.status.status = None;
+        self.font = None;
+        self.atlas = None;
+        self.selected_glyph = None;
+        // Page ids mean something else in another font.
+        self.options.pages = None;
+        self.warnings.clear();
+        self.size_estimate = None;
+        self.input_dir = Path::new(file).parent().map(Path::to_path_buf);
+        self.font_edited = false;
+        self.edit_history = EditHistory::default();
+        self.reveal_glyphs = true;
+    }
+
+    /// Converts the input of a history entry again with the settings it was
+    /// converted with, saving over its output.
+    fn rerun(&mut self, ctx: &egui::Context, entry: HistoryEntry) {
+        self.select_file(&entry.input);
+        if let Some(options) = entry.options {
+            self.options = options;
+        }
+        let then = AfterConvert::SaveTo(PathBuf::from(entry.output));
+        self.job = Some(Job::spawn(ctx, &self.parse_cache, entry.input.clone(), JobKind::Convert { then }, self.options.clone(), None));
+        self.selected_file = Some(entry.input);
+    }
+
+    /// Exchanges the state of the active font with `tab`.
+    fn swap_tab(&mut self, tab: &mut FontTab) {
+        std::mem::swap(&mut self.selected_file, &mut tab.selected_file);
+        std::mem::swap(&mut self.status, &mut tab.status);
+        std::mem::swap(&mut self.options, &mut tab.options);
+        std::mem::swap(&mut self.subset_ranges, &mut tab.subset_ranges);
+        std::mem::swap(&mut self.subset_text, &mut tab.subset_text);
+        std::mem::swap(&mut self.font, &mut tab.font);
+        std::mem::swap(&mut self.preview, &mut tab.preview);
+        std::mem::swap(&mut self.atlas, &mut tab.atlas);
+        std::mem::swap(&mut self.selected_glyph, &mut tab.selected_glyph);
+        std::mem::swap(&mut self.warnings, &mut tab.warnings);
+        std::mem::swap(&mut self.parse_failure, &mut tab.parse_failure);
+        std::mem::swap(&mut self.job, &mut tab.job);
+        std::mem::swap(&mut self.font_edited, &mut tab.font_edited);
+        std::mem::swap(&mut self.edit_history, &mut tab.edit_history);
+        std::mem::swap(&mut self.size_estimate, &mut tab.size_estimate);
+    }
+
+    /// Makes tab `index` the active one, keeping the state of the one that
+    /// was active in its entry.
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        let mut tab = std::mem::take(&mut self.tabs[index]);
+        self.swap_tab(&mut tab);
+        self.tabs[self.active_tab] = tab;
+        self.active_tab = index;
+        self.confirm_overwrite = None;
+        self.overwrite_diff = None;
+    }
+
+    /// Opens an empty tab after the others and switches to it. It starts
+    /// with the export settings of the active tab.
+    fn new_tab(&mut self) {
+        self.tabs.push(FontTab {
+            options: self.options.clone(),
+            subset_ranges: self.subset_ranges.clone(),
+            subset_text: self.subset_text.clone(),
+            ..Default::default()
+        });
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Closes tab `index`, dropping its font and any job still running for
+    /// it. Closing the only tab empties it instead.
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() == 1 {
+            let mut tab = FontTab {
+                options: self.options.clone(),
+                subset_ranges: self.subset_ranges.clone(),
+                subset_text: self.subset_text.clone(),
+                ..Default::default()
+            };
+            self.swap_tab(&mut tab);
+            return;
+        }
+        if index == self.active_tab {
+            self.switch_tab(if index + 1 < self.tabs.len() { index + 1 } else { index - 1 });
+        }
+        self.tabs.remove(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+    }
+
+    /// The font loaded in tab `index`, whether or not it is the active one.
+    fn tab_font(&self, index: usize) -> Option<&Font> {
+        if index == self.active_tab { self.font.as_ref() } else { self.tabs[index].font.as_ref() }
+    }
+
+    /// Sets the baseline shift of every tab with a loaded font so their
+    /// baselines line up; see [`baseline_shifts`].
+    fn align_tab_baselines(&mut self) {
+        let loaded: Vec<usize> = (0..self.tabs.len()).filter(|index| self.tab_font(*index).is_some()).collect();
+        let fonts: Vec<&Font> = loaded.iter().filter_map(|index| self.tab_font(*index)).collect();
+        let shifts = baseline_shifts(&fonts);
+        for (index, shift) in loaded.iter().zip(shifts) {
+            let options = if *index == self.active_tab { &mut self.options } else { &mut self.tabs[*index].options };
+            options.baseline_shift = shift;
+        }
+        self.status.message = trf("✅ Aligned the baselines of {0} fonts", &[&loaded.len()]);
+        self.status.status = Some("success".to_string());
+    }
+
+    /// The name a tab is listed by: the file name of its font.
+    fn tab_title(selected_file: Option<&String>) -> String {
+        selected_file.map_or_else(
+            || tr("New tab").to_string(),
+            |file| Path::new(file).file_name().unwrap_or_default().to_string_lossy().into_owned(),
+        )
+    }
+
+    /// A tab per open font, with the size and line height of each loaded one
+    /// on hover for comparing a family's metrics, and buttons to open and
+    /// close tabs.
+    fn tabs_ui(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut close = None;
+        ui.horizontal_wrapped(|ui| {
+            for index in 0..self.tabs.len() {
+                let (file, font) = if index == self.active_tab {
+                    (self.selected_file.as_ref(), self.font.as_ref())
+                } else {
+                    (self.tabs[index].selected_file.as_ref(), self.tabs[index].font.as_ref())
+                };
+                let mut hover = file.cloned().unwrap_or_else(|| tr("No font loaded").to_string());
+                if let Some(font) = font {
+                    let line_height = font.metrics.common.as_ref().map_or(0.0, |common| common.line_height);
+                    hover.push('\n');
+                    hover.push_str(&trf("Size {0}, line height {1}", &[&font.metrics.size, &line_height]));
+                }
+                let title = Self::tab_title(file);
+                if ui.selectable_label(index == self.active_tab, title).on_hover_text(hover).clicked() {
+                    switch_to = Some(index);
+                }
+                if (self.tabs.len() > 1 || file.is_some())
+                    && ui.small_button("✖").on_hover_text(tr("✖ Close tab")).clicked()
+                {
+                    close = Some(index);
+                }
+                ui.separator();
+            }
+            let shortcut = ui.ctx().format_shortcut(&NEW_TAB_SHORTCUT);
+            if ui.small_button("➕").on_hover_text(trf("Open another tab ({0})", &[&shortcut])).clicked() {
+                self.new_tab();
+            }
+        });
+        if let Some(index) = switch_to {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+    }
+
+    /// Applies the result of the running [`Job`] once it has finished,
+    /// recording a parse error for the error panel.
+    fn poll_job(&mut self, ctx: &egui::Context) {
+        let Some(ref mut job) = self.job else {
+            return;
+        };
+        let mut output = None;
+        while let Ok(message) = job.messages.try_recv() {
+            match message {
+                JobMessage::Progress(progress) => job.progress = progress,
+                JobMessage::Done(done) => output = Some(*done),
+            }
+        }
+        let Some(output) = output else {
+            return;
+        };
+        let Some(Job { file, kind, .. }) = self.job.take() else {
+            return;
+        };
+
+        let font = match output.font {
+            Ok(font) => font,
+            Err(failure) => {
+                self.report_error(trf("❌ Error parsing file: {0}", &[failure.error.cause()]), &failure.error);
+                self.parse_failure = Some(failure);
+                return;
+            }
+        };
+        self.parse_failure = None;
+        self.warnings = output.warnings;
+
+        match output.output {
+            Some(Ok(output)) => {
+                let preview = Preview {
+                    input: file,
+                    output,
+                    glyph_count: font.characters.len(),
+                };
+                if kind == (JobKind::Convert { then: AfterConvert::Copy }) {
+                    ctx.copy_text(preview.output.clone());
+                    self.status.message = trf("📋 Copied {0} glyphs to the clipboard", &[&preview.glyph_count]);
+                    self.status.status = Some("success".to_string());
+                }
+                self.preview = Some(preview);
+            }
+            Some(Err(e)) => {
+                self.report_error(format!("❌ {e}"), &e);
+                self.preview = None;
+            }
+            None => {}
+        }
+        self.font = Some(font);
+        match kind {
+            JobKind::Convert { then: AfterConvert::Save } => self.save_preview(),
+            JobKind::Convert { then: AfterConvert::SaveTo(output) } => {
+                let overwrite = match self.options.overwrite {
+                    Overwrite::Backup => Overwrite::Backup,
+                    _ => Overwrite::Replace,
+                };
+                self.write_preview(output, overwrite);
+            }
+            _ => {}
+        }
+    }
+
+    /// Ctrl+O opens a font, Ctrl+S or Ctrl+Enter converts and saves it,
+    /// Ctrl+Shift+C copies the output, Ctrl+T and Ctrl+W open and close a
+    /// tab, Ctrl+P opens the command palette and Esc clears the status
+    /// message.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_shortcut(&OPEN_SHORTCUT)) {
+            self.dispatch(ctx, Action::Open);
+        }
+        if ctx.input_mut(|i| SAVE_SHORTCUTS.iter().any(|shortcut| i.consume_shortcut(shortcut))) {
+            self.dispatch(ctx, Action::Save);
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&COPY_SHORTCUT)) {
+            self.dispatch(ctx, Action::Copy);
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&NEW_TAB_SHORTCUT)) {
+            self.dispatch(ctx, Action::NewTab);
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&CLOSE_TAB_SHORTCUT)) {
+            self.dispatch(ctx, Action::CloseTab);
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&PALETTE_SHORTCUT)) {
+            self.command_palette = Some(CommandPalette::default());
+        }
+        // Esc closes popups and leaves text fields before it clears anything.
+        if self.command_palette.is_none()
+            && !ctx.wants_keyboard_input()
+            && !ctx.memory(|memory| memory.any_popup_open())
+            && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape))
+        {
+            self.status.message.clear();
+            self.status.status = None;
+        }
+    }
+
+    /// Runs `action`, whichever button, shortcut or palette entry asked for it.
+    fn dispatch(&mut self, ctx: &egui::Context, action: Action) {
+        match action {
+            Action::Open => self.pick_input(ctx),
+            Action::OpenRecent(file) => self.load_file(ctx, file),
+            Action::Convert => {
+                let then = if self.quick_convert { AfterConvert::Save } else { AfterConvert::Show };
+                self.convert_selected(ctx, then);
+            }
+            Action::Save => match self.preview {
+                Some(_) => self.save_preview(),
+                None => self.convert_selected(ctx, AfterConvert::Save),
+            },
+            Action::Copy => self.convert_selected(ctx, AfterConvert::Copy),
+            Action::SetFormat(format) => self.options.format = format,
+            Action::ToggleWatch => self.toggle_watch(ctx),
+            Action::NewTab => self.new_tab(),
+            Action::CloseTab => self.close_tab(self.active_tab),
+        }
+    }
+
+    /// The actions the command palette offers right now.
+    fn palette_actions(&self) -> Vec<Action> {
+        let mut actions = vec![Action::Open, Action::Convert, Action::Save, Action::Copy, Action::NewTab];
+        if self.selected_file.is_some() {
+            actions.push(Action::ToggleWatch);
+        }
+        if self.tabs.len() > 1 || self.selected_file.is_some() {
+            actions.push(Action::CloseTab);
+        }
+        actions.extend(
+            FORMATTERS
+                .iter()
+                .map(|formatter| formatter.format())
+                .filter(|format| *format != self.options.format)
+                .map(Action::SetFormat),
+        );
+        actions.extend(
+            self.recent_inputs()
+                .into_iter()
+                .filter(|input| Path::new(input).exists())
+                .map(|input| Action::OpenRecent(input.to_string())),
+        );
+        actions
+    }
+
+    /// The command palette: a search field over every action, best matches
+    /// first, run with Enter or a click.
+    fn command_palette_ui(&mut self, ctx: &egui::Context) {
+        if self.command_palette.is_none() {
+            return;
+        }
+        let actions = self.palette_actions();
+        let Some(ref mut palette) = self.command_palette else {
+            return;
+        };
+        let mut matches: Vec<(i32, Action)> = actions
+            .into_iter()
+            .filter_map(|action| fuzzy_score(&palette.query, &action.label()).map(|score| (score, action)))
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        let mut chosen = None;
+        let modal = egui::Modal::new(egui::Id::new("command_palette")).show(ctx, |ui| {
+            ui.set_width(360.0);
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::ArrowDown)) {
+                palette.selected += 1;
+            }
+            if ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::ArrowUp)) {
+                palette.selected = palette.selected.saturating_sub(1);
+            }
+            let query = ui.add(egui::TextEdit::singleline(&mut palette.query).hint_text(tr("Type a command…")).desired_width(f32::INFINITY));
+            if query.changed() {
+                palette.selected = 0;
+            }
+            query.request_focus();
+            palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+            if ui.input(|i| i.key_pressed(Key::Enter)) {
+                chosen = matches.get(palette.selected).map(|(_, action)| action.clone());
+            }
+
+            ui.separator();
+            if matches.is_empty() {
+                ui.weak(tr("No matching commands"));
+            }
+            egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                for (index, (_, action)) in matches.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let selected = index == palette.selected;
+                        let response = ui.selectable_label(selected, action.label());
+                        if selected {
+                            response.scroll_to_me(None);
+                        }
+                        if response.clicked() {
+                            chosen = Some(action.clone());
+                        }
+                        if let Some(shortcut) = action.shortcut() {
+                            ui.weak(ctx.format_shortcut(&shortcut));
+                        }
+                    });
+                }
+            });
+        });
+        if modal.should_close() || chosen.is_some() {
+            self.command_palette = None;
+        }
+        if let Some(action) = chosen {
+            self.dispatch(ctx, action);
+        }
+    }
+
+    /// Where quick convert saves the output for `input`: named after it in
+    /// the chosen folder, or next to it.
+    fn quick_output(&self, input: &Path) -> PathBuf {
+        let directory = self.quick_output_dir.as_deref().or(input.parent()).unwrap_or(Path::new(""));
+        directory.join(output_file_name(input, self.font.as_ref(), &self.options))
+    }
+
+    /// The quick convert toggle and the folder it saves into.
+    fn quick_convert_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.quick_convert, tr("⚡ Quick convert"))
+                .on_hover_text(tr("Save conversions without a dialog, named after the input"));
+            if !self.quick_convert {
+                return;
+            }
+            ui.label(tr("Into:"));
+            let folder = match self.quick_output_dir {
+                Some(ref directory) => directory.display().to_string(),
+                None => tr("the input's folder").to_string(),
+            };
+            if ui.button(format!("📁 {folder}")).clicked()
+                && let Some(directory) = self.output_dialog().pick_folder()
+            {
+                self.quick_output_dir = Some(directory);
+            }
+            if self.quick_output_dir.is_some() && ui.small_button("✖").on_hover_text(tr("Save next to the input")).clicked() {
+                self.quick_output_dir = None;
+            }
+        });
+    }
+
+    /// The Rojo project previews can be saved into and the folder they go in.
+    fn rojo_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Rojo project:"));
+            let project = match self.rojo_project {
+                Some(ref project) => project.display().to_string(),
+                None => tr("none").to_string(),
+            };
+            if ui.button(format!("📁 {project}")).clicked()
+                && let Some(project) = self.output_dialog().add_filter(tr("Rojo project"), &["json"]).pick_file()
+            {
+                self.rojo_project = Some(project);
+            }
+            if self.rojo_project.is_none() {
+                return;
+            }
+            if ui.small_button("✖").on_hover_text(tr("Forget the Rojo project")).clicked() {
+                self.rojo_project = None;
+            }
+            ui.label(tr("Folder:"));
+            ui.add(egui::TextEdit::singleline(&mut self.rojo_folder).desired_width(160.0))
+                .on_hover_text(tr("Relative to the project file; a $path of the project must include it"));
+        });
+    }
+
+    /// Asks for a font to load.
+    fn pick_input(&mut self, ctx: &egui::Context) {
+        if let Some(path) = self
+            .input_dialog()
+            .add_filter(tr("FNT files"), &["fnt", "json", "gz"])
+            .pick_file()
+        {
+            self.load_file(ctx, path.display().to_string());
+        }
+    }
+
+    /// Details of the last parse error, expanded on demand.
+    fn error_panel(&self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let Some(ref failure) = self.parse_failure else {
+            return;
+        };
+
+        egui::CollapsingHeader::new(RichText::new(tr("❌ Error details")).color(palette.error))
+            .id_salt("error_details")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(failure.error.cause().to_string());
+                if let ConvertError::BadAttribute { ref key, ref value } = *failure.error.cause() {
+                    ui.label(trf("Attribute: {0} = {1}", &[key, &format!("{value:?}")]));
+                }
+                if let Some(location) = failure.error.location() {
+                    ui.label(trf("At {0} (byte {1})", &[&location, &location.offset]));
+                }
+                if let Some(ref excerpt) = failure.excerpt {
+                    ui.code(excerpt);
+                }
+            });
+    }
+
+    /// Shows `message` as the status of a failed action, followed by what
+    /// can be done about `error` if it has a [`ConvertError::hint`].
+    fn report_error(&mut self, message: String, error: &ConvertError) {
+        self.status.message = match error.hint() {
+            Some(hint) => format!("{message}\n💡 {}", tr(hint)),
+            None => message,
+        };
+        self.status.status = Some("error".to_string());
+    }
+
+    /// A file dialog opening in the folder the last input was picked from.
+    fn input_dialog(&self) -> rfd::FileDialog {
+        match self.input_dir {
+            Some(ref directory) => rfd::FileDialog::new().set_directory(directory),
+            None => rfd::FileDialog::new(),
+        }
+    }
+
+    /// A file dialog opening in the folder the last output was written to.
+    fn output_dialog(&self) -> rfd::FileDialog {
+        match self.output_dir {
+            Some(ref directory) => rfd::FileDialog::new().set_directory(directory),
+            None => rfd::FileDialog::new(),
+        }
+    }
+
+    /// The most recently converted inputs, newest first.
+    fn recent_inputs(&self) -> Vec<&str> {
+        let mut recent: Vec<&str> = Vec::new();
+        for entry in &self.history {
+            if !recent.contains(&entry.input.as_str()) {
+                recent.push(&entry.input);
+            }
+        }
+        recent
+    }
+
+    /// A dropdown of [`recent_inputs`](Self::recent_inputs).
+    fn recent_menu(&mut self, ui: &mut egui::Ui) {
+        let recent = self.recent_inputs();
+        let mut selected = None;
+        ui.add_enabled_ui(!recent.is_empty(), |ui| {
+            ui.menu_button(tr("🕘 Recent ⏷"), |ui| {
+                for input in recent {
+                    let name = Path::new(input).file_name().unwrap_or_default().to_string_lossy();
+                    let response = ui
+                        .add_enabled(Path::new(input).exists(), egui::Button::new(name))
+                        .on_hover_text(input)
+                        .on_disabled_hover_text(tr("File no longer exists"));
+                    if response.clicked() {
+                        selected = Some(input.to_string());
+                        ui.close_menu();
+                    }
+                }
+            });
+        });
+
+        if let Some(file) = selected {
+            self.dispatch(ui.ctx(), Action::OpenRecent(file));
+        }
+    }
+
+    /// The conversions saved so far, newest first, each of which can be
+    /// reopened or re-run with the settings it was converted with.
+    fn history_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let mut selected = None;
+        let mut rerun = None;
+
+        egui::CollapsingHeader::new(tr("🕘 Recent")).id_salt("🕘 Recent").show(ui, |ui| {
+            if self.history.is_empty() {
+                ui.weak(tr("No conversions yet"));
+                return;
+            }
+
+            for entry in &self.history {
+                let exists = Path::new(&entry.input).exists();
+                ui.horizontal(|ui| {
+                    match entry.error {
+                        Some(ref error) => ui.colored_label(palette.error, "❌").on_hover_text(error),
+                        None => ui.colored_label(palette.success, "✅"),
+                    };
+                    let rerun_clicked = ui
+                        .add_enabled(exists && entry.options.is_some(), egui::Button::new("🔁").small())
+                        .on_hover_text(tr("Re-run with the same settings, saving over the output"))
+                        .on_disabled_hover_text(tr("The input no longer exists, or the settings were not recorded"))
+                        .clicked();
+                    if rerun_clicked {
+                        rerun = Some(entry.clone());
+                    }
+                    let format = entry.options.as_ref().map(|options| tr(options.format.label())).unwrap_or_default();
+                    let label = format!("{}  {} → {}  {format}", entry.timestamp, entry.input, entry.output);
+                    let response = ui
+                        .add_enabled(exists, egui::Button::new(label).frame(false))
+                        .on_hover_text(tr("Open the input"))
+                        .on_disabled_hover_text(tr("File no longer exists"));
+                    if response.clicked() {
+                        selected = Some(entry.input.clone());
+                    }
+                });
+            }
+
+            if ui.button(tr("🗑 Clear history")).clicked() {
+                self.history.clear();
+            }
+        });
+
+        if let Some(entry) = rerun {
+            self.rerun(ui.ctx(), entry);
+        } else if let Some(file) = selected {
+            self.load_file(ui.ctx(), file);
+        }
+    }
+
+    /// The events logged so far, newest last, to copy into a bug report.
+    fn log_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        egui::CollapsingHeader::new(tr("📜 Log")).id_salt("log").default_open(false).show(ui, |ui| {
+            let entries = self.log.entries();
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!entries.is_empty(), egui::Button::new(tr("📋 Copy log"))).clicked() {
+                    let text: Vec<String> = entries.iter().map(ToString::to_string).collect();
+                    ui.ctx().copy_text(text.join("\n"));
+                }
+                if ui.add_enabled(!entries.is_empty(), egui::Button::new(tr("🗑 Clear log"))).clicked() {
+                    self.log.clear();
+                }
+            });
+            if entries.is_empty() {
+                ui.weak(tr("Nothing logged yet"));
+                return;
+            }
+            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+            egui::ScrollArea::vertical()
+                .id_salt("log_entries")
+                .max_height(200.0)
+                .stick_to_bottom(true)
+                .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                    for entry in &entries[rows] {
+                        let color = match entry.level {
+                            tracing::Level::ERROR => palette.error,
+                            tracing::Level::WARN => palette.warning,
+                            _ => palette.text,
+                        };
+                        ui.label(RichText::new(entry.to_string()).monospace().color(color));
+                    }
+                });
+        });
+    }
+
+    /// Replaces the output options with those of the preset at `path`, which
+    /// is re-read so edits to a shared preset are picked up.
+    fn apply_preset(&mut self, path: PathBuf) {
+        match preset_options(&path) {
+            Ok(options) => {
+                if let VectorStyle::FunctionCall(ref name) = options.vector_style {
+                    self.function_name = name.clone();
+                }
+                if let Some(ref subset) = options.subset {
+                    self.subset_ranges = subset.to_string();
+                    self.subset_text.clear();
+                }
+                if let CharacterOrder::Priority(ref ranges) = options.character_order {
+                    self.priority_ranges = ranges.to_string();
+                }
+                self.options = options;
+                self.status.message = trf("✅ Applied preset {0}", &[&path.display()]);
+                self.status.status = Some("success".to_string());
+                if !self.presets.contains(&path) {
+                    self.presets.push(path.clone());
+                }
+                self.preset = Some(path);
+            }
+            Err(e) => {
+                self.status.message = trf("❌ Error reading preset: {0}", &[&e]);
+                self.status.status = Some("error".to_string());
+            }
+        }
+    }
+
+    fn options_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        egui::CollapsingHeader::new(tr("⚙ Output options")).id_salt("⚙ Output options").show(ui, |ui| {
+            let mut chosen = None;
+            ui.horizontal(|ui| {
+                ui.label(tr("Preset:"));
+                let name = |path: &Path| path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                egui::ComboBox::from_id_salt("preset")
+                    .selected_text(self.preset.as_deref().map_or(tr("None").to_string(), name))
+                    .show_ui(ui, |ui| {
+                        for path in &self.presets {
+                            if ui
+                                .selectable_label(self.preset.as_ref() == Some(path), name(path))
+                                .on_hover_text(path.display().to_string())
+                                .clicked()
+                            {
+                                chosen = Some(path.clone());
+                            }
+                        }
+                    });
+                if ui.button(tr("Add…")).on_hover_text(tr("Load a TOML preset, as used by --preset")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().add_filter(tr("Preset"), &["toml"]).pick_file()
+                {
+                    chosen = Some(path);
+                }
+            });
+            if let Some(path) = chosen {
+                self.apply_preset(path);
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr("Format:"));
+                egui::ComboBox::from_id_salt("output_format")
+                    .selected_text(tr(self.options.format.label()))
+                    .show_ui(ui, |ui| {
+                        for formatter in FORMATTERS {
+                            if ui.selectable_label(self.options.format == formatter.format(), tr(formatter.label())).clicked() {
+                                self.dispatch(ui.ctx(), Action::SetFormat(formatter.format()));
+                            }
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Vectors:"));
+                let function_call = VectorStyle::FunctionCall(self.function_name.clone());
+                egui::ComboBox::from_id_salt("vector_style")
+                    .selected_text(tr(self.options.vector_style.label()))
+                    .show_ui(ui, |ui| {
+                        for style in [
+                            VectorStyle::Vector2New,
+                            function_call,
+                            VectorStyle::TableLiteral,
+                            VectorStyle::Array,
+                            VectorStyle::Flattened,
+                        ] {
+                            let label = tr(style.label());
+                            ui.selectable_value(&mut self.options.vector_style, style, label);
+                        }
+                    });
+                if let VectorStyle::FunctionCall(ref mut name) = self.options.vector_style
+                    && ui.text_edit_singleline(name).changed()
+                {
+                    self.function_name = name.clone();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Glyph rects:"));
+                let label = |style: Option<RectStyle>| tr(style.map_or("Separate pairs", |style| style.label()));
+                egui::ComboBox::from_id_salt("rect_style")
+                    .selected_text(label(self.options.rect_style))
+                    .show_ui(ui, |ui| {
+                        for style in [None, Some(RectStyle::RectNew), Some(RectStyle::Array)] {
+                            ui.selectable_value(&mut self.options.rect_style, style, label(style));
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr("Combine atlas position and size into (x, y, width, height)"));
+            });
+
+            ui.horizontal(|ui| {
+                let mut named = matches!(self.options.module_style, ModuleStyle::NamedTable { .. });
+                ui.checkbox(&mut named, tr("Named module table"));
+                match (named, &mut self.options.module_style) {
+                    (true, ModuleStyle::NamedTable { name, indexed }) => {
+                        ui.add(egui::TextEdit::singleline(name).desired_width(80.0));
+                        ui.checkbox(indexed, tr("Indexed keys"));
+                        if let Err(e) = check_module_name(name) {
+                            ui.label(RichText::new(format!("❌ {e}")).color(palette.error));
+                        }
+                    }
+                    (true, ModuleStyle::BareReturn) => {
+                        self.options.module_style = ModuleStyle::NamedTable {
+                            name: "Font".to_string(),
+                            indexed: false,
+                        };
+                    }
+                    (false, _) => self.options.module_style = ModuleStyle::BareReturn,
+                }
+            });
+            let mut header = self.options.header.is_some();
+            ui.checkbox(&mut header, tr("Header comment"));
+            match (header, &mut self.options.header) {
+                (true, Some(options)) => {
+                    ui.add(egui::TextEdit::multiline(&mut options.text).desired_rows(1))
+                        .on_hover_text(tr("{source} is replaced with the input file name"));
+                    ui.checkbox(&mut options.timestamp, tr("Include timestamp"));
+                    ui.checkbox(&mut options.provenance, tr("Record provenance"))
+                        .on_hover_text(tr("Adds the source checksum, converter version, time and changed options"));
+                }
+                (true, None) => self.options.header = Some(HeaderOptions::default()),
+                (false, _) => self.options.header = None,
+            }
+            ui.horizontal(|ui| {
+                let mut custom = self.options.template.is_some();
+                ui.checkbox(&mut custom, tr("Custom template"))
+                    .on_hover_text(tr("Handlebars template with size, characters, kerning and pages in scope"));
+                if custom
+                    && ui.button(tr("Load…")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().pick_file()
+                {
+                    match std::fs::read_to_string(&path) {
+                        Ok(template) => self.options.template = Some(template),
+                        Err(e) => {
+                            self.status.message = trf("❌ Error reading template: {0}", &[&e]);
+                            self.status.status = Some("error".to_string());
+                        }
+                    }
+                }
+                match (custom, &self.options.template) {
+                    (true, None) => self.options.template = Some(String::new()),
+                    (false, Some(_)) => self.options.template = None,
+                    _ => {}
+                }
+            });
+            if let Some(ref mut template) = self.options.template {
+                ui.add(egui::TextEdit::multiline(template).code_editor().desired_rows(4));
+            }
+            let mut subset = self.options.subset.is_some();
+            ui.checkbox(&mut subset, tr("Only export a subset"))
+                .on_hover_text(tr("Characters in the ranges or in the text are exported"));
+            if subset {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Ranges:"));
+                    ui.text_edit_singleline(&mut self.subset_ranges)
+                        .on_hover_text(tr("Codepoints and ranges, e.g. 0x20-0x7E,0x400-0x4FF"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Characters:"));
+                    ui.text_edit_singleline(&mut self.subset_text);
+                });
+                match Subset::parse_ranges(&self.subset_ranges) {
+                    Ok(ranges) => self.options.subset = Some(ranges.union(Subset::from_text(&self.subset_text))),
+                    Err(e) => {
+                        ui.label(RichText::new(format!("❌ {e}")).color(palette.error));
+                        self.options.subset.get_or_insert_default();
+                    }
+                }
+            } else {
+                self.options.subset = None;
+            }
+            let filter = &mut self.options.glyph_filter;
+            ui.checkbox(&mut filter.empty_rects, tr("Drop empty glyphs"))
+                .on_hover_text(tr("Leave out glyphs whose rect has no width or height, such as the space"));
+            ui.checkbox(&mut filter.blank_pixels, tr("Drop blank glyphs"))
+                .on_hover_text(tr("Leave out glyphs without a visible pixel in the atlas"));
+            ui.checkbox(&mut filter.printable_only, tr("Printable characters only"))
+                .on_hover_text(tr("Leave out control characters, noncharacters and invisible format characters"));
+            ui.horizontal(|ui| {
+                let mut remap = self.options.remap.is_some();
+                ui.checkbox(&mut remap, tr("Remap codepoints"))
+                    .on_hover_text(tr("Move glyphs to the codepoints listed in a CSV file of from,to lines or a TOML file"));
+                if remap
+                    && ui.button(tr("Load…")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().add_filter(tr("Codepoint maps"), &["csv", "toml"]).pick_file()
+                {
+                    match read_codepoint_map(&path) {
+                        Ok(map) => self.options.remap = Some(map),
+                        Err(e) => {
+                            self.status.message = trf("❌ Error reading codepoint map: {0}", &[&e]);
+                            self.status.status = Some("error".to_string());
+                        }
+                    }
+                }
+                match (remap, &self.options.remap) {
+                    (true, None) => self.options.remap = Some(CodepointMap::default()),
+                    (false, Some(_)) => self.options.remap = None,
+                    (true, Some(map)) => {
+                        ui.label(trf("{0} codepoint(s) remapped", &[&map.len()]));
+                    }
+                    _ => {}
+                }
+            });
+            ui.horizontal(|ui| {
+                let mut icons = self.options.icons.is_some();
+                ui.checkbox(&mut icons, tr("Icon names"))
+                    .on_hover_text(tr("Add an Icons table naming each icon's codepoint, from a CSV file of name,codepoint lines or a JSON file such as Nerd Fonts' glyphnames.json (Lua)"));
+                if icons
+                    && ui.button(tr("Load…")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().add_filter(tr("Icon names"), &["csv", "json"]).pick_file()
+                {
+                    match read_icon_names(&path) {
+                        Ok(names) => self.options.icons = Some(names),
+                        Err(e) => {
+                            self.status.message = trf("❌ Error reading icon names: {0}", &[&e]);
+                            self.status.status = Some("error".to_string());
+                        }
+                    }
+                }
+                match (icons, &self.options.icons) {
+                    (true, None) => self.options.icons = Some(IconNames::default()),
+                    (false, Some(_)) => self.options.icons = None,
+                    (true, Some(names)) => {
+                        ui.label(trf("{0} icon name(s)", &[&names.len()]));
+                    }
+                    _ => {}
+                }
+            });
+            self.synthesize_ui(ui);
+            self.excluded_ui(ui);
+            self.pages_ui(ui);
+            self.page_paths_ui(ui);
+            ui.checkbox(&mut self.options.metrics_only, tr("Font metrics only"))
+                .on_hover_text(tr("Leave out the Characters table"));
+            ui.add_enabled(!self.options.metrics_only, egui::Checkbox::new(&mut self.options.advances_only, tr("Advances only")))
+                .on_hover_text(tr("Write each glyph as just its advance, for measuring text width without the texture (Lua and JSON)"));
+            ui.checkbox(&mut self.options.kerning, tr("Kerning"))
+                .on_hover_text(tr("Write the kerning pairs"));
+            ui.add_enabled(self.options.kerning, egui::Checkbox::new(&mut self.options.prune_kerning, tr("Prune unused kerning")))
+                .on_hover_text(tr("Drop kerning pairs with a glyph that is left out of the export or missing from the font"));
+            ui.checkbox(&mut self.options.texture_checksums, tr("Texture checksums"))
+                .on_hover_text(tr("Write the CRC-32 of each texture page, so a game can detect a texture that no longer matches the metrics"));
+            ui.checkbox(&mut self.options.vertical_metrics, tr("Ascent and descent"))
+                .on_hover_text(tr("Write how far the glyphs reach above and below the baseline, for aligning text vertically"));
+            ui.horizontal(|ui| {
+                ui.label(tr("Scale:"));
+                if self.options.scales.is_empty() {
+                    ui.add(egui::DragValue::new(&mut self.options.scale).speed(0.05).range(0.01..=16.0).suffix("×"))
+                        .on_hover_text(tr("Multiplies every metric before rounding"));
+                }
+                let mut removed = None;
+                for (index, scale) in self.options.scales.iter_mut().enumerate() {
+                    ui.add(egui::DragValue::new(scale).speed(0.05).range(0.01..=16.0).suffix("×"));
+                    if ui.small_button("✖").on_hover_text(tr("Remove this scale")).clicked() {
+                        removed = Some(index);
+                    }
+                }
+                if let Some(index) = removed {
+                    self.options.scales.remove(index);
+                }
+                if ui
+                    .small_button("➕")
+                    .on_hover_text(tr("Write a sub-table per scale, so UI code can pick the closest pre-scaled metrics (Lua)"))
+                    .clicked()
+                {
+                    let next = match self.options.scales.last() {
+                        Some(last) => last * 0.75,
+                        None => {
+                            self.options.scales.push(self.options.scale);
+                            self.options.scale * 0.75
+                        }
+                    };
+                    self.options.scales.push(next);
+                }
+            });
+            ui.checkbox(&mut self.options.bake_padding, tr("Remove exporter padding"))
+                .on_hover_text(tr("Shrinks each glyph rect by the padding in the font's info, adjusting offsets to match"));
+            ui.horizontal(|ui| {
+                ui.label(tr("Glyph padding:"));
+                ui.add(egui::DragValue::new(&mut self.options.padding).range(-16..=16).suffix(" px"))
+                    .on_hover_text(tr("Grows (or shrinks, when negative) each glyph rect on every side, adjusting offsets to match"));
+            });
+            let loaded = (0..self.tabs.len()).filter(|index| self.tab_font(*index).is_some()).count();
+            let align = ui
+                .horizontal(|ui| {
+                    ui.label(tr("Baseline shift:"));
+                    ui.add(egui::DragValue::new(&mut self.options.baseline_shift).speed(0.5).suffix(" px"))
+                        .on_hover_text(tr("Moves every glyph down, with the base and line height, so the font lines up with others of its family"));
+                    ui.add_enabled(loaded > 1, egui::Button::new(tr("Align across tabs")))
+                        .on_hover_text(tr("Sets the shift of the font in every tab so their baselines line up when mixed in one run of text"))
+                        .clicked()
+                })
+                .inner;
+            if align {
+                self.align_tab_baselines();
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr("Rounding:"));
+                egui::ComboBox::from_id_salt("rounding")
+                    .selected_text(tr(self.options.rounding.label()))
+                    .show_ui(ui, |ui| {
+                        for rounding in [Rounding::Round, Rounding::Floor, Rounding::Ceil, Rounding::KeepFloat] {
+                            ui.selectable_value(&mut self.options.rounding, rounding, tr(rounding.label()));
+                        }
+                    });
+            });
+            self.character_order_ui(ui);
+            ui.checkbox(&mut self.options.numeric_keys, tr("Numeric keys"))
+                .on_hover_text(tr("Key characters by codepoint, e.g. [65] instead of [\"A\"]"));
+            ui.add_enabled_ui(!self.options.numeric_keys, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Control character keys:"));
+                    egui::ComboBox::from_id_salt("key_escape")
+                        .selected_text(tr(self.options.key_escape.label()))
+                        .show_ui(ui, |ui| {
+                            for escape in [KeyEscape::Escaped, KeyEscape::StringChar, KeyEscape::Utf8Char] {
+                                ui.selectable_value(&mut self.options.key_escape, escape, tr(escape.label()));
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("Escaped strings and string.char calls work in every Lua version; utf8.char needs Lua 5.3 or Luau"));
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Glyph rects:"));
+                egui::ComboBox::from_id_salt("uv")
+                    .selected_text(tr(self.options.uv.label()))
+                    .show_ui(ui, |ui| {
+                        for uv in [UvCoordinates::Off, UvCoordinates::Replace, UvCoordinates::Append] {
+                            ui.selectable_value(&mut self.options.uv, uv, tr(uv.label()));
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr("UV coordinates are the position and size divided by the texture size, for shaders that do not know it (Lua and JSON)"));
+            });
+            ui.checkbox(&mut self.options.prefer_letters, tr("Use letter attribute"))
+                .on_hover_text(tr("Key glyphs by the letter=\"…\" attribute some exporters write, when present"));
+            ui.checkbox(&mut self.options.luau_types, tr("Luau types"))
+                .on_hover_text(tr("Adds --!strict and exported GlyphData and FontData types"));
+            ui.add_enabled(!self.options.metrics_only, egui::Checkbox::new(&mut self.options.measure_text, tr("MeasureText function")))
+                .on_hover_text(tr("Adds Font.MeasureText(text), which returns the width of text in pixels from the advances and kerning"));
+            ui.checkbox(&mut self.options.packed, tr("Packed glyph data"))
+                .on_hover_text(tr("Writes the glyphs as one compact string that the module decodes when required, rounded to whole pixels"));
+            self.chunk_size_ui(ui);
+            self.style_ui(ui);
+            ui.checkbox(&mut self.options.trailing_newline, tr("Trailing newline"));
+            ui.checkbox(&mut self.options.bom, tr("UTF-8 BOM"));
+            ui.checkbox(&mut self.options.dedupe_metrics, tr("Share identical metrics"))
+                .on_hover_text(tr("Emit each distinct size/offset/advance once and reference it by index"));
+            ui.checkbox(&mut self.options.clamp_negative, tr("Clamp negative sizes to 0"));
+            ui.horizontal(|ui| {
+                ui.label(tr("Existing files:"));
+                egui::ComboBox::from_id_salt("overwrite")
+                    .selected_text(tr(self.options.overwrite.label()))
+                    .show_ui(ui, |ui| {
+                        for overwrite in Overwrite::ALL {
+                            ui.selectable_value(&mut self.options.overwrite, overwrite, tr(overwrite.label()));
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr("Keep asks before quick convert replaces a file and fails batch conversions instead"));
+            });
+            ui.add_enabled(can_verify(&self.options), egui::Checkbox::new(&mut self.options.verify, tr("Verify after export")))
+                .on_hover_text(tr("Read saved files back and report any glyph, metric or kerning that did not survive"))
+                .on_disabled_hover_text(tr("Only Lua, JSON and FNT output without a template can be read back"));
+            ui.checkbox(&mut self.options.lenient, tr("Skip malformed elements"))
+                .on_hover_text(tr("Convert what can be read and list the elements that could not be parsed as warnings"));
+            ui.checkbox(&mut self.options.check_schema, tr("Check XML structure"))
+                .on_hover_text(tr("Before parsing, list every missing element or attribute and malformed value with its line"));
+            ui.checkbox(&mut self.options.absolute_size, tr("Use absolute font size"))
+                .on_hover_text(tr("BMFont uses a negative size to mean \"match character height\""));
+            ui.horizontal(|ui| {
+                let mut enabled = self.options.size_override.is_some();
+                ui.checkbox(&mut enabled, tr("Override size"));
+                match (enabled, self.options.size_override.as_mut()) {
+                    (true, Some(size)) => {
+                        ui.add(egui::DragValue::new(size));
+                    }
+                    (true, None) => {
+                        let parsed = self.font.as_ref().map(|font| font.metrics.size.abs()).unwrap_or_default();
+                        self.options.size_override = Some(parsed);
+                    }
+                    (false, _) => self.options.size_override = None,
+                }
+            });
+        });
+    }
+
+    fn style_ui(&mut self, ui: &mut egui::Ui) {
+        let style = &mut self.options.style;
+        ui.checkbox(&mut style.minified, tr("Minified"))
+            .on_hover_text(tr("Emit the whole table on a single line"));
+        ui.add_enabled_ui(!style.minified, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Indent:"));
+                ui.add(egui::DragValue::new(&mut style.indent_width).range(0..=8));
+                egui::ComboBox::from_id_salt("indent_kind")
+                    .selected_text(tr(if style.use_tabs { "tabs" } else { "spaces" }))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut style.use_tabs, false, tr("spaces"));
+                        ui.selectable_value(&mut style.use_tabs, true, tr("tabs"));
+                    });
+            });
+            ui.checkbox(&mut style.trailing_commas, tr("Trailing commas"));
+            ui.checkbox(&mut style.field_per_line, tr("One value per line"))
+                .on_hover_text(tr("Write each glyph's values on separate lines"));
+        });
+    }
+
+    /// Starts converting the selected file into a [`Preview`], copying it to
+    /// the clipboard as well if `copy` is set.
+    fn convert_selected(&mut self, ctx: &egui::Context, then: AfterConvert) {
+        let Some(file) = self.selected_file.clone() else {
+            self.status.message = tr("⚠️ Please select a .fnt file first").to_string();
+            self.status.status = Some("warning".to_string());
+            return;
+        };
+
+        self.status.message.clear();
+        self.status.status = None;
+        let edited = self.font.clone().filter(|_| self.font_edited);
+        self.job = Some(Job::spawn(ctx, &self.parse_cache, file, JobKind::Convert { then }, self.options.clone(), edited));
+    }
+
+    /// A spinner, and a progress bar once the glyph count is known, while a
+    /// [`Job`] is running.
+    fn job_ui(&self, ui: &mut egui::Ui) {
+        let Some(ref job) = self.job else {
+            return;
+        };
+        let name = Path::new(&job.file).file_name().unwrap_or_default().to_string_lossy();
+        let action = match job.kind {
+            JobKind::Load => "Parsing {0}… {1}s",
+            JobKind::Convert { .. } => "Converting {0}… {1}s",
+        };
+        let text = trf(action, &[&name, &format!("{:.1}", job.started.elapsed().as_secs_f32())]);
+        ui.horizontal(|ui| {
+            ui.spinner();
+            match job.progress {
+                Progress::Fraction(fraction) => ui.add(egui::ProgressBar::new(fraction).text(text)),
+                Progress::Indeterminate => ui.label(text),
+            };
+        });
+        // Keep the elapsed time ticking between worker messages.
+        ui.ctx().request_repaint_after(Duration::from_millis(100));
+    }
+
+    /// Shows the last conversion and writes it to disk only once confirmed.
+    fn preview_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(ref preview) = self.preview else {
+            return;
+        };
+
+        ui.separator();
+        ui.label(trf(
+            "👁 Preview ({0} glyphs, {1} KB)",
+            &[&preview.glyph_count, &preview.output.len().div_ceil(1024)],
+        ));
+        egui::ScrollArea::both()
+            .id_salt("output_preview")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                let theme = egui_extras::syntax_highlighting::CodeTheme::from_style(ui.style());
+                let language = if self.options.template.is_some() { "txt" } else { self.options.format.extension() };
+                egui_extras::syntax_highlighting::code_view_ui(ui, &theme, &preview.output, language);
+            });
+
+        let rojo_enabled = self.rojo_project.is_some() && matches!(self.options.format, OutputFormat::Lua);
+        let (copy, save, rojo, discard) = ui
+            .horizontal(|ui| {
+                (
+                    ui.button(tr("📋 Copy")).clicked(),
+                    ui.button(tr("💾 Save…")).clicked(),
+                    ui.add_enabled(rojo_enabled, egui::Button::new(tr("🎮 Save to Rojo")))
+                        .on_disabled_hover_text(tr("Pick a Rojo project and convert to Lua first"))
+                        .clicked(),
+                    ui.button(tr("✖ Discard")).clicked(),
+                )
+            })
+            .inner;
+        if copy {
+            ui.ctx().copy_text(preview.output.clone());
+        }
+        if discard {
+            self.preview = None;
+        } else if save {
+            self.save_preview();
+        } else if rojo {
+            self.save_to_rojo();
+        }
+    }
+
+    /// Writes the [`Preview`] into the Rojo project, as `init.lua` in a
+    /// folder named after the font. An existing module is only replaced once
+    /// confirmed.
+    fn save_to_rojo(&mut self) {
+        let (Some(preview), Some(project)) = (&self.preview, &self.rojo_project) else {
+            return;
+        };
+        let module = module_path(project, Path::new(&self.rojo_folder), Path::new(&preview.input), &self.options)
+            .and_then(|module| {
+                std::fs::create_dir_all(module.parent().unwrap_or(Path::new("")))?;
+                Ok(module)
+            });
+        match module {
+            Ok(module) if self.options.overwrite == Overwrite::Refuse && module.exists() => self.ask_overwrite(module),
+            Ok(module) => self.write_preview(module, self.options.overwrite),
+            Err(e) => self.report_error(trf("❌ Error saving file: {0}", &[&e]), &e),
+        }
+    }
+
+    /// Writes the [`Preview`] where quick convert names it, or asks where.
+    /// An existing quick convert target is only replaced once confirmed.
+    fn save_preview(&mut self) {
+        let Some(ref preview) = self.preview else {
+            return;
+        };
+        // Never quick-save over the input, e.g. an imported Lua module.
+        let input = Path::new(&preview.input);
+        let quick = self.quick_convert.then(|| self.quick_output(input)).filter(|output| output != input);
+        match quick {
+            Some(output_file) if self.options.overwrite == Overwrite::Refuse && output_file.exists() => self.ask_overwrite(output_file),
+            Some(output_file) => self.write_preview(output_file, self.options.overwrite),
+            None => {
+                let file_name = output_file_name(input, self.font.as_ref(), &self.options);
+                let Some(output_file) = self
+                    .output_dialog()
+                    .add_filter(tr(self.options.format.label()), &[self.options.format.extension()])
+                    .set_file_name(file_name.display().to_string())
+                    .save_file()
+                else {
+                    return;
+                };
+                self.output_dir = output_file.parent().map(Path::to_path_buf);
+                // The save dialog has already asked about replacing the file.
+                let overwrite = match self.options.overwrite {
+                    Overwrite::Backup => Overwrite::Backup,
+                    _ => Overwrite::Replace,
+                };
+                self.write_preview(output_file, overwrite);
+            }
+        }
+    }
+
+    /// Whether whitespace the font leaves out is synthesized, and how wide.
+    fn synthesize_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut synthesize = self.options.synthesize.is_some();
+            ui.checkbox(&mut synthesize, tr("Synthesize whitespace"))
+                .on_hover_text(tr("Add empty glyphs for a space, tab and no-break space when the font has none, marked Synthesized = true"));
+            match (synthesize, &mut self.options.synthesize) {
+                (true, None) => self.options.synthesize = Some(WhitespaceRules::default()),
+                (false, Some(_)) => self.options.synthesize = None,
+                (true, Some(rules)) => {
+                    ui.label(tr("Space:"));
+                    egui::ComboBox::from_id_salt("space_width")
+                        .selected_text(tr(rules.space.label()))
+                        .show_ui(ui, |ui| {
+                            for width in [SpaceWidth::AverageDigit, SpaceWidth::Em(0.25), SpaceWidth::Pixels(8.0)] {
+                                if ui.selectable_label(rules.space.label() == width.label(), tr(width.label())).clicked() {
+                                    rules.space = width;
+                                }
+                            }
+                        });
+                    match rules.space {
+                        SpaceWidth::AverageDigit => {}
+                        SpaceWidth::Em(ref mut fraction) => {
+                            ui.add(egui::DragValue::new(fraction).speed(0.01).range(0.0..=2.0).suffix(" em"));
+                        }
+                        SpaceWidth::Pixels(ref mut width) => {
+                            ui.add(egui::DragValue::new(width).speed(0.5).range(0.0..=512.0).suffix(" px"));
+                        }
+                    }
+                    ui.label(tr("Tab:"));
+                    ui.add(egui::DragValue::new(&mut rules.tab_spaces).speed(0.1).range(0.0..=16.0))
+                        .on_hover_text(tr("How many spaces a synthesized tab advances"));
+                }
+                _ => {}
+            }
+        });
+    }
+
+    /// A checklist of the loaded font's glyphs, and of those already
+    /// excluded, to pick the codepoints that are never exported.
+    fn excluded_ui(&mut self, ui: &mut egui::Ui) {
+        let title = trf("Excluded characters ({0})", &[&self.options.excluded.len()]);
+        egui::CollapsingHeader::new(title).id_salt("excluded").default_open(false).show(ui, |ui| {
+            let mut ids: BTreeSet<u32> = self.options.excluded.clone();
+            if let Some(ref font) = self.font {
+                ids.extend(font.characters.keys());
+            }
+            let ids: Vec<u32> = ids.into_iter().collect();
+            ui.horizontal(|ui| {
+                if ui.button(tr("Keep all")).clicked() {
+                    self.options.excluded.clear();
+                }
+                if ui.button(tr("Reset")).on_hover_text(tr("Exclude NUL and carriage return")).clicked() {
+                    self.options.excluded = OutputOptions::default().excluded;
+                }
+            });
+            let row_height = ui.spacing().interact_size.y;
+            egui::ScrollArea::vertical().id_salt("excluded_list").max_height(160.0).show_rows(ui, row_height, ids.len(), |ui, rows| {
+                for id in &ids[rows] {
+                    let mut excluded = self.options.excluded.contains(id);
+                    if ui.checkbox(&mut excluded, format!("U+{id:04X} {}", glyph_label(*id))).changed() {
+                        if excluded {
+                            self.options.excluded.insert(*id);
+                        } else {
+                            self.options.excluded.remove(id);
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    /// Checkboxes choosing the texture pages of a multi-page font to export.
+    fn pages_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(ref font) = self.font else {
+            return;
+        };
+        if font.metrics.pages.len() < 2 {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr("Pages:"));
+            for (id, file) in &font.metrics.pages {
+                let glyphs = font.characters.values().filter(|data| data.page == *id).count();
+                let mut included = self.options.pages.as_ref().is_none_or(|pages| pages.contains(id));
+                if ui
+                    .checkbox(&mut included, id.to_string())
+                    .on_hover_text(trf("{0}: {1} glyph(s)", &[file, &glyphs]))
+                    .changed()
+                {
+                    let pages = self.options.pages.get_or_insert_with(|| font.metrics.pages.keys().copied().collect());
+                    if included {
+                        pages.insert(*id);
+                    } else {
+                        pages.remove(id);
+                    }
+                    if font.metrics.pages.keys().all(|id| pages.contains(id)) {
+                        self.options.pages = None;
+                    }
+                }
+            }
+        });
+    }
+
+    /// The rules rewriting texture file names, each a pattern and its
+    /// replacement, with what the pages of the loaded font are written as.
+    fn page_paths_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        ui.horizontal(|ui| {
+            ui.label(tr("Texture paths:"));
+            if ui
+                .button(tr("Add rule"))
+                .on_hover_text(tr("Write the texture file names matching a pattern as something else, e.g. font_0.png as rbxassetid://123456"))
+                .clicked()
+            {
+                self.options.page_paths.rules.push(PageRule {
+                    pattern: "*.png".to_string(),
+                    replacement: "{file}".to_string(),
+                });
+            }
+        });
+        let mut removed = None;
+        for (index, rule) in self.options.page_paths.rules.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut rule.pattern).hint_text("font_*.png").desired_width(120.0));
+                ui.label("→");
+                ui.add(egui::TextEdit::singleline(&mut rule.replacement).hint_text("rbxassetid://…").desired_width(160.0))
+                    .on_hover_text(tr("{file}, {stem} and {page} stand for the file name, the name without its extension and the page id"));
+                if ui.small_button("✖").on_hover_text(tr("Remove this rule")).clicked() {
+                    removed = Some(index);
+                }
+                if let Err(e) = rule.check() {
+                    ui.label(RichText::new(format!("❌ {e}")).color(palette.error));
+                }
+            });
+        }
+        if let Some(index) = removed {
+            self.options.page_paths.rules.remove(index);
+        }
+        if let Some(ref font) = self.font
+            && !self.options.page_paths.is_empty()
+        {
+            for (id, file) in &font.metrics.pages {
+                ui.weak(format!("{file} → {}", self.options.page_paths.rewrite(*id, file)));
+            }
+        }
+    }
+
+    fn character_order_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        ui.horizontal(|ui| {
+            ui.label(tr("Glyph order:"));
+            let priority = CharacterOrder::Priority(Subset::parse_ranges(&self.priority_ranges).unwrap_or_default());
+            egui::ComboBox::from_id_salt("character_order")
+                .selected_text(tr(self.options.character_order.label()))
+                .show_ui(ui, |ui| {
+                    for order in [CharacterOrder::Codepoint, CharacterOrder::Source, priority] {
+                        let selected = std::mem::discriminant(&self.options.character_order) == std::mem::discriminant(&order);
+                        if ui.selectable_label(selected, tr(order.label())).clicked() {
+                            self.options.character_order = order;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr("The order glyphs are listed in the Characters table"));
+        });
+        if let CharacterOrder::Priority(ref mut ranges) = self.options.character_order {
+            ui.horizontal(|ui| {
+                ui.label(tr("First:"));
+                ui.text_edit_singleline(&mut self.priority_ranges)
+                    .on_hover_text(tr("Ranges listed first, in this order, e.g. 0x20-0x7E,0x400-0x4FF"));
+            });
+            match Subset::parse_ranges(&self.priority_ranges) {
+                Ok(parsed) => *ranges = parsed,
+                Err(e) => {
+                    ui.label(RichText::new(format!("❌ {e}")).color(palette.error));
+                }
+            }
+        }
+    }
+
+    /// Whether the Lua output is split into part modules, and of how many
+    /// glyphs.
+    fn chunk_size_ui(&mut self, ui: &mut egui::Ui) {
+        let lua = matches!(self.options.format, OutputFormat::Lua) && self.options.template.is_none();
+        ui.add_enabled_ui(lua && !self.options.metrics_only && !self.options.packed, |ui| {
+            ui.horizontal(|ui| {
+                let mut split = self.options.chunk_size.is_some();
+                ui.checkbox(&mut split, tr("Split into parts of"))
+                    .on_hover_text(tr("Writes the glyphs to part modules next to the output, which loads them when required"));
+                let mut size = self.options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+                ui.add_enabled(split, egui::DragValue::new(&mut size).range(1..=100_000).suffix(tr(" glyphs")));
+                self.options.chunk_size = split.then_some(size);
+            });
+        });
+    }
+
+    /// Asks whether to replace `output_file` with the preview, comparing the
+    /// two first.
+    fn ask_overwrite(&mut self, output_file: PathBuf) {
+        self.overwrite_diff = match (std::fs::read_to_string(&output_file), &self.preview) {
+            (Ok(existing), Some(preview)) => Some(Comparison {
+                diff: lua_diff(&existing, &preview.output),
+                lua_diff: diff_excerpt(&diff_lines(&existing, &preview.output), DIFF_CONTEXT),
+            }),
+            _ => None,
+        };
+        self.confirm_overwrite = Some(output_file);
+    }
+
+    /// Asks whether to replace the file in `confirm_overwrite`.
+    fn overwrite_prompt(&mut self, ctx: &egui::Context) {
+        let Some(output_file) = self.confirm_overwrite.clone() else {
+            return;
+        };
+        let palette = self.theme.palette();
+        let mut choice = None;
+        let modal = egui::Modal::new(egui::Id::new("confirm_overwrite")).show(ctx, |ui| {
+            ui.label(trf("{0} already exists.", &[&output_file.display()]));
+            if let Some(ref comparison) = self.overwrite_diff {
+                ui.label(tr("Replacing it changes:"));
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| comparison_ui(ui, comparison, &palette));
+            }
+            ui.horizontal(|ui| {
+                if ui.button(tr("Overwrite")).clicked() {
+                    choice = Some(Overwrite::Replace);
+                }
+                if ui.button(tr("Back up, then overwrite")).clicked() {
+                    choice = Some(Overwrite::Backup);
+                }
+                if ui.button(tr("Cancel")).clicked() {
+                    self.confirm_overwrite = None;
+                }
+            });
+        });
+        if modal.should_close() {
+            self.confirm_overwrite = None;
+        }
+        if let Some(overwrite) = choice {
+            self.confirm_overwrite = None;
+            self.write_preview(output_file, overwrite);
+        }
+    }
+
+    fn write_preview(&mut self, output_file: PathBuf, overwrite: Overwrite) {
+        let Some(ref preview) = self.preview else {
+            return;
+        };
+        // The preview holds one module; split output is rendered again.
+        let chunks = match self.font {
+            Some(ref font) if self.options.chunk_size.is_some() => {
+                let inputs = [Path::new(&preview.input)];
+                with_checksums(&self.options, font, &inputs)
+                    .and_then(|options| render_chunks(font, &source_name(&inputs), &options, &module_name(&output_file)))
+            }
+            _ => Ok(LuaChunks {
+                index: preview.output.clone(),
+                parts: Vec::new(),
+            }),
+        };
+        let bytes = chunks.as_ref().map_or(0, |chunks| chunks_size(chunks, &self.options));
+        let written = chunks.and_then(|chunks| {
+            let backup = write_chunks(&chunks, &output_file, &self.options, overwrite)?;
+            info!(output = %output_file.display(), "wrote output");
+            if let Some(ref font) = self.font
+                && self.options.verify
+                && can_verify(&self.options)
+            {
+                let options = with_checksums(&self.options, font, &[&preview.input])?;
+                verify_output(font, &read_source(&output_file.display().to_string())?, &options)?;
+            }
+            Ok(backup)
+        });
+        match written {
+            Ok(backup) => {
+                self.status.message = trf(
+                    "✅ Saved {0} glyphs ({1} KB) to {2}",
+                    &[&preview.glyph_count, &bytes.div_ceil(1024), &output_file.display()],
+                );
+                if let Some(backup) = backup {
+                    self.status.message.push_str(&trf(" (previous file backed up to {0})", &[&backup.display()]));
+                }
+                self.status.status = Some("success".to_string());
+                self.record_history(output_file, None);
+                self.preview = None;
+            }
+            Err(e) => {
+                self.record_history(output_file, Some(e.to_string()));
+                self.report_error(trf("❌ Error saving file: {0}", &[&e]), &e);
+            }
+        }
+    }
+
+    /// Adds saving the [`Preview`] to `output_file` to the history, replacing
+    /// any earlier entry for the same input and output.
+    fn record_history(&mut self, output_file: PathBuf, error: Option<String>) {
+        let Some(ref preview) = self.preview else {
+            return;
+        };
+        let input = preview.input.clone();
+        let output = output_file.display().to_string();
+        self.history.retain(|entry| entry.input != input || entry.output != output);
+        self.history.insert(
+            0,
+            HistoryEntry {
+                input,
+                output,
+                timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                options: Some(self.options.clone()),
+                error,
+            },
+        );
+        self.history.truncate(HISTORY_LIMIT);
+    }
+
+    /// Toggles reconverting the selected file whenever it changes, and shows
+    /// how the last reconversion went.
+    fn watch_ui(&mut self, ui: &mut egui::Ui) {
+        if self.selected_file.is_none() {
+            return;
+        }
+
+        let mut watching = self.watch.is_some();
+        if ui.checkbox(&mut watching, tr("👁 Watch for changes")).changed() {
+            self.dispatch(ui.ctx(), Action::ToggleWatch);
+        }
+
+        if let Some(ref watch) = self.watch {
+            let line = match watch.last_conversion {
+                None => trf("Watching {0} → {1}", &[&watch.input.display(), &watch.output.display()]),
+                Some((ref time, Ok(()))) => trf("✅ Converted to {0} at {1}", &[&watch.output.display(), time]),
+                Some((ref time, Err(ref e))) => trf("❌ Conversion failed at {0}: {1}", &[time, e]),
+            };
+            ui.weak(line);
+        }
+    }
+
+    /// Stops watching, or starts watching the selected file. The output goes
+    /// where that file was last saved, or to a newly picked file.
+    fn toggle_watch(&mut self, ctx: &egui::Context) {
+        if self.watch.take().is_some() {
+            return;
+        }
+        let Some(ref file) = self.selected_file else {
+            return;
+        };
+        let previous = self.history.iter().find(|entry| entry.input == *file).map(|entry| PathBuf::from(&entry.output));
+        let output = previous.or_else(|| {
+            self.output_dialog()
+                .add_filter(tr(self.options.format.label()), &[self.options.format.extension()])
+                .save_file()
+        });
+        if let Some(output) = output {
+            match FileWatch::new(ctx, PathBuf::from(file), output) {
+                Ok(watch) => self.watch = Some(watch),
+                Err(e) => {
+                    self.status.message = trf("❌ Error watching file: {0}", &[&e]);
+                    self.status.status = Some("error".to_string());
+                }
+            }
+        }
+    }
+
+    /// Reconverts the watched file if it changed since the last frame.
+    fn poll_watch(&mut self, ctx: &egui::Context) {
+        let Some(ref mut watch) = self.watch else {
+            return;
+        };
+
+        let names = [watch.input.file_name().unwrap_or_default()];
+        let mut changed = false;
+        while let Ok(event) = watch.events.try_recv() {
+            changed |= event.is_ok_and(|event| touches_input(&event, &names));
+        }
+        if !changed {
+            return;
+        }
+
+        // The watched output is rewritten on every change by design.
+        let options = OutputOptions { overwrite: Overwrite::Replace, ..self.options.clone() };
+        let result = convert_file_cached(&self.parse_cache, &watch.input, &watch.output, &options)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        watch.last_conversion = Some((chrono::Local::now().format("%H:%M:%S").to_string(), result));
+        if Some(watch.input.display().to_string()) == self.selected_file {
+            let input = watch.input.display().to_string();
+            self.load_file(ctx, input);
+        }
+    }
+
+    fn batch_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        egui::CollapsingHeader::new(tr("📚 Batch conversion")).id_salt("📚 Batch conversion").show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr("➕ Add files…")).clicked()
+                    && let Some(inputs) = self
+                        .input_dialog()
+                        .add_filter(tr("FNT files"), &["fnt", "json", "gz"])
+                        .pick_files()
+                {
+                    self.input_dir = inputs.first().and_then(|input| input.parent()).map(Path::to_path_buf);
+                    for input in inputs {
+                        self.batch_queue.push(input);
+                    }
+                }
+                if ui.button(tr("Clear")).clicked() {
+                    self.batch_queue.clear();
+                }
+                ui.label(tr("Threads:"));
+                ui.add(egui::DragValue::new(&mut self.batch_threads).range(0..=64))
+                    .on_hover_text(tr("0 uses one thread per core"));
+            });
+
+            let idle = self.batch_run.is_none();
+            let mut action = None;
+            egui::ScrollArea::vertical()
+                .id_salt("batch_queue")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for (index, entry) in self.batch_queue.entries().iter().enumerate() {
+                        let name = entry.input.file_name().unwrap_or_default().to_string_lossy();
+                        let converting = entry.state == JobState::Converting;
+                        let row = ui.horizontal(|ui| {
+                            ui.dnd_drag_source(egui::Id::new(("batch_entry", index)), index, |ui| ui.label("☰"))
+                                .response
+                                .on_hover_text(tr("Drag to reorder"));
+                            if ui
+                                .add_enabled(idle && !converting, egui::Button::new("🔁").small())
+                                .on_hover_text(tr("Convert again"))
+                                .clicked()
+                            {
+                                action = Some(QueueAction::Rerun(index));
+                            }
+                            if ui
+                                .add_enabled(!converting, egui::Button::new("✖").small())
+                                .on_hover_text(tr("Remove from the queue"))
+                                .clicked()
+                            {
+                                action = Some(QueueAction::Remove(index));
+                            }
+                            match entry.state {
+                                JobState::Pending => ui.label(format!("⏳ {name}")),
+                                JobState::Converting => ui.label(format!("🔄 {name}")),
+                                JobState::Done(ref output) => ui.label(format!("✅ {name} → {}", output.display())),
+                                JobState::Failed(ref e) => ui.label(RichText::new(format!("❌ {name}: {e}")).color(palette.error)),
+                            };
+                        });
+                        if row.response.dnd_hover_payload::<usize>().is_some() {
+                            let rect = row.response.rect;
+                            ui.painter().hline(rect.x_range(), rect.top(), Stroke::new(2.0, palette.accent));
+                        }
+                        if let Some(from) = row.response.dnd_release_payload::<usize>() {
+                            action = Some(QueueAction::Move(*from, index));
+                        }
+                    }
+                });
+            match action {
+                // Dropped entries go before the row they are dropped on.
+                Some(QueueAction::Move(from, to)) => self.batch_queue.move_entry(from, if from < to { to - 1 } else { to }),
+                Some(QueueAction::Remove(index)) => {
+                    self.batch_queue.remove(index);
+                }
+                Some(QueueAction::Rerun(index)) => {
+                    if let Some(input) = self.batch_queue.requeue(index) {
+                        self.start_batch(ui.ctx(), None, vec![input]);
+                    }
+                }
+                None => {}
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Exclude:"));
+                ui.text_edit_singleline(&mut self.batch_exclude)
+                    .on_hover_text(tr("Glob matched against paths inside the folder, e.g. **/test*"));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Output folder:"));
+                let folder = match self.batch_output_dir {
+                    Some(ref directory) => directory.display().to_string(),
+                    None => tr("Ask each time").to_string(),
+                };
+                if ui.button(format!("📁 {folder}")).clicked()
+                    && let Some(directory) = self.output_dialog().set_title(tr("Output folder")).pick_folder()
+                {
+                    self.batch_output_dir = Some(directory);
+                }
+                if self.batch_output_dir.is_some() && ui.small_button("✖").on_hover_text(tr("Ask for the folder on each run")).clicked() {
+                    self.batch_output_dir = None;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("File names:"));
+                let mut pattern = self.options.file_name.clone().unwrap_or_else(|| "{name}.{ext}".to_string());
+                let response = ui.text_edit_singleline(&mut pattern).on_hover_text(tr(
+                    "{name} is the input file name, {ext} the format's extension, and {size}, {line_height} and {glyphs} come from the font, e.g. {name}_{size}.{ext}",
+                ));
+                if response.changed() {
+                    self.options.file_name = Some(pattern).filter(|pattern| !pattern.trim().is_empty());
+                }
+            });
+
+            if let Some(ref run) = self.batch_run {
+                let name = run
+                    .current
+                    .as_ref()
+                    .map(|input| input.file_name().unwrap_or_default().to_string_lossy())
+                    .unwrap_or_default();
+                let fraction = if run.total == 0 { 0.0 } else { run.completed as f32 / run.total as f32 };
+                let text = trf(
+                    "{0}/{1} files · {2} · {3}s",
+                    &[&run.completed, &run.total, &name, &format!("{:.1}", run.started.elapsed().as_secs_f32())],
                 );
+                ui.add(egui::ProgressBar::new(fraction).text(text));
+                ui.ctx().request_repaint_after(Duration::from_millis(100));
+            }
+
+            let (convert_queue, convert_folder) = ui
+                .horizontal(|ui| {
+                    (
+                        ui.add_enabled(idle && !self.batch_queue.is_empty(), egui::Button::new(tr("Convert queue…")))
+                            .clicked(),
+                        ui.add_enabled(idle, egui::Button::new(tr("📁 Convert folder…"))).clicked(),
+                    )
+                })
+                .inner;
+
+            if convert_folder {
+                if let Some(root) = self.input_dialog().set_title(tr("Folder to convert")).pick_folder() {
+                    self.input_dir = Some(root.clone());
+                    self.start_batch(ui.ctx(), Some(root), Vec::new());
+                }
+            } else if convert_queue {
+                self.batch_queue.reset();
+                let inputs = self.batch_queue.pending();
+                self.start_batch(ui.ctx(), None, inputs);
+            }
+        });
+    }
+
+    /// Starts a [`BatchRun`] of every font under `root`, or of `inputs` when
+    /// there is no root, asking for the output folder if none is set.
+    fn start_batch(&mut self, ctx: &egui::Context, root: Option<PathBuf>, inputs: Vec<PathBuf>) {
+        let Some(output_dir) = self
+            .batch_output_dir
+            .clone()
+            .or_else(|| self.output_dialog().set_title(tr("Output folder")).pick_folder())
+        else {
+            return;
+        };
+        self.output_dir = Some(output_dir.clone());
+
+        if root.is_some() {
+            // The queue fills in as the worker finds the folder's fonts.
+            self.batch_queue.clear();
+        }
+        let exclude = Some(self.batch_exclude.trim()).filter(|pattern| !pattern.is_empty());
+        self.batch_run = Some(BatchRun::spawn(
+            ctx,
+            root,
+            inputs,
+            output_dir,
+            exclude.map(str::to_string),
+            self.options.clone(),
+            self.batch_threads,
+        ));
+        self.status.message.clear();
+        self.status.status = None;
+    }
+
+    /// Applies progress from the running [`BatchRun`] and reports the outcome
+    /// once it is done.
+    fn poll_batch(&mut self) {
+        let Some(ref mut run) = self.batch_run else {
+            return;
+        };
+
+        let mut done = None;
+        while let Ok(message) = run.messages.try_recv() {
+            match message {
+                BatchMessage::Started { input, total } => {
+                    self.batch_queue.start(&input);
+                    run.total = total;
+                    run.current = Some(input);
+                }
+                BatchMessage::Finished { result, completed } => {
+                    run.completed = run.completed.max(completed);
+                    self.batch_queue.finish(&result);
+                }
+                BatchMessage::Done(result) => done = Some(result),
+            }
+        }
+        let Some(done) = done else {
+            return;
+        };
+        let Some(run) = self.batch_run.take() else {
+            return;
+        };
+
+        self.batch_queue.interrupt();
+        if let Err(e) = done {
+            self.status.message = trf("❌ Error reading folder: {0}", &[&e]);
+            self.status.status = Some("error".to_string());
+            return;
+        }
+        let (converted, failed) = self.batch_queue.outcome();
+
+        let (icon, status) = if failed == 0 { ("✅", "success") } else { ("⚠️", "warning") };
+        self.status.message = trf(
+            "{0} Converted {1}/{2} files to {3} in {4}s",
+            &[
+                &icon,
+                &converted,
+                &self.batch_queue.len(),
+                &run.output_dir.display(),
+                &format!("{:.1}", run.started.elapsed().as_secs_f32()),
+            ],
+        );
+        self.status.status = Some(status.to_string());
+    }
+
+    /// Compares an old and a new version of a font: the glyphs added, removed
+    /// and changed, the font-wide metrics, and the Lua modules they convert to.
+    fn compare_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        egui::CollapsingHeader::new(tr("🔀 Compare fonts")).id_salt("🔀 Compare fonts").default_open(false).show(ui, |ui| {
+            for (index, label) in ["Old:", "New:"].into_iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(tr(label));
+                    if ui.button(tr("📂 Pick font…")).clicked()
+                        && let Some(input) = self.input_dialog().add_filter(tr("FNT files"), &["fnt", "json", "gz"]).pick_file()
+                    {
+                        self.input_dir = input.parent().map(Path::to_path_buf);
+                        self.compare_inputs[index] = Some(input);
+                        self.comparison = None;
+                    }
+                    match self.compare_inputs[index] {
+                        Some(ref path) => ui.label(path.display().to_string()),
+                        None => ui.weak(tr("No font selected")),
+                    };
+                });
             }
-            Ok(Event::Empty(ref e)) if e.name().as_ref() == b"info" => {
-                for attr in e.attributes() {
-                    let attr = attr?;
-                    if attr.key.as_ref() == b"size" {
-                        font_size = std::str::from_utf8(&attr.value)?.parse()?;
+
+            let [Some(old_input), Some(new_input)] = &self.compare_inputs else {
+                return;
+            };
+            if ui.button(tr("🔀 Compare")).clicked() {
+                let fonts = parse_fnt(&old_input.display().to_string())
+                    .and_then(|old| Ok((old, parse_fnt(&new_input.display().to_string())?)));
+                // Both modules name the new file as their source so only
+                // differences in the fonts show up.
+                let source = source_name(&[new_input]);
+                let options = OutputOptions { format: OutputFormat::Lua, ..self.options.clone() };
+                let compared = fonts.and_then(|(old, new)| {
+                    let old_lua = render(&old, &source, &options)?;
+                    let new_lua = render(&new, &source, &options)?;
+                    Ok(Comparison {
+                        diff: Some(FontDiff::of(&old, &new)),
+                        lua_diff: diff_excerpt(&diff_lines(&old_lua, &new_lua), DIFF_CONTEXT),
+                    })
+                });
+                match compared {
+                    Ok(comparison) => self.comparison = Some(comparison),
+                    Err(e) => {
+                        self.report_error(trf("❌ Error parsing file: {0}", &[&e]), &e);
+                        self.comparison = None;
                     }
                 }
             }
+
+            if let Some(ref comparison) = self.comparison {
+                comparison_ui(ui, comparison, &palette);
+            }
+        });
+    }
+
+    /// Rasterizes a TrueType/OpenType font into a new BMFont, which is then
+    /// loaded like a picked file.
+    fn ttf_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("🔠 Generate from TrueType")).id_salt("🔠 Generate from TrueType").default_open(false).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr("📂 Pick font…")).clicked()
+                    && let Some(path) = self.input_dialog().add_filter(tr("Fonts"), &["ttf", "otf"]).pick_file()
+                {
+                    self.ttf_file = Some(path);
+                }
+                match self.ttf_file {
+                    Some(ref path) => ui.label(path.display().to_string()),
+                    None => ui.weak(tr("No font selected")),
+                };
+            });
+
+            let raster = &mut self.raster_options;
+            ui.horizontal(|ui| {
+                ui.label(tr("Size (px):"));
+                ui.add(egui::DragValue::new(&mut raster.size).range(4.0..=512.0));
+                ui.label(tr("Texture:"));
+                egui::ComboBox::from_id_salt("ttf_texture_size")
+                    .selected_text(format!("{0}×{0}", raster.texture_size))
+                    .show_ui(ui, |ui| {
+                        for size in [128, 256, 512, 1024, 2048, 4096] {
+                            ui.selectable_value(&mut raster.texture_size, size, format!("{size}×{size}"));
+                        }
+                    });
+                ui.label(tr("Padding:"));
+                ui.add(egui::DragValue::new(&mut raster.padding).range(0..=16));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Characters:"));
+                ui.text_edit_singleline(&mut self.ttf_charset)
+                    .on_hover_text(tr("Codepoints and ranges, e.g. 0x20-0x7E,0x400-0x4FF"));
+            });
+            ui.checkbox(&mut raster.kerning, tr("Kerning"))
+                .on_hover_text(tr("Look up kerning for every pair of glyphs; slow for very large character sets"));
+
+            if ui
+                .add_enabled(self.ttf_file.is_some(), egui::Button::new(tr("⚙ Generate BMFont…")))
+                .clicked()
+            {
+                self.generate_from_ttf(ui.ctx());
+            }
+        });
+    }
+
+    /// Writes the BMFont XML and atlas for the picked TrueType font where the
+    /// user chooses, then loads it.
+    fn generate_from_ttf(&mut self, ctx: &egui::Context) {
+        let Some(ref ttf_file) = self.ttf_file else {
+            return;
+        };
+        let charset = match Subset::parse_ranges(&self.ttf_charset) {
+            Ok(charset) => charset,
             Err(e) => {
-                eprintln!("Error parsing XML: {:?}", e);
-                break;
+                self.status.message = format!("❌ {e}");
+                self.status.status = Some("error".to_string());
+                return;
+            }
+        };
+        let stem = ttf_file.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let Some(fnt_path) = self
+            .output_dialog()
+            .set_file_name(format!("{stem}.fnt"))
+            .add_filter("BMFont", &["fnt"])
+            .save_file()
+        else {
+            return;
+        };
+
+        let options = RasterOptions {
+            charset,
+            ..self.raster_options.clone()
+        };
+        let name = fnt_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let generated = std::fs::read(ttf_file)
+            .map_err(ConvertError::from)
+            .and_then(|data| rasterize(&data, &name, &options))
+            .and_then(|raster| raster.save(&fnt_path).map(|()| raster));
+        match generated {
+            Ok(raster) => {
+                self.output_dir = fnt_path.parent().map(Path::to_path_buf);
+                self.load_file(ctx, fnt_path.display().to_string());
+                self.status.message = trf(
+                    "✅ Generated {0} glyphs on {1} page(s) to {2}",
+                    &[&raster.font.characters.len(), &raster.pages.len(), &fnt_path.display()],
+                );
+                self.status.status = Some("success".to_string());
+            }
+            Err(e) => {
+                self.status.message = trf("❌ Error generating font: {0}", &[&e]);
+                self.status.status = Some("error".to_string());
             }
-            _ => {}
         }
-        buf.clear();
     }
 
-    Ok((font_size, characters))
-}
+    /// Merges a primary font with fallbacks, each with its own scale.
+    fn merge_ui(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("🧩 Merge fonts")).id_salt("🧩 Merge fonts").show(ui, |ui| {
+            if ui.button(tr("➕ Add fonts…")).clicked()
+                && let Some(inputs) = self
+                    .input_dialog()
+                    .add_filter(tr("FNT files"), &["fnt", "json", "gz"])
+                    .pick_files()
+            {
+                self.input_dir = inputs.first().and_then(|input| input.parent()).map(Path::to_path_buf);
+                for input in inputs {
+                    if !self.merge_sources.iter().any(|(path, _)| *path == input) {
+                        self.merge_sources.push((input, 1.0));
+                    }
+                }
+            }
+
+            let mut remove = None;
+            for (index, (path, scale)) in self.merge_sources.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let role = if index == 0 { "Primary: {0}" } else { "Fallback: {0}" };
+                    ui.label(trf(role, &[&path.file_name().unwrap_or_default().to_string_lossy()]));
+                    ui.add(egui::DragValue::new(scale).speed(0.01).range(0.01..=16.0).prefix("×"))
+                        .on_hover_text(tr("Scales this font's glyph sizes, offsets and advances"));
+                    if ui.small_button("✖").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = remove {
+                self.merge_sources.remove(index);
+            }
 
-fn format_output(font_size: i32, font_data: &BTreeMap<u32, Character>) -> String {
-    let indentation = 4;
-    let spaces = " ".repeat(indentation);
-    let mut output = format!("return {{\n{spaces}Size = {font_size},\n{spaces}Characters = {{\n");
+            let clicked = ui
+                .horizontal(|ui| {
+                    let clicked = ui
+                        .add_enabled(self.merge_sources.len() > 1, egui::Button::new(tr("🧩 Merge…")))
+                        .clicked();
+                    ui.label(tr("On conflict:"));
+                    egui::ComboBox::from_id_salt("conflict_policy")
+                        .selected_text(tr(self.conflict_policy.label()))
+                        .show_ui(ui, |ui| {
+                            for policy in [ConflictPolicy::KeepFirst, ConflictPolicy::KeepLast] {
+                                ui.selectable_value(&mut self.conflict_policy, policy, tr(policy.label()));
+                            }
+                        });
+                    clicked
+                })
+                .inner;
 
-    for (id, data) in font_data {
-        let char_repr = match *id {
-            0 | 13 => "".to_string(),
-            _ => match std::char::from_u32(*id) {
-                Some(c) if c == '"' => "\\\"".to_string(), // Escape double quotes
-                Some(c) if c == '\\' => "\\\\".to_string(), // Escape backslashes
-                Some(c) if c.is_control() => format!("\\u{{{:X}}}", id),
-                Some(c) => c.to_string(),
-                None => format!("\\u{{{:X}}}", id),
-            },
+            if !clicked {
+                return;
+            }
+            let (inputs, scales): (Vec<PathBuf>, Vec<f32>) = self.merge_sources.iter().cloned().unzip();
+
+            let (font, warnings) = match merge_files(&inputs, &scales, self.conflict_policy) {
+                Ok(merged) => merged,
+                Err(e) => {
+                    self.report_error(trf("❌ Error parsing file: {0}", &[&e]), &e);
+                    return;
+                }
+            };
+            self.warnings = warnings;
+
+            if let Some(output_file) = self
+                .output_dialog()
+                .add_filter(tr(self.options.format.label()), &[self.options.format.extension()])
+                .save_file()
+            {
+                self.output_dir = output_file.parent().map(Path::to_path_buf);
+                let written = with_checksums(&self.options, &font, &inputs)
+                    .and_then(|options| render_chunks(&font, &source_name(&inputs), &options, &module_name(&output_file)))
+                    .and_then(|chunks| {
+                        // The save dialog has already asked about replacing the file.
+                        write_chunks(&chunks, &output_file, &self.options, Overwrite::Replace)?;
+                        Ok(chunks_size(&chunks, &self.options))
+                    });
+                match written {
+                    Ok(output) => {
+                        self.status.message = trf(
+                            "✅ Merged {0} files, {1} glyphs ({2} KB) to {3}",
+                            &[&inputs.len(), &font.characters.len(), &output.div_ceil(1024), &output_file.display()],
+                        );
+                        self.status.status = Some("success".to_string());
+                    }
+                    Err(e) => {
+                        self.status.message = trf("❌ Error saving file: {0}", &[&e]);
+                        self.status.status = Some("error".to_string());
+                    }
+                }
+            }
+            self.font = Some(font);
+            self.atlas = None;
+            self.selected_glyph = None;
+            self.size_estimate = None;
+        });
+    }
+
+    /// Coverage and size figures for the loaded font, to judge whether it
+    /// should be subset before export.
+    fn stats_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let Some(ref font) = self.font else {
+            return;
         };
 
-        output.push_str(&format!(
-            "{spaces}{spaces}[\"{}\"] = {{ Vector2.new({}, {}), Vector2.new({}, {}), Vector2.new({}, {}), {} }},\n",
-            char_repr, data.size.width, data.size.height, data.position.x, data.position.y, data.offset.x, data.offset.y, data.advance
-        ));
+        let source = source_name(&[Path::new(self.selected_file.as_deref().unwrap_or_default())]);
+        let dialog = self.output_dialog();
+
+        egui::CollapsingHeader::new(tr("📊 Statistics")).id_salt("📊 Statistics").default_open(false).show(ui, |ui| {
+            let stats = FontStats::of(font);
+            let options_key = serde_json::to_string(&self.options).unwrap_or_default();
+            if self.size_estimate.as_ref().is_none_or(|(key, _)| *key != options_key) {
+                let size = render(font, &source, &self.options)
+                    .map(|output| output_bytes(&output, &self.options).len())
+                    .map_err(|e| e.to_string());
+                self.size_estimate = Some((options_key, size));
+            }
+
+            egui::Grid::new("font_stats").num_columns(2).striped(true).show(ui, |ui| {
+                ui.label(tr("Glyphs"));
+                ui.label(stats.glyphs.to_string());
+                ui.end_row();
+
+                ui.label(tr("Advance"));
+                ui.label(match stats.advance_range {
+                    Some((min, max)) => format!("{min} – {max}"),
+                    None => "–".to_string(),
+                });
+                ui.end_row();
+
+                ui.label(tr("Texture utilization"));
+                ui.label(match stats.texture_utilization {
+                    Some(fraction) => format!("{:.1}%", fraction * 100.0),
+                    None => tr("unknown (no <common> texture size)").to_string(),
+                });
+                ui.end_row();
+
+                ui.label(tr("Kerning pairs"));
+                ui.label(stats.kerning_pairs.to_string());
+                ui.end_row();
+
+                let pruned = pruned_kerning(font, &self.options);
+                if pruned.total() > 0 {
+                    ui.label(tr("Pruned kerning pairs"));
+                    ui.label(trf(
+                        "{0}: {1} outside the subset, {2} with a missing glyph",
+                        &[&pruned.total(), &pruned.unexported, &pruned.missing],
+                    ));
+                    ui.end_row();
+                }
+
+                ui.label(trf("Estimated {0} size", &[&tr(self.options.format.label())]));
+                match self.size_estimate {
+                    Some((_, Ok(size))) => ui.label(format!("{} KB", size.div_ceil(1024))),
+                    Some((_, Err(ref e))) => ui.label(RichText::new(e).color(palette.error)),
+                    None => ui.label("–"),
+                };
+                ui.end_row();
+            });
+
+            ui.label(trf("Unicode blocks ({0}):", &[&stats.blocks.len()]));
+            for (block, count) in &stats.blocks {
+                ui.label(format!("  {block}: {count}"));
+            }
+            if stats.other_glyphs > 0 {
+                ui.weak(trf("  Other: {0}", &[&stats.other_glyphs]));
+            }
+
+            if ui.button(tr("📄 Export coverage report…")).clicked()
+                && let Some(path) = dialog
+                    .add_filter("JSON", &[ReportFormat::Json.extension()])
+                    .add_filter("Markdown", &[ReportFormat::Markdown.extension()])
+                    .save_file()
+            {
+                let format = if path.extension().is_some_and(|ext| ext == ReportFormat::Markdown.extension()) {
+                    ReportFormat::Markdown
+                } else {
+                    ReportFormat::Json
+                };
+                let report = CoverageReport::of(font).render(format, &source);
+                match std::fs::write(&path, report) {
+                    Ok(()) => {
+                        self.status.message = trf("✅ Saved {0} coverage report to {1}", &[&format.label(), &path.display()]);
+                        self.status.status = Some("success".to_string());
+                    }
+                    Err(e) => {
+                        self.status.message = trf("❌ Error saving file: {0}", &[&e]);
+                        self.status.status = Some("error".to_string());
+                    }
+                }
+            }
+        });
     }
 
-    output.push_str(&format!("{spaces}}}\n}}\n"));
-    output
-}
+    /// Checks the loaded font against a set of required characters, typed in
+    /// or loaded from a text file.
+    fn coverage_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let Some(ref font) = self.font else {
+            return;
+        };
+        let dialog = self.input_dialog();
 
-struct ParsingStatus {
-    message: String,
-    status: Option<String>
-}
+        egui::CollapsingHeader::new(tr("✅ Required characters")).id_salt("✅ Required characters").default_open(false).show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.required_chars)
+                    .desired_rows(3)
+                    .hint_text(tr("Paste the text the font must render")),
+            );
+            if ui.button(tr("📂 Load from file…")).clicked()
+                && let Some(path) = dialog.add_filter(tr("Text"), &["txt"]).pick_file()
+            {
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => self.required_chars = text,
+                    Err(e) => {
+                        self.status.message = trf("❌ Error reading {0}: {1}", &[&path.display(), &e]);
+                        self.status.status = Some("error".to_string());
+                    }
+                }
+            }
 
-struct FontParserApp {
-    selected_file: Option<String>,
-    status: ParsingStatus
+            if self.required_chars.is_empty() {
+                return;
+            }
+            let missing = missing_characters(font, &self.required_chars);
+            if missing.is_empty() {
+                ui.label(RichText::new(tr("All required characters are present")).color(palette.success));
+                return;
+            }
+            ui.label(
+                RichText::new(trf("❌ Missing {0} character(s):", &[&missing.len()])).color(palette.error),
+            );
+            egui::ScrollArea::vertical().id_salt("missing_chars").max_height(120.0).show(ui, |ui| {
+                for c in missing {
+                    ui.label(format!("{c} (U+{:04X})", c as u32));
+                }
+            });
+        });
+    }
+
+    /// Draws the texture pages with every glyph rectangle outlined; hovering a
+    /// rectangle shows that glyph's metrics.
+    fn atlas_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let (Some(font), Some(file)) = (&self.font, &self.selected_file) else {
+            return;
+        };
+        let dialog = self.output_dialog();
+        let mut repack_clicked = false;
+        let mut split_clicked = false;
+
+        egui::CollapsingHeader::new(tr("🖼 Atlas")).id_salt("🖼 Atlas").default_open(false).show(ui, |ui| {
+            let atlas = self.atlas.get_or_insert_with(|| Atlas::load(ui.ctx(), font, Path::new(file)));
+
+            repack_clicked = ui
+                .button(tr("📦 Repack atlas…"))
+                .on_hover_text(tr("Copy the glyphs into the smallest power-of-two texture that holds them and save it as a new font"))
+                .clicked();
+
+            if font.uses_channels() {
+                split_clicked = ui
+                    .button(tr("🎨 Split channels…"))
+                    .on_hover_text(tr("Save the font with a page per atlas channel, for runtimes that cannot pick a channel in a shader"))
+                    .clicked();
+            }
+
+            if ui
+                .button(tr("🌐 Export contact sheet…"))
+                .on_hover_text(tr("An HTML page or PNG image showing every glyph cut out of the atlas"))
+                .clicked()
+                && let Some(path) = dialog.add_filter("HTML", &["html"]).add_filter("PNG", &["png"]).save_file()
+            {
+                let saved = if is_png(&path) {
+                    load_pages(font, Path::new(file).parent().unwrap_or(Path::new("")))
+                        .and_then(|pages| Ok(render_contact_sheet(font, &pages).save(&path)?))
+                } else {
+                    let source = source_name(&[Path::new(file)]);
+                    let html = write_contact_sheet(font, &source, &page_prefix(Path::new(file), &path));
+                    std::fs::write(&path, html).map_err(ConvertError::from)
+                };
+                match saved {
+                    Ok(()) => {
+                        self.status.message = trf("✅ Saved contact sheet to {0}", &[&path.display()]);
+                        self.status.status = Some("success".to_string());
+                    }
+                    Err(e) => {
+                        self.status.message = trf("❌ Error saving file: {0}", &[&e]);
+                        self.status.status = Some("error".to_string());
+                    }
+                }
+            }
+
+            if atlas.pages.len() > 1 {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Page:"));
+                    egui::ComboBox::from_id_salt("atlas_page")
+                        .selected_text(atlas.page.to_string())
+                        .show_ui(ui, |ui| {
+                            for id in atlas.pages.keys() {
+                                ui.selectable_value(&mut atlas.page, *id, id.to_string());
+                            }
+                        });
+                });
+            }
+
+            let texture = match atlas.pages.get(&atlas.page) {
+                Some(Ok(texture)) => texture,
+                Some(Err(e)) => {
+                    ui.label(RichText::new(format!("❌ {e}")).color(palette.error));
+                    return;
+                }
+                None => {
+                    ui.weak(tr("The font references no texture pages"));
+                    return;
+                }
+            };
+
+            let size = texture.size_vec2();
+            let fit = (ui.available_width() / size.x).min(ATLAS_VIEW_HEIGHT / size.y).min(1.0);
+            ui.horizontal(|ui| {
+                let zoom = atlas.zoom.unwrap_or(fit);
+                ui.label(format!("{:.0}%", zoom * 100.0));
+                if ui.button(tr("Fit")).clicked() {
+                    atlas.zoom = None;
+                    atlas.pan = egui::Vec2::ZERO;
+                }
+                if ui.button("1:1").clicked() {
+                    atlas.zoom = Some(1.0);
+                }
+                ui.weak(tr("Scroll to zoom, drag to pan, click a glyph to find it in the table"));
+            });
+
+            let view_size = egui::vec2(ui.available_width(), ATLAS_VIEW_HEIGHT.min(size.y * fit).max(ATLAS_VIEW_HEIGHT / 2.0));
+            let (response, painter) = ui.allocate_painter(view_size, egui::Sense::click_and_drag());
+            let view = response.rect;
+            let painter = painter.with_clip_rect(view);
+            painter.rect_stroke(view, 0.0, Stroke::new(1.0, palette.border), egui::StrokeKind::Inside);
+
+            let mut zoom = atlas.zoom.unwrap_or(fit);
+            if response.dragged() {
+                atlas.pan += response.drag_delta();
+            }
+            if let Some(pointer) = response.hover_pos() {
+                // Taken so the panel around the atlas does not scroll too.
+                let scroll = ui.input_mut(|i| std::mem::take(&mut i.smooth_scroll_delta).y);
+                let factor = ui.input(|i| i.zoom_delta()) * (scroll * 0.002).exp();
+                if factor != 1.0 {
+                    let zoomed = (zoom * factor).clamp(ATLAS_MIN_ZOOM, ATLAS_MAX_ZOOM);
+                    // Keep the texel under the pointer where it is.
+                    let anchor = pointer - view.min;
+                    atlas.pan = anchor - (anchor - atlas.pan) * (zoomed / zoom);
+                    zoom = zoomed;
+                    atlas.zoom = Some(zoom);
+                }
+            }
+
+            let origin = view.min + atlas.pan;
+            let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+            painter.image(texture.id(), egui::Rect::from_min_size(origin, size * zoom), uv, Color32::WHITE);
+
+            let mut hovered = None;
+            for (id, data) in font.characters.iter().filter(|(_, data)| data.page == atlas.page) {
+                let rect = egui::Rect::from_min_size(
+                    origin + egui::vec2(data.position.x, data.position.y) * zoom,
+                    egui::vec2(data.size.width, data.size.height) * zoom,
+                );
+                if !rect.intersects(view) {
+                    continue;
+                }
+                let is_hovered = response.hover_pos().is_some_and(|pos| rect.contains(pos));
+                let color = if is_hovered {
+                    palette.warning
+                } else if self.selected_glyph == Some(*id) {
+                    palette.success
+                } else {
+                    palette.accent
+                };
+                painter.rect_stroke(rect, 0.0, Stroke::new(1.0, color), egui::StrokeKind::Inside);
+                if is_hovered {
+                    hovered = Some((*id, data));
+                }
+            }
+
+            if let Some((id, data)) = hovered {
+                if response.clicked() {
+                    self.selected_glyph = Some(id);
+                    self.scroll_to_glyph = true;
+                }
+                response.on_hover_ui_at_pointer(|ui| {
+                    ui.strong(format!("{} (U+{id:04X})", glyph_label(id)));
+                    ui.label(trf("Offset: {0}, {1}", &[&data.offset.x, &data.offset.y]));
+                    ui.label(trf("Advance: {0}", &[&data.advance]));
+                });
+            }
+        });
+
+        if repack_clicked {
+            self.repack_atlas(ui.ctx());
+        }
+        if split_clicked {
+            self.split_atlas_channels(ui.ctx());
+        }
+    }
+
+    /// Saves the loaded font with its glyphs copied into a new, minimal atlas,
+    /// then loads the result.
+    fn repack_atlas(&mut self, ctx: &egui::Context) {
+        let (Some(font), Some(file)) = (&self.font, &self.selected_file) else {
+            return;
+        };
+        let Some(fnt_path) = self
+            .output_dialog()
+            .add_filter("BMFont", &["fnt"])
+            .set_file_name("packed.fnt")
+            .save_file()
+        else {
+            return;
+        };
+
+        let name = fnt_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let packed = load_pages(font, Path::new(file).parent().unwrap_or(Path::new("")))
+            .and_then(|pages| repack(font, &pages, &name, 1, 4096))
+            .and_then(|packed| packed.save(&fnt_path).map(|()| packed));
+        match packed {
+            Ok(packed) => {
+                let old_pages = font.metrics.pages.len();
+                self.output_dir = fnt_path.parent().map(Path::to_path_buf);
+                self.load_file(ctx, fnt_path.display().to_string());
+                self.status.message = trf(
+                    "✅ Repacked {0} page(s) into {1} page(s) at {2}",
+                    &[&old_pages, &packed.pages.len(), &fnt_path.display()],
+                );
+                self.status.status = Some("success".to_string());
+            }
+            Err(e) => self.report_error(trf("❌ Error repacking atlas: {0}", &[&e]), &e),
+        }
+    }
+
+    /// Saves the loaded channel-packed font with a page per channel, then
+    /// loads the result.
+    fn split_atlas_channels(&mut self, ctx: &egui::Context) {
+        let (Some(font), Some(file)) = (&self.font, &self.selected_file) else {
+            return;
+        };
+        let Some(fnt_path) = self
+            .output_dialog()
+            .add_filter("BMFont", &["fnt"])
+            .set_file_name("channels.fnt")
+            .save_file()
+        else {
+            return;
+        };
+
+        let name = fnt_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let split = load_pages(font, Path::new(file).parent().unwrap_or(Path::new("")))
+            .map(|pages| split_channels(font, &pages, &name))
+            .and_then(|split| split.save(&fnt_path).map(|()| split));
+        match split {
+            Ok(split) => {
+                let old_pages = font.metrics.pages.len();
+                self.output_dir = fnt_path.parent().map(Path::to_path_buf);
+                self.load_file(ctx, fnt_path.display().to_string());
+                self.status.message = trf(
+                    "✅ Split {0} page(s) into {1} page(s) at {2}",
+                    &[&old_pages, &split.pages.len(), &fnt_path.display()],
+                );
+                self.status.status = Some("success".to_string());
+            }
+            Err(e) => self.report_error(trf("❌ Error splitting channels: {0}", &[&e]), &e),
+        }
+    }
+
+    /// Renders typed text from the atlas textures using the parsed metrics.
+    fn sample_text_ui(&mut self, ui: &mut egui::Ui) {
+        let palette = self.theme.palette();
+        let (Some(font), Some(file)) = (&self.font, &self.selected_file) else {
+            return;
+        };
+
+        egui::CollapsingHeader::new(tr("🔤 Sample text")).id_salt("🔤 Sample text").default_open(false).show(ui, |ui| {
+            ui.add(egui::TextEdit::multiline(&mut self.sample_text).desired_rows(2));
+            let atlas = self.atlas.get_or_insert_with(|| Atlas::load(ui.ctx(), font, Path::new(file)));
+
+            let placed = layout_text(font, &self.sample_text);
+            let width = placed
+                .iter()
+                .map(|glyph| glyph.x + glyph.character.size.width)
+                .fold(0.0, f32::max);
+            let height = placed
+                .iter()
+                .map(|glyph| glyph.y + glyph.character.size.height)
+                .fold(0.0, f32::max);
+
+            egui::ScrollArea::horizontal().id_salt("sample_text").show(ui, |ui| {
+                let (response, painter) = ui.allocate_painter(egui::vec2(width, height), egui::Sense::hover());
+                for glyph in &placed {
+                    let Some(Ok(texture)) = atlas.pages.get(&glyph.character.page) else {
+                        continue;
+                    };
+                    let texture_size = texture.size_vec2();
+                    let data = glyph.character;
+                    let rect = egui::Rect::from_min_size(
+                        response.rect.min + egui::vec2(glyph.x, glyph.y),
+                        egui::vec2(data.size.width, data.size.height),
+                    );
+                    let uv = egui::Rect::from_min_size(
+                        egui::pos2(data.position.x / texture_size.x, data.position.y / texture_size.y),
+                        egui::vec2(data.size.width / texture_size.x, data.size.height / texture_size.y),
+                    );
+                    painter.image(texture.id(), rect, uv, Color32::WHITE);
+                }
+            });
+
+            let missing: String = self
+                .sample_text
+                .chars()
+                .filter(|c| *c != '\n' && !font.characters.contains_key(&(*c as u32)))
+                .collect();
+            if !missing.is_empty() {
+                ui.label(RichText::new(trf("⚠️ Not in font: {0}", &[&missing])).color(palette.warning));
+            }
+        });
+    }
+
+    /// The loaded glyphs as parsed, opened as soon as a picked font loads so a
+    /// bad parse shows before anything is converted. In edit mode offsets and
+    /// advances are editable and each glyph has a button to delete it. Edits
+    /// are what gets converted and exported, and can be undone with Ctrl+Z
+    /// and redone with Ctrl+Shift+Z or Ctrl+Y.
+    fn glyph_table(&mut self, ui: &mut egui::Ui) {
+        let Some(ref mut font) = self.font else {
+            return;
+        };
+
+        let history = &mut self.edit_history;
+        if !ui.input(|i| i.pointer.any_down()) {
+            history.dragging = None;
+        }
+        // Undo in a focused text field is the field's own.
+        let mut step = None;
+        if self.editing_glyphs && !ui.ctx().wants_keyboard_input() {
+            let redo = [
+                egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z),
+                egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y),
+            ];
+            // Checked first, as Ctrl+Z also matches with Shift held.
+            if ui.input_mut(|i| redo.iter().any(|shortcut| i.consume_shortcut(shortcut))) {
+                step = Some(false);
+            } else if ui.input_mut(|i| i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z))) {
+                step = Some(true);
+            }
+        }
+
+        let mut edited = false;
+        let title = if self.font_edited { "🔍 Glyphs ({0}, edited)" } else { "🔍 Glyphs ({0})" };
+        let scroll_to = self.selected_glyph.filter(|_| std::mem::take(&mut self.scroll_to_glyph));
+        if scroll_to.is_some_and(|id| !glyph_matches(id, &self.glyph_filter)) {
+            self.glyph_filter.clear();
+        }
+        egui::CollapsingHeader::new(trf(title, &[&font.characters.len()]))
+            .id_salt("glyph_table")
+            .default_open(false)
+            .open((scroll_to.is_some() || std::mem::take(&mut self.reveal_glyphs)).then_some(true))
+            .show(ui, |ui| {
+                let mut trim = false;
+                let editing = &mut self.editing_glyphs;
+                ui.horizontal(|ui| {
+                    ui.toggle_value(editing, tr("✏ Edit"))
+                        .on_hover_text(tr("Edit offsets and advances, delete glyphs and trim rects before exporting"));
+                    if !*editing {
+                        return;
+                    }
+                    ui.separator();
+                    trim = ui
+                        .button(tr("✂ Trim rects to alpha"))
+                        .on_hover_text(tr("Shrink each glyph to the opaque pixels of its atlas page, moving offsets to match"))
+                        .clicked();
+                    ui.separator();
+                    if ui
+                        .add_enabled(!history.undo.is_empty(), egui::Button::new(tr("↶ Undo")))
+                        .on_hover_text(tr("Undo the last glyph edit (Ctrl+Z)"))
+                        .clicked()
+                    {
+                        step = Some(true);
+                    }
+                    if ui
+                        .add_enabled(!history.redo.is_empty(), egui::Button::new(tr("↷ Redo")))
+                        .on_hover_text(tr("Redo the last undone glyph edit (Ctrl+Shift+Z)"))
+                        .clicked()
+                    {
+                        step = Some(false);
+                    }
+                });
+                if trim {
+                    let directory = self.selected_file.as_deref().and_then(|file| Path::new(file).parent());
+                    match load_pages(font, directory.unwrap_or(Path::new(""))) {
+                        Ok(pages) => {
+                            let before = font.characters.clone();
+                            let trimmed = trim_glyphs(font, &pages, 0);
+                            if trimmed > 0 {
+                                history.record(before.into_iter().map(|(id, glyph)| (id, Some(glyph))).collect());
+                                edited = true;
+                            }
+                            self.status.message = trf("✂ Trimmed {0} glyph rects", &[&trimmed]);
+                            self.status.status = Some("success".to_string());
+                        }
+                        Err(e) => {
+                            self.status.message = trf("❌ Error loading atlas: {0}", &[&e]);
+                            self.status.status = Some("error".to_string());
+                        }
+                    }
+                }
+
+                let characters = &mut font.characters;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Filter:"));
+                    ui.text_edit_singleline(&mut self.glyph_filter)
+                        .on_hover_text(tr("Character, decimal id, hex codepoint (0x41 / U+0041) or Unicode block name"));
+                    let mut blocks: Vec<&str> = characters.keys().filter_map(|id| block_of(*id)).collect();
+                    blocks.dedup();
+                    egui::ComboBox::from_id_salt("glyph_block")
+                        .selected_text(tr("Block"))
+                        .show_ui(ui, |ui| {
+                            for block in blocks {
+                                ui.selectable_value(&mut self.glyph_filter, block.to_string(), block);
+                            }
+                        });
+                });
+
+                let ids: Vec<u32> = characters
+                    .keys()
+                    .copied()
+                    .filter(|id| glyph_matches(*id, &self.glyph_filter))
+                    .collect();
+                let row_height = ui.spacing().interact_size.y;
+                let editing = self.editing_glyphs;
+                let titles: &[&str] = if editing {
+                    &["Char", "Id", "Block", "Size", "Position", "Offset", "Advance", ""]
+                } else {
+                    &["Char", "Id", "Block", "Size", "Position", "Offset", "Advance"]
+                };
+                let mut deleted = None;
+
+                let mut table = egui_extras::TableBuilder::new(ui);
+                if let Some(index) = scroll_to.and_then(|id| ids.iter().position(|row| *row == id)) {
+                    table = table.scroll_to_row(index, Some(egui::Align::Center));
+                }
+                table
+                    .striped(true)
+                    .max_scroll_height(240.0)
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                    .columns(egui_extras::Column::auto(), titles.len())
+                    .header(row_height, |mut header| {
+                        for title in titles {
+                            header.col(|ui| {
+                                ui.strong(tr(title));
+                            });
+                        }
+                    })
+                    .body(|body| {
+                        body.rows(row_height, ids.len(), |mut row| {
+                            let id = ids[row.index()];
+                            let Some(data) = characters.get_mut(&id) else {
+                                return;
+                            };
+                            row.set_selected(self.selected_glyph == Some(id));
+                            row.col(|ui| {
+                                ui.label(glyph_label(id));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{id} (U+{id:04X})"));
+                            });
+                            row.col(|ui| {
+                                ui.weak(block_of(id).unwrap_or_default());
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{}×{}", data.size.width, data.size.height));
+                            });
+                            row.col(|ui| {
+                                ui.label(format!("{}, {}", data.position.x, data.position.y));
+                            });
+                            if !editing {
+                                row.col(|ui| {
+                                    ui.label(format!("{}, {}", data.offset.x, data.offset.y));
+                                });
+                                row.col(|ui| {
+                                    ui.label(data.advance.to_string());
+                                });
+                                return;
+                            }
+                            let before = data.clone();
+                            let mut responses = Vec::new();
+                            row.col(|ui| {
+                                responses.push(ui.add(egui::DragValue::new(&mut data.offset.x).speed(0.1)));
+                                responses.push(ui.add(egui::DragValue::new(&mut data.offset.y).speed(0.1)));
+                            });
+                            row.col(|ui| {
+                                responses.push(ui.add(egui::DragValue::new(&mut data.advance).speed(0.1)));
+                            });
+                            if let Some(response) = responses.iter().find(|response| response.changed()) {
+                                if !response.dragged() || history.dragging != Some(response.id) {
+                                    history.record(vec![(id, Some(before))]);
+                                }
+                                history.dragging = response.dragged().then_some(response.id);
+                                edited = true;
+                            }
+                            row.col(|ui| {
+                                if ui.small_button("🗑").on_hover_text(tr("Delete this glyph")).clicked() {
+                                    deleted = Some(id);
+                                }
+                            });
+                        });
+                    });
+
+                if let Some(id) = deleted
+                    && let Some(glyph) = characters.remove(&id)
+                {
+                    history.record(vec![(id, Some(glyph))]);
+                    edited = true;
+                }
+            });
+
+        if let Some(undo) = step {
+            let characters = &mut font.characters;
+            edited |= if undo { history.undo(characters) } else { history.redo(characters) };
+        }
+        if edited {
+            self.font_edited = true;
+            // The preview and warnings describe the font before the edit.
+            self.preview = None;
+            self.size_estimate = None;
+            self.warnings = collect_warnings(font);
+        }
+    }
 }
 
 impl Default for FontParserApp {
     fn default() -> Self {
         Self {
             selected_file: None,
+            theme: Theme::default(),
+            language: Language::default(),
             status: ParsingStatus {
                 message: String::new(),
                 status: None
-            }
+            },
+            options: OutputOptions {
+                header: Some(HeaderOptions::default()),
+                ..Default::default()
+            },
+            function_name: "vec2".to_string(),
+            presets: Vec::new(),
+            preset: None,
+            quick_convert: false,
+            quick_output_dir: None,
+            confirm_overwrite: None,
+            overwrite_diff: None,
+            batch_output_dir: None,
+            rojo_project: None,
+            rojo_folder: rojo::DEFAULT_FOLDER.to_string(),
+            font: None,
+            preview: None,
+            atlas: None,
+            selected_glyph: None,
+            scroll_to_glyph: false,
+            sample_text: "The quick brown fox jumps over the lazy dog".to_string(),
+            subset_ranges: "0x20-0x7E".to_string(),
+            subset_text: String::new(),
+            priority_ranges: "0x20-0x7E".to_string(),
+            required_chars: String::new(),
+            glyph_filter: String::new(),
+            editing_glyphs: false,
+            reveal_glyphs: false,
+            warnings: Vec::new(),
+            batch_queue: JobQueue::default(),
+            batch_exclude: String::new(),
+            batch_threads: 0,
+            conflict_policy: ConflictPolicy::KeepFirst,
+            history: Vec::new(),
+            merge_sources: Vec::new(),
+            compare_inputs: [None, None],
+            comparison: None,
+            ttf_file: None,
+            raster_options: RasterOptions::default(),
+            ttf_charset: "0x20-0x7E".to_string(),
+            input_dir: None,
+            output_dir: None,
+            watch: None,
+            parse_failure: None,
+            parse_cache: Arc::default(),
+            job: None,
+            batch_run: None,
+            log: LogBuffer::default(),
+            font_edited: false,
+            edit_history: EditHistory::default(),
+            size_estimate: None,
+            command_palette: None,
+            tabs: vec![FontTab::default()],
+            active_tab: 0,
         }
     }
 }
 
 impl eframe::App for FontParserApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, HISTORY_KEY, &self.history);
+        let settings = Settings {
+            options: self.options.clone(),
+            function_name: self.function_name.clone(),
+            subset_ranges: self.subset_ranges.clone(),
+            subset_text: self.subset_text.clone(),
+            priority_ranges: Some(self.priority_ranges.clone()),
+            batch_threads: self.batch_threads,
+            input_dir: self.input_dir.clone(),
+            output_dir: self.output_dir.clone(),
+            required_chars: self.required_chars.clone(),
+            presets: self.presets.clone(),
+            theme: self.theme,
+            language: self.language,
+            quick_convert: self.quick_convert,
+            quick_output_dir: self.quick_output_dir.clone(),
+            batch_output_dir: self.batch_output_dir.clone(),
+            rojo_project: self.rojo_project.clone(),
+            rojo_folder: Some(self.rojo_folder.clone()),
+        };
+        eframe::set_value(storage, SETTINGS_KEY, &settings);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        ctx.style_mut(|style| {
-            style.visuals.override_text_color = Some(Color32::from_rgb(204, 214, 244));
-            style.visuals.panel_fill = Color32::from_rgb(17, 17, 27);
-        });
+        let palette = self.theme.palette();
+        palette.apply(ctx);
+        set_language(self.language);
+
+        self.handle_dropped_files(ctx);
+        self.handle_shortcuts(ctx);
+        self.overwrite_prompt(ctx);
+        self.command_palette_ui(ctx);
+        self.poll_watch(ctx);
+        self.poll_job(ctx);
+        self.poll_batch();
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("🎨 .fnt to .lua Converter");
+            ui.horizontal(|ui| {
+                ui.heading(tr("🎨 .fnt to .lua Converter"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    egui::ComboBox::from_id_salt("theme")
+                        .selected_text(tr(self.theme.label()))
+                        .show_ui(ui, |ui| {
+                            for theme in Theme::ALL {
+                                ui.selectable_value(&mut self.theme, theme, tr(theme.label()));
+                            }
+                        });
+                    ui.label(tr("Theme:"));
+                    egui::ComboBox::from_id_salt("language")
+                        .selected_text(self.language.label())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(&mut self.language, language, language.label());
+                            }
+                        });
+                    ui.label("🌐");
+                    if ui
+                        .button("🔎")
+                        .on_hover_text(trf("Command palette ({0})", &[&ctx.format_shortcut(&PALETTE_SHORTCUT)]))
+                        .clicked()
+                    {
+                        self.command_palette = Some(CommandPalette::default());
+                    }
+                });
+            });
+            ui.separator();
+            self.tabs_ui(ui);
             ui.separator();
 
-            let response = ui.add(
-                egui::Button::new(
-                    egui::RichText::new("📂 Select .fnt file")
-                        .size(12.0)
-                        .color(Color32::from_rgb(204, 214, 244)),
-                )
-                .corner_radius(4.0)
-                .fill(Color32::from_rgb(17, 17, 27)) // Default background color
-                .stroke(Stroke::new(1.0, Color32::from_rgb(49, 50, 68))) // Default border
-            );
-            
-            if response.hovered() {
-                // Re-render the button with the hover styles
-                ui.painter().rect_filled(
-                    response.rect,
-                    4.0,
-                    Color32::from_rgb(137, 180, 250), // Hover background
-                );
-            
-                ui.painter().text(
-                    response.rect.center(),
-                    egui::Align2::CENTER_CENTER,
-                    "📂 Select .fnt file",
-                    egui::FontId::proportional(12.0),
-                    Color32::from_rgb(17, 17, 27), // Hover text color
-                );
+            let response = ui
+                .horizontal(|ui| {
+                    let response = themed_button(
+                        ui,
+                        tr("📂 Select .fnt file"),
+                        12.0,
+                        4.0,
+                        &palette.secondary_button(),
+                    );
+                    self.recent_menu(ui);
+                    response
+                })
+                .inner;
 
-                ui.painter().rect_stroke(
-                    response.rect,
-                    4.0,
-                    Stroke::new(1.0, Color32::from_rgb(137, 180, 250)),
-                    egui::StrokeKind::Outside
-                );
+            if response.on_hover_text(ctx.format_shortcut(&OPEN_SHORTCUT)).clicked() {
+                self.dispatch(ctx, Action::Open);
             }
 
-            if response.clicked() {
-                if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("FNT files", &["fnt"])
-                    .pick_file()
-                {
-                    self.selected_file = Some(path.display().to_string());
-                    self.status.message.clear();
-                    self.status.status = None;
-                }
+            if let Some(ref file) = self.selected_file {
+                ui.label(trf("📄 Selected: {0}", &[file]));
             }
+            self.glyph_table(ui);
 
-            if let Some(ref file) = self.selected_file {
-                ui.label(format!("📄 Selected: {}", file));
+            self.options_ui(ui);
+
+            let (convert, copy) = ui
+                .horizontal(|ui| {
+                    let convert_button = themed_button(
+                        ui,
+                        tr("⚡ Convert"),
+                        20.0,
+                        8.0,
+                        &palette.primary_button(),
+                    );
+                    let copy_button = ui
+                        .button(tr("📋 Copy to clipboard"))
+                        .on_hover_text(trf(
+                            "Convert and copy the output without saving a file ({0})",
+                            &[&ctx.format_shortcut(&COPY_SHORTCUT)],
+                        ));
+                    let convert_button = convert_button.on_hover_text(trf(
+                        "Convert and preview the output; {0} also saves it",
+                        &[&ctx.format_shortcut(&SAVE_SHORTCUTS[1])],
+                    ));
+                    (convert_button.clicked(), copy_button.clicked())
+                })
+                .inner;
+
+            self.quick_convert_ui(ui);
+            self.rojo_ui(ui);
+            if convert {
+                self.dispatch(ctx, Action::Convert);
+            }
+            if copy {
+                self.dispatch(ctx, Action::Copy);
             }
+            self.job_ui(ui);
 
-            let convert_button = ui.add(
-                egui::Button::new(
-                    egui::RichText::new("⚡ Convert")
-                        .size(20.0) // Larger text
-                        .color(Color32::from_rgb(17, 17, 27)), // Dark text
-                )
-                .corner_radius(8.0)
-                .fill(Color32::from_rgb(137, 180, 250)) // Gradient-like blue
-            );
-            
-            // Hover effect
-            if convert_button.hovered() {
-                ui.painter().rect_filled(
-                    convert_button.rect,
-                    8.0,
-                    Color32::from_rgb(203, 166, 247), // Lighter blue on hover
-                );
+            self.preview_ui(ui);
+            self.watch_ui(ui);
 
-                ui.painter().text(
-                    convert_button.rect.center(),
-                    egui::Align2::CENTER_CENTER,
-                    "⚡ Convert",
-                    egui::FontId::proportional(20.0),
-                    Color32::from_rgb(17, 17, 27), // Hover text color
-                );
+            let (import, export) = ui
+                .horizontal(|ui| {
+                    let import = ui
+                        .button(tr("📥 Import Lua…"))
+                        .on_hover_text(tr("Load a previously generated Lua module, e.g. to export it as .fnt"));
+                    let export = ui.add_enabled(self.font.is_some(), egui::Button::new(tr("💾 Export .fnt")));
+                    (import.clicked(), export.clicked())
+                })
+                .inner;
+
+            if import
+                && let Some(path) = self.input_dialog().add_filter(tr("Lua files"), &["lua"]).pick_file()
+            {
+                self.load_file(ctx, path.display().to_string());
             }
 
-            if convert_button.clicked() {
-                if let Some(ref file) = self.selected_file {
-                    if let Ok((font_size, font_data)) = parse_fnt(file) {
-                        if let Some(output_file) = rfd::FileDialog::new()
-                            .add_filter("Lua files", &["lua"])
-                            .save_file()
-                        {
-                            match std::fs::write(&output_file, format_output(font_size, &font_data)) {
-                                Ok(_) => {
-                                    self.status.message = format!("✅ Saved to {}", output_file.display());
-                                    self.status.status = Some("success".to_string());
-                                }
-                                Err(e) => {
-                                    self.status.message = format!("❌ Error saving file: {}", e);
-                                    self.status.status = Some("error".to_string());
-                                }
-                            }
-                        }
-                    } else {
-                        self.status.message = "❌ Error parsing file!".to_string();
+            if let Some(ref font) = self.font
+                && export
+                && let Some(output_file) = self
+                    .output_dialog()
+                    .add_filter(tr("FNT files"), &["fnt"])
+                    .save_file()
+            {
+                self.output_dir = output_file.parent().map(Path::to_path_buf);
+                match write_atomic(&output_file, write_fnt_xml(font, false)) {
+                    Ok(_) => {
+                        self.status.message = trf("✅ Saved to {0}", &[&output_file.display()]);
+                        self.status.status = Some("success".to_string());
+                    }
+                    Err(e) => {
+                        self.status.message = trf("❌ Error saving file: {0}", &[&e]);
                         self.status.status = Some("error".to_string());
                     }
-                } else {
-                    self.status.message = "⚠️ Please select a .fnt file first".to_string();
-                    self.status.status = Some("warning".to_string());
                 }
             }
 
+            self.batch_ui(ui);
+            self.merge_ui(ui);
+            self.compare_ui(ui);
+            self.ttf_ui(ui);
+            self.history_ui(ui);
+            self.log_ui(ui);
+
             if !self.status.message.is_empty() {
                 let message = RichText::new(self.status.message.clone())
-                    .color(match self.status.status.as_deref() {
-                        Some("success") => Color32::from_rgb(166, 227, 161),
-                        Some("error") => Color32::from_rgb(243, 139, 168),
-                        Some("warning") => Color32::from_rgb(249, 226, 175),
-                        _ => Color32::from_rgb(204, 214, 244),
-                    });
+                    .color(palette.status(self.status.status.as_deref()));
                 ui.label(message.clone());
             }
+
+            self.error_panel(ui);
+
+            for warning in &self.warnings {
+                ui.label(RichText::new(format!("⚠️ {warning}")).color(palette.warning));
+            }
+
+            self.stats_ui(ui);
+            self.coverage_ui(ui);
+            self.sample_text_ui(ui);
+            self.atlas_ui(ui);
         });
     }
 }
 
+/// The directory watched for changes to `input`. Parent directories are
+/// watched rather than the files themselves so editors that save by replacing
+/// the file are still picked up.
+fn watch_directory(input: &Path) -> &Path {
+    match input.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Whether `event` creates or modifies a file named one of `names`.
+fn touches_input(event: &notify::Event, names: &[&OsStr]) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name().is_some_and(|name| names.contains(&name)))
+}
+
+/// The font the app was launched with by a file association or "Open
+/// with", which pass its path as the only argument. Such a launch opens the
+/// GUI with the font loaded, unless a project config governs the font, in
+/// which case it is converted as on the command line.
+fn launched_file() -> Option<PathBuf> {
+    let mut args = std::env::args_os().skip(1);
+    let file = PathBuf::from(args.next()?);
+    let is_flag = file.to_string_lossy().starts_with('-');
+    (args.next().is_none() && !is_flag && file.is_file() && find_project_config(&file).is_none()).then_some(file)
+}
+
 fn main() -> Result<(), eframe::Error> {
+    let file = launched_file();
+    if file.is_none() && std::env::args_os().len() > 1 {
+        if let Err(e) = parse_cli().and_then(run_cli) {
+            eprintln!("Error: {e}");
+            std::process::exit(exit_code(e.as_ref()));
+        }
+        return Ok(());
+    }
+
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([300.0, 200.0]).with_title("Converter"), // Smaller window
+        viewport: egui::ViewportBuilder::default().with_inner_size([480.0, 480.0]).with_title("Converter"),
         ..Default::default()
     };
 
+    let log = log_to_buffer();
     eframe::run_native(
         "Converter",
         options,
-        Box::new(|_cc| Ok(Box::new(FontParserApp::default()))),
+        Box::new(|cc| Ok(Box::new(FontParserApp::new(cc, log, file)))),
     )
 }
@@ -0,0 +1,113 @@
+//! Combining several fonts into one.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::parser::parse_fnt;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Which glyph wins when merged fonts define the same codepoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    /// The first font is the primary and later ones only fill in missing
+    /// codepoints.
+    KeepFirst,
+    KeepLast,
+}
+
+impl ConflictPolicy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictPolicy::KeepFirst => "Keep first",
+            ConflictPolicy::KeepLast => "Keep last",
+        }
+    }
+}
+
+/// Unions the glyph maps of several fonts into one. The merged font takes the
+/// size and line metrics of the first input; overlapping codepoints are
+/// resolved by `policy` and reported as warnings. Page ids of later fonts are shifted past the
+/// pages already merged so each glyph keeps pointing at its own texture.
+pub fn merge_fonts(fonts: Vec<(String, Font)>, policy: ConflictPolicy) -> (Font, Vec<String>) {
+    let mut merged = Font::default();
+    if let Some((_, first)) = fonts.first() {
+        merged.metrics.size = first.metrics.size;
+        merged.metrics.common = first.metrics.common.clone();
+    }
+    let mut sources: BTreeMap<u32, String> = BTreeMap::new();
+    let mut ordered = HashSet::new();
+    let mut warnings = Vec::new();
+
+    for (name, font) in fonts {
+        let page_offset = merged.metrics.pages.keys().next_back().map_or(0, |last| last + 1);
+        for (id, file) in font.metrics.pages {
+            merged.metrics.pages.insert(id + page_offset, file);
+        }
+
+        // Each glyph keeps the position of its first definition.
+        for id in font.source_order.iter().chain(font.characters.keys()) {
+            if font.characters.contains_key(id) && ordered.insert(*id) {
+                merged.source_order.push(*id);
+            }
+        }
+        for (id, mut data) in font.characters {
+            if let Some(previous) = sources.get(&id) {
+                warnings.push(format!("Warning: codepoint U+{id:04X} is defined in both {previous} and {name}"));
+                if policy == ConflictPolicy::KeepFirst {
+                    continue;
+                }
+            }
+            data.page += page_offset;
+            sources.insert(id, name.clone());
+            merged.characters.insert(id, data);
+        }
+
+        for (pair, amount) in font.metrics.kerning {
+            if policy == ConflictPolicy::KeepLast || !merged.metrics.kerning.contains_key(&pair) {
+                merged.metrics.kerning.insert(pair, amount);
+            }
+        }
+    }
+
+    (merged, warnings)
+}
+
+/// Multiplies the glyph sizes, offsets and advances and the kerning of `font`
+/// by `scale`, e.g. to match a fallback font to the primary font's size.
+/// Texture positions are kept so glyphs still point into their own atlas.
+pub fn scale_glyphs(font: &mut Font, scale: f32) {
+    if scale == 1.0 {
+        return;
+    }
+    for data in font.characters.values_mut() {
+        data.size.width *= scale;
+        data.size.height *= scale;
+        data.offset.x *= scale;
+        data.offset.y *= scale;
+        data.advance *= scale;
+    }
+    for amount in font.metrics.kerning.values_mut() {
+        *amount = (*amount * scale).round();
+    }
+}
+
+/// Parses and merges the files at `inputs`; see [`merge_fonts`]. The first
+/// input is the primary font; `scales[i]` is applied to `inputs[i]` with
+/// [`scale_glyphs`] and missing entries default to 1.
+pub fn merge_files(
+    inputs: &[PathBuf],
+    scales: &[f32],
+    policy: ConflictPolicy,
+) -> Result<(Font, Vec<String>), ConvertError> {
+    let mut fonts = Vec::new();
+    for (index, input) in inputs.iter().enumerate() {
+        let mut font = parse_fnt(&input.display().to_string())?;
+        scale_glyphs(&mut font, scales.get(index).copied().unwrap_or(1.0));
+        let name = input.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        fonts.push((name, font));
+    }
+    let (font, warnings) = merge_fonts(fonts, policy);
+    info!(files = inputs.len(), glyphs = font.characters.len(), conflicts = warnings.len(), "merged fonts");
+    Ok((font, warnings))
+}
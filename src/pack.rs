@@ -0,0 +1,205 @@
+//! Packing glyph rects into atlas pages, and fonts bundled with the pages
+//! generated for them.
+
+use crate::batch::write_atomic;
+use crate::error::ConvertError;
+use crate::font::{ALL_CHANNELS, Font};
+use crate::writer::write_fnt_xml;
+use image::{Rgba, RgbaImage};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::info;
+
+/// A font and the atlas pages generated for it, indexed by page id.
+pub struct PackedFont {
+    pub font: Font,
+    pub pages: Vec<RgbaImage>,
+}
+
+impl PackedFont {
+    /// Writes the BMFont XML to `fnt_path` and the atlas pages next to it,
+    /// under the file names the font's `<page>` elements reference.
+    pub fn save(&self, fnt_path: &Path) -> Result<(), ConvertError> {
+        self.save_pages(fnt_path.parent().unwrap_or(Path::new("")))?;
+        write_atomic(fnt_path, write_fnt_xml(&self.font, false))?;
+        Ok(())
+    }
+
+    /// Writes only the atlas pages, into `directory`.
+    pub fn save_pages(&self, directory: &Path) -> Result<(), ConvertError> {
+        for (id, page) in self.pages.iter().enumerate() {
+            let file = &self.font.metrics.pages[&(id as u32)];
+            page.save(directory.join(file))?;
+        }
+        Ok(())
+    }
+}
+
+/// Where [`shelf_pack`] put a rect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Placement {
+    pub page: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// Places `(id, width, height)` rects on shelves, tallest first, in pages of
+/// `page_width`×`page_height`, starting a new page when the current one is
+/// full. Returns the placements by id and the number of pages used.
+pub(crate) fn shelf_pack(
+    rects: &[(u32, u32, u32)],
+    page_width: u32,
+    page_height: u32,
+    padding: u32,
+) -> Result<(BTreeMap<u32, Placement>, u32), ConvertError> {
+    let mut order: Vec<&(u32, u32, u32)> = rects.iter().collect();
+    order.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+
+    let mut placements = BTreeMap::new();
+    let mut page = 0;
+    let (mut x, mut y, mut shelf_height) = (padding, padding, 0);
+    for &&(id, width, height) in &order {
+        // Empty glyphs such as spaces take up no room.
+        if width == 0 || height == 0 {
+            placements.insert(id, Placement { page, x: 0, y: 0 });
+            continue;
+        }
+        if width + 2 * padding > page_width || height + 2 * padding > page_height {
+            return Err(ConvertError::GlyphTooLarge {
+                id,
+                width,
+                height,
+                texture_width: page_width,
+                texture_height: page_height,
+            });
+        }
+        if x + width + padding > page_width {
+            x = padding;
+            y += shelf_height + padding;
+            shelf_height = 0;
+        }
+        if y + height + padding > page_height {
+            page += 1;
+            x = padding;
+            y = padding;
+            shelf_height = 0;
+        }
+
+        placements.insert(id, Placement { page, x, y });
+        x += width + padding;
+        shelf_height = shelf_height.max(height);
+    }
+    Ok((placements, page + 1))
+}
+
+/// Copies every glyph of `font` out of its texture `pages` into the smallest
+/// power-of-two page that holds them all, up to `max_size`×`max_size`, or
+/// into as many pages of that size as needed. New pages are named
+/// `{name}_{page}.png`; positions, pages and the texture size are updated.
+pub fn repack(
+    font: &Font,
+    pages: &BTreeMap<u32, RgbaImage>,
+    name: &str,
+    padding: u32,
+    max_size: u32,
+) -> Result<PackedFont, ConvertError> {
+    let rects: Vec<(u32, u32, u32)> = font
+        .characters
+        .iter()
+        .map(|(id, data)| (*id, data.size.width.round().max(0.0) as u32, data.size.height.round().max(0.0) as u32))
+        .collect();
+    let area: u64 = rects.iter().map(|(_, width, height)| u64::from(width + padding) * u64::from(height + padding)).sum();
+
+    let powers: Vec<u32> = (4..32).map(|shift| 1 << shift).take_while(|size| *size <= max_size).collect();
+    let mut candidates: Vec<(u32, u32)> =
+        powers.iter().flat_map(|width| powers.iter().map(move |height| (*width, *height))).collect();
+    candidates.retain(|(width, height)| u64::from(*width) * u64::from(*height) >= area);
+    candidates.sort_by_key(|(width, height)| (u64::from(*width) * u64::from(*height), width.abs_diff(*height)));
+
+    let single_page = candidates.into_iter().find_map(|(width, height)| match shelf_pack(&rects, width, height, padding) {
+        Ok((placements, 1)) => Some((placements, 1, width, height)),
+        _ => None,
+    });
+    let (placements, page_count, width, height) = match single_page {
+        Some(packed) => packed,
+        None => {
+            let (placements, page_count) = shelf_pack(&rects, max_size, max_size, padding)?;
+            (placements, page_count, max_size, max_size)
+        }
+    };
+
+    let mut packed = font.clone();
+    let mut new_pages = vec![RgbaImage::new(width, height); page_count as usize];
+    for (id, data) in packed.characters.iter_mut() {
+        let placement = placements[id];
+        if let Some(source) = pages.get(&data.page) {
+            let left = data.position.x.round().max(0.0) as u32;
+            let top = data.position.y.round().max(0.0) as u32;
+            let (glyph_width, glyph_height) = (data.size.width.round().max(0.0) as u32, data.size.height.round().max(0.0) as u32);
+            let target = &mut new_pages[placement.page as usize];
+            for row in 0..glyph_height.min(source.height().saturating_sub(top)) {
+                for column in 0..glyph_width.min(source.width().saturating_sub(left)) {
+                    target.put_pixel(placement.x + column, placement.y + row, *source.get_pixel(left + column, top + row));
+                }
+            }
+        }
+        data.position.x = placement.x as f32;
+        data.position.y = placement.y as f32;
+        data.page = placement.page;
+    }
+
+    packed.metrics.pages = (0..page_count).map(|page| (page, format!("{name}_{page}.png"))).collect();
+    packed.metrics.glyph_spacing.spacing = [padding as i32; 2];
+    if let Some(ref mut common) = packed.metrics.common {
+        common.scale_w = width;
+        common.scale_h = height;
+    }
+    info!(glyphs = packed.characters.len(), pages = page_count, width, height, "repacked atlas");
+    Ok(PackedFont { font: packed, pages: new_pages })
+}
+
+/// Splits the channel-packed texture `pages` of `font` into a page per
+/// channel that holds glyphs, white with that channel as alpha, so runtimes
+/// without a channel-select shader can still draw the font. Pages of glyphs
+/// drawn into all channels are copied unchanged. New pages are named
+/// `{name}_{page}.png`; every glyph's page is remapped and its channel set
+/// to [`ALL_CHANNELS`].
+pub fn split_channels(font: &Font, pages: &BTreeMap<u32, RgbaImage>, name: &str) -> PackedFont {
+    let mut targets: BTreeMap<(u32, u8), u32> =
+        font.characters.values().map(|data| ((data.page, data.channel), 0)).collect();
+    for (index, target) in targets.values_mut().enumerate() {
+        *target = index as u32;
+    }
+
+    let (width, height) = font.metrics.common.as_ref().map_or((0, 0), |common| (common.scale_w, common.scale_h));
+    let new_pages = targets
+        .keys()
+        .map(|&(page, channel)| match pages.get(&page) {
+            Some(source) if channel == ALL_CHANNELS => source.clone(),
+            Some(source) => RgbaImage::from_fn(source.width(), source.height(), |x, y| {
+                Rgba([255, 255, 255, channel_value(source.get_pixel(x, y), channel)])
+            }),
+            None => RgbaImage::new(width, height),
+        })
+        .collect::<Vec<_>>();
+
+    let mut split = font.clone();
+    for data in split.characters.values_mut() {
+        data.page = targets[&(data.page, data.channel)];
+        data.channel = ALL_CHANNELS;
+    }
+    split.metrics.pages = (0..new_pages.len() as u32).map(|page| (page, format!("{name}_{page}.png"))).collect();
+    info!(pages = pages.len(), split = new_pages.len(), "split atlas channels");
+    PackedFont { font: split, pages: new_pages }
+}
+
+/// The strongest of the channels in the `chnl` bitmask `channel`.
+fn channel_value(pixel: &Rgba<u8>, channel: u8) -> u8 {
+    // Bits 1, 2, 4 and 8 select blue, green, red and alpha.
+    [(1, 2), (2, 1), (4, 0), (8, 3)]
+        .iter()
+        .filter(|(bit, _)| channel & bit != 0)
+        .map(|&(_, index)| pixel[index])
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,95 @@
+//! Rewriting the texture file names of `<page file=…>`, e.g. to the
+//! `rbxassetid://` ids the pages were uploaded as, or to where the game
+//! keeps its textures.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use globset::Glob;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use tracing::debug;
+
+/// Pages whose file name matches `pattern`, a glob such as `font_*.png`, are
+/// written as `replacement`, in which `{file}` stands for the file name,
+/// `{stem}` for it without its extension and `{page}` for the page id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageRule {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+impl PageRule {
+    /// Parses a rule written `PATTERN=REPLACEMENT`, e.g.
+    /// `font_0.png=rbxassetid://123456`. The pattern ends at the first `=`.
+    pub fn parse(rule: &str) -> Result<Self, ConvertError> {
+        let (pattern, replacement) = rule
+            .split_once('=')
+            .ok_or_else(|| ConvertError::PagePath(format!("expected `PATTERN=REPLACEMENT`, found {rule:?}")))?;
+        let rule = Self {
+            pattern: pattern.trim().to_string(),
+            replacement: replacement.trim().to_string(),
+        };
+        rule.check()?;
+        Ok(rule)
+    }
+
+    /// Fails if the pattern is empty or not a valid glob.
+    pub fn check(&self) -> Result<(), ConvertError> {
+        if self.pattern.is_empty() {
+            return Err(ConvertError::PagePath("empty pattern".to_string()));
+        }
+        Glob::new(&self.pattern).map_err(|e| ConvertError::PagePath(format!("{:?}: {}", self.pattern, e.kind())))?;
+        Ok(())
+    }
+
+    /// What page `id` with `file` is written as, or `None` when the pattern
+    /// does not match. Invalid patterns match nothing.
+    pub fn rewrite(&self, id: u32, file: &str) -> Option<String> {
+        let glob = Glob::new(&self.pattern).ok()?.compile_matcher();
+        if !glob.is_match(file) {
+            return None;
+        }
+        let stem = Path::new(file).file_stem().map_or(file.into(), |stem| stem.to_string_lossy());
+        Some(self.replacement.replace("{file}", file).replace("{stem}", &stem).replace("{page}", &id.to_string()))
+    }
+}
+
+impl fmt::Display for PageRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.pattern, self.replacement)
+    }
+}
+
+/// Rules rewriting page file names. When several match a page, the last one
+/// wins, so rules given later, such as on the command line after those of a
+/// config, override earlier ones. Pages no rule matches keep their name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PagePaths {
+    pub rules: Vec<PageRule>,
+}
+
+impl PagePaths {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// What page `id` with `file` is written as.
+    pub fn rewrite(&self, id: u32, file: &str) -> String {
+        self.rules.iter().rev().find_map(|rule| rule.rewrite(id, file)).unwrap_or_else(|| file.to_string())
+    }
+
+    /// A copy of `font` with its page file names rewritten.
+    pub fn apply(&self, font: &Font) -> Font {
+        let mut rewritten = font.clone();
+        for (id, file) in &mut rewritten.metrics.pages {
+            let new = self.rewrite(*id, file);
+            if new != *file {
+                debug!(page = id, from = %file, to = %new, "rewrote texture path");
+                *file = new;
+            }
+        }
+        rewritten
+    }
+}
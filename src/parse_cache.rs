@@ -0,0 +1,198 @@
+//! Keeping parsed fonts around while their files are unchanged, so watch
+//! mode and repeated conversions of a huge font only re-run the formatting
+//! when just the output options change.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::debug;
+
+/// How many fonts a [`ParseCache`] keeps before dropping the oldest.
+pub const CACHE_LIMIT: usize = 8;
+
+/// What a file looked like on disk when it was parsed.
+#[derive(Debug, Clone, PartialEq)]
+struct Stamp {
+    len: u64,
+    modified: Option<SystemTime>,
+    checksum: u32,
+}
+
+impl Stamp {
+    fn read(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            checksum: crc32fast::hash(&std::fs::read(path)?),
+        })
+    }
+
+    /// Whether the file still matches. The size and modification time
+    /// settle it when both are the same; otherwise the contents are compared,
+    /// so a file saved again without changes still matches.
+    fn matches(&mut self, path: &Path) -> bool {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return false;
+        };
+        if metadata.len() != self.len {
+            return false;
+        }
+        let modified = metadata.modified().ok();
+        if modified.is_some() && modified == self.modified {
+            return true;
+        }
+        let Ok(contents) = std::fs::read(path) else {
+            return false;
+        };
+        let unchanged = crc32fast::hash(&contents) == self.checksum;
+        if unchanged {
+            self.modified = modified;
+        }
+        unchanged
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    lenient: bool,
+    stamp: Stamp,
+    font: Font,
+}
+
+/// Fonts parsed from files, each reused until its file changes. A cache can
+/// be shared between threads; parsing happens outside its lock.
+#[derive(Default)]
+pub struct ParseCache {
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl ParseCache {
+    /// The font parsed from `path` with `lenient` parsing, from the cache if
+    /// the file is unchanged since, or else from calling `parse`. `-` for
+    /// stdin is always parsed.
+    pub fn parse(
+        &self,
+        path: &Path,
+        lenient: bool,
+        parse: impl FnOnce() -> Result<Font, ConvertError>,
+    ) -> Result<Font, ConvertError> {
+        if path == Path::new("-") {
+            return parse();
+        }
+        if let Some(font) = self.get(path, lenient) {
+            debug!(input = %path.display(), "reusing parsed font");
+            return Ok(font);
+        }
+        // Stamped first, so a change made while parsing is not missed.
+        let stamp = Stamp::read(path)?;
+        let font = parse()?;
+        let mut entries = self.entries.lock().expect("parse cache lock poisoned");
+        entries.retain(|entry| entry.path != path || entry.lenient != lenient);
+        if entries.len() == CACHE_LIMIT {
+            entries.pop_front();
+        }
+        entries.push_back(Entry {
+            path: path.to_path_buf(),
+            lenient,
+            stamp,
+            font: font.clone(),
+        });
+        Ok(font)
+    }
+
+    fn get(&self, path: &Path, lenient: bool) -> Option<Font> {
+        let mut entries = self.entries.lock().expect("parse cache lock poisoned");
+        let index = entries.iter().position(|entry| entry.path == path && entry.lenient == lenient)?;
+        if entries[index].stamp.matches(path) {
+            return Some(entries[index].font.clone());
+        }
+        entries.remove(index);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs::File;
+    use std::time::Duration;
+
+    /// A file of its own in the temporary directory holding `contents`.
+    fn scratch(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("xml_to_lua_parse_cache_{}_{name}.fnt", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// Sets the modification time of `path` `seconds` past the epoch.
+    fn touch(path: &Path, seconds: u64) {
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+    }
+
+    /// Parses through `cache`, returning the size of the font and whether
+    /// `parse` ran.
+    fn parse(cache: &ParseCache, path: &Path, lenient: bool, size: i32) -> (i32, bool) {
+        let ran = Cell::new(false);
+        let font = cache
+            .parse(path, lenient, || {
+                ran.set(true);
+                let mut font = Font::default();
+                font.metrics.size = size;
+                Ok(font)
+            })
+            .unwrap();
+        (font.metrics.size, ran.get())
+    }
+
+    #[test]
+    fn fonts_are_reused_until_their_file_changes() {
+        let path = scratch("changes", "size=1");
+        touch(&path, 1_000);
+        let cache = ParseCache::default();
+        assert_eq!(parse(&cache, &path, false, 1), (1, true));
+        assert_eq!(parse(&cache, &path, false, 2), (1, false));
+        // Lenient parses are kept apart from strict ones.
+        assert_eq!(parse(&cache, &path, true, 3), (3, true));
+
+        // Saved again unchanged: only the modification time moves.
+        touch(&path, 2_000);
+        assert_eq!(parse(&cache, &path, false, 4), (1, false));
+
+        // Edited in place to the same length with a new modification time.
+        std::fs::write(&path, "size=2").unwrap();
+        touch(&path, 3_000);
+        assert_eq!(parse(&cache, &path, false, 5), (5, true));
+        assert_eq!(parse(&cache, &path, false, 6), (5, false));
+
+        std::fs::write(&path, "size=20").unwrap();
+        assert_eq!(parse(&cache, &path, false, 7), (7, true));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn stdin_is_never_cached() {
+        let cache = ParseCache::default();
+        assert_eq!(parse(&cache, Path::new("-"), false, 1), (1, true));
+        assert_eq!(parse(&cache, Path::new("-"), false, 2), (2, true));
+    }
+
+    #[test]
+    fn the_oldest_font_is_dropped_past_the_limit() {
+        let cache = ParseCache::default();
+        let paths: Vec<PathBuf> = (0..=CACHE_LIMIT).map(|index| scratch(&format!("limit{index}"), "size")).collect();
+        for (index, path) in paths.iter().enumerate() {
+            parse(&cache, path, false, index as i32);
+        }
+        assert_eq!(parse(&cache, &paths[0], false, 100), (100, true));
+        assert_eq!(parse(&cache, &paths[CACHE_LIMIT], false, 100), (CACHE_LIMIT as i32, false));
+        for path in paths {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
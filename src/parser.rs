@@ -0,0 +1,549 @@
+//! Reading BMFont XML descriptors.
+
+use crate::Progress;
+use crate::binary::{self, parse_fnt_binary_with_progress};
+use crate::error::ConvertError;
+use crate::font::{Character, CommonMetrics, DistanceField, Font, FontMetrics, GlyphSpacing};
+use crate::json_format::{is_json, parse_json};
+use crate::quirks::CharAttributes;
+use crate::text_format::parse_text;
+use flate2::read::GzDecoder;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use quick_xml::events::attributes::Attribute;
+use encoding_rs::{Encoding, UTF_8};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+use tracing::{debug, info, warn};
+
+pub(crate) fn attribute_value<T: FromStr>(attr: &Attribute) -> Result<T, ConvertError> {
+    let value = attr.unescape_value()?;
+    value.parse().map_err(|_| ConvertError::BadAttribute {
+        key: String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+        value: value.to_string(),
+    })
+}
+
+/// Like [`attribute_value`], for metrics, which must be finite numbers.
+pub(crate) fn attribute_number(attr: &Attribute) -> Result<f32, ConvertError> {
+    let number: f32 = attribute_value(attr)?;
+    if !number.is_finite() {
+        return Err(ConvertError::BadAttribute {
+            key: String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+            value: attr.unescape_value()?.into_owned(),
+        });
+    }
+    Ok(number)
+}
+
+/// Parses a comma-separated attribute such as `padding="1,1,1,1"`.
+pub(crate) fn int_list<const N: usize>(key: &str, value: &str) -> Result<[i32; N], ConvertError> {
+    let bad = || ConvertError::BadAttribute {
+        key: key.to_string(),
+        value: value.to_string(),
+    };
+    let numbers: Vec<i32> = value.split(',').map(|number| number.trim().parse().map_err(|_| bad())).collect::<Result<_, _>>()?;
+    numbers.try_into().map_err(|_| bad())
+}
+
+/// Decodes a `letter` attribute: XML entities such as `&quot;` are expanded
+/// and `space` names the space character.
+pub(crate) fn decode_letter(raw: &str) -> String {
+    match raw {
+        "space" => " ".to_string(),
+        _ => quick_xml::escape::unescape(raw).map_or_else(|_| raw.to_string(), |letter| letter.into_owned()),
+    }
+}
+
+/// Reads `filename`, transparently decompressing gzip input.
+pub fn read_bytes(filename: &str) -> Result<Vec<u8>, ConvertError> {
+    let mut bytes = Vec::new();
+    File::open(filename)?.read_to_end(&mut bytes)?;
+    Ok(decompress(&bytes)?.into_owned())
+}
+
+/// Decompresses gzip `bytes`, passing anything else through unchanged.
+pub fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, ConvertError> {
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut contents = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut contents)?;
+        return Ok(Cow::Owned(contents));
+    }
+    Ok(Cow::Borrowed(bytes))
+}
+
+/// Reads `filename` as text, transparently decompressing gzip input; see
+/// [`decode_text`] for the encodings understood.
+pub fn read_source(filename: &str) -> Result<String, ConvertError> {
+    Ok(decode_text(&read_bytes(filename)?)?.into_owned())
+}
+
+/// Decodes `bytes` into UTF-8 text. A byte order mark selects UTF-8 or UTF-16;
+/// otherwise the `encoding` of an XML declaration is honoured, e.g.
+/// ISO-8859-1, and anything else must be UTF-8.
+pub fn decode_text(bytes: &[u8]) -> Result<Cow<'_, str>, ConvertError> {
+    let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+        Some(found) => found,
+        None => match declared_encoding(bytes) {
+            Some(encoding) if encoding != UTF_8 => (encoding, 0),
+            _ => return Ok(Cow::Borrowed(std::str::from_utf8(bytes)?)),
+        },
+    };
+    let (text, malformed) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+    if malformed {
+        return Err(ConvertError::Encoding(encoding.name()));
+    }
+    Ok(text)
+}
+
+/// The encoding named by an `<?xml ... encoding="..."?>` declaration at the
+/// start of `bytes`, if any.
+fn declared_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let prolog = bytes.strip_prefix(b"<?xml")?;
+    let prolog = &prolog[..prolog.windows(2).position(|window| window == b"?>")?];
+    let prolog = std::str::from_utf8(prolog).ok()?;
+    let value = prolog.split_once("encoding")?.1.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let label = value[1..].split(quote).next()?;
+    Encoding::for_label(label.as_bytes())
+}
+
+/// Whether `contents` is an XML descriptor rather than the text format.
+pub(crate) fn is_xml(contents: &str) -> bool {
+    contents.trim_start_matches('\u{feff}').trim_start().starts_with('<')
+}
+
+/// Parses the BMFont file at `filename`, in the XML, text, binary or JSON
+/// format.
+/// The file may be gzip-compressed.
+pub fn parse_fnt(
+    filename: &str,
+) -> Result<Font, ConvertError> {
+    parse_fnt_with_progress(filename, |_| {})
+}
+
+/// Like [`parse_fnt`], calling `progress` as glyphs are read.
+pub fn parse_fnt_with_progress<P: FnMut(Progress)>(
+    filename: &str,
+    progress: P,
+) -> Result<Font, ConvertError> {
+    parse_fnt_with(filename, false, progress)
+}
+
+/// Like [`parse_fnt_with_progress`]. When `lenient` is set, elements or lines
+/// that fail to parse are left out and listed in [`Font::skipped`] instead of
+/// failing the whole font.
+///
+/// UTF-8 XML is parsed as it is read, so even very large fonts are never held
+/// in memory as text; other files are read whole first.
+pub fn parse_fnt_with<P: FnMut(Progress)>(filename: &str, lenient: bool, progress: P) -> Result<Font, ConvertError> {
+    debug!(file = filename, lenient, "reading font");
+    let mut source = open_decompressed(filename)?;
+    if !streams(source.fill_buf()?) {
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+        return parse_fnt_bytes_with(&bytes, lenient, progress);
+    }
+    // Only offsets are known while streaming; the text is read again to turn
+    // them into lines and columns.
+    let contents = || read_source(filename).ok();
+    let locate = |e: ConvertError, contents: &Option<String>| match contents {
+        Some(contents) => e.with_line_column(contents),
+        None => e,
+    };
+    let (mut font, skipped) =
+        collect_font(|collect, skipped| parse_xml(source, collect, progress, lenient.then_some(skipped)))
+            .map_err(|e| match e.location() {
+                Some(_) => locate(e, &contents()),
+                None => e,
+            })?;
+    if !skipped.is_empty() {
+        let contents = contents();
+        font.skipped = describe_skipped(skipped, |e| locate(e, &contents));
+    }
+    log_parsed(&font, "streamed XML");
+    Ok(font)
+}
+
+/// Logs the outcome of parsing a font in `format`.
+fn log_parsed(font: &Font, format: &str) {
+    info!(format, glyphs = font.characters.len(), pages = font.metrics.pages.len(), "parsed font");
+    if !font.skipped.is_empty() {
+        warn!(skipped = font.skipped.len(), "left out malformed elements");
+    }
+}
+
+/// `filename` opened for buffered reading, decompressed as it is read if it
+/// is gzipped.
+fn open_decompressed(filename: &str) -> Result<Box<dyn BufRead>, ConvertError> {
+    let mut file = BufReader::new(File::open(filename)?);
+    if file.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+        return Ok(Box::new(BufReader::new(GzDecoder::new(file))));
+    }
+    Ok(Box::new(file))
+}
+
+/// Whether a file starting with `start` is XML in UTF-8, which the streaming
+/// parser reads without decoding it first.
+fn streams(start: &[u8]) -> bool {
+    let start = match Encoding::for_bom(start) {
+        Some((encoding, bom_len)) if encoding == UTF_8 => &start[bom_len..],
+        Some(_) => return false,
+        None => start,
+    };
+    start.trim_ascii_start().starts_with(b"<") && declared_encoding(start).is_none_or(|encoding| encoding == UTF_8)
+}
+
+/// Like [`parse_fnt_with_progress`], for a font already in memory, e.g. read
+/// from stdin.
+pub fn parse_fnt_bytes_with_progress<P: FnMut(Progress)>(bytes: &[u8], progress: P) -> Result<Font, ConvertError> {
+    parse_fnt_bytes_with(bytes, false, progress)
+}
+
+/// Like [`parse_fnt_with`], for a font already in memory. The binary format
+/// has no elements to skip and is always parsed strictly.
+pub fn parse_fnt_bytes_with<P: FnMut(Progress)>(bytes: &[u8], lenient: bool, progress: P) -> Result<Font, ConvertError> {
+    let bytes = decompress(bytes)?;
+    if bytes.starts_with(binary::MAGIC) {
+        let (font, _) = collect_font(|collect, _| parse_fnt_binary_with_progress(&bytes, collect, progress))?;
+        log_parsed(&font, "binary");
+        return Ok(font);
+    }
+    let contents = &decode_text(&bytes)?;
+    if is_json(contents) {
+        let (mut font, skipped) = collect_font(|collect, skipped| parse_json(contents, collect, progress, lenient.then_some(skipped)))?;
+        font.skipped = describe_skipped(skipped, |e| e);
+        log_parsed(&font, "JSON");
+        return Ok(font);
+    }
+    let xml = is_xml(contents);
+    let (mut font, skipped) = collect_font(|collect, skipped| {
+        let skipped = lenient.then_some(skipped);
+        if xml {
+            parse_xml(contents.as_bytes(), collect, progress, skipped)
+        } else {
+            parse_text(contents, collect, progress, skipped)
+        }
+    })
+    .map_err(|e| e.with_line_column(contents))?;
+    font.skipped = describe_skipped(skipped, |e| e.with_line_column(contents));
+    log_parsed(&font, if xml { "XML" } else { "text" });
+    Ok(font)
+}
+
+/// Elements a lenient parse left out: the element, if the error was inside
+/// one, and why it could not be read.
+pub(crate) type Skipped = Vec<(Option<String>, ConvertError)>;
+
+/// Records `error`, hit while reading `element`, in `skipped`, or returns it
+/// when parsing strictly.
+pub(crate) fn skip_or_fail(skipped: &mut Option<&mut Skipped>, element: Option<String>, error: ConvertError) -> Result<(), ConvertError> {
+    match skipped {
+        Some(skipped) => {
+            skipped.push((element, error));
+            Ok(())
+        }
+        None => Err(error),
+    }
+}
+
+/// Warnings for the elements in `skipped`, with `locate` adding the line and
+/// column to their errors.
+fn describe_skipped(skipped: Skipped, locate: impl Fn(ConvertError) -> ConvertError) -> Vec<String> {
+    skipped
+        .into_iter()
+        .map(|(element, error)| match element {
+            Some(element) => format!("Warning: skipped a malformed `{element}`: {}", locate(error)),
+            None => format!("Warning: skipped malformed XML: {}", locate(error)),
+        })
+        .collect()
+}
+
+/// Runs `parse`, gathering the glyphs it reports into a [`Font`] along with
+/// the duplicates, the source order and any mismatch with the declared count.
+/// Returns the elements it skipped too.
+fn collect_font(
+    parse: impl FnOnce(&mut dyn FnMut(u32, Character), &mut Skipped) -> Result<FontMetrics, ConvertError>,
+) -> Result<(Font, Skipped), ConvertError> {
+    let mut characters = BTreeMap::new();
+    let mut duplicate_ids = Vec::new();
+    let mut source_order = Vec::new();
+    let mut skipped = Vec::new();
+    let mut parsed = 0;
+    let metrics = parse(
+        &mut |id, character| {
+            parsed += 1;
+            if characters.insert(id, character).is_some() {
+                duplicate_ids.push(id);
+            } else {
+                source_order.push(id);
+            }
+        },
+        &mut skipped,
+    )?;
+    let font = Font {
+        characters,
+        duplicate_ids,
+        source_order,
+        count_mismatch: metrics.declared_chars.filter(|declared| *declared != parsed).map(|declared| (declared, parsed)),
+        skipped: Vec::new(),
+        synthesized: BTreeSet::new(),
+        metrics,
+    };
+    Ok((font, skipped))
+}
+
+/// Parses BMFont XML from `source`, handing each glyph to `callback` as soon as
+/// its `<char>` element is read instead of collecting them. Returns the
+/// font-wide metrics.
+///
+/// Elements are read from their attributes alone, so `<char ... />` and
+/// `<char ...></char>`, as some exporters and hand edits write them, parse
+/// the same.
+pub fn parse_fnt_streaming<R: BufRead, F: FnMut(u32, Character)>(
+    source: R,
+    callback: F,
+) -> Result<FontMetrics, ConvertError> {
+    parse_fnt_streaming_with_progress(source, callback, |_| {})
+}
+
+/// Like [`parse_fnt_streaming`], calling `progress` after every glyph.
+pub fn parse_fnt_streaming_with_progress<R: BufRead, F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: R,
+    callback: F,
+    progress: P,
+) -> Result<FontMetrics, ConvertError> {
+    parse_xml(source, callback, progress, None)
+}
+
+/// [`parse_fnt_streaming_with_progress`], recording elements that fail to
+/// parse in `skipped` and moving on when it is given.
+fn parse_xml<R: BufRead, F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: R,
+    mut callback: F,
+    mut progress: P,
+    mut skipped: Option<&mut Skipped>,
+) -> Result<FontMetrics, ConvertError> {
+    let mut reader = Reader::from_reader(source);
+    let mut buf = Vec::new();
+    let mut font_size = None;
+    let mut glyph_spacing = GlyphSpacing::default();
+    let mut common = None;
+    let mut distance_field = None;
+    let mut pages = BTreeMap::new();
+    let mut kerning = BTreeMap::new();
+    let mut char_count: Option<usize> = None;
+    let mut filled_in = Vec::new();
+    let mut parsed = 0;
+
+    loop {
+        let start = reader.buffer_position();
+        let event = match reader.read_event_into(&mut buf) {
+            Ok(Event::Eof) => break,
+            Ok(event) => event,
+            // The reader resumes after the malformed markup; without progress
+            // it would report the same error forever.
+            Err(e) if reader.buffer_position() > start => {
+                skip_or_fail(&mut skipped, None, ConvertError::at(reader.error_position(), e))?;
+                buf.clear();
+                continue;
+            }
+            Err(e) => return Err(ConvertError::at(reader.error_position(), e)),
+        };
+
+        let handled = (|| -> Result<(), ConvertError> {
+            // A start tag is handled like an empty one; its end tag and any
+            // whitespace inside are ignored below.
+            match event {
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"char" => {
+                    let mut attributes = CharAttributes::default();
+                    let mut letter = None;
+
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"letter" => letter = Some(decode_letter(std::str::from_utf8(&attr.value)?)),
+                            key => attributes.set(std::str::from_utf8(key)?, &attr.unescape_value()?)?,
+                        }
+                    }
+
+                    let (id, character) = attributes.finish(letter, &mut filled_in)?;
+                    callback(id, character);
+
+                    parsed += 1;
+                    progress(match char_count {
+                        Some(count) if count > 0 => Progress::Fraction((parsed as f32 / count as f32).min(1.0)),
+                        _ => Progress::Indeterminate,
+                    });
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"chars" => {
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        if attr.key.as_ref() == b"count" {
+                            char_count = Some(attribute_value(&attr)?);
+                        }
+                    }
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"page" => {
+                    let mut id = 0;
+                    let mut file = String::new();
+
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"id" => id = attribute_value(&attr)?,
+                            b"file" => file = attr.unescape_value()?.into_owned(),
+                            _ => {}
+                        }
+                    }
+
+                    pages.insert(id, file);
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"kerning" => {
+                    let mut first = 0;
+                    let mut second = 0;
+                    let mut amount = 0.0;
+
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"first" => first = attribute_value(&attr)?,
+                            b"second" => second = attribute_value(&attr)?,
+                            b"amount" => amount = attribute_number(&attr)?,
+                            _ => {}
+                        }
+                    }
+
+                    kerning.insert((first, second), amount);
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"common" => {
+                    let mut line_height = 0.0;
+                    let mut base = 0.0;
+                    let mut scale_w = 0;
+                    let mut scale_h = 0;
+
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"lineHeight" => line_height = attribute_number(&attr)?,
+                            b"base" => base = attribute_number(&attr)?,
+                            b"scaleW" => scale_w = attribute_value(&attr)?,
+                            b"scaleH" => scale_h = attribute_value(&attr)?,
+                            _ => {}
+                        }
+                    }
+
+                    common = Some(CommonMetrics {
+                        line_height,
+                        base,
+                        scale_w,
+                        scale_h,
+                    });
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"distanceField" => {
+                    let mut field_type = String::new();
+                    let mut range = 0.0;
+
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"fieldType" => field_type = attr.unescape_value()?.into_owned(),
+                            b"distanceRange" => range = attribute_number(&attr)?,
+                            _ => {}
+                        }
+                    }
+
+                    distance_field = Some(DistanceField { field_type, range });
+                }
+                Event::Empty(ref e) | Event::Start(ref e) if e.name().as_ref() == b"info" => {
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(quick_xml::Error::from)?;
+                        match attr.key.as_ref() {
+                            b"size" => font_size = Some(attribute_value(&attr)?),
+                            b"padding" => glyph_spacing.padding = int_list("padding", &attr.unescape_value()?)?,
+                            b"spacing" => glyph_spacing.spacing = int_list("spacing", &attr.unescape_value()?)?,
+                            b"outline" => glyph_spacing.outline = attribute_value(&attr)?,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        })();
+        if let Err(e) = handled {
+            let element = match event {
+                Event::Empty(ref e) | Event::Start(ref e) => Some(String::from_utf8_lossy(e.name().as_ref()).into_owned()),
+                _ => None,
+            };
+            skip_or_fail(&mut skipped, element, ConvertError::at(start, e))?;
+        }
+        buf.clear();
+    }
+
+    let size = font_size.ok_or(ConvertError::MissingInfo)?;
+    progress(Progress::Fraction(1.0));
+    Ok(FontMetrics {
+        size,
+        glyph_spacing,
+        common,
+        distance_field,
+        pages,
+        kerning,
+        declared_chars: char_count,
+        filled_in,
+        extents: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn gzipped_font_parses_like_the_plain_one() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/font.fnt.gz");
+        let plain = serde_json::to_value(example_font()).unwrap();
+        // Streamed from the file, and decompressed whole from memory.
+        assert_eq!(serde_json::to_value(parse_fnt(path).unwrap()).unwrap(), plain);
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(serde_json::to_value(parse_fnt_bytes_with_progress(&bytes, |_| {}).unwrap()).unwrap(), plain);
+    }
+
+    #[test]
+    fn paired_and_self_closing_elements_parse_alike() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/char_forms.fnt");
+        let mixed = parse_fnt(path).unwrap();
+        let self_closing = std::fs::read_to_string(path)
+            .unwrap()
+            .replace("></info>", "/>")
+            .replace("></common>", "/>")
+            .replace("></char>", "/>")
+            .replace(">\n    </char>", "/>");
+        assert!(!self_closing.contains("</char>"));
+        let self_closing = parse_fnt_bytes_with_progress(self_closing.as_bytes(), |_| {}).unwrap();
+
+        assert_eq!(mixed.characters.len(), 4);
+        assert_eq!(mixed.count_mismatch, None);
+        assert_eq!(serde_json::to_value(&mixed).unwrap(), serde_json::to_value(&self_closing).unwrap());
+    }
+
+    #[test]
+    fn non_finite_metrics_are_refused() {
+        let font = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/font.fnt")).unwrap();
+        for (from, to) in [
+            (r#"amount="-1""#, r#"amount="NaN""#),
+            (r#"lineHeight="50""#, r#"lineHeight="inf""#),
+            (r#"base="41""#, r#"base="-inf""#),
+            (r#"xadvance="19""#, r#"xadvance="NaN""#),
+        ] {
+            let broken = font.replacen(from, to, 1);
+            let error = parse_fnt_bytes_with_progress(broken.as_bytes(), |_| {}).unwrap_err();
+            assert!(matches!(error.cause(), ConvertError::BadAttribute { .. }), "{to}: {error}");
+        }
+    }
+}
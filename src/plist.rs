@@ -0,0 +1,154 @@
+//! Writing fonts as cocos2d sprite frame property lists, which load every
+//! glyph of the atlas as a named sprite frame.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputFormatter, OutputOptions, adjusted_font, script_name};
+use crate::writer::xml_writer;
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+
+/// The property list of [`write_plist`], named after the exported module.
+pub struct PlistFormatter;
+
+impl OutputFormatter for PlistFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Plist
+    }
+
+    fn name(&self) -> &'static str {
+        "plist"
+    }
+
+    fn label(&self) -> &'static str {
+        "cocos2d plist"
+    }
+
+    fn description(&self) -> &'static str {
+        "cocos2d sprite frame property list with a frame per glyph"
+    }
+
+    fn extension(&self) -> &'static str {
+        "plist"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        write_plist(&adjusted_font(font, options), &script_name(source, options), options.style.minified)
+    }
+}
+
+/// The name of the sprite frame for glyph `id`: `name/A` for the character
+/// it maps to, or `name/U+0009` for whitespace, control characters and ids
+/// that are no character.
+pub fn frame_name(name: &str, id: u32) -> String {
+    match char::from_u32(id) {
+        Some(c) if !c.is_control() && !c.is_whitespace() => format!("{name}/{c}"),
+        _ => format!("{name}/U+{id:04X}"),
+    }
+}
+
+fn key_value(writer: &mut Writer<Vec<u8>>, key: &str, kind: &str, value: &str) -> std::io::Result<()> {
+    writer.create_element("key").write_text_content(BytesText::new(key))?;
+    writer.create_element(kind).write_text_content(BytesText::new(value))?;
+    Ok(())
+}
+
+/// Renders an already adjusted `font` as a format 2 sprite frame list, with
+/// a frame per glyph named by [`frame_name`]. Each frame is trimmed from a
+/// cell as wide as the glyph's advance and as tall as the line, so a sprite
+/// drawn from it sits where the glyph does on the line. Fails for fonts with
+/// more than one texture page, since the list names a single texture.
+pub fn write_plist(font: &Font, name: &str, minified: bool) -> Result<String, ConvertError> {
+    if font.metrics.pages.len() > 1 {
+        return Err(ConvertError::SinglePage("cocos2d plist", font.metrics.pages.len()));
+    }
+    let texture = font.metrics.pages.values().next().map_or("", String::as_str);
+    let common = font.metrics.common.as_ref();
+    let line_height = common.map_or(0.0, |common| common.line_height);
+    let mut writer = xml_writer(minified);
+
+    let result: std::io::Result<()> = (|| {
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer.write_event(Event::DocType(BytesText::from_escaped(
+            r#"plist PUBLIC "-//Apple Computer//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd""#,
+        )))?;
+        writer.create_element("plist").with_attribute(("version", "1.0")).write_inner_content(|writer| {
+            writer.create_element("dict").write_inner_content(|writer| {
+                writer.create_element("key").write_text_content(BytesText::new("frames"))?;
+                writer.create_element("dict").write_inner_content(|writer| {
+                    for (id, data) in &font.characters {
+                        writer.create_element("key").write_text_content(BytesText::new(&frame_name(name, *id)))?;
+                        writer.create_element("dict").write_inner_content(|writer| {
+                            let (x, y) = (data.position.x, data.position.y);
+                            let (width, height) = (data.size.width, data.size.height);
+                            key_value(writer, "frame", "string", &format!("{{{{{x},{y}}},{{{width},{height}}}}}"))?;
+                            let (left, top) = (data.offset.x, data.offset.y);
+                            // Offsets are from the cell's centre to the frame's, with y up.
+                            let offset_x = left + width / 2.0 - data.advance / 2.0;
+                            let offset_y = line_height / 2.0 - top - height / 2.0;
+                            key_value(writer, "offset", "string", &format!("{{{offset_x},{offset_y}}}"))?;
+                            writer.create_element("key").write_text_content(BytesText::new("rotated"))?;
+                            writer.create_element("false").write_empty()?;
+                            key_value(writer, "sourceColorRect", "string", &format!("{{{{{left},{top}}},{{{width},{height}}}}}"))?;
+                            key_value(writer, "sourceSize", "string", &format!("{{{},{line_height}}}", data.advance))?;
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                writer.create_element("key").write_text_content(BytesText::new("metadata"))?;
+                writer.create_element("dict").write_inner_content(|writer| {
+                    writer.create_element("key").write_text_content(BytesText::new("format"))?;
+                    writer.create_element("integer").write_text_content(BytesText::new("2"))?;
+                    key_value(writer, "realTextureFileName", "string", texture)?;
+                    let size = common.map_or((0, 0), |common| (common.scale_w, common.scale_h));
+                    key_value(writer, "size", "string", &format!("{{{},{}}}", size.0, size.1))?;
+                    key_value(writer, "textureFileName", "string", texture)?;
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+        Ok(())
+    })();
+    result.expect("writing XML into memory cannot fail");
+
+    let mut output = String::from_utf8(writer.into_inner()).expect("XML writer emits UTF-8");
+    output.push('\n');
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn frames_are_named_by_character() {
+        assert_eq!(frame_name("font", 65), "font/A");
+        assert_eq!(frame_name("font", 32), "font/U+0020");
+        assert_eq!(frame_name("font", 13), "font/U+000D");
+        assert_eq!(frame_name("font", 0xD800), "font/U+D800");
+    }
+
+    #[test]
+    fn each_glyph_is_a_frame_of_the_single_texture() {
+        let font = example_font();
+        let plist = write_plist(&font, "font", false).unwrap();
+        assert_eq!(plist.matches("<key>sourceSize</key>").count(), font.characters.len());
+        assert!(plist.contains("<key>font/A</key>"));
+        assert!(plist.contains("<string>{{514,0},{19,30}}</string>"));
+        assert_eq!(plist.matches("<string>ROBUST_0.png</string>").count(), 2);
+
+        let minified = write_plist(&font, "font", true).unwrap();
+        assert_eq!(minified.lines().count(), 1);
+    }
+
+    #[test]
+    fn fonts_with_several_pages_are_refused() {
+        let mut font = example_font();
+        font.metrics.pages.insert(1, "ROBUST_1.png".to_string());
+        assert!(matches!(write_plist(&font, "font", false), Err(ConvertError::SinglePage(_, 2))));
+    }
+}
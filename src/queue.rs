@@ -0,0 +1,147 @@
+//! The fonts waiting for a batch conversion, in the order they are run, and
+//! how far each has got.
+
+use crate::batch::BatchResult;
+use std::path::{Path, PathBuf};
+
+/// Where a [`QueueEntry`] is in its conversion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    Pending,
+    Converting,
+    /// Converted into the file given.
+    Done(PathBuf),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueEntry {
+    pub input: PathBuf,
+    pub state: JobState,
+}
+
+/// Fonts queued for batch conversion, each listed once.
+#[derive(Debug, Clone, Default)]
+pub struct JobQueue {
+    entries: Vec<QueueEntry>,
+}
+
+impl JobQueue {
+    pub fn entries(&self) -> &[QueueEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, input: &Path) -> bool {
+        self.position(input).is_some()
+    }
+
+    fn position(&self, input: &Path) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.input == input)
+    }
+
+    /// Appends `input` as pending unless it is already queued. Returns
+    /// whether it was added.
+    pub fn push(&mut self, input: PathBuf) -> bool {
+        if self.contains(&input) {
+            return false;
+        }
+        self.entries.push(QueueEntry {
+            input,
+            state: JobState::Pending,
+        });
+        true
+    }
+
+    /// Removes the entry at `index`, unless it is being converted.
+    pub fn remove(&mut self, index: usize) -> Option<QueueEntry> {
+        match self.entries.get(index) {
+            Some(entry) if entry.state != JobState::Converting => Some(self.entries.remove(index)),
+            _ => None,
+        }
+    }
+
+    /// Moves the entry at `from` so it ends up at `to`, shifting the ones in
+    /// between.
+    pub fn move_entry(&mut self, from: usize, to: usize) {
+        if from >= self.entries.len() {
+            return;
+        }
+        let entry = self.entries.remove(from);
+        self.entries.insert(to.min(self.entries.len()), entry);
+    }
+
+    /// Removes every entry that is not being converted.
+    pub fn clear(&mut self) {
+        self.entries.retain(|entry| entry.state == JobState::Converting);
+    }
+
+    /// Marks every entry pending again, e.g. before the whole queue is rerun.
+    pub fn reset(&mut self) {
+        for entry in &mut self.entries {
+            entry.state = JobState::Pending;
+        }
+    }
+
+    /// Marks the entry at `index` pending again and returns its input, unless
+    /// it is being converted.
+    pub fn requeue(&mut self, index: usize) -> Option<PathBuf> {
+        let entry = self.entries.get_mut(index).filter(|entry| entry.state != JobState::Converting)?;
+        entry.state = JobState::Pending;
+        Some(entry.input.clone())
+    }
+
+    /// The pending inputs, in queue order.
+    pub fn pending(&self) -> Vec<PathBuf> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state == JobState::Pending)
+            .map(|entry| entry.input.clone())
+            .collect()
+    }
+
+    /// Marks `input` as being converted, queueing it first if it is not, as
+    /// happens for fonts found while converting a folder.
+    pub fn start(&mut self, input: &Path) {
+        self.push(input.to_path_buf());
+        if let Some(index) = self.position(input) {
+            self.entries[index].state = JobState::Converting;
+        }
+    }
+
+    /// Records the outcome of converting `result.input`, if it is still
+    /// queued.
+    pub fn finish(&mut self, result: &BatchResult) {
+        let Some(index) = self.position(&result.input) else {
+            return;
+        };
+        self.entries[index].state = match result.result {
+            Ok(ref output) => JobState::Done(output.clone()),
+            Err(ref e) => JobState::Failed(e.clone()),
+        };
+    }
+
+    /// Marks entries still converting as pending, e.g. after a run ended
+    /// without reporting them.
+    pub fn interrupt(&mut self) {
+        for entry in &mut self.entries {
+            if entry.state == JobState::Converting {
+                entry.state = JobState::Pending;
+            }
+        }
+    }
+
+    /// How many entries are done and how many failed.
+    pub fn outcome(&self) -> (usize, usize) {
+        let done = self.entries.iter().filter(|entry| matches!(entry.state, JobState::Done(_))).count();
+        let failed = self.entries.iter().filter(|entry| matches!(entry.state, JobState::Failed(_))).count();
+        (done, failed)
+    }
+}
@@ -0,0 +1,193 @@
+//! Reading glyphs from the slightly non-conforming descriptors some
+//! exporters write, notably Hiero and Littera: blank or left-out attributes,
+//! `-1` for "none", and whole numbers written as decimals. Rather than
+//! failing the glyph, such values are evened out to what BMFont would have
+//! written, with a warning naming what was filled in. Negative sizes are
+//! kept as written, for validation to report and
+//! [`clamp_negative`](crate::formatter::OutputOptions::clamp_negative) to
+//! clamp.
+
+use crate::error::ConvertError;
+use crate::font::{ALL_CHANNELS, Character, CharacterOffset, CharacterPosition, CharacterSize};
+use tracing::debug;
+
+/// The numeric attributes of a `<char>` element or `char` line, gathered as
+/// they are read. Left-out and blank attributes stay `None`.
+#[derive(Default)]
+pub(crate) struct CharAttributes {
+    id: Option<f64>,
+    x: Option<f64>,
+    y: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+    xoffset: Option<f64>,
+    yoffset: Option<f64>,
+    xadvance: Option<f64>,
+    page: Option<f64>,
+    chnl: Option<f64>,
+}
+
+impl CharAttributes {
+    /// Records the attribute `key`, ignoring ones that are not numeric glyph
+    /// metrics. Fails when `raw` is not a finite number.
+    pub fn set(&mut self, key: &str, raw: &str) -> Result<(), ConvertError> {
+        let slot = match key {
+            "id" => &mut self.id,
+            "x" => &mut self.x,
+            "y" => &mut self.y,
+            "width" => &mut self.width,
+            "height" => &mut self.height,
+            "xoffset" => &mut self.xoffset,
+            "yoffset" => &mut self.yoffset,
+            "xadvance" => &mut self.xadvance,
+            "page" => &mut self.page,
+            "chnl" => &mut self.chnl,
+            _ => return Ok(()),
+        };
+        let value = raw.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+        let number = value.parse::<f64>().ok().filter(|number| number.is_finite()).ok_or_else(|| ConvertError::BadAttribute {
+            key: key.to_string(),
+            value: raw.to_string(),
+        })?;
+        *slot = Some(number);
+        Ok(())
+    }
+
+    /// The glyph's codepoint and layout, with `letter` as read by the caller.
+    /// A warning is added to `filled_in` for each glyph with attributes that
+    /// had to be filled in, other than a left-out `page` or `chnl`, which
+    /// BMFont's own text output may leave out.
+    ///
+    /// - A left-out, negative or fractional `id` is an error, since there is
+    ///   no glyph to attach the metrics to.
+    /// - Left-out positions, sizes and offsets become 0.
+    /// - A left-out `xadvance` becomes the distance to the right edge of the
+    ///   glyph.
+    /// - A left-out or negative `page` becomes the first page.
+    /// - A left-out `chnl`, and the 0 or out-of-range values some exporters
+    ///   write instead of 15, become [`ALL_CHANNELS`].
+    pub fn finish(self, letter: Option<String>, filled_in: &mut Vec<String>) -> Result<(u32, Character), ConvertError> {
+        let id = self.id.ok_or(ConvertError::MissingAttribute("id"))?;
+        if id < 0.0 || id.fract() != 0.0 || id > f64::from(u32::MAX) {
+            return Err(ConvertError::BadAttribute {
+                key: "id".to_string(),
+                value: id.to_string(),
+            });
+        }
+        let id = id as u32;
+
+        let mut fixed = Vec::new();
+        let mut metric = |value: Option<f64>, name| match value {
+            Some(value) => value as f32,
+            None => {
+                fixed.push(name);
+                0.0
+            }
+        };
+        let x = metric(self.x, "x");
+        let y = metric(self.y, "y");
+        let width = metric(self.width, "width");
+        let height = metric(self.height, "height");
+        let xoffset = metric(self.xoffset, "xoffset");
+        let yoffset = metric(self.yoffset, "yoffset");
+        let advance = match self.xadvance {
+            Some(advance) => advance as f32,
+            None => {
+                fixed.push("xadvance");
+                xoffset + width
+            }
+        };
+        let page = match self.page {
+            Some(page) if page >= 0.0 => page.round() as u32,
+            Some(_) => {
+                fixed.push("page");
+                0
+            }
+            None => 0,
+        };
+        let channel = match self.chnl {
+            Some(chnl) if (1.0..=f64::from(ALL_CHANNELS)).contains(&chnl) => chnl as u8,
+            Some(_) => {
+                fixed.push("chnl");
+                ALL_CHANNELS
+            }
+            None => ALL_CHANNELS,
+        };
+        if !fixed.is_empty() {
+            debug!(id, attributes = ?fixed, "filled in non-conforming char attributes");
+            filled_in.push(format!("Warning: glyph {id} has a left-out or invalid {}; filled in as BMFont would", fixed.join(", ")));
+        }
+
+        Ok((
+            id,
+            Character {
+                size: CharacterSize { width, height },
+                position: CharacterPosition { x, y },
+                offset: CharacterOffset {
+                    x: xoffset,
+                    y: yoffset,
+                },
+                advance,
+                page,
+                channel,
+                letter,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(attributes: &[(&str, &str)]) -> (Result<(u32, Character), ConvertError>, Vec<String>) {
+        let mut parsed = CharAttributes::default();
+        for (key, raw) in attributes {
+            parsed.set(key, raw).unwrap();
+        }
+        let mut filled_in = Vec::new();
+        (parsed.finish(None, &mut filled_in), filled_in)
+    }
+
+    const METRICS: [(&str, &str); 8] = [
+        ("id", "65"),
+        ("x", "1"),
+        ("y", "2"),
+        ("width", "3"),
+        ("height", "4"),
+        ("xoffset", "0"),
+        ("yoffset", "5"),
+        ("xadvance", "6"),
+    ];
+
+    #[test]
+    fn left_out_page_and_channel_are_filled_in_quietly() {
+        let (parsed, filled_in) = glyph(&METRICS);
+        let (_, glyph) = parsed.unwrap();
+        assert_eq!((glyph.page, glyph.channel), (0, ALL_CHANNELS));
+        assert!(filled_in.is_empty(), "{filled_in:?}");
+    }
+
+    #[test]
+    fn invalid_values_are_filled_in_with_a_warning() {
+        let attributes: Vec<_> = METRICS.iter().copied().chain([("page", "-1"), ("chnl", "0")]).collect();
+        let (parsed, filled_in) = glyph(&attributes);
+        let (_, data) = parsed.unwrap();
+        assert_eq!((data.page, data.channel), (0, ALL_CHANNELS));
+        assert_eq!(filled_in, ["Warning: glyph 65 has a left-out or invalid page, chnl; filled in as BMFont would"]);
+
+        let (parsed, filled_in) = glyph(&METRICS[..7]);
+        assert_eq!(parsed.unwrap().1.advance, 3.0);
+        assert_eq!(filled_in.len(), 1);
+    }
+
+    #[test]
+    fn glyphs_need_a_whole_id() {
+        assert!(matches!(glyph(&METRICS[1..]).0, Err(ConvertError::MissingAttribute("id"))));
+        assert!(matches!(glyph(&[("id", "6.5")]).0, Err(ConvertError::BadAttribute { .. })));
+        assert!(CharAttributes::default().set("x", "NaN").is_err());
+    }
+}
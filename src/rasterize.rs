@@ -0,0 +1,184 @@
+//! Generating a BMFont from a TrueType or OpenType font: rasterizing the
+//! glyphs, packing them into atlas pages and filling in the metrics.
+
+use crate::error::ConvertError;
+use crate::font::{ALL_CHANNELS, Character, CharacterOffset, CharacterPosition, CharacterSize, CommonMetrics, Font, FontMetrics, GlyphSpacing};
+use crate::pack::{PackedFont, shelf_pack};
+use crate::subset::Subset;
+use ab_glyph::{Font as _, FontRef, GlyphId, PxScale, ScaleFont, point};
+use image::{Rgba, RgbaImage};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::info;
+
+/// Settings for [`rasterize`].
+#[derive(Debug, Clone)]
+pub struct RasterOptions {
+    /// The em size to render at, in pixels; written as `<info size>`.
+    pub size: f32,
+    /// The characters to include. Ones the font has no glyph for are skipped.
+    pub charset: Subset,
+    /// Width and height of each atlas page.
+    pub texture_size: u32,
+    /// Empty pixels between packed glyphs.
+    pub padding: u32,
+    /// Look up kerning for every pair of included glyphs, which is slow for
+    /// very large character sets.
+    pub kerning: bool,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            size: 32.0,
+            charset: Subset::parse_ranges("0x20-0x7E").expect("valid default charset"),
+            texture_size: 512,
+            padding: 1,
+            kerning: true,
+        }
+    }
+}
+
+/// A rasterized glyph waiting to be packed.
+struct Bitmap {
+    id: u32,
+    glyph: GlyphId,
+    width: u32,
+    height: u32,
+    offset: (f32, f32),
+    advance: f32,
+    pixels: Vec<u8>,
+}
+
+/// Rasterizes the charset of the font file `data` as white glyphs on a
+/// transparent background. Pages are named `{name}_{page}.png`.
+pub fn rasterize(data: &[u8], name: &str, options: &RasterOptions) -> Result<PackedFont, ConvertError> {
+    let font = FontRef::try_from_slice(data)?;
+    // ab_glyph scales to ascent minus descent; BMFont sizes are em sizes.
+    let units_per_em = font.units_per_em().unwrap_or(font.height_unscaled());
+    let scale = PxScale::from(options.size * font.height_unscaled() / units_per_em);
+    let scaled = font.as_scaled(scale);
+
+    let mut bitmaps = Vec::new();
+    for id in options.charset.codepoints() {
+        let Some(c) = char::from_u32(id) else {
+            continue;
+        };
+        let glyph = font.glyph_id(c);
+        if glyph.0 == 0 {
+            continue;
+        }
+
+        // Positioned on the baseline of a line starting at y = 0, so bounds
+        // are relative to the top of the line as BMFont offsets are.
+        let outlined = font.outline_glyph(glyph.with_scale_and_position(scale, point(0.0, scaled.ascent())));
+        let mut bitmap = Bitmap {
+            id,
+            glyph,
+            width: 0,
+            height: 0,
+            offset: (0.0, 0.0),
+            advance: scaled.h_advance(glyph).round(),
+            pixels: Vec::new(),
+        };
+        if let Some(outlined) = outlined {
+            let bounds = outlined.px_bounds();
+            bitmap.width = bounds.width() as u32;
+            bitmap.height = bounds.height() as u32;
+            bitmap.offset = (bounds.min.x, bounds.min.y);
+            bitmap.pixels = vec![0; (bitmap.width * bitmap.height) as usize];
+            outlined.draw(|x, y, coverage| {
+                if x < bitmap.width && y < bitmap.height {
+                    bitmap.pixels[(y * bitmap.width + x) as usize] = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            });
+        }
+        bitmaps.push(bitmap);
+    }
+
+    let mut kerning = BTreeMap::new();
+    if options.kerning {
+        for first in &bitmaps {
+            for second in &bitmaps {
+                let amount = scaled.kern(first.glyph, second.glyph).round();
+                if amount != 0.0 {
+                    kerning.insert((first.id, second.id), amount);
+                }
+            }
+        }
+    }
+
+    let (characters, pages) = pack(bitmaps, options)?;
+    let page_names = (0..pages.len() as u32).map(|page| (page, format!("{name}_{page}.png"))).collect();
+    let font = Font {
+        metrics: FontMetrics {
+            size: options.size.round() as i32,
+            common: Some(CommonMetrics {
+                line_height: (scaled.ascent() - scaled.descent() + scaled.line_gap()).round(),
+                base: scaled.ascent().round(),
+                scale_w: options.texture_size,
+                scale_h: options.texture_size,
+            }),
+            distance_field: None,
+            declared_chars: None,
+            filled_in: Vec::new(),
+            glyph_spacing: GlyphSpacing {
+                spacing: [options.padding as i32; 2],
+                ..Default::default()
+            },
+            extents: None,
+            pages: page_names,
+            kerning,
+        },
+        characters,
+        duplicate_ids: Vec::new(),
+        source_order: Vec::new(),
+        count_mismatch: None,
+        skipped: Vec::new(),
+        synthesized: BTreeSet::new(),
+    };
+    info!(glyphs = font.characters.len(), pages = pages.len(), size = options.size, "rasterized font");
+    Ok(PackedFont { font, pages })
+}
+
+/// Packs the glyphs into pages and draws them there.
+fn pack(bitmaps: Vec<Bitmap>, options: &RasterOptions) -> Result<(BTreeMap<u32, Character>, Vec<RgbaImage>), ConvertError> {
+    let size = options.texture_size;
+    let rects: Vec<(u32, u32, u32)> = bitmaps.iter().map(|bitmap| (bitmap.id, bitmap.width, bitmap.height)).collect();
+    let (placements, page_count) = shelf_pack(&rects, size, size, options.padding)?;
+
+    let mut characters = BTreeMap::new();
+    let mut pages = vec![RgbaImage::new(size, size); page_count as usize];
+    for bitmap in bitmaps {
+        let placement = placements[&bitmap.id];
+        let page = &mut pages[placement.page as usize];
+        for row in 0..bitmap.height {
+            for column in 0..bitmap.width {
+                let alpha = bitmap.pixels[(row * bitmap.width + column) as usize];
+                page.put_pixel(placement.x + column, placement.y + row, Rgba([255, 255, 255, alpha]));
+            }
+        }
+
+        characters.insert(
+            bitmap.id,
+            Character {
+                size: CharacterSize {
+                    width: bitmap.width as f32,
+                    height: bitmap.height as f32,
+                },
+                position: CharacterPosition {
+                    x: placement.x as f32,
+                    y: placement.y as f32,
+                },
+                offset: CharacterOffset {
+                    x: bitmap.offset.0,
+                    y: bitmap.offset.1,
+                },
+                advance: bitmap.advance,
+                page: placement.page,
+                channel: ALL_CHANNELS,
+                letter: None,
+            },
+        );
+    }
+    Ok((characters, pages))
+}
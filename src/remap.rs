@@ -0,0 +1,152 @@
+//! Moving glyphs to other codepoints before export, e.g. icons a designer
+//! put in the private use area onto codepoints that can be typed.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::subset::parse_codepoint;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::debug;
+
+/// Which codepoint each remapped glyph moves to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodepointMap {
+    map: BTreeMap<u32, u32>,
+}
+
+impl CodepointMap {
+    /// Parses `from,to` lines, with codepoints written as for
+    /// [`Subset::parse_ranges`](crate::subset::Subset::parse_ranges), e.g.
+    /// `U+E000,U+2605`. Blank lines and lines starting with `#` are skipped.
+    pub fn parse_csv(text: &str) -> Result<Self, ConvertError> {
+        let mut map = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad = || ConvertError::Remap(format!("line {}: expected `from,to`, found {line:?}", index + 1));
+            let (from, to) = line.split_once(',').ok_or_else(bad)?;
+            map.insert(parse_codepoint(from).ok_or_else(bad)?, parse_codepoint(to).ok_or_else(bad)?);
+        }
+        Ok(Self { map })
+    }
+
+    /// Parses one codepoint of a mapping given some other way, e.g. as a
+    /// TOML key, written as in [`parse_csv`](Self::parse_csv).
+    pub fn parse_codepoint(value: &str) -> Result<u32, ConvertError> {
+        parse_codepoint(value).ok_or_else(|| ConvertError::Remap(format!("invalid codepoint {value:?}")))
+    }
+
+    pub fn insert(&mut self, from: u32, to: u32) {
+        self.map.insert(from, to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// A copy of `font` with each mapped glyph moved to its new codepoint,
+    /// along with its kerning pairs. A glyph moved onto a codepoint the font
+    /// already defines replaces that glyph, unless that one moves too, so
+    /// two glyphs can swap places. Moved glyphs lose their `letter`, which
+    /// named the old character.
+    pub fn apply(&self, font: &Font) -> Font {
+        let target = |id: u32| self.map.get(&id).copied().unwrap_or(id);
+        let moved: BTreeMap<u32, _> = font
+            .characters
+            .iter()
+            .filter(|(id, _)| self.map.contains_key(id))
+            .map(|(id, data)| {
+                let mut data = data.clone();
+                data.letter = None;
+                (target(*id), data)
+            })
+            .collect();
+        // Glyphs that stay where they are but have a moved glyph land on them.
+        let replaced: BTreeSet<u32> =
+            moved.keys().copied().filter(|id| !self.map.contains_key(id) && font.characters.contains_key(id)).collect();
+
+        let mut remapped = font.clone();
+        remapped.characters.retain(|id, _| !self.map.contains_key(id) && !replaced.contains(id));
+        remapped.characters.extend(moved);
+        remapped.metrics.kerning = font
+            .metrics
+            .kerning
+            .iter()
+            .filter(|((first, second), _)| !replaced.contains(first) && !replaced.contains(second))
+            .map(|(&(first, second), &amount)| ((target(first), target(second)), amount))
+            .collect();
+        let mut seen = BTreeSet::new();
+        remapped.source_order = font
+            .source_order
+            .iter()
+            .filter(|id| !replaced.contains(id))
+            .map(|id| target(*id))
+            .filter(|id| seen.insert(*id))
+            .collect();
+        remapped.duplicate_ids = font.duplicate_ids.iter().map(|id| target(*id)).collect();
+        debug!(mapped = self.map.len(), replaced = replaced.len(), "remapped codepoints");
+        remapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    #[test]
+    fn csv_lines_are_parsed() {
+        let map = CodepointMap::parse_csv("# icons\nU+E000,U+2605\n\n 0x41 , 97\n").unwrap();
+        assert_eq!(map.map, BTreeMap::from([(0xe000, 0x2605), (0x41, 97)]));
+    }
+
+    #[test]
+    fn bad_lines_are_refused_with_their_number() {
+        for (text, line) in [("65,66\n65", "line 2: expected `from,to`, found \"65\""), ("U+ZZ,66", "line 1: expected `from,to`, found \"U+ZZ,66\"")] {
+            match CodepointMap::parse_csv(text) {
+                Err(ConvertError::Remap(message)) => assert_eq!(message, line),
+                other => panic!("expected a remap error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn moved_glyphs_take_their_kerning_along() {
+        let font = example_font();
+        let mut map = CodepointMap::default();
+        map.insert(65, 0xe000);
+        let remapped = map.apply(&font);
+        assert!(!remapped.characters.contains_key(&65));
+        assert_eq!(remapped.characters[&0xe000].position.x, font.characters[&65].position.x);
+        assert_eq!(remapped.characters[&0xe000].letter, None);
+        assert_eq!(remapped.metrics.kerning[&(0xe000, 34)], -1.0);
+        assert!(!remapped.metrics.kerning.keys().any(|(first, second)| *first == 65 || *second == 65));
+    }
+
+    #[test]
+    fn glyphs_can_swap_places() {
+        let font = example_font();
+        let mut map = CodepointMap::default();
+        map.insert(65, 66);
+        map.insert(66, 65);
+        let swapped = map.apply(&font);
+        assert_eq!(swapped.characters.len(), font.characters.len());
+        assert_eq!(swapped.characters[&66].position.x, font.characters[&65].position.x);
+        assert_eq!(swapped.characters[&65].position.x, font.characters[&66].position.x);
+
+        // Moving onto a glyph that stays replaces it, with its kerning.
+        let mut map = CodepointMap::default();
+        map.insert(65, 66);
+        let replaced = map.apply(&font);
+        assert_eq!(replaced.characters.len(), font.characters.len() - 1);
+        assert_eq!(replaced.characters[&66].position.x, font.characters[&65].position.x);
+        let kerned_with_b = |font: &Font| font.metrics.kerning.keys().filter(|(first, _)| *first == 66).count();
+        assert_eq!(kerned_with_b(&replaced), font.metrics.kerning.keys().filter(|(first, _)| *first == 65).count());
+    }
+}
@@ -0,0 +1,100 @@
+//! Machine-readable per-file results of a conversion, for CI scripts.
+
+use crate::error::{ConvertError, ErrorKind};
+use crate::font::Font;
+use crate::formatter::{OutputOptions, exported_font};
+use crate::validate::collect_warnings;
+use serde::Serialize;
+
+/// What a successful conversion produced.
+#[derive(Debug, Clone, Default)]
+pub struct ConvertSummary {
+    /// Glyphs written to the output, after any subset.
+    pub glyphs: usize,
+    /// Validation warnings for the font.
+    pub warnings: Vec<String>,
+}
+
+impl ConvertSummary {
+    /// Counts the glyphs of `font` that `options` exports and collects its
+    /// validation warnings.
+    pub fn of(font: &Font, options: &OutputOptions) -> Self {
+        Self {
+            glyphs: exported_font(font, options).characters.keys().filter(|id| options.includes(**id)).count(),
+            warnings: collect_warnings(font),
+        }
+    }
+}
+
+/// Whether a file converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+/// Why a file failed to convert, and where in the input.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Byte offset into the (decompressed) input.
+    pub offset: Option<u64>,
+}
+
+impl ErrorReport {
+    pub fn of(error: &ConvertError) -> Self {
+        let location = error.location();
+        let line_column = location.and_then(|location| location.line_column);
+        Self {
+            kind: error.kind(),
+            message: error.cause().to_string(),
+            line: line_column.map(|(line, _)| line),
+            column: line_column.map(|(_, column)| column),
+            offset: location.map(|location| location.offset),
+        }
+    }
+}
+
+/// The result of converting one file, serialized as one entry of
+/// `--report json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileReport {
+    pub input: String,
+    pub output: Option<String>,
+    pub status: Status,
+    pub glyphs: Option<usize>,
+    pub warnings: Vec<String>,
+    pub error: Option<ErrorReport>,
+}
+
+impl FileReport {
+    pub fn converted(input: String, output: Option<String>, summary: ConvertSummary) -> Self {
+        Self {
+            input,
+            output,
+            status: Status::Ok,
+            glyphs: Some(summary.glyphs),
+            warnings: summary.warnings,
+            error: None,
+        }
+    }
+
+    pub fn failed(input: String, error: &ConvertError) -> Self {
+        let warnings = match error.cause() {
+            ConvertError::Validation(warnings) => warnings.clone(),
+            _ => Vec::new(),
+        };
+        Self {
+            input,
+            output: None,
+            status: Status::Error,
+            glyphs: None,
+            warnings,
+            error: Some(ErrorReport::of(error)),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Writing converted fonts into a Rojo project, so the module lands in the
+//! source tree Rojo syncs into the game instead of being copied there by hand.
+
+use crate::batch::source_name;
+use crate::error::ConvertError;
+use crate::formatter::{OutputOptions, script_name};
+use std::path::{Component, Path, PathBuf};
+use tracing::debug;
+
+/// The folder, relative to the project file, fonts are written into unless
+/// another is given.
+pub const DEFAULT_FOLDER: &str = "src/shared/Fonts";
+
+/// The files and folders the Rojo project file at `project` syncs: every
+/// `$path` in its tree, relative to the directory the project file is in.
+pub fn synced_paths(project: &Path) -> Result<Vec<PathBuf>, ConvertError> {
+    let text = std::fs::read_to_string(project)?;
+    let json: serde_json::Value =
+        serde_json::from_str(&text).map_err(|e| ConvertError::Rojo(format!("{}: {e}", project.display())))?;
+    let Some(tree) = json.get("tree") else {
+        return Err(ConvertError::Rojo(format!("{} has no `tree`", project.display())));
+    };
+    let mut paths = Vec::new();
+    collect_paths(tree, &mut paths);
+    Ok(paths)
+}
+
+fn collect_paths(node: &serde_json::Value, paths: &mut Vec<PathBuf>) {
+    let Some(node) = node.as_object() else {
+        return;
+    };
+    if let Some(path) = node.get("$path").and_then(serde_json::Value::as_str) {
+        paths.push(normalized(Path::new(path)));
+    }
+    // Keys other than `$className`, `$properties` and the like are children.
+    for (_, child) in node.iter().filter(|(key, _)| !key.starts_with('$')) {
+        collect_paths(child, paths);
+    }
+}
+
+/// `path` without `.` components, so `./src` and `src` compare equal.
+fn normalized(path: &Path) -> PathBuf {
+    path.components().filter(|component| *component != Component::CurDir).collect()
+}
+
+/// Where the module converted from `input` goes in the Rojo project at
+/// `project`: `init.lua` in a folder under `folder` named like the module
+/// script of a Roblox model export, which Rojo syncs as a ModuleScript of
+/// that name. `folder` is relative to the project file and must lie inside
+/// one of its [`synced_paths`].
+pub fn module_path(project: &Path, folder: &Path, input: &Path, options: &OutputOptions) -> Result<PathBuf, ConvertError> {
+    let root = project.parent().unwrap_or(Path::new(""));
+    let relative = if folder.is_absolute() {
+        folder
+            .strip_prefix(root)
+            .map_err(|_| ConvertError::Rojo(format!("{} is outside the project", folder.display())))?
+    } else {
+        folder
+    };
+    let relative = normalized(relative);
+    if !synced_paths(project)?.iter().any(|synced| relative.starts_with(synced)) {
+        return Err(ConvertError::Rojo(format!(
+            "{} is not inside any $path of {}, so Rojo would not sync it",
+            relative.display(),
+            project.display()
+        )));
+    }
+    let name = script_name(&source_name(&[input]), options);
+    let module = root.join(relative).join(name).join("init.lua");
+    debug!(project = %project.display(), module = %module.display(), "placed module in Rojo project");
+    Ok(module)
+}
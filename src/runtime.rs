@@ -0,0 +1,143 @@
+//! Writing fonts as a compact binary file for engines where parsing a large
+//! Lua table at startup is too slow, and the Lua module that decodes it.
+//!
+//! The file is little-endian throughout:
+//!
+//! | Offset | Size | Field                                   |
+//! |--------|------|-----------------------------------------|
+//! | 0      | 4    | magic bytes `XLFB`                      |
+//! | 4      | 1    | version, currently 1                    |
+//! | 5      | 1    | reserved, 0                             |
+//! | 6      | 2    | font size (i16)                         |
+//! | 8      | 2    | line height (i16)                       |
+//! | 10     | 2    | base (i16)                              |
+//! | 12     | 2    | texture width (u16)                     |
+//! | 14     | 2    | texture height (u16)                    |
+//! | 16     | 2    | page count (u16)                        |
+//! | 18     | 4    | glyph count (u32)                       |
+//! | 22     | 4    | kerning pair count (u32)                |
+//!
+//! The header is followed by each page file name as a u16 byte length and
+//! its UTF-8 bytes, then 20 bytes per glyph in codepoint order: codepoint
+//! (u32); width, height, x and y (u16); x offset, y offset and advance
+//! (i16); the 0-based index of its page in the list (u8) and its `chnl`
+//! (u8). Last come 10 bytes per kerning pair: first and second codepoint
+//! (u32) and amount (i16). Metrics are those of the other formats, rounded
+//! to whole pixels.
+
+use crate::font::Font;
+use crate::formatter::{OutputOptions, adjusted_font, exported_font};
+
+const MAGIC: &[u8; 4] = b"XLFB";
+const VERSION: u8 = 1;
+
+fn i16_bytes(value: f32) -> [u8; 2] {
+    (value.round().clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16).to_le_bytes()
+}
+
+fn u16_bytes(value: f32) -> [u8; 2] {
+    (value.round().clamp(0.0, f32::from(u16::MAX)) as u16).to_le_bytes()
+}
+
+/// The exported glyphs, metrics, pages and kerning of `font` in the binary
+/// layout described in the [module documentation](self).
+pub fn write_runtime_binary(font: &Font, options: &OutputOptions) -> Vec<u8> {
+    let font = adjusted_font(&exported_font(font, options), options);
+    let common = font.metrics.common.as_ref();
+    let kerning = &font.metrics.kerning;
+
+    let mut bytes = Vec::with_capacity(26 + font.characters.len() * 20 + kerning.len() * 10);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&[VERSION, 0]);
+    bytes.extend_from_slice(&i16_bytes(font.metrics.size as f32));
+    bytes.extend_from_slice(&i16_bytes(common.map_or(0.0, |common| common.line_height)));
+    bytes.extend_from_slice(&i16_bytes(common.map_or(0.0, |common| common.base)));
+    bytes.extend_from_slice(&u16_bytes(common.map_or(0.0, |common| common.scale_w as f32)));
+    bytes.extend_from_slice(&u16_bytes(common.map_or(0.0, |common| common.scale_h as f32)));
+    bytes.extend_from_slice(&(font.metrics.pages.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&(font.characters.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(kerning.len() as u32).to_le_bytes());
+
+    for file in font.metrics.pages.values() {
+        bytes.extend_from_slice(&(file.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(file.as_bytes());
+    }
+    for (id, data) in &font.characters {
+        let page = font.metrics.pages.keys().position(|page| *page == data.page).unwrap_or(0);
+        bytes.extend_from_slice(&id.to_le_bytes());
+        for value in [data.size.width, data.size.height, data.position.x, data.position.y] {
+            bytes.extend_from_slice(&u16_bytes(value));
+        }
+        for value in [data.offset.x, data.offset.y, data.advance] {
+            bytes.extend_from_slice(&i16_bytes(value));
+        }
+        bytes.extend_from_slice(&[page as u8, data.channel]);
+    }
+    for ((first, second), amount) in kerning {
+        bytes.extend_from_slice(&first.to_le_bytes());
+        bytes.extend_from_slice(&second.to_le_bytes());
+        bytes.extend_from_slice(&i16_bytes(*amount));
+    }
+    bytes
+}
+
+/// A Lua module returning a function that decodes the contents of a file
+/// written by [`write_runtime_binary`] into a font table. Glyphs are keyed
+/// by codepoint and hold `Width`, `Height`, `X`, `Y`, `XOffset`, `YOffset`,
+/// `Advance`, a 1-based `Page` into `Pages` and `Channel`; `Kerning` maps
+/// first to second codepoint to amount. It only uses `string.byte` and
+/// `string.sub`, so it runs on Lua 5.1 and later, LuaJIT and Luau.
+pub fn runtime_loader() -> &'static str {
+    include_str!("runtime_loader.lua")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn binary_follows_the_documented_layout() {
+        let (font, options) = (example_font(), OutputOptions::default());
+        let exported = adjusted_font(&exported_font(&font, &options), &options);
+        let bytes = write_runtime_binary(&font, &options);
+
+        assert_eq!(&bytes[..6], b"XLFB\x01\x00");
+        assert_eq!([2, 4, 6, 8, 10].map(|field| u16_at(&bytes, 4 + field)), [50, 50, 41, 1024, 1024]);
+        assert_eq!(u16_at(&bytes, 16), 1);
+        let (glyphs, pairs) = (u32_at(&bytes, 18) as usize, u32_at(&bytes, 22) as usize);
+        assert_eq!((glyphs, pairs), (exported.characters.len(), exported.metrics.kerning.len()));
+
+        let name = b"ROBUST_0.png";
+        assert_eq!(u16_at(&bytes, 26) as usize, name.len());
+        assert_eq!(&bytes[28..28 + name.len()], name);
+        let glyph_start = 28 + name.len();
+        assert_eq!(bytes.len(), glyph_start + glyphs * 20 + pairs * 10);
+
+        // Glyphs are in codepoint order, so A sits at its index among them.
+        let a = glyph_start + 20 * exported.characters.keys().position(|id| *id == 65).unwrap();
+        assert_eq!(u32_at(&bytes, a), 65);
+        assert_eq!([0, 2, 4, 6, 8, 10, 12].map(|field| u16_at(&bytes, a + 4 + field) as i16), [19, 30, 514, 0, 0, 11, 19]);
+        assert_eq!(bytes[a + 18..a + 20], [0, 15]);
+
+        let kerning = glyph_start + glyphs * 20 + 10 * exported.metrics.kerning.keys().position(|pair| *pair == (65, 34)).unwrap();
+        assert_eq!((u32_at(&bytes, kerning), u32_at(&bytes, kerning + 4)), (65, 34));
+        assert_eq!(u16_at(&bytes, kerning + 8) as i16, -1);
+    }
+
+    #[test]
+    fn metrics_are_rounded_and_clamped() {
+        assert_eq!(i16_bytes(-2.6), (-3i16).to_le_bytes());
+        assert_eq!(i16_bytes(1e9), i16::MAX.to_le_bytes());
+        assert_eq!(u16_bytes(-5.0), [0, 0]);
+        assert_eq!(u16_bytes(70000.0), u16::MAX.to_le_bytes());
+    }
+}
@@ -0,0 +1,64 @@
+-- Decodes the binary font files written by XmlToLuaConverter's
+-- --runtime-binary. Require this module and call it with a file's contents:
+--   local font = require(path.to.loader)(contents)
+
+local function u16(data, at)
+	local low, high = string.byte(data, at, at + 1)
+	return low + high * 256
+end
+
+local function i16(data, at)
+	local value = u16(data, at)
+	if value >= 32768 then
+		value = value - 65536
+	end
+	return value
+end
+
+local function u32(data, at)
+	local a, b, c, d = string.byte(data, at, at + 3)
+	return a + b * 256 + c * 65536 + d * 16777216
+end
+
+return function(data)
+	assert(string.sub(data, 1, 4) == "XLFB", "not a runtime font file")
+	assert(string.byte(data, 5) == 1, "unsupported runtime font version")
+	local font = {
+		Size = i16(data, 7),
+		LineHeight = i16(data, 9),
+		Base = i16(data, 11),
+		TextureWidth = u16(data, 13),
+		TextureHeight = u16(data, 15),
+		Pages = {},
+		Characters = {},
+		Kerning = {},
+	}
+	local pageCount, glyphCount, pairCount = u16(data, 17), u32(data, 19), u32(data, 23)
+	local at = 27
+	for page = 1, pageCount do
+		local length = u16(data, at)
+		font.Pages[page] = string.sub(data, at + 2, at + 1 + length)
+		at = at + 2 + length
+	end
+	for _ = 1, glyphCount do
+		font.Characters[u32(data, at)] = {
+			Width = u16(data, at + 4),
+			Height = u16(data, at + 6),
+			X = u16(data, at + 8),
+			Y = u16(data, at + 10),
+			XOffset = i16(data, at + 12),
+			YOffset = i16(data, at + 14),
+			Advance = i16(data, at + 16),
+			Page = string.byte(data, at + 18) + 1,
+			Channel = string.byte(data, at + 19),
+		}
+		at = at + 20
+	end
+	for _ = 1, pairCount do
+		local first, second = u32(data, at), u32(data, at + 4)
+		font.Kerning[first] = font.Kerning[first] or {}
+		font.Kerning[first][second] = i16(data, at + 8)
+		at = at + 10
+	end
+	return font
+end
@@ -0,0 +1,365 @@
+//! Checking BMFont XML against the structure BMFont writes before parsing
+//! it, so every missing element or attribute and every malformed value is
+//! reported at once with its line and column, rather than parsing stopping
+//! at the first bad attribute deep inside the glyphs.
+
+use crate::binary;
+use crate::error::{ConvertError, Location};
+use crate::parser::{decode_text, is_xml, read_bytes};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::BTreeSet;
+
+/// What an attribute's value must look like.
+#[derive(Clone, Copy)]
+enum Kind {
+    /// Any text.
+    Text,
+    /// `0` or `1`.
+    Flag,
+    /// A whole number, possibly negative.
+    Integer,
+    /// A whole number that is not negative.
+    Count,
+    /// Any number.
+    Number,
+    /// A whole number that is not negative, possibly written as a decimal
+    /// such as `65.0`, as the glyph attributes are read.
+    Id,
+    /// This many whole numbers separated by commas.
+    List(usize),
+}
+
+impl Kind {
+    fn accepts(self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            Kind::Text => true,
+            Kind::Flag => matches!(value, "0" | "1"),
+            Kind::Integer => value.parse::<i32>().is_ok(),
+            Kind::Count => value.parse::<u32>().is_ok(),
+            Kind::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            Kind::Id => value.parse::<f64>().is_ok_and(|id| id >= 0.0 && id.fract() == 0.0 && id <= f64::from(u32::MAX)),
+            Kind::List(len) => {
+                let numbers: Vec<&str> = value.split(',').collect();
+                numbers.len() == len && numbers.iter().all(|number| number.trim().parse::<i32>().is_ok())
+            }
+        }
+    }
+
+    fn expected(self) -> String {
+        match self {
+            Kind::Text => "text".to_string(),
+            Kind::Flag => "0 or 1".to_string(),
+            Kind::Integer => "a whole number".to_string(),
+            Kind::Count | Kind::Id => "a whole number of at least 0".to_string(),
+            Kind::Number => "a number".to_string(),
+            Kind::List(len) => format!("{len} whole numbers separated by commas"),
+        }
+    }
+}
+
+/// An element of the BMFont structure: where it goes, whether a font must
+/// have it, and its attributes with their kinds and whether each is
+/// required. Elements that are not listed are left alone, as are unknown
+/// attributes, which some exporters add.
+struct Element {
+    name: &'static str,
+    parent: Option<&'static str>,
+    required: bool,
+    /// Whether it may appear more than once.
+    repeated: bool,
+    attributes: &'static [(&'static str, Kind, bool)],
+}
+
+const ELEMENTS: &[Element] = &[
+    Element {
+        name: "font",
+        parent: None,
+        required: true,
+        repeated: false,
+        attributes: &[],
+    },
+    Element {
+        name: "info",
+        parent: Some("font"),
+        required: true,
+        repeated: false,
+        attributes: &[
+            ("face", Kind::Text, false),
+            ("size", Kind::Integer, true),
+            ("bold", Kind::Flag, false),
+            ("italic", Kind::Flag, false),
+            ("charset", Kind::Text, false),
+            ("unicode", Kind::Flag, false),
+            ("stretchH", Kind::Number, false),
+            ("smooth", Kind::Flag, false),
+            ("aa", Kind::Integer, false),
+            ("padding", Kind::List(4), false),
+            ("spacing", Kind::List(2), false),
+            ("outline", Kind::Integer, false),
+        ],
+    },
+    Element {
+        name: "common",
+        parent: Some("font"),
+        required: true,
+        repeated: false,
+        attributes: &[
+            ("lineHeight", Kind::Number, true),
+            ("base", Kind::Number, true),
+            ("scaleW", Kind::Count, true),
+            ("scaleH", Kind::Count, true),
+            ("pages", Kind::Count, false),
+            ("packed", Kind::Flag, false),
+            ("alphaChnl", Kind::Count, false),
+            ("redChnl", Kind::Count, false),
+            ("greenChnl", Kind::Count, false),
+            ("blueChnl", Kind::Count, false),
+        ],
+    },
+    Element {
+        name: "distanceField",
+        parent: Some("font"),
+        required: false,
+        repeated: false,
+        attributes: &[("fieldType", Kind::Text, true), ("distanceRange", Kind::Number, true)],
+    },
+    Element {
+        name: "pages",
+        parent: Some("font"),
+        required: true,
+        repeated: false,
+        attributes: &[],
+    },
+    Element {
+        name: "page",
+        parent: Some("pages"),
+        required: false,
+        repeated: true,
+        attributes: &[("id", Kind::Count, true), ("file", Kind::Text, true)],
+    },
+    Element {
+        name: "chars",
+        parent: Some("font"),
+        required: true,
+        repeated: false,
+        attributes: &[("count", Kind::Count, false)],
+    },
+    Element {
+        name: "char",
+        parent: Some("chars"),
+        required: false,
+        repeated: true,
+        attributes: &[
+            ("id", Kind::Id, true),
+            ("x", Kind::Number, true),
+            ("y", Kind::Number, true),
+            ("width", Kind::Number, true),
+            ("height", Kind::Number, true),
+            ("xoffset", Kind::Number, true),
+            ("yoffset", Kind::Number, true),
+            ("xadvance", Kind::Number, true),
+            ("page", Kind::Number, false),
+            ("chnl", Kind::Number, false),
+            ("letter", Kind::Text, false),
+        ],
+    },
+    Element {
+        name: "kernings",
+        parent: Some("font"),
+        required: false,
+        repeated: false,
+        attributes: &[("count", Kind::Count, false)],
+    },
+    Element {
+        name: "kerning",
+        parent: Some("kernings"),
+        required: false,
+        repeated: true,
+        attributes: &[("first", Kind::Count, true), ("second", Kind::Count, true), ("amount", Kind::Number, true)],
+    },
+];
+
+/// Checks the BMFont file at `filename` with [`check_schema`]. Binary, text
+/// and JSON fonts have no XML structure and always pass.
+pub fn check_schema_file(filename: &str) -> Result<(), ConvertError> {
+    let bytes = read_bytes(filename)?;
+    if bytes.starts_with(binary::MAGIC) {
+        return Ok(());
+    }
+    check_schema(&decode_text(&bytes)?)
+}
+
+/// Checks `text`, if it is BMFont XML, for the elements and attributes
+/// BMFont writes: a `<font>` root holding an `<info>`, `<common>`, `<pages>`
+/// and `<chars>`, each element inside the one it belongs in, the required
+/// attributes of each, and values of the right kind. Fails with
+/// [`ConvertError::Schema`] listing every problem, each with its line and
+/// column. Text that is not XML passes.
+pub fn check_schema(text: &str) -> Result<(), ConvertError> {
+    if !is_xml(text) {
+        return Ok(());
+    }
+    let mut problems = Vec::new();
+    let mut problem = |offset: u64, message: String| problems.push(format!("{}: {message}", Location::in_text(offset, text)));
+
+    let mut reader = Reader::from_str(text);
+    // Mismatched end tags are reported below, which keeps track of the
+    // open elements past them.
+    reader.config_mut().check_end_names = false;
+    // The open elements, innermost last.
+    let mut open: Vec<String> = Vec::new();
+    let mut seen = BTreeSet::new();
+    let mut root = None;
+    loop {
+        let start = reader.buffer_position();
+        let (element, empty) = match reader.read_event() {
+            Ok(Event::Eof) => break,
+            Ok(Event::Start(element)) => (element, false),
+            Ok(Event::Empty(element)) => (element, true),
+            Ok(Event::End(element)) => {
+                let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+                match open.pop() {
+                    Some(expected) if expected != name => {
+                        problem(start, format!("malformed XML: expected </{expected}>, found </{name}>"));
+                    }
+                    None => problem(start, format!("malformed XML: </{name}> closes no element")),
+                    _ => {}
+                }
+                continue;
+            }
+            Ok(_) => continue,
+            // The reader resumes after the malformed markup; without progress
+            // it would report the same error forever.
+            Err(e) if reader.buffer_position() > start => {
+                problem(reader.error_position(), format!("malformed XML: {e}"));
+                continue;
+            }
+            Err(e) => {
+                problem(reader.error_position(), format!("malformed XML: {e}"));
+                break;
+            }
+        };
+        let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+        let parent = open.last().map(String::as_str);
+        if parent.is_none() {
+            root.get_or_insert(start);
+        }
+        if let Some(schema) = ELEMENTS.iter().find(|schema| schema.name == name) {
+            check_element(schema, &element, parent, start, !seen.insert(schema.name), &mut problem);
+        } else if parent.is_none() {
+            problem(start, format!("the root element is <{name}>, not <font>"));
+        }
+        if !empty {
+            open.push(name);
+        }
+    }
+
+    for schema in ELEMENTS.iter().filter(|schema| schema.required && !seen.contains(schema.name)) {
+        match schema.parent {
+            Some(parent) => problem(root.unwrap_or(0), format!("missing <{}> inside <{parent}>", schema.name)),
+            None => problem(root.unwrap_or(0), format!("missing the <{}> root element", schema.name)),
+        }
+    }
+
+    if problems.is_empty() { Ok(()) } else { Err(ConvertError::Schema(problems)) }
+}
+
+/// Reports where `element`, read at `offset` inside `parent`, breaks its
+/// `schema`.
+fn check_element(
+    schema: &Element,
+    element: &BytesStart,
+    parent: Option<&str>,
+    offset: u64,
+    seen_before: bool,
+    problem: &mut impl FnMut(u64, String),
+) {
+    let name = schema.name;
+    if parent != schema.parent {
+        match (schema.parent, parent) {
+            (Some(expected), Some(found)) => problem(offset, format!("<{name}> belongs inside <{expected}>, not <{found}>")),
+            (Some(expected), None) => problem(offset, format!("<{name}> belongs inside <{expected}>, not at the root")),
+            (None, Some(found)) => problem(offset, format!("<{name}> belongs at the root, not inside <{found}>")),
+            (None, None) => {}
+        }
+    }
+    if seen_before && !schema.repeated {
+        problem(offset, format!("<{name}> appears more than once"));
+    }
+
+    let mut present = Vec::new();
+    for attribute in element.attributes() {
+        let attribute = match attribute {
+            Ok(attribute) => attribute,
+            Err(e) => {
+                problem(offset, format!("malformed attribute in <{name}>: {e}"));
+                continue;
+            }
+        };
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let Some(&(_, kind, required)) = schema.attributes.iter().find(|(known, _, _)| *known == key) else {
+            continue;
+        };
+        present.push(key.clone());
+        let value = match attribute.unescape_value() {
+            Ok(value) => value,
+            Err(e) => {
+                problem(offset, format!("malformed value for attribute `{key}` of <{name}>: {e}"));
+                continue;
+            }
+        };
+        if value.trim().is_empty() && !matches!(kind, Kind::Text) {
+            if required {
+                problem(offset, format!("attribute `{key}` of <{name}> is empty; expected {}", kind.expected()));
+            }
+        } else if !kind.accepts(&value) {
+            problem(offset, format!("invalid value {:?} for attribute `{key}` of <{name}>; expected {}", value.as_ref(), kind.expected()));
+        }
+    }
+    for (key, _, _) in schema.attributes.iter().filter(|(key, _, required)| *required && !present.iter().any(|present| present == key)) {
+        problem(offset, format!("<{name}> is missing the `{key}` attribute"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(text: &str) -> Vec<String> {
+        match check_schema(text) {
+            Err(ConvertError::Schema(problems)) => problems,
+            other => panic!("expected schema problems, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bmfont_output_passes() {
+        check_schema(&std::fs::read_to_string("examples/font.fnt").unwrap()).unwrap();
+        check_schema("info face=\"Test\" size=32").unwrap();
+    }
+
+    #[test]
+    fn problems_are_reported_with_line_and_column() {
+        let text = "<font>\n  <info size=\"big\"/>\n  <common lineHeight=\"40\" base=\"30\" scaleW=\"256\"/>\n  <pages/>\n</font>";
+        assert_eq!(
+            problems(text),
+            [
+                "line 2, column 3: invalid value \"big\" for attribute `size` of <info>; expected a whole number",
+                "line 3, column 3: <common> is missing the `scaleH` attribute",
+                "line 1, column 1: missing <chars> inside <font>",
+            ]
+        );
+    }
+
+    #[test]
+    fn misplaced_and_repeated_elements_are_reported() {
+        let text = "<font><info size=\"32\"/><common lineHeight=\"40\" base=\"30\" scaleW=\"1\" scaleH=\"1\"/>\
+            <pages/><chars><page id=\"0\" file=\"a.png\"/></chars><chars/></font>";
+        let problems = problems(text);
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].ends_with("<page> belongs inside <pages>, not <chars>"));
+        assert!(problems[1].ends_with("<chars> appears more than once"));
+    }
+}
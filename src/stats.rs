@@ -0,0 +1,64 @@
+//! Summary figures for a parsed font, to judge whether it needs subsetting
+//! before export.
+
+use crate::font::Font;
+use crate::unicode_block::block_of;
+use std::collections::BTreeSet;
+
+/// What a font covers and how much of its texture it uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontStats {
+    pub glyphs: usize,
+    /// Glyph counts per Unicode block, in block order.
+    pub blocks: Vec<(&'static str, usize)>,
+    /// Glyphs outside every block in [`crate::unicode_block::BLOCKS`].
+    pub other_glyphs: usize,
+    /// Smallest and largest advance, or `None` for a font without glyphs.
+    pub advance_range: Option<(f32, f32)>,
+    /// Fraction of the texture pages covered by glyph rectangles, from 0.0
+    /// to 1.0. `None` without `<common>` texture dimensions.
+    pub texture_utilization: Option<f32>,
+    pub kerning_pairs: usize,
+}
+
+impl FontStats {
+    pub fn of(font: &Font) -> Self {
+        let mut blocks: Vec<(&'static str, usize)> = Vec::new();
+        let mut other_glyphs = 0;
+        // Glyphs are in codepoint order, so each block's glyphs are adjacent.
+        for id in font.characters.keys() {
+            match (block_of(*id), blocks.last_mut()) {
+                (Some(block), Some((last, count))) if *last == block => *count += 1,
+                (Some(block), _) => blocks.push((block, 1)),
+                (None, _) => other_glyphs += 1,
+            }
+        }
+
+        let advance_range = font.characters.values().map(|data| data.advance).fold(None, |range, advance| match range {
+            None => Some((advance, advance)),
+            Some((min, max)) => Some((f32::min(min, advance), f32::max(max, advance))),
+        });
+
+        let texture_utilization = font.metrics.common.as_ref().and_then(|common| {
+            let pages: BTreeSet<u32> = font
+                .metrics
+                .pages
+                .keys()
+                .copied()
+                .chain(font.characters.values().map(|data| data.page))
+                .collect();
+            let texture_area = common.scale_w as f32 * common.scale_h as f32 * pages.len().max(1) as f32;
+            let glyph_area: f32 = font.characters.values().map(|data| data.size.width * data.size.height).sum();
+            (texture_area > 0.0).then(|| glyph_area / texture_area)
+        });
+
+        FontStats {
+            glyphs: font.characters.len(),
+            blocks,
+            other_glyphs,
+            advance_range,
+            texture_utilization,
+            kerning_pairs: font.metrics.kerning.len(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Restricting output to a subset of the font's characters.
+
+use crate::error::ConvertError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The codepoints to export, as a list of inclusive ranges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Subset {
+    ranges: Vec<RangeInclusive<u32>>,
+}
+
+impl Subset {
+    /// Parses a comma-separated list of codepoints and ranges, e.g.
+    /// `0x20-0x7E,0x400-0x4FF,8364`. Codepoints are decimal, or hex with a
+    /// `0x` or `U+` prefix.
+    pub fn parse_ranges(spec: &str) -> Result<Self, ConvertError> {
+        let mut ranges = Vec::new();
+        for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let bad_range = || ConvertError::BadRange(item.to_string());
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (start, end),
+                None => (item, item),
+            };
+            let start = parse_codepoint(start).ok_or_else(bad_range)?;
+            let end = parse_codepoint(end).ok_or_else(bad_range)?;
+            if start > end {
+                return Err(bad_range());
+            }
+            ranges.push(start..=end);
+        }
+        Ok(Self { ranges })
+    }
+
+    /// The characters that appear in `text`.
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            ranges: text.chars().map(|c| c as u32..=c as u32).collect(),
+        }
+    }
+
+    /// Characters in either subset.
+    pub fn union(mut self, other: Subset) -> Self {
+        self.ranges.extend(other.ranges);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, id: u32) -> bool {
+        self.ranges.iter().any(|range| range.contains(&id))
+    }
+
+    /// The position of the first range holding `id`, for ordering glyphs by
+    /// the ranges they fall in.
+    pub fn rank(&self, id: u32) -> Option<usize> {
+        self.ranges.iter().position(|range| range.contains(&id))
+    }
+
+    /// Every codepoint in the subset, in codepoint order and without repeats.
+    pub fn codepoints(&self) -> BTreeSet<u32> {
+        self.ranges.iter().flat_map(Clone::clone).collect()
+    }
+}
+
+/// Writes the ranges in the form [`Subset::parse_ranges`] reads.
+impl fmt::Display for Subset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, range) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            if range.start() == range.end() {
+                write!(f, "0x{:X}", range.start())?;
+            } else {
+                write!(f, "0x{:X}-0x{:X}", range.start(), range.end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_codepoint(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .or_else(|| value.strip_prefix("U+"))
+        .or_else(|| value.strip_prefix("u+"));
+    match hex {
+        Some(digits) => u32::from_str_radix(digits, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use crate::formatter::{OutputOptions, render};
+    use crate::lua_import::parse_lua;
+
+    #[test]
+    fn ranges_are_parsed_and_written_back() {
+        let subset = Subset::parse_ranges("0x41-0x43, U+20AC,97").unwrap();
+        assert_eq!(subset.codepoints(), BTreeSet::from([65, 66, 67, 97, 0x20ac]));
+        assert_eq!((subset.rank(66), subset.rank(97), subset.rank(68)), (Some(0), Some(2), None));
+        assert_eq!(subset.to_string(), "0x41-0x43,0x20AC,0x61");
+        assert_eq!(Subset::parse_ranges(&subset.to_string()).unwrap(), subset);
+        for bad in ["0x43-0x41", "A", "1-", "0xZZ"] {
+            assert!(matches!(Subset::parse_ranges(bad), Err(ConvertError::BadRange(item)) if item == bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn subset_keeps_kerning_only_between_kept_glyphs() {
+        let font = example_font();
+        let options = OutputOptions {
+            subset: Some(Subset::from_text("AT").union(Subset::parse_ranges("0x56").unwrap())),
+            ..Default::default()
+        };
+        let exported = parse_lua(&render(&font, "font.fnt", &options).unwrap()).unwrap();
+        assert_eq!(exported.characters.keys().copied().collect::<Vec<_>>(), [65, 84, 86]);
+        let kept = |(first, second): (u32, u32)| [65, 84, 86].contains(&first) && [65, 84, 86].contains(&second);
+        let expected: Vec<_> = font.metrics.kerning.iter().filter(|(pair, _)| kept(**pair)).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(exported.metrics.kerning.iter().collect::<Vec<_>>(), expected);
+    }
+}
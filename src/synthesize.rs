@@ -0,0 +1,160 @@
+//! Stand-in glyphs for the whitespace a font leaves out, so runtimes looking
+//! up a space, tab or no-break space never get `nil`.
+
+use crate::font::{ALL_CHANNELS, Character, CharacterOffset, CharacterPosition, CharacterSize, Font};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+pub const SPACE: u32 = 0x20;
+pub const TAB: u32 = 0x09;
+pub const NO_BREAK_SPACE: u32 = 0xA0;
+
+/// How wide a synthesized space is.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpaceWidth {
+    /// The average advance of the digits 0–9, which most fonts make as wide
+    /// as a figure space. A quarter of the font size when there are none.
+    AverageDigit,
+    /// This fraction of the font size, e.g. 0.25 for a quarter em.
+    Em(f32),
+    /// This many pixels.
+    Pixels(f32),
+}
+
+impl SpaceWidth {
+    /// Parses `digits`, a fraction of the font size such as `0.25em`, or a
+    /// width in pixels such as `8px`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value == "digits" {
+            return Some(SpaceWidth::AverageDigit);
+        }
+        if let Some(fraction) = value.strip_suffix("em") {
+            return fraction.trim().parse().ok().map(SpaceWidth::Em);
+        }
+        value.strip_suffix("px").unwrap_or(value).trim().parse().ok().map(SpaceWidth::Pixels)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpaceWidth::AverageDigit => "Average digit",
+            SpaceWidth::Em(_) => "Fraction of size",
+            SpaceWidth::Pixels(_) => "Pixels",
+        }
+    }
+}
+
+/// The glyphs [`synthesize_whitespace`] adds and how wide they are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WhitespaceRules {
+    /// The width of a space, when the font has none.
+    pub space: SpaceWidth,
+    /// A tab advances as far as this many spaces.
+    pub tab_spaces: f32,
+}
+
+impl Default for WhitespaceRules {
+    fn default() -> Self {
+        Self {
+            space: SpaceWidth::AverageDigit,
+            tab_spaces: 4.0,
+        }
+    }
+}
+
+/// A copy of `font` with an empty glyph added for each of a space, tab and
+/// no-break space it does not define, recorded in
+/// [`Font::synthesized`]. A tab advances [`WhitespaceRules::tab_spaces`]
+/// spaces and a no-break space as far as a space, whether that space is the
+/// font's own or synthesized too.
+pub fn synthesize_whitespace(font: &Font, rules: &WhitespaceRules) -> Font {
+    let space = match font.characters.get(&SPACE) {
+        Some(space) => space.advance,
+        None => match rules.space {
+            SpaceWidth::AverageDigit => {
+                let digits: Vec<f32> = ('0'..='9')
+                    .filter_map(|digit| font.characters.get(&u32::from(digit)))
+                    .map(|data| data.advance)
+                    .collect();
+                if digits.is_empty() {
+                    font.metrics.size.abs() as f32 / 4.0
+                } else {
+                    digits.iter().sum::<f32>() / digits.len() as f32
+                }
+            }
+            SpaceWidth::Em(fraction) => font.metrics.size.abs() as f32 * fraction,
+            SpaceWidth::Pixels(width) => width,
+        },
+    };
+
+    let mut synthesized = font.clone();
+    let page = font.metrics.pages.keys().next().copied().unwrap_or(0);
+    for (id, advance) in [(SPACE, space), (TAB, space * rules.tab_spaces), (NO_BREAK_SPACE, space)] {
+        if font.characters.contains_key(&id) {
+            continue;
+        }
+        synthesized.characters.insert(
+            id,
+            Character {
+                size: CharacterSize { width: 0.0, height: 0.0 },
+                position: CharacterPosition { x: 0.0, y: 0.0 },
+                offset: CharacterOffset { x: 0.0, y: 0.0 },
+                advance,
+                page,
+                channel: ALL_CHANNELS,
+                letter: None,
+            },
+        );
+        synthesized.synthesized.insert(id);
+    }
+    debug!(glyphs = synthesized.synthesized.len() - font.synthesized.len(), space, "synthesized whitespace");
+    synthesized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn space_widths_are_parsed() {
+        assert_eq!(SpaceWidth::parse(" digits "), Some(SpaceWidth::AverageDigit));
+        assert_eq!(SpaceWidth::parse("0.25em"), Some(SpaceWidth::Em(0.25)));
+        assert_eq!(SpaceWidth::parse("8px"), Some(SpaceWidth::Pixels(8.0)));
+        assert_eq!(SpaceWidth::parse("8"), Some(SpaceWidth::Pixels(8.0)));
+        assert_eq!(SpaceWidth::parse("wide"), None);
+    }
+
+    #[test]
+    fn missing_whitespace_follows_the_fonts_space() {
+        let font = example_font();
+        let rules = WhitespaceRules {
+            space: SpaceWidth::Pixels(100.0),
+            tab_spaces: 2.0,
+        };
+        let synthesized = synthesize_whitespace(&font, &rules);
+        assert_eq!(synthesized.synthesized, BTreeSet::from([TAB, NO_BREAK_SPACE]));
+        // The font's own space is kept and sets the width.
+        assert_eq!(synthesized.characters[&SPACE].advance, 11.0);
+        assert_eq!(synthesized.characters[&TAB].advance, 22.0);
+        assert_eq!(synthesized.characters[&NO_BREAK_SPACE].advance, 11.0);
+        assert_eq!(synthesized.characters[&TAB].size.width, 0.0);
+    }
+
+    #[test]
+    fn a_missing_space_is_sized_by_the_rules() {
+        let mut font = example_font();
+        font.characters.remove(&SPACE);
+        let digits: f32 = ('0'..='9').map(|digit| font.characters[&u32::from(digit)].advance).sum();
+        let space = |font: &Font, space| synthesize_whitespace(font, &WhitespaceRules { space, tab_spaces: 4.0 }).characters[&SPACE].advance;
+        assert_eq!(space(&font, SpaceWidth::AverageDigit), digits / 10.0);
+        assert_eq!(space(&font, SpaceWidth::Em(0.5)), 25.0);
+        assert_eq!(space(&font, SpaceWidth::Pixels(7.0)), 7.0);
+
+        // Without digits a space is a quarter of the size.
+        font.characters.retain(|id, _| !(48..=57).contains(id));
+        assert_eq!(space(&font, SpaceWidth::AverageDigit), 12.5);
+    }
+}
@@ -0,0 +1,162 @@
+//! Rendering fonts through user-supplied Handlebars templates instead of the
+//! built-in Lua layout.
+
+use crate::error::ConvertError;
+use crate::font::{Font, number};
+use crate::formatter::{OutputOptions, adjust_font_metrics, adjust_metrics, glyph_extents, glyph_key, lua_string};
+use handlebars::{Handlebars, handlebars_helper};
+use serde::{Serialize, Serializer};
+
+fn optional_number<S: Serializer>(value: &Option<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => number(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Serialize)]
+struct GlyphContext {
+    id: u32,
+    /// The codepoint escaped for use inside a double-quoted Lua string
+    /// literal. It is not safe inside a `[[...]]` long string, which has no
+    /// escapes.
+    key: String,
+    #[serde(serialize_with = "number")]
+    width: f32,
+    #[serde(serialize_with = "number")]
+    height: f32,
+    #[serde(serialize_with = "number")]
+    x: f32,
+    #[serde(serialize_with = "number")]
+    y: f32,
+    #[serde(serialize_with = "number")]
+    xoffset: f32,
+    #[serde(serialize_with = "number")]
+    yoffset: f32,
+    #[serde(serialize_with = "number")]
+    advance: f32,
+    page: u32,
+    channel: u8,
+}
+
+#[derive(Serialize)]
+struct KerningContext {
+    first: u32,
+    second: u32,
+    first_key: String,
+    second_key: String,
+    #[serde(serialize_with = "number")]
+    amount: f32,
+}
+
+#[derive(Serialize)]
+struct PageContext<'a> {
+    id: u32,
+    file: &'a str,
+    /// The CRC-32 of the texture as 8 hex digits, with
+    /// [`OutputOptions::texture_checksums`].
+    checksum: Option<String>,
+}
+
+/// The values a template can refer to, e.g. `{{size}}` or
+/// `{{#each characters}}{{key}}{{/each}}`.
+#[derive(Serialize)]
+struct TemplateContext<'a> {
+    source: &'a str,
+    size: i32,
+    #[serde(serialize_with = "optional_number")]
+    line_height: Option<f32>,
+    #[serde(serialize_with = "optional_number")]
+    base: Option<f32>,
+    /// How far the glyphs reach above and below the baseline.
+    #[serde(serialize_with = "optional_number")]
+    ascent: Option<f32>,
+    #[serde(serialize_with = "optional_number")]
+    descent: Option<f32>,
+    /// The `<info>` padding (up, right, down, left), spacing (horizontal,
+    /// vertical) and outline.
+    padding: [i32; 4],
+    spacing: [i32; 2],
+    outline: i32,
+    texture_width: Option<u32>,
+    texture_height: Option<u32>,
+    pages: Vec<PageContext<'a>>,
+    characters: Vec<GlyphContext>,
+    kerning: Vec<KerningContext>,
+}
+
+handlebars_helper!(lua_string_helper: |value: str| lua_string(value));
+
+/// Renders `font` with the Handlebars `template`. Glyph metrics are scaled,
+/// clamped and rounded according to `options` first, and `size` honours the size
+/// overrides, so templates see the same numbers the Lua output would.
+///
+/// `{{lua_string file}}` quotes any value as a Lua string literal, escaping
+/// quotes, backslashes and control characters.
+pub fn render_template(font: &Font, source: &str, options: &OutputOptions, template: &str) -> Result<String, ConvertError> {
+    let adjusted = adjust_metrics(font, options);
+    let characters = options
+        .character_order
+        .arrange(&adjusted, &font.source_order)
+        .into_iter()
+        .map(|id| (id, &adjusted[&id]))
+        .map(|(id, data)| GlyphContext {
+            id,
+            key: glyph_key(id, &font.characters, options),
+            width: data.size.width,
+            height: data.size.height,
+            x: data.position.x,
+            y: data.position.y,
+            xoffset: data.offset.x,
+            yoffset: data.offset.y,
+            advance: data.advance,
+            page: data.page,
+            channel: data.channel,
+        })
+        .collect();
+
+    let metrics = adjust_font_metrics(font, options);
+    let common = metrics.common.as_ref();
+    let extents = glyph_extents(font, options);
+    let context = TemplateContext {
+        source,
+        size: options.font_size(font),
+        line_height: common.map(|common| common.line_height),
+        base: common.map(|common| common.base),
+        ascent: extents.map(|extents| extents.ascent),
+        descent: extents.map(|extents| extents.descent),
+        padding: metrics.glyph_spacing.padding,
+        spacing: metrics.glyph_spacing.spacing,
+        outline: metrics.glyph_spacing.outline,
+        texture_width: common.map(|common| common.scale_w),
+        texture_height: common.map(|common| common.scale_h),
+        pages: font
+            .metrics
+            .pages
+            .iter()
+            .map(|(id, file)| PageContext {
+                id: *id,
+                file,
+                checksum: options.page_checksums.get(id).map(|checksum| format!("{checksum:08x}")),
+            })
+            .collect(),
+        characters,
+        kerning: metrics
+            .kerning
+            .iter()
+            .map(|((first, second), amount)| KerningContext {
+                first: *first,
+                second: *second,
+                first_key: glyph_key(*first, &font.characters, options),
+                second_key: glyph_key(*second, &font.characters, options),
+                amount: *amount,
+            })
+            .collect(),
+    };
+
+    let mut handlebars = Handlebars::new();
+    // Output is source code, not HTML.
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.register_helper("lua_string", Box::new(lua_string_helper));
+    Ok(handlebars.render_template(template, &context)?)
+}
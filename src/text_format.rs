@@ -0,0 +1,240 @@
+//! Reading the plain-text BMFont format, where each line is a tag followed by
+//! `key=value` pairs, e.g. `char id=65 x=0 y=0 width=12 ...`.
+
+use crate::Progress;
+use crate::error::ConvertError;
+use crate::font::{Character, CommonMetrics, DistanceField, FontMetrics, GlyphSpacing};
+use crate::parser::{Skipped, decode_letter, int_list, skip_or_fail};
+use crate::quirks::CharAttributes;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Splits a line into its tag and `(key, value)` pairs, with quotes removed
+/// from quoted values.
+fn tokenize(line: &str) -> (&str, Vec<(&str, &str)>) {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+
+    let mut pairs = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        let Some((key, after)) = rest.split_once('=') else {
+            break;
+        };
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+            None => after.split_once(char::is_whitespace).unwrap_or((after, "")),
+        };
+        pairs.push((key.trim(), value));
+        rest = after;
+    }
+    (tag, pairs)
+}
+
+fn value<T: FromStr>(key: &str, value: &str) -> Result<T, ConvertError> {
+    value.parse().map_err(|_| ConvertError::BadAttribute {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Like [`value`], for metrics, which must be finite numbers.
+fn number(key: &str, raw: &str) -> Result<f32, ConvertError> {
+    let number: f32 = value(key, raw)?;
+    if !number.is_finite() {
+        return Err(ConvertError::BadAttribute {
+            key: key.to_string(),
+            value: raw.to_string(),
+        });
+    }
+    Ok(number)
+}
+
+/// Text-format counterpart of
+/// [`parse_fnt_streaming_with_progress`](crate::parser::parse_fnt_streaming_with_progress).
+pub fn parse_fnt_text_with_progress<F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: &str,
+    callback: F,
+    progress: P,
+) -> Result<FontMetrics, ConvertError> {
+    parse_text(source, callback, progress, None)
+}
+
+/// [`parse_fnt_text_with_progress`], recording lines that fail to parse in
+/// `skipped` and moving on when it is given.
+pub(crate) fn parse_text<F: FnMut(u32, Character), P: FnMut(Progress)>(
+    source: &str,
+    mut callback: F,
+    mut progress: P,
+    mut skipped: Option<&mut Skipped>,
+) -> Result<FontMetrics, ConvertError> {
+    let mut font_size = None;
+    let mut glyph_spacing = GlyphSpacing::default();
+    let mut common = None;
+    let mut distance_field = None;
+    let mut pages = BTreeMap::new();
+    let mut kerning = BTreeMap::new();
+    let mut char_count: Option<usize> = None;
+    let mut filled_in = Vec::new();
+    let mut parsed = 0;
+
+    for line in source.lines() {
+        let (tag, pairs) = tokenize(line);
+        let handled = (|| -> Result<(), ConvertError> {
+            match tag {
+                "char" => {
+                    let mut attributes = CharAttributes::default();
+                    let mut letter = None;
+
+                    for (key, raw) in pairs {
+                        match key {
+                            "letter" => letter = Some(decode_letter(raw)),
+                            key => attributes.set(key, raw)?,
+                        }
+                    }
+
+                    let (id, character) = attributes.finish(letter, &mut filled_in)?;
+                    callback(id, character);
+
+                    parsed += 1;
+                    progress(match char_count {
+                        Some(count) if count > 0 => Progress::Fraction((parsed as f32 / count as f32).min(1.0)),
+                        _ => Progress::Indeterminate,
+                    });
+                }
+                "chars" => {
+                    for (key, raw) in pairs {
+                        if key == "count" {
+                            char_count = Some(value(key, raw)?);
+                        }
+                    }
+                }
+                "page" => {
+                    let mut id = 0;
+                    let mut file = String::new();
+
+                    for (key, raw) in pairs {
+                        match key {
+                            "id" => id = value(key, raw)?,
+                            "file" => file = raw.to_string(),
+                            _ => {}
+                        }
+                    }
+
+                    pages.insert(id, file);
+                }
+                "kerning" => {
+                    let mut first = 0;
+                    let mut second = 0;
+                    let mut amount = 0.0;
+
+                    for (key, raw) in pairs {
+                        match key {
+                            "first" => first = value(key, raw)?,
+                            "second" => second = value(key, raw)?,
+                            "amount" => amount = number(key, raw)?,
+                            _ => {}
+                        }
+                    }
+
+                    kerning.insert((first, second), amount);
+                }
+                "common" => {
+                    let mut line_height = 0.0;
+                    let mut base = 0.0;
+                    let mut scale_w = 0;
+                    let mut scale_h = 0;
+
+                    for (key, raw) in pairs {
+                        match key {
+                            "lineHeight" => line_height = number(key, raw)?,
+                            "base" => base = number(key, raw)?,
+                            "scaleW" => scale_w = value(key, raw)?,
+                            "scaleH" => scale_h = value(key, raw)?,
+                            _ => {}
+                        }
+                    }
+
+                    common = Some(CommonMetrics {
+                        line_height,
+                        base,
+                        scale_w,
+                        scale_h,
+                    });
+                }
+                "distanceField" => {
+                    let mut field_type = String::new();
+                    let mut range = 0.0;
+
+                    for (key, raw) in pairs {
+                        match key {
+                            "fieldType" => field_type = raw.to_string(),
+                            "distanceRange" => range = number(key, raw)?,
+                            _ => {}
+                        }
+                    }
+
+                    distance_field = Some(DistanceField { field_type, range });
+                }
+                "info" => {
+                    for (key, raw) in pairs {
+                        match key {
+                            "size" => font_size = Some(value(key, raw)?),
+                            "padding" => glyph_spacing.padding = int_list(key, raw)?,
+                            "spacing" => glyph_spacing.spacing = int_list(key, raw)?,
+                            "outline" => glyph_spacing.outline = value(key, raw)?,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+            Ok(())
+        })();
+        let offset = line.as_ptr() as usize - source.as_ptr() as usize;
+        if let Err(e) = handled {
+            skip_or_fail(&mut skipped, Some(tag.to_string()), ConvertError::at(offset as u64, e))?;
+        }
+    }
+
+    let size = font_size.ok_or(ConvertError::MissingInfo)?;
+    progress(Progress::Fraction(1.0));
+    Ok(FontMetrics {
+        size,
+        glyph_spacing,
+        common,
+        distance_field,
+        pages,
+        kerning,
+        declared_chars: char_count,
+        filled_in,
+        extents: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use crate::parser::parse_fnt;
+
+    #[test]
+    fn text_font_parses_like_the_xml_one() {
+        let text = parse_fnt(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/font_text.fnt")).unwrap();
+        let xml = example_font();
+        assert_eq!(serde_json::to_value(&text).unwrap(), serde_json::to_value(&xml).unwrap());
+        assert_eq!(text.metrics.declared_chars, Some(96));
+        assert!(text.metrics.filled_in.is_empty());
+    }
+
+    #[test]
+    fn non_finite_metrics_are_refused() {
+        for line in ["common lineHeight=NaN base=41 scaleW=1 scaleH=1", "common lineHeight=50 base=inf scaleW=1 scaleH=1", "kerning first=65 second=66 amount=NaN"] {
+            let source = format!("info size=50\n{line}\n");
+            let error = parse_fnt_text_with_progress(&source, |_, _| {}, |_| {}).unwrap_err();
+            assert!(matches!(error.cause(), ConvertError::BadAttribute { .. }), "{line}: {error}");
+        }
+        let source = "info size=50\nchar id=65 x=0 y=0 width=1 height=1 xoffset=0 yoffset=0 xadvance=inf\n";
+        assert!(parse_fnt_text_with_progress(source, |_, _| {}, |_| {}).is_err());
+    }
+}
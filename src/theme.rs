@@ -0,0 +1,167 @@
+//! Color themes for the GUI.
+
+use egui::{Color32, RichText, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+
+/// A named palette, picked in the GUI and remembered between sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    /// Pure black and white with saturated accents.
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Dark, Theme::Light, Theme::HighContrast];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Dark => "Dark",
+            Theme::Light => "Light",
+            Theme::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            Theme::Dark => Palette {
+                dark: true,
+                background: Color32::from_rgb(17, 17, 27),
+                text: Color32::from_rgb(204, 214, 244),
+                border: Color32::from_rgb(49, 50, 68),
+                accent: Color32::from_rgb(137, 180, 250),
+                accent_hover: Color32::from_rgb(203, 166, 247),
+                success: Color32::from_rgb(166, 227, 161),
+                warning: Color32::from_rgb(249, 226, 175),
+                error: Color32::from_rgb(243, 139, 168),
+            },
+            Theme::Light => Palette {
+                dark: false,
+                background: Color32::from_rgb(239, 241, 245),
+                text: Color32::from_rgb(76, 79, 105),
+                border: Color32::from_rgb(188, 192, 204),
+                accent: Color32::from_rgb(30, 102, 245),
+                accent_hover: Color32::from_rgb(136, 57, 239),
+                success: Color32::from_rgb(64, 160, 43),
+                warning: Color32::from_rgb(223, 142, 29),
+                error: Color32::from_rgb(210, 15, 57),
+            },
+            Theme::HighContrast => Palette {
+                dark: true,
+                background: Color32::BLACK,
+                text: Color32::WHITE,
+                border: Color32::WHITE,
+                accent: Color32::from_rgb(255, 255, 0),
+                accent_hover: Color32::from_rgb(0, 255, 255),
+                success: Color32::from_rgb(0, 255, 0),
+                warning: Color32::from_rgb(255, 200, 0),
+                error: Color32::from_rgb(255, 90, 90),
+            },
+        }
+    }
+}
+
+/// The colors of a [`Theme`].
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    /// Whether egui's dark visuals are the base for the remaining widgets.
+    pub dark: bool,
+    pub background: Color32,
+    pub text: Color32,
+    pub border: Color32,
+    pub accent: Color32,
+    pub accent_hover: Color32,
+    pub success: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+}
+
+impl Palette {
+    /// Makes the palette the visuals of every widget drawn afterwards.
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.style_mut(|style| {
+            style.visuals = if self.dark { Visuals::dark() } else { Visuals::light() };
+            style.visuals.override_text_color = Some(self.text);
+            style.visuals.panel_fill = self.background;
+        });
+    }
+
+    /// The color for a status message of kind `"success"`, `"error"` or
+    /// `"warning"`.
+    pub fn status(&self, status: Option<&str>) -> Color32 {
+        match status {
+            Some("success") => self.success,
+            Some("error") => self.error,
+            Some("warning") => self.warning,
+            _ => self.text,
+        }
+    }
+
+    /// An outlined button that fills with the accent on hover.
+    pub fn secondary_button(&self) -> ButtonPalette {
+        ButtonPalette {
+            fill: self.background,
+            text: self.text,
+            stroke: Stroke::new(1.0, self.border),
+            hover_fill: self.accent,
+            hover_text: self.background,
+            hover_stroke: Stroke::new(1.0, self.accent),
+        }
+    }
+
+    /// A button filled with the accent, for the main action.
+    pub fn primary_button(&self) -> ButtonPalette {
+        ButtonPalette {
+            fill: self.accent,
+            text: self.background,
+            stroke: Stroke::NONE,
+            hover_fill: self.accent_hover,
+            hover_text: self.background,
+            hover_stroke: Stroke::NONE,
+        }
+    }
+}
+
+pub struct ButtonPalette {
+    fill: Color32,
+    text: Color32,
+    stroke: Stroke,
+    hover_fill: Color32,
+    hover_text: Color32,
+    hover_stroke: Stroke,
+}
+
+/// Adds a button whose idle and hover colors come from scoped widget visuals,
+/// so egui handles the hover state itself.
+pub fn themed_button(
+    ui: &mut egui::Ui,
+    text: &str,
+    size: f32,
+    corner_radius: f32,
+    palette: &ButtonPalette,
+) -> egui::Response {
+    ui.scope(|ui| {
+        let visuals = &mut ui.style_mut().visuals;
+        visuals.override_text_color = None;
+
+        let widgets = &mut visuals.widgets;
+        let states = [
+            (&mut widgets.inactive, palette.fill, palette.text, palette.stroke),
+            (&mut widgets.hovered, palette.hover_fill, palette.hover_text, palette.hover_stroke),
+            (&mut widgets.active, palette.hover_fill, palette.hover_text, palette.hover_stroke),
+        ];
+        for (state, fill, text, stroke) in states {
+            state.weak_bg_fill = fill;
+            state.bg_fill = fill;
+            state.fg_stroke.color = text;
+            state.bg_stroke = stroke;
+            state.corner_radius = corner_radius.into();
+            state.expansion = 0.0;
+        }
+
+        ui.add(egui::Button::new(RichText::new(text).size(size)))
+    })
+    .inner
+}
@@ -0,0 +1,76 @@
+//! Shrinking glyph rects to the opaque pixels of the atlas, since exporters
+//! often pad them and every transparent pixel still costs fill rate.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use image::RgbaImage;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::debug;
+
+/// Loads the texture pages of `font`, whose file names are relative to
+/// `directory`.
+pub fn load_pages(font: &Font, directory: &Path) -> Result<BTreeMap<u32, RgbaImage>, ConvertError> {
+    font.metrics
+        .pages
+        .iter()
+        .map(|(id, file)| {
+            let path = directory.join(file);
+            let page = image::open(&path).map_err(|source| ConvertError::Texture {
+                file: path.display().to_string(),
+                source,
+            })?;
+            Ok((*id, page.to_rgba8()))
+        })
+        .collect()
+}
+
+/// Shrinks each glyph's rect to the bounding box of its pixels with alpha
+/// above `threshold`, moving the offset by as much as the top-left corner
+/// moved so the glyph renders in the same place. Fully transparent glyphs
+/// become empty. Returns how many glyphs changed.
+pub fn trim_glyphs(font: &mut Font, pages: &BTreeMap<u32, RgbaImage>, threshold: u8) -> usize {
+    let mut trimmed = 0;
+    for data in font.characters.values_mut() {
+        let Some(page) = pages.get(&data.page) else {
+            continue;
+        };
+        let left = data.position.x.max(0.0).round() as u32;
+        let top = data.position.y.max(0.0).round() as u32;
+        let right = ((data.position.x + data.size.width).round() as u32).min(page.width());
+        let bottom = ((data.position.y + data.size.height).round() as u32).min(page.height());
+        if left >= right || top >= bottom {
+            continue;
+        }
+
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for y in top..bottom {
+            for x in left..right {
+                if page.get_pixel(x, y)[3] > threshold {
+                    bounds = Some(match bounds {
+                        None => (x, y, x, y),
+                        Some((min_x, min_y, max_x, max_y)) => (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)),
+                    });
+                }
+            }
+        }
+
+        let (min_x, min_y, width, height) = match bounds {
+            Some((min_x, min_y, max_x, max_y)) => (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1),
+            None => (left, top, 0, 0),
+        };
+        if (min_x, min_y, width, height) == (left, top, right - left, bottom - top) {
+            continue;
+        }
+
+        data.offset.x += (min_x - left) as f32;
+        data.offset.y += (min_y - top) as f32;
+        data.position.x = min_x as f32;
+        data.position.y = min_y as f32;
+        data.size.width = width as f32;
+        data.size.height = height as f32;
+        trimmed += 1;
+    }
+    debug!(trimmed, threshold, "trimmed glyph rects");
+    trimmed
+}
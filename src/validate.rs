@@ -0,0 +1,165 @@
+//! Sanity checks on parsed fonts.
+
+use crate::font::Font;
+use crate::formatter::{OutputOptions, exported_font};
+use std::collections::BTreeSet;
+
+/// The kerning pairs of a font that [`OutputOptions::prune_kerning`] drops
+/// from an export, by why they were dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PrunedKerning {
+    /// Pairs with a glyph the subset or exclusions leave out.
+    pub unexported: usize,
+    /// Pairs with a glyph the font does not define.
+    pub missing: usize,
+}
+
+impl PrunedKerning {
+    pub fn total(&self) -> usize {
+        self.unexported + self.missing
+    }
+}
+
+/// Lists problems in `font` that do not stop conversion but likely indicate a
+/// broken export.
+pub fn collect_warnings(font: &Font) -> Vec<String> {
+    let mut warnings = font.skipped.clone();
+    warnings.extend(font.metrics.filled_in.iter().cloned());
+
+    for id in &font.duplicate_ids {
+        warnings.push(format!("Warning: glyph {id} is defined more than once"));
+    }
+
+    if let Some((declared, parsed)) = font.count_mismatch {
+        warnings.push(format!(
+            "Warning: the file declares {declared} characters but holds {parsed}; it may be truncated"
+        ));
+    }
+
+    for (id, data) in &font.characters {
+        if std::char::from_u32(*id).is_none() {
+            warnings.push(format!("Warning: glyph id {id:#X} is not a valid Unicode scalar"));
+        }
+
+        let metrics = [
+            ("width", data.size.width),
+            ("height", data.size.height),
+            ("advance", data.advance),
+        ];
+        for (name, value) in metrics {
+            if value < 0.0 {
+                warnings.push(format!("Warning: glyph {id} has negative {name} ({value})"));
+            }
+        }
+
+        if !font.metrics.pages.contains_key(&data.page) {
+            warnings.push(format!("Warning: glyph {id} references missing page {}", data.page));
+        }
+
+        if let Some(ref common) = font.metrics.common
+            && (data.position.x + data.size.width > common.scale_w as f32
+                || data.position.y + data.size.height > common.scale_h as f32)
+        {
+            warnings.push(format!(
+                "Warning: glyph {id} extends past the {}×{} texture",
+                common.scale_w, common.scale_h
+            ));
+        }
+
+        let printable = std::char::from_u32(*id).is_some_and(|c| !c.is_control() && !c.is_whitespace());
+        if printable && data.size.width > 0.0 && data.size.height > 0.0 && data.advance == 0.0 {
+            warnings.push(format!("Warning: visible glyph {id} has zero advance"));
+        }
+    }
+
+    for (first, second) in font.metrics.kerning.keys() {
+        for id in [first, second] {
+            if !font.characters.contains_key(id) {
+                warnings.push(format!("Warning: kerning pair ({first}, {second}) references missing glyph {id}"));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Counts the kerning pairs of `font` that exporting it with `options` prunes;
+/// none when pruning or kerning is off. Pairs are counted after
+/// [`OutputOptions::remap`] and [`OutputOptions::pages`] apply, so their
+/// codepoints are those the subset refers to.
+pub fn pruned_kerning(font: &Font, options: &OutputOptions) -> PrunedKerning {
+    let mut pruned = PrunedKerning::default();
+    if !options.kerning || !options.prune_kerning {
+        return pruned;
+    }
+    let font = exported_font(font, options);
+    for &(first, second) in font.metrics.kerning.keys() {
+        if !font.characters.contains_key(&first) || !font.characters.contains_key(&second) {
+            pruned.missing += 1;
+        } else if !options.includes_pair((first, second)) {
+            pruned.unexported += 1;
+        }
+    }
+    pruned
+}
+
+/// The characters of `required` that `font` has no glyph for, in codepoint
+/// order and without repeats. Line breaks and other control characters are
+/// ignored so a text file of strings can be passed as is.
+pub fn missing_characters(font: &Font, required: &str) -> Vec<char> {
+    let missing: BTreeSet<char> = required
+        .chars()
+        .filter(|c| !c.is_control() && !font.characters.contains_key(&(*c as u32)))
+        .collect();
+    missing.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use crate::subset::Subset;
+
+    #[test]
+    fn broken_glyphs_and_kerning_are_warned_about() {
+        let mut font = example_font();
+        assert_eq!(collect_warnings(&font), Vec::<String>::new());
+
+        let a = font.characters.get_mut(&65).unwrap();
+        a.size.width = -3.0;
+        a.page = 7;
+        font.characters.get_mut(&66).unwrap().advance = 0.0;
+        font.characters.get_mut(&67).unwrap().position.x = 1020.0;
+        font.metrics.kerning.insert((65, 0x4e00), -1.0);
+        assert_eq!(
+            collect_warnings(&font),
+            [
+                "Warning: glyph 65 has negative width (-3)",
+                "Warning: glyph 65 references missing page 7",
+                "Warning: visible glyph 66 has zero advance",
+                "Warning: glyph 67 extends past the 1024×1024 texture",
+                "Warning: kerning pair (65, 19968) references missing glyph 19968",
+            ]
+        );
+    }
+
+    #[test]
+    fn pruned_kerning_is_counted_by_cause() {
+        let mut font = example_font();
+        font.metrics.kerning.insert((65, 0x4e00), -1.0);
+        let options = OutputOptions {
+            subset: Some(Subset::from_text("A")),
+            ..Default::default()
+        };
+        let pruned = pruned_kerning(&font, &options);
+        assert_eq!(pruned.missing, 1);
+        // The font has no pair of A with itself, so every other pair goes.
+        assert_eq!(pruned.unexported, font.metrics.kerning.len() - 1);
+        assert_eq!(pruned_kerning(&font, &OutputOptions { prune_kerning: false, ..options }).total(), 0);
+    }
+
+    #[test]
+    fn missing_characters_ignore_control_characters() {
+        assert_eq!(missing_characters(&example_font(), "Hello, €uro!\r\n\u{4e00}€"), ['\u{20ac}', '\u{4e00}']);
+    }
+}
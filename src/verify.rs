@@ -0,0 +1,155 @@
+//! Reading exported output back and comparing it with the font it was
+//! rendered from, so escaping and formatting bugs are caught before the file
+//! is used in a game.
+
+use crate::diff::FontDiff;
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputOptions, UvCoordinates, adjusted_font, exported_font};
+use crate::lua_import::parse_lua;
+use crate::parser::parse_fnt_bytes_with_progress;
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+
+/// Differences listed in a [`ConvertError::RoundTrip`] before the rest are
+/// only counted.
+const MAX_DIFFERENCES: usize = 20;
+
+/// Whether output rendered with `options` can be read back: Lua, JSON and
+/// BMFont XML or text without a custom template, and with whole glyphs in
+/// pixels.
+pub fn can_verify(options: &OutputOptions) -> bool {
+    options.template.is_none()
+        && !((options.advances_only || options.uv != UvCoordinates::Off) && matches!(options.format, OutputFormat::Lua | OutputFormat::Json))
+        && !((options.chunk_size.is_some() || !options.scales.is_empty()) && matches!(options.format, OutputFormat::Lua))
+        && matches!(options.format, OutputFormat::Lua | OutputFormat::Json | OutputFormat::Fnt | OutputFormat::FntText)
+}
+
+/// Parses `output`, rendered from `font` with `options`, and fails with
+/// [`ConvertError::RoundTrip`] if it does not hold the glyphs, metrics and
+/// kerning that were exported.
+pub fn verify_output(font: &Font, output: &str, options: &OutputOptions) -> Result<(), ConvertError> {
+    if !can_verify(options) {
+        let format = match options.template {
+            Some(_) => "templated",
+            None if options.advances_only => "advances-only",
+            None if options.uv != UvCoordinates::Off => "UV",
+            None => options.format.label(),
+        };
+        return Err(ConvertError::RoundTrip(vec![format!("{format} output cannot be read back")]));
+    }
+    // Output read back without `read_source` still has its BOM.
+    let output = output.trim_start_matches('\u{feff}');
+    let expected = adjusted_font(&exported_font(font, options), options);
+
+    let mut differences = match options.format {
+        OutputFormat::Json => json_differences(&expected, output)?,
+        OutputFormat::Fnt | OutputFormat::FntText => {
+            let actual = parse_fnt_bytes_with_progress(output.as_bytes(), |_| {}).map_err(read_back_error)?;
+            font_differences(&expected, &actual)
+        }
+        _ => {
+            let actual = parse_lua(output).map_err(read_back_error)?;
+            font_differences(&lua_reading(expected, options), &actual)
+        }
+    };
+    if differences.is_empty() {
+        debug!(format = options.format.formatter().name(), "output reads back as exported");
+        return Ok(());
+    }
+    warn!(differences = differences.len(), "output does not read back as exported");
+    if differences.len() > MAX_DIFFERENCES {
+        let more = differences.len() - MAX_DIFFERENCES;
+        differences.truncate(MAX_DIFFERENCES);
+        differences.push(format!("{more} more"));
+    }
+    Err(ConvertError::RoundTrip(differences))
+}
+
+fn read_back_error(error: ConvertError) -> ConvertError {
+    ConvertError::RoundTrip(vec![format!("output does not parse: {error}")])
+}
+
+/// `font` as a Lua module reads back: glyphs keyed by the character their
+/// table key spells and pages numbered by their position in `Pages`.
+fn lua_reading(mut font: Font, options: &OutputOptions) -> Font {
+    let letters: BTreeMap<u32, u32> = font
+        .characters
+        .iter()
+        .filter_map(|(id, data)| {
+            let mut letter = data.letter.as_deref()?.chars();
+            match (letter.next(), letter.next()) {
+                (Some(c), None) => Some((*id, c as u32)),
+                _ => None,
+            }
+        })
+        .collect();
+    let key = |id: u32| {
+        if options.numeric_keys {
+            return id;
+        }
+        match letters.get(&id) {
+            Some(letter) if options.prefer_letters => *letter,
+            _ => id,
+        }
+    };
+
+    let positions: BTreeMap<u32, u32> = font.metrics.pages.keys().zip(0..).map(|(id, position)| (*id, position)).collect();
+    let multiple_pages = positions.len() > 1;
+    font.characters = std::mem::take(&mut font.characters)
+        .into_iter()
+        .map(|(id, mut data)| {
+            // Glyphs only carry a page when there is more than one.
+            data.page = if multiple_pages { positions.get(&data.page).copied().unwrap_or(data.page) } else { 0 };
+            (key(id), data)
+        })
+        .collect();
+    font.metrics.pages = std::mem::take(&mut font.metrics.pages).into_values().zip(0..).map(|(file, position)| (position, file)).collect();
+    font.metrics.kerning = std::mem::take(&mut font.metrics.kerning)
+        .into_iter()
+        .map(|((first, second), amount)| ((key(first), key(second)), amount))
+        .collect();
+    font
+}
+
+fn font_differences(expected: &Font, actual: &Font) -> Vec<String> {
+    let diff = FontDiff::of(expected, actual);
+    let mut differences = Vec::new();
+    for change in &diff.metrics {
+        differences.push(format!("{}: exported {}, read back {}", change.field, change.old, change.new));
+    }
+    for id in &diff.removed {
+        differences.push(format!("U+{id:04X} is missing"));
+    }
+    for id in &diff.added {
+        differences.push(format!("U+{id:04X} was not exported"));
+    }
+    for change in &diff.changed {
+        for field in &change.fields {
+            differences.push(format!("U+{:04X} {}: exported {}, read back {}", change.id, field.field, field.old, field.new));
+        }
+    }
+    if diff.kerning_changes > 0 {
+        differences.push(format!("{} kerning pair(s) differ", diff.kerning_changes));
+    }
+    differences
+}
+
+/// The top-level fields of the JSON output that differ from `expected`
+/// serialized again.
+fn json_differences(expected: &Font, output: &str) -> Result<Vec<String>, ConvertError> {
+    let actual: serde_json::Value = serde_json::from_str(output)
+        .map_err(|e| ConvertError::RoundTrip(vec![format!("output does not parse: {e}")]))?;
+    // Serialized to text first so floats are compared as written, not as
+    // widened to f64.
+    let expected = serde_json::to_string(expected).expect("font serializes to JSON");
+    let expected: serde_json::Value = serde_json::from_str(&expected).expect("serialized JSON parses");
+    let (serde_json::Value::Object(expected), serde_json::Value::Object(actual)) = (&expected, &actual) else {
+        return Ok(vec!["output is not a JSON object".to_string()]);
+    };
+    let fields = expected.keys().chain(actual.keys().filter(|key| !expected.contains_key(*key)));
+    Ok(fields
+        .filter(|field| expected.get(*field) != actual.get(*field))
+        .map(|field| format!("{field} differs"))
+        .collect())
+}
@@ -0,0 +1,375 @@
+//! Writing fonts back out as BMFont XML or text.
+
+use crate::error::ConvertError;
+use crate::font::Font;
+use crate::formatter::{OutputFormat, OutputFormatter, OutputOptions, adjusted_font, render, script_name};
+use quick_xml::Writer;
+use quick_xml::events::{BytesCData, BytesDecl, BytesText, Event};
+
+/// An XML writer that indents by two spaces, or writes everything on one
+/// line when `minified`.
+pub(crate) fn xml_writer(minified: bool) -> Writer<Vec<u8>> {
+    if minified {
+        Writer::new(Vec::new())
+    } else {
+        Writer::new_with_indent(Vec::new(), b' ', 2)
+    }
+}
+
+/// Serializes `font` as a BMFont XML descriptor.
+pub fn write_fnt_xml(font: &Font, minified: bool) -> String {
+    let mut writer = xml_writer(minified);
+
+    let result: std::io::Result<()> = (|| {
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", None, None)))?;
+        writer.create_element("font").write_inner_content(|writer| {
+            let mut info = writer.create_element("info").with_attribute(("size", font.metrics.size.to_string().as_str()));
+            let spacing = &font.metrics.glyph_spacing;
+            if !spacing.is_zero() {
+                let list = |values: &[i32]| values.iter().map(i32::to_string).collect::<Vec<_>>().join(",");
+                info = info
+                    .with_attribute(("padding", list(&spacing.padding).as_str()))
+                    .with_attribute(("spacing", list(&spacing.spacing).as_str()))
+                    .with_attribute(("outline", spacing.outline.to_string().as_str()));
+            }
+            info.write_empty()?;
+
+            if let Some(ref common) = font.metrics.common {
+                writer
+                    .create_element("common")
+                    .with_attribute(("lineHeight", common.line_height.to_string().as_str()))
+                    .with_attribute(("base", common.base.to_string().as_str()))
+                    .with_attribute(("scaleW", common.scale_w.to_string().as_str()))
+                    .with_attribute(("scaleH", common.scale_h.to_string().as_str()))
+                    .with_attribute(("pages", font.metrics.pages.len().to_string().as_str()))
+                    .write_empty()?;
+            }
+
+            if let Some(ref field) = font.metrics.distance_field {
+                writer
+                    .create_element("distanceField")
+                    .with_attribute(("fieldType", field.field_type.as_str()))
+                    .with_attribute(("distanceRange", field.range.to_string().as_str()))
+                    .write_empty()?;
+            }
+
+            writer.create_element("pages").write_inner_content(|writer| {
+                for (id, file) in &font.metrics.pages {
+                    writer
+                        .create_element("page")
+                        .with_attribute(("id", id.to_string().as_str()))
+                        .with_attribute(("file", file.as_str()))
+                        .write_empty()?;
+                }
+                Ok(())
+            })?;
+
+            let count = font.characters.len().to_string();
+            writer
+                .create_element("chars")
+                .with_attribute(("count", count.as_str()))
+                .write_inner_content(|writer| {
+                    for (id, data) in &font.characters {
+                        let attributes = [
+                            ("id", id.to_string()),
+                            ("x", data.position.x.to_string()),
+                            ("y", data.position.y.to_string()),
+                            ("width", data.size.width.to_string()),
+                            ("height", data.size.height.to_string()),
+                            ("xoffset", data.offset.x.to_string()),
+                            ("yoffset", data.offset.y.to_string()),
+                            ("xadvance", data.advance.to_string()),
+                            ("page", data.page.to_string()),
+                            ("chnl", data.channel.to_string()),
+                        ];
+                        let letter = data.letter.as_deref().map(|letter| match letter {
+                            " " => "space",
+                            letter => letter,
+                        });
+                        writer
+                            .create_element("char")
+                            .with_attributes(attributes.iter().map(|(k, v)| (*k, v.as_str())))
+                            .with_attributes(letter.map(|letter| ("letter", letter)))
+                            .write_empty()?;
+                    }
+                    Ok(())
+                })?;
+
+            if !font.metrics.kerning.is_empty() {
+                let count = font.metrics.kerning.len().to_string();
+                writer
+                    .create_element("kernings")
+                    .with_attribute(("count", count.as_str()))
+                    .write_inner_content(|writer| {
+                        for ((first, second), amount) in &font.metrics.kerning {
+                            writer
+                                .create_element("kerning")
+                                .with_attribute(("first", first.to_string().as_str()))
+                                .with_attribute(("second", second.to_string().as_str()))
+                                .with_attribute(("amount", amount.to_string().as_str()))
+                                .write_empty()?;
+                        }
+                        Ok(())
+                    })?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    })();
+    result.expect("writing XML into memory cannot fail");
+
+    let mut output = String::from_utf8(writer.into_inner()).expect("XML writer emits UTF-8");
+    output.push('\n');
+    output
+}
+
+/// BMFont XML of [`write_fnt_xml`], with the metric adjustments applied.
+pub struct FntFormatter;
+
+impl OutputFormatter for FntFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Fnt
+    }
+
+    fn name(&self) -> &'static str {
+        "fnt"
+    }
+
+    fn label(&self) -> &'static str {
+        "BMFont XML"
+    }
+
+    fn description(&self) -> &'static str {
+        "BMFont XML descriptor"
+    }
+
+    fn extension(&self) -> &'static str {
+        "fnt"
+    }
+
+    fn render(&self, font: &Font, _source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(write_fnt_xml(&adjusted_font(font, options), options.style.minified))
+    }
+}
+
+/// Serializes `font` in the plain-text BMFont format, naming its face `face`.
+/// Attributes the model does not keep, such as `bold` or the channel
+/// layout, are written with BMFont's defaults, since some importers expect
+/// every one of them.
+pub fn write_fnt_text(font: &Font, face: &str) -> String {
+    let quoted = |value: &str| format!("\"{}\"", value.replace('"', "'"));
+    let list = |values: &[i32]| values.iter().map(i32::to_string).collect::<Vec<_>>().join(",");
+    let spacing = &font.metrics.glyph_spacing;
+    let mut lines = vec![format!(
+        "info face={} size={} bold=0 italic=0 charset=\"\" unicode=1 stretchH=100 smooth=1 aa=1 padding={} spacing={} outline={}",
+        quoted(face),
+        font.metrics.size,
+        list(&spacing.padding),
+        list(&spacing.spacing),
+        spacing.outline
+    )];
+    if let Some(ref common) = font.metrics.common {
+        lines.push(format!(
+            "common lineHeight={} base={} scaleW={} scaleH={} pages={} packed=0 alphaChnl=1 redChnl=0 greenChnl=0 blueChnl=0",
+            common.line_height,
+            common.base,
+            common.scale_w,
+            common.scale_h,
+            font.metrics.pages.len()
+        ));
+    }
+    if let Some(ref field) = font.metrics.distance_field {
+        lines.push(format!("distanceField fieldType={} distanceRange={}", field.field_type, field.range));
+    }
+    for (id, file) in &font.metrics.pages {
+        lines.push(format!("page id={id} file={}", quoted(file)));
+    }
+
+    lines.push(format!("chars count={}", font.characters.len()));
+    for (id, data) in &font.characters {
+        let mut line = format!(
+            "char id={id} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page={} chnl={}",
+            data.position.x, data.position.y, data.size.width, data.size.height, data.offset.x, data.offset.y, data.advance, data.page, data.channel
+        );
+        if let Some(ref letter) = data.letter {
+            let letter = match letter.as_str() {
+                " " => "space".into(),
+                letter => quick_xml::escape::escape(letter),
+            };
+            line.push_str(&format!(" letter=\"{letter}\""));
+        }
+        lines.push(line);
+    }
+
+    if !font.metrics.kerning.is_empty() {
+        lines.push(format!("kernings count={}", font.metrics.kerning.len()));
+        for ((first, second), amount) in &font.metrics.kerning {
+            lines.push(format!("kerning first={first} second={second} amount={amount}"));
+        }
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+/// BMFont text of [`write_fnt_text`], with the metric adjustments applied, as
+/// Godot, Defold and cocos2d import bitmap fonts.
+pub struct FntTextFormatter;
+
+impl OutputFormatter for FntTextFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::FntText
+    }
+
+    fn name(&self) -> &'static str {
+        "fnt-text"
+    }
+
+    fn label(&self) -> &'static str {
+        "BMFont text"
+    }
+
+    fn description(&self) -> &'static str {
+        "BMFont text descriptor, as Godot, Defold and cocos2d import"
+    }
+
+    fn extension(&self) -> &'static str {
+        "fnt"
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        Ok(write_fnt_text(&adjusted_font(font, options), &script_name(source, options)))
+    }
+}
+
+/// The Lua output, or the template's, wrapped by [`write_rbxmx`].
+pub struct RbxmxFormatter;
+
+impl OutputFormatter for RbxmxFormatter {
+    fn format(&self) -> OutputFormat {
+        OutputFormat::Rbxmx
+    }
+
+    fn name(&self) -> &'static str {
+        "rbxmx"
+    }
+
+    fn label(&self) -> &'static str {
+        "Roblox model"
+    }
+
+    fn description(&self) -> &'static str {
+        "Roblox model holding the Lua module as a ModuleScript"
+    }
+
+    fn extension(&self) -> &'static str {
+        "rbxmx"
+    }
+
+    fn replaced_by_template(&self) -> bool {
+        false
+    }
+
+    fn render(&self, font: &Font, source: &str, options: &OutputOptions) -> Result<String, ConvertError> {
+        // `font` already has glyphs moved and dropped.
+        let lua = render(font, source, &OutputOptions { format: OutputFormat::Lua, remap: None, pages: None, ..options.clone() })?;
+        Ok(write_rbxmx(&script_name(source, options), &lua, options.style.minified))
+    }
+}
+
+/// Wraps Lua `source` in a Roblox XML model holding a single ModuleScript
+/// called `name`.
+pub fn write_rbxmx(name: &str, source: &str, minified: bool) -> String {
+    let mut writer = xml_writer(minified);
+
+    let result: std::io::Result<()> = (|| {
+        writer
+            .create_element("roblox")
+            .with_attribute(("xmlns:xmime", "http://www.w3.org/2005/05/xmlmime"))
+            .with_attribute(("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"))
+            .with_attribute(("xsi:noNamespaceSchemaLocation", "http://www.roblox.com/roblox.xsd"))
+            .with_attribute(("version", "4"))
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("Item")
+                    .with_attribute(("class", "ModuleScript"))
+                    .with_attribute(("referent", "RBX0"))
+                    .write_inner_content(|writer| {
+                        writer.create_element("Properties").write_inner_content(|writer| {
+                            writer
+                                .create_element("string")
+                                .with_attribute(("name", "Name"))
+                                .write_text_content(BytesText::new(name))?;
+                            writer
+                                .create_element("ProtectedString")
+                                .with_attribute(("name", "Source"))
+                                .write_inner_content(|writer| {
+                                    // A CDATA section cannot contain `]]>`, so split it across two.
+                                    for part in BytesCData::escaped(source) {
+                                        writer.write_event(Event::CData(part))?;
+                                    }
+                                    Ok(())
+                                })?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+        Ok(())
+    })();
+    result.expect("writing XML into memory cannot fail");
+
+    let mut output = String::from_utf8(writer.into_inner()).expect("XML writer emits UTF-8");
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_font;
+    use crate::parser::parse_fnt_bytes_with_progress;
+
+    #[test]
+    fn xml_round_trips_through_the_parser() {
+        let font = example_font();
+        for minified in [false, true] {
+            let written = write_fnt_xml(&font, minified);
+            let reparsed = parse_fnt_bytes_with_progress(written.as_bytes(), |_| {}).unwrap();
+            assert_eq!(serde_json::to_value(&reparsed).unwrap(), serde_json::to_value(&font).unwrap());
+            assert_eq!(reparsed.metrics.declared_chars, Some(font.characters.len()));
+            assert!(!reparsed.metrics.kerning.is_empty());
+        }
+    }
+
+    #[test]
+    fn text_round_trips_through_the_parser() {
+        let font = example_font();
+        let written = write_fnt_text(&font, "ROBUST");
+        assert!(written.starts_with("info face=\"ROBUST\" size=50 "));
+        let reparsed = parse_fnt_bytes_with_progress(written.as_bytes(), |_| {}).unwrap();
+        assert_eq!(serde_json::to_value(&reparsed).unwrap(), serde_json::to_value(&font).unwrap());
+    }
+
+    #[test]
+    fn rbxmx_keeps_the_source_intact() {
+        let source = "return { [\"]]>\"] = \"<&>\" }\n";
+        for minified in [false, true] {
+            let model = write_rbxmx("Font", source, minified);
+            assert!(model.contains("<Item class=\"ModuleScript\" referent=\"RBX0\">"));
+            assert!(model.contains("<string name=\"Name\">Font</string>"));
+            let mut reader = quick_xml::Reader::from_str(&model);
+            let mut read = String::new();
+            loop {
+                match reader.read_event().unwrap() {
+                    Event::CData(part) => read.push_str(std::str::from_utf8(&part).unwrap()),
+                    Event::Eof => break,
+                    _ => {}
+                }
+            }
+            assert_eq!(read, source);
+        }
+    }
+}